@@ -1,16 +1,24 @@
-use crate::events::AppEvent;
-use crate::views::instances::InstanceState;
+use crate::events::{AppEvent, ControlMessage, ControlResponder, PermissionResponder};
+use crate::providers::TranscriptReader;
+use crate::types::{AgentProvider, Keymap, PermissionDecision, PermissionRule};
 use crate::views::instances::operations::TaskPaneConfig;
+use crate::views::instances::{ClaudeState, InstanceState};
+use crate::views::mouse::MouseRegions;
+use crate::views::palette::PaletteState;
+use crate::views::permissions::{PermissionRequest, PermissionScope, PermissionsState};
 use crate::views::pull_requests::PullRequestsState;
 use crate::views::roadmap::RoadmapState;
-use crate::views::settings::SettingsState;
+use crate::views::settings::{MergeStrategy, SettingsState, VcsCommand};
+use crate::views::tasks::state::{QueuePriority, WorktreeDeletion, WorktreeDeletionPurpose};
 use crate::views::tasks::{TaskType, TasksState};
 use crate::views::worktree::WorktreeTabState;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
-const DEFAULT_PTY_ROWS: u16 = 24;
-const DEFAULT_PTY_COLUMNS: u16 = 80;
+/// How long an agent started by the auto-run queue counts as running before it reports so.
+const QUEUE_LAUNCH_GRACE: Duration = Duration::from_mins(1);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Tab {
@@ -33,9 +41,31 @@ pub struct App {
     #[serde(skip)]
     pub settings: SettingsState,
     #[serde(skip)]
+    pub permissions: PermissionsState,
+    #[serde(skip)]
+    pub palette: PaletteState,
+    /// What the last frame drew where, for matching mouse events to it.
+    #[serde(skip)]
+    pub mouse_regions: MouseRegions,
+    /// The key bindings, built from the defaults and the `keybindings` setting.
+    #[serde(skip)]
+    pub keymap: Keymap,
+    #[serde(skip)]
     pub showing_exit_confirmation: bool,
+    /// Set when another Chloe owns this project: the board is shown but never saved, and no
+    /// agents are started.
+    #[serde(skip)]
+    pub read_only: bool,
+    #[serde(skip)]
+    last_autosave: Option<Instant>,
+    #[serde(skip)]
+    last_base_sync: Option<Instant>,
     #[serde(skip)]
     event_sender: Option<mpsc::UnboundedSender<AppEvent>>,
+    #[serde(skip)]
+    control_subscribers: Vec<ControlResponder>,
+    #[serde(skip)]
+    transcripts: TranscriptReader,
 }
 
 impl App {
@@ -49,8 +79,17 @@ impl App {
             worktree: WorktreeTabState::new(),
             pull_requests: PullRequestsState::new(),
             settings: SettingsState::new(),
+            permissions: PermissionsState::default(),
+            palette: PaletteState::default(),
+            mouse_regions: MouseRegions::default(),
+            keymap: Keymap::default(),
             showing_exit_confirmation: false,
+            read_only: false,
+            last_autosave: None,
+            last_base_sync: None,
             event_sender: None,
+            control_subscribers: Vec::new(),
+            transcripts: TranscriptReader::default(),
         }
     }
 
@@ -64,45 +103,158 @@ impl App {
         self.event_sender.clone()
     }
 
-    #[must_use]
-    pub fn load_or_default() -> Self {
-        let settings = crate::persistence::storage::load_settings().unwrap_or_default();
-
-        if let Ok(mut app) = crate::persistence::storage::load_state() {
-            let active_instance_ids: Vec<uuid::Uuid> = app
-                .instances
-                .collect_panes()
-                .iter()
-                .map(|pane| pane.id)
-                .collect();
-            for column in &mut app.tasks.columns {
-                for task in &mut column.tasks {
-                    if let Some(instance_id) = task.instance_id
-                        && !active_instance_ids.contains(&instance_id)
-                    {
-                        task.instance_id = None;
-                    }
-                }
+    pub fn add_control_subscriber(&mut self, subscriber: ControlResponder) {
+        self.control_subscribers.push(subscriber);
+    }
+
+    /// Pushes a notification to every `subscribe` connection, dropping the ones whose client
+    /// has disconnected.
+    pub fn notify_control_subscribers(&mut self, event: &str, data: &serde_json::Value) {
+        if self.control_subscribers.is_empty() {
+            return;
+        }
+
+        self.control_subscribers.retain(|subscriber| {
+            subscriber
+                .send(ControlMessage::notification(event, data.clone()))
+                .is_ok()
+        });
+    }
+
+    /// Loads the saved board with the project settings merged over the global ones, or an
+    /// empty board when nothing was saved yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the saved state or a settings file exists but cannot be read.
+    pub fn load() -> crate::types::Result<Self> {
+        let layers = crate::persistence::storage::load_settings_layers()?;
+        let settings = layers.merged().map_err(|error| {
+            crate::types::AppError::Config(format!(
+                "Invalid settings: {error}. Fix the global or project settings.json"
+            ))
+        })?;
+
+        let mut app = crate::persistence::storage::load_state()?;
+        app.roadmap.sort_items_by_priority();
+        app.settings = SettingsState::with_settings(settings, layers);
+        app.instances.prune_all_activity_events();
+        app.apply_column_settings();
+        app.apply_keymap_settings();
+        Ok(app)
+    }
+
+    /// Lays the board out with the columns from the settings. When they are invalid the board
+    /// keeps its columns, so a bad edit does not move tasks out of columns it left out.
+    pub fn apply_column_settings(&mut self) {
+        match self.settings.settings.validated_columns() {
+            Ok(columns) => self.tasks.apply_column_definitions(columns),
+            Err(error) => {
+                self.tasks.apply_default_workflows();
+                self.tasks.error_message = Some(error);
             }
+        }
+    }
 
-            app.roadmap.sort_items_by_priority();
-            app.settings = SettingsState::with_settings(settings);
-            app.instances.prune_all_activity_events();
-            app
-        } else {
-            Self {
-                settings: SettingsState::with_settings(settings),
-                ..Default::default()
+    /// Builds the keymap from the `keybindings` setting, keeping the default bindings when it is
+    /// invalid.
+    pub fn apply_keymap_settings(&mut self) {
+        match self.settings.settings.keymap() {
+            Ok(keymap) => self.keymap = keymap,
+            Err(error) => {
+                self.keymap = Keymap::default();
+                self.tasks.error_message = Some(error);
             }
         }
     }
 
     pub fn save(&self) -> crate::types::Result<()> {
+        if self.read_only {
+            return Ok(());
+        }
         crate::persistence::storage::save_state(self)
     }
 
-    pub fn save_settings(&self) -> crate::types::Result<()> {
-        crate::persistence::storage::save_settings(&self.settings.settings)
+    pub fn save_settings(&mut self) -> crate::types::Result<()> {
+        if self.read_only {
+            return Ok(());
+        }
+        crate::persistence::storage::save_settings(
+            &self.settings.settings,
+            &mut self.settings.layers,
+        )
+    }
+
+    /// Saves the board every `auto_save_interval_seconds`, so a crash loses at most that much.
+    /// An interval of 0 turns autosave off.
+    pub fn autosave_if_due(&mut self) {
+        let interval = Duration::from_secs(self.settings.settings.auto_save_interval_seconds);
+        if interval.is_zero() {
+            return;
+        }
+
+        let now = Instant::now();
+        let last_autosave = *self.last_autosave.get_or_insert(now);
+        if now.duration_since(last_autosave) >= interval {
+            self.last_autosave = Some(now);
+            let _ = self.save();
+        }
+    }
+
+    /// Every `base_sync_interval_seconds`, brings the base branch into the worktrees of
+    /// started tasks whose agents are not working. An interval of 0 turns this off.
+    pub fn sync_tasks_with_base_if_due(&mut self) {
+        let interval = Duration::from_secs(self.settings.settings.base_sync_interval_seconds);
+        if self.read_only || interval.is_zero() {
+            return;
+        }
+
+        let now = Instant::now();
+        let last_base_sync = *self.last_base_sync.get_or_insert(now);
+        if now.duration_since(last_base_sync) < interval {
+            return;
+        }
+        self.last_base_sync = Some(now);
+
+        let busy_instances: Vec<uuid::Uuid> = self
+            .instances
+            .collect_panes()
+            .iter()
+            .filter(|pane| {
+                matches!(
+                    pane.claude_state,
+                    ClaudeState::Running | ClaudeState::NeedsPermissions
+                )
+            })
+            .map(|pane| pane.id)
+            .collect();
+        let review_column_index = self.tasks.review_column_index();
+        let idle_task_ids: Vec<uuid::Uuid> = self
+            .tasks
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(column_index, _)| {
+                self.tasks.is_agent_column(*column_index)
+                    || review_column_index == Some(*column_index)
+            })
+            .flat_map(|(_, column)| &column.tasks)
+            .filter(|task| task.worktree_info.is_some())
+            .filter(|task| {
+                task.instance_id
+                    .is_none_or(|instance_id| !busy_instances.contains(&instance_id))
+            })
+            .map(|task| task.id)
+            .collect();
+
+        // Worktrees with uncommitted work are skipped until a later tick finds them clean
+        for task_id in idle_task_ids {
+            if let Err(error) = self.sync_with_base_and_hand_off_conflicts(task_id)
+                && !error.is::<crate::views::worktree::UncommittedChanges>()
+            {
+                self.tasks.error_message = Some(format!("Sync failed: {error}"));
+            }
+        }
     }
 
     pub fn switch_tab(&mut self, tab: Tab) {
@@ -155,16 +307,48 @@ impl App {
         }
     }
 
+    /// Brings restored panes back to life after startup.
+    ///
+    /// Panes whose agents the daemon kept running are reattached. The remaining task panes
+    /// have no PTY, so they are dropped and respawned by `sync_task_instances`, resuming the
+    /// provider session when one was recorded.
+    pub fn restore_task_instances(&mut self) {
+        if self.read_only {
+            return;
+        }
+
+        let live_instance_ids = self.instances.attach_daemon_sessions();
+
+        let stale_instance_ids: Vec<uuid::Uuid> = self
+            .tasks
+            .columns
+            .iter_mut()
+            .flat_map(|column| &mut column.tasks)
+            .filter(|task| {
+                task.instance_id
+                    .is_some_and(|instance_id| !live_instance_ids.contains(&instance_id))
+            })
+            .filter_map(|task| task.instance_id.take())
+            .collect();
+        for instance_id in stale_instance_ids {
+            self.instances.close_pane_by_id(instance_id);
+        }
+
+        self.sync_task_instances();
+    }
+
     pub fn sync_task_instances(&mut self) {
-        if self.tasks.columns.len() < 2 {
+        if self.read_only {
             return;
         }
 
-        let default_provider = self.settings.settings.default_provider;
-        let in_progress_column = &self.tasks.columns[1];
-        let tasks_needing_instances: Vec<_> = in_progress_column
+        let default_provider = &self.settings.settings.default_provider;
+        let tasks_needing_instances: Vec<_> = self
             .tasks
+            .columns
             .iter()
+            .filter(|column| column.workflow.starts_agent)
+            .flat_map(|column| &column.tasks)
             .filter(|task| task.instance_id.is_none())
             .map(|task| {
                 let worktree_path = task
@@ -175,20 +359,37 @@ impl App {
                     .worktree_info
                     .as_ref()
                     .map(|info| info.branch_name.clone());
-                let provider = task.provider.unwrap_or(default_provider);
+                let setup_command = task
+                    .worktree_info
+                    .as_ref()
+                    .and_then(|info| info.pending_setup.clone());
+                let provider = task
+                    .provider
+                    .clone()
+                    .unwrap_or_else(|| default_provider.clone());
                 (
                     task.id,
                     task.title.clone(),
                     task.description.clone(),
                     worktree_path,
                     pane_name,
+                    setup_command,
+                    task.session_id.clone(),
                     provider,
                 )
             })
             .collect();
 
-        for (task_id, task_title, task_description, working_directory, pane_name, provider) in
-            tasks_needing_instances
+        for (
+            task_id,
+            task_title,
+            task_description,
+            working_directory,
+            pane_name,
+            setup_command,
+            session_id,
+            provider,
+        ) in tasks_needing_instances
         {
             let permission_config = self
                 .settings
@@ -197,25 +398,44 @@ impl App {
                 .get(&provider)
                 .cloned()
                 .unwrap_or_default();
+            let spec = self.settings.settings.provider_spec(&provider);
 
             let config = TaskPaneConfig {
                 task_id,
-                title: task_title,
+                title: task_title.clone(),
                 description: task_description,
                 working_directory,
                 pane_name,
+                session_id,
                 provider,
+                spec,
                 vcs_command: self.settings.settings.vcs_command.clone(),
-                rows: DEFAULT_PTY_ROWS,
-                columns: DEFAULT_PTY_COLUMNS,
                 permission_config,
+                setup_command,
             };
-            let instance_id = self.instances.create_pane_for_task(config);
-            self.tasks.link_task_to_instance(task_id, instance_id);
+            match self.instances.create_pane_for_task(config) {
+                Some(instance_id) => self.tasks.link_task_to_instance(task_id, instance_id),
+                None => self.tasks.error_message = Some(no_room_for_agent_message(&task_title)),
+            }
+        }
+    }
+
+    pub fn start_roadmap_generation(&mut self) {
+        if let Ok(current_directory) = std::env::current_dir()
+            && let Some(event_sender) = self.event_sender()
+        {
+            self.roadmap.start_generation(
+                current_directory.to_string_lossy().to_string(),
+                event_sender,
+            );
         }
     }
 
     pub fn jump_to_task_instance(&mut self) -> bool {
+        if self.read_only {
+            return false;
+        }
+
         if let Some(task) = self.tasks.get_kanban_selected_task() {
             let task_id = task.id;
             let task_title = task.title.clone();
@@ -228,9 +448,15 @@ impl App {
                 .worktree_info
                 .as_ref()
                 .map(|info| info.branch_name.clone());
+            let setup_command = task
+                .worktree_info
+                .as_ref()
+                .and_then(|info| info.pending_setup.clone());
             let provider = task
                 .provider
-                .unwrap_or(self.settings.settings.default_provider);
+                .clone()
+                .unwrap_or_else(|| self.settings.settings.default_provider.clone());
+            let session_id = task.session_id.clone();
 
             if let Some(instance_id) = task.instance_id {
                 self.active_tab = Tab::Instances;
@@ -244,20 +470,25 @@ impl App {
                 .get(&provider)
                 .cloned()
                 .unwrap_or_default();
+            let spec = self.settings.settings.provider_spec(&provider);
 
             let config = TaskPaneConfig {
                 task_id,
-                title: task_title,
+                title: task_title.clone(),
                 description: task_description,
                 working_directory,
                 pane_name,
+                session_id,
                 provider,
+                spec,
                 vcs_command: self.settings.settings.vcs_command.clone(),
-                rows: DEFAULT_PTY_ROWS,
-                columns: DEFAULT_PTY_COLUMNS,
                 permission_config,
+                setup_command,
+            };
+            let Some(instance_id) = self.instances.create_pane_for_task(config) else {
+                self.tasks.error_message = Some(no_room_for_agent_message(&task_title));
+                return false;
             };
-            let instance_id = self.instances.create_pane_for_task(config);
             self.tasks.link_task_to_instance(task_id, instance_id);
             self.active_tab = Tab::Instances;
             self.instances.mode = crate::views::instances::InstanceMode::Focused;
@@ -276,6 +507,115 @@ impl App {
             .map(|pane| pane.claude_state)
     }
 
+    /// Starts queued Planning tasks while fewer agents are running than the global and
+    /// per-provider limits allow.
+    pub fn start_queued_tasks(&mut self) {
+        // Queued tasks wait for a pane to be closed rather than start without an agent
+        if self.read_only
+            || self.tasks.queued_task_count() == 0
+            || !self.instances.has_room_for_pane()
+        {
+            return;
+        }
+
+        let now = Instant::now();
+        self.tasks
+            .queue_launches
+            .retain(|(_, launched_at)| now.duration_since(*launched_at) < QUEUE_LAUNCH_GRACE);
+
+        // An agent that was just started has not reported running yet but already holds a slot
+        let launching_instances: Vec<uuid::Uuid> = self
+            .tasks
+            .queue_launches
+            .iter()
+            .filter_map(|(task_id, _)| self.tasks.find_task_by_id(*task_id)?.instance_id)
+            .collect();
+        let mut running = HashMap::<AgentProvider, usize>::new();
+        for pane in self.instances.collect_panes() {
+            let is_busy = match pane.claude_state {
+                ClaudeState::Running | ClaudeState::NeedsPermissions => true,
+                ClaudeState::Idle => launching_instances.contains(&pane.id),
+                ClaudeState::Done => false,
+            };
+            if is_busy {
+                *running.entry(pane.provider.clone()).or_default() += 1;
+            }
+        }
+
+        let settings = &self.settings.settings;
+        let task_ids = self.tasks.select_queued_tasks_to_start(
+            running,
+            settings.max_running_agents,
+            &settings.default_provider,
+            |provider| settings.provider_config(provider).max_running_agents,
+        );
+        if task_ids.is_empty() {
+            return;
+        }
+
+        let (started, without_room) = self.start_planned_tasks(&task_ids);
+        self.tasks
+            .queue_launches
+            .extend(started.iter().map(|task_id| (*task_id, now)));
+
+        // A task that failed to start would otherwise be retried on every tick
+        for task_id in task_ids
+            .iter()
+            .filter(|task_id| !started.contains(task_id) && !without_room.contains(task_id))
+        {
+            let _ = self.tasks.set_task_queue_priority(*task_id, None);
+        }
+    }
+
+    /// Starts the given Planning tasks, returning those that were started and those that were
+    /// put back in Planning, still queued if they were, because no pane was left for an agent.
+    fn start_planned_tasks(
+        &mut self,
+        task_ids: &[uuid::Uuid],
+    ) -> (Vec<uuid::Uuid>, Vec<uuid::Uuid>) {
+        if self.read_only {
+            return (Vec::new(), Vec::new());
+        }
+
+        let queue_priorities: HashMap<uuid::Uuid, Option<QueuePriority>> = task_ids
+            .iter()
+            .filter_map(|task_id| {
+                let task = self.tasks.find_task_by_id(*task_id)?;
+                Some((*task_id, task.queue_priority))
+            })
+            .collect();
+        let vcs_command = self.settings.settings.vcs_command.clone();
+        let instances = &mut self.instances;
+        let started = self
+            .tasks
+            .start_planned_tasks(task_ids, &vcs_command, |tasks| {
+                if let Some(instance_id) = tasks.pending_instance_termination.take() {
+                    instances.close_pane_by_id(instance_id);
+                }
+            });
+        self.sync_task_instances();
+
+        let (started, without_room): (Vec<_>, Vec<_>) = started.into_iter().partition(|task_id| {
+            self.tasks
+                .find_task_by_id(*task_id)
+                .is_some_and(|task| task.instance_id.is_some())
+        });
+        for task_id in &without_room {
+            let queue_priority = queue_priorities.get(task_id).copied().flatten();
+            self.tasks.return_task_to_planning(*task_id, queue_priority);
+        }
+        let _ = self.save();
+
+        for task_id in &started {
+            if let Some(reference) = self.tasks.find_task_reference(*task_id)
+                && let Ok(task) = serde_json::to_value(&reference)
+            {
+                self.notify_control_subscribers("task_moved", &task);
+            }
+        }
+        (started, without_room)
+    }
+
     pub fn auto_transition_completed_tasks(&mut self) {
         let completed_instances: Vec<uuid::Uuid> = self
             .instances
@@ -290,9 +630,42 @@ impl App {
         }
     }
 
-    pub fn process_hook_event(&mut self, event: &crate::events::HookEvent) {
+    /// Applies a provider hook to the task it came from. `responder` is set when the hook is
+    /// a permission request blocked on an answer from the inbox.
+    pub fn process_hook_event(
+        &mut self,
+        event: &crate::events::HookEvent,
+        responder: Option<PermissionResponder>,
+    ) {
         let task_id = event.worktree_id;
 
+        if let Some(session_id) = event.session_id()
+            && self.tasks.set_task_session_id(task_id, session_id)
+        {
+            let _ = self.save();
+        }
+
+        if let Some(report) = self.transcripts.usage_from_hook(event) {
+            let session_id = event.session_id().unwrap_or_default();
+            if self.tasks.record_task_usage(task_id, session_id, report) {
+                if self.tasks.pause_task_over_budget(task_id) {
+                    self.interrupt_task_over_budget(task_id);
+                }
+                let _ = self.save();
+            }
+        }
+
+        // A paused task stays paused until its budget is raised, even if prompted again.
+        if event.event_type() == crate::events::EventType::Start
+            && self
+                .tasks
+                .find_task_by_id(task_id)
+                .is_some_and(|task| task.is_paused)
+        {
+            self.interrupt_task_over_budget(task_id);
+            return;
+        }
+
         let instance_id = self
             .tasks
             .columns
@@ -308,6 +681,7 @@ impl App {
         let Some(pane) = self.instances.find_pane_mut(instance_id) else {
             return;
         };
+        crate::views::instances::activity::log_hook_activity(pane, event);
 
         match event.event_type() {
             crate::events::EventType::Start => {
@@ -315,14 +689,160 @@ impl App {
                 let vcs_command = &self.settings.settings.vcs_command;
                 self.tasks
                     .move_task_to_in_progress_by_id(task_id, vcs_command);
+                self.permissions.clear_task(task_id);
             }
             crate::events::EventType::End => {
                 pane.claude_state = crate::views::instances::ClaudeState::Done;
+                self.permissions.clear_task(task_id);
             }
             crate::events::EventType::Permission => {
                 pane.claude_state = crate::views::instances::ClaudeState::NeedsPermissions;
+                let request = PermissionRequest::from_hook(event, responder);
+                self.receive_permission_request(request);
             }
-            crate::events::EventType::Unknown(_) => {}
+            crate::events::EventType::ToolUse
+            | crate::events::EventType::ToolResult
+            | crate::events::EventType::Notification
+            | crate::events::EventType::Unknown(_) => {}
+        }
+    }
+
+    /// Stops the agent of a task that has used up its budget, the way a user would by
+    /// pressing Escape in its terminal.
+    fn interrupt_task_over_budget(&mut self, task_id: uuid::Uuid) {
+        let Some(task) = self.tasks.find_task_by_id(task_id) else {
+            return;
+        };
+        let message = format!(
+            "Paused: budget of ${:.2} reached",
+            task.budget_usd.unwrap_or_default()
+        );
+
+        if let Some(instance_id) = task.instance_id {
+            self.instances
+                .send_raw_input_to_instance(instance_id, b"\x1b");
+            if let Some(pane) = self.instances.find_pane_mut(instance_id) {
+                pane.claude_state = crate::views::instances::ClaudeState::Idle;
+                pane.add_activity_event(
+                    crate::views::instances::state::ActivityEventType::ProviderNotification,
+                    message,
+                    None,
+                );
+            }
+        }
+
+        if let Some(reference) = self.tasks.find_task_reference(task_id)
+            && let Ok(task) = serde_json::to_value(&reference)
+        {
+            self.notify_control_subscribers("task_paused", &task);
+        }
+    }
+
+    /// Answers `request` from a standing rule, or queues it in the inbox.
+    fn receive_permission_request(&mut self, request: PermissionRequest) {
+        match self.standing_permission_decision(&request) {
+            Some(decision) => self.deliver_permission_decision(request, decision),
+            None => self.permissions.push(request),
+        }
+    }
+
+    fn standing_permission_decision(
+        &self,
+        request: &PermissionRequest,
+    ) -> Option<PermissionDecision> {
+        self.tasks
+            .find_task_by_id(request.task_id)
+            .and_then(|task| PermissionRule::find_decision(&task.permission_rules, &request.tool))
+            .or_else(|| {
+                PermissionRule::find_decision(
+                    &self.settings.settings.permission_rules,
+                    &request.tool,
+                )
+            })
+    }
+
+    /// Answers the selected inbox request, recording a rule when the answer should stick.
+    pub fn answer_permission(&mut self, decision: PermissionDecision, scope: PermissionScope) {
+        let Some(request) = self.permissions.take_selected() else {
+            return;
+        };
+
+        let rule = PermissionRule {
+            tool: request.tool.clone(),
+            decision,
+        };
+        match scope {
+            PermissionScope::Once => {}
+            PermissionScope::Task => {
+                self.tasks.add_task_permission_rule(request.task_id, rule);
+                let _ = self.save();
+            }
+            PermissionScope::Tool => {
+                PermissionRule::upsert(&mut self.settings.settings.permission_rules, rule);
+                let _ = self.save_settings();
+            }
+        }
+
+        self.deliver_permission_decision(request, decision);
+
+        if scope != PermissionScope::Once {
+            // Run the rest of the inbox past the new rule.
+            for request in self.permissions.take_all() {
+                self.receive_permission_request(request);
+            }
+        }
+    }
+
+    /// Sends `decision` to the waiting hook, or types it into the agent's terminal when the
+    /// provider has no hook that can take an answer.
+    fn deliver_permission_decision(
+        &mut self,
+        request: PermissionRequest,
+        decision: PermissionDecision,
+    ) {
+        let task_id = request.task_id;
+        let answered_through_hook = request.answer_through_hook(decision);
+        let Some(instance_id) = self
+            .tasks
+            .find_task_by_id(task_id)
+            .and_then(|task| task.instance_id)
+        else {
+            return;
+        };
+
+        if !answered_through_hook {
+            // Terminal permission prompts confirm their default "allow" choice on Enter and
+            // cancel on Escape.
+            let keys: &[u8] = match decision {
+                PermissionDecision::Allow => b"\r",
+                PermissionDecision::Deny => b"\x1b",
+            };
+            self.instances.send_raw_input_to_instance(instance_id, keys);
+        }
+
+        if let Some(pane) = self.instances.find_pane_mut(instance_id) {
+            pane.claude_state = crate::views::instances::ClaudeState::Running;
+        }
+    }
+
+    /// Leaves the selected request for the user to answer in the agent's own terminal.
+    pub fn open_permission_terminal(&mut self) {
+        let Some(request) = self.permissions.take_selected() else {
+            return;
+        };
+        self.permissions.close();
+
+        let instance_id = self
+            .tasks
+            .find_task_by_id(request.task_id)
+            .and_then(|task| task.instance_id);
+        // Dropping the request releases its hook, so the agent shows its own prompt.
+        drop(request);
+
+        if let Some(instance_id) = instance_id
+            && self.instances.select_pane_by_id(instance_id)
+        {
+            self.active_tab = Tab::Instances;
         }
     }
 
@@ -422,14 +942,103 @@ Do not push to remote.";
             .send_input_to_instance(instance_id, commit_prompt);
     }
 
+    /// Brings the base branch into the task's worktree with the configured merge strategy.
+    pub fn sync_task_with_base(&mut self, task_id: uuid::Uuid) {
+        if let Err(error) = self.sync_with_base_and_hand_off_conflicts(task_id) {
+            self.tasks.error_message = Some(format!("Sync failed: {error}"));
+        }
+    }
+
+    /// Leaves any conflicts in the worktree and asks the task's agent to resolve them, moving
+    /// the task back to its agent column when it is waiting for review. When either step
+    /// fails the conflicts are reported in `tasks.error_message`.
+    fn sync_with_base_and_hand_off_conflicts(&mut self, task_id: uuid::Uuid) -> anyhow::Result<()> {
+        let Some(task) = self.tasks.find_task_by_id(task_id) else {
+            return Ok(());
+        };
+        let Some(worktree_info) = task.worktree_info.clone() else {
+            anyhow::bail!("No worktree associated with this task.");
+        };
+        let instance_id = task.instance_id;
+        let title = task.title.clone();
+
+        let vcs_command = &self.settings.settings.vcs_command;
+        let strategy = self.settings.settings.merge_strategy;
+        let sync_result =
+            crate::views::worktree::sync_with_base(&worktree_info, strategy, vcs_command)?;
+        let crate::views::worktree::MergeResult::Conflicts { conflicted_files } = sync_result
+        else {
+            return Ok(());
+        };
+
+        let base_branch =
+            crate::views::worktree::get_default_branch(&worktree_info.worktree_path, vcs_command)
+                .unwrap_or_else(|_| "main".to_string());
+        let next_step = match (vcs_command, strategy) {
+            (VcsCommand::Jujutsu, _) => {
+                "Please resolve the conflicts in the working copy; jj has already recorded them in the affected changes."
+            }
+            (VcsCommand::Git, MergeStrategy::Rebase) => {
+                "Please resolve the conflicts, stage the files and run `git rebase --continue` until the rebase finishes."
+            }
+            (VcsCommand::Git, _) => {
+                "Please resolve the conflicts, stage the files and commit the merge."
+            }
+        };
+        let conflict_message = format!(
+            "Syncing this branch with {base_branch} conflicts in the following files:\n{}\n\n{next_step}",
+            conflicted_files.join("\n")
+        );
+
+        // A task waiting for review goes back to its agent column; when it cannot (the column
+        // is at its WIP limit), its agent is still told about the conflicts if it is running
+        let move_error = if let Some(task_index) = self.tasks.find_task_index_by_id(task_id)
+            && self
+                .tasks
+                .move_task_to_in_progress(task_index, vcs_command)
+                .is_none()
+        {
+            self.tasks.error_message.take()
+        } else {
+            None
+        };
+
+        let handed_off = instance_id.is_some_and(|instance_id| {
+            self.instances
+                .send_input_to_instance(instance_id, &conflict_message)
+        });
+        let problem = match (handed_off, move_error) {
+            (true, None) => return Ok(()),
+            (true, Some(move_error)) => {
+                format!("its agent was told, but the task stays in review: {move_error}")
+            }
+            (false, Some(move_error)) => format!("and no agent was told: {move_error}"),
+            (false, None) => "and no agent is running to resolve them".to_string(),
+        };
+        self.tasks.error_message = Some(format!(
+            "Syncing \"{title}\" left conflicts in its worktree, {problem}"
+        ));
+        Ok(())
+    }
+
+    /// Lands the task's branch on `target` with `strategy`. A squash commit is titled
+    /// `squash_subject`, or the task's title when there is none.
     pub fn merge_task_branch(
         &mut self,
         task_id: uuid::Uuid,
         target: &crate::views::tasks::state::MergeTarget,
+        strategy: MergeStrategy,
+        squash_subject: Option<&str>,
     ) {
         let Some(task) = self.tasks.find_task_by_id(task_id) else {
             return;
         };
+        if self.tasks.removing_worktrees.contains(&task_id) {
+            self.tasks.error_message =
+                Some("The worktree of this task is still being deleted.".to_string());
+            return;
+        }
+        let squash_subject = squash_subject.unwrap_or(&task.title).to_string();
 
         let Some(worktree_info) = &task.worktree_info else {
             self.tasks.error_message =
@@ -449,11 +1058,15 @@ Do not push to remote.";
             return;
         };
 
-        let has_conflicts =
-            crate::views::worktree::check_merge_conflicts(&repository_root, &worktree_info)
-                .ok()
-                .flatten()
-                .is_some();
+        let vcs_command = &self.settings.settings.vcs_command;
+        let has_conflicts = crate::views::worktree::check_merge_conflicts(
+            &repository_root,
+            &worktree_info,
+            vcs_command,
+        )
+        .ok()
+        .flatten()
+        .is_some();
 
         if has_conflicts {
             self.resolve_task_conflicts(task_id);
@@ -461,26 +1074,38 @@ Do not push to remote.";
         }
 
         let target_branch = target.branch_name();
-        let merge_result =
-            crate::views::worktree::merge_worktree(&repository_root, &worktree_info, target_branch);
+        let merge_result = crate::views::worktree::merge_worktree(
+            &repository_root,
+            &worktree_info,
+            target_branch,
+            strategy,
+            Some(&squash_subject),
+            vcs_command,
+        );
 
         match merge_result {
             Ok(crate::views::worktree::MergeResult::Success) => {
-                let vcs_command = &self.settings.settings.vcs_command;
-                let _ = crate::views::worktree::delete_worktree(
-                    &repository_root,
-                    &worktree_info,
-                    vcs_command,
-                );
-                self.tasks.move_task_to_done_by_id(task_id, vcs_command);
-                let _ = self.save();
+                self.delete_worktree(WorktreeDeletion {
+                    task_id,
+                    worktree_info,
+                    purpose: WorktreeDeletionPurpose::Complete,
+                });
             }
             Ok(crate::views::worktree::MergeResult::Conflicts { conflicted_files }) => {
+                let catch_up = match (vcs_command, strategy) {
+                    // jj has already rebased the workspace, leaving the conflicts in its changes
+                    (VcsCommand::Jujutsu, MergeStrategy::Rebase) => {
+                        format!("edit the conflicted changes now rebased onto {target_branch}")
+                    }
+                    (_, MergeStrategy::Rebase) => {
+                        format!("rebase this branch onto {target_branch}")
+                    }
+                    _ => format!("merge {target_branch} into this branch"),
+                };
                 let conflict_message = format!(
-                    "Please resolve merge conflicts in the following files:\n{}\n\nThen commit the resolution.",
+                    "Landing this branch on {target_branch} conflicts in the following files:\n{}\n\nPlease {catch_up}, resolve the conflicts and commit the resolution.",
                     conflicted_files.join("\n")
                 );
-                let vcs_command = &self.settings.settings.vcs_command;
                 if let Some(task_index) = self.tasks.find_task_index_by_id(task_id)
                     && let Some(instance_id) =
                         self.tasks.move_task_to_in_progress(task_index, vcs_command)
@@ -495,6 +1120,97 @@ Do not push to remote.";
         }
     }
 
+    /// Deletes the worktrees that moves let go of.
+    pub fn delete_pending_worktrees(&mut self) {
+        for deletion in std::mem::take(&mut self.tasks.pending_worktree_deletions) {
+            self.delete_worktree(deletion);
+        }
+    }
+
+    /// Deletes a task's worktree on a background thread, since its teardown command can take a
+    /// while, and finishes what the deletion was for once it is done.
+    fn delete_worktree(&mut self, deletion: WorktreeDeletion) {
+        let repository_root = std::env::current_dir()
+            .map_err(anyhow::Error::from)
+            .and_then(|directory| crate::views::worktree::find_repository_root(&directory));
+        let repository_root = match repository_root {
+            Ok(repository_root) => repository_root,
+            Err(error) => {
+                self.finish_worktree_deletion(deletion, Err(error.to_string()));
+                return;
+            }
+        };
+
+        let vcs_command = self.settings.settings.vcs_command.clone();
+        let delete = move |deletion: &WorktreeDeletion| {
+            crate::views::worktree::delete_worktree(
+                &repository_root,
+                &deletion.worktree_info,
+                &vcs_command,
+            )
+            .map_err(|error| format!("{error:#}"))
+        };
+
+        let Some(event_sender) = self.event_sender() else {
+            let result = delete(&deletion);
+            self.finish_worktree_deletion(deletion, result);
+            return;
+        };
+
+        self.tasks.removing_worktrees.insert(deletion.task_id);
+        std::thread::spawn(move || {
+            let result = delete(&deletion);
+            let _ = event_sender.send(AppEvent::WorktreeDeleted { deletion, result });
+        });
+    }
+
+    /// Moves the task on once its worktree is gone. When the worktree could not be deleted, the
+    /// task keeps it and stays where it was before.
+    pub fn finish_worktree_deletion(
+        &mut self,
+        deletion: WorktreeDeletion,
+        result: Result<(), String>,
+    ) {
+        let task_id = deletion.task_id;
+        self.tasks.removing_worktrees.remove(&task_id);
+        let title = self
+            .tasks
+            .find_task_by_id(task_id)
+            .map(|task| task.title.clone())
+            .unwrap_or_default();
+
+        match (result, deletion.purpose) {
+            (Ok(()), WorktreeDeletionPurpose::Moved { .. }) => return,
+            (Ok(()), WorktreeDeletionPurpose::Complete) => {
+                self.tasks.move_task_to_done_by_id(task_id);
+            }
+            (
+                Err(error),
+                WorktreeDeletionPurpose::Moved {
+                    source_column_index,
+                },
+            ) => {
+                self.tasks.error_message = Some(format!(
+                    "Could not delete the worktree of \"{title}\", so the task went back: {error}"
+                ));
+                self.tasks
+                    .restore_worktree(task_id, deletion.worktree_info, source_column_index);
+            }
+            (Err(error), WorktreeDeletionPurpose::Complete) => {
+                self.tasks.error_message = Some(format!(
+                    "\"{title}\" was merged, but it stays in review because its worktree could \
+                     not be deleted: {error}"
+                ));
+            }
+        }
+
+        if let Some(instance_id) = self.tasks.pending_instance_termination.take() {
+            self.instances.close_pane_by_id(instance_id);
+        }
+        self.sync_task_instances();
+        let _ = self.save();
+    }
+
     pub fn resolve_task_conflicts(&mut self, task_id: uuid::Uuid) {
         let Some(task) = self.tasks.find_task_by_id(task_id) else {
             return;
@@ -514,14 +1230,19 @@ Do not push to remote.";
             return;
         };
 
-        let conflicts =
-            crate::views::worktree::check_merge_conflicts(&repository_root, &worktree_info)
-                .ok()
-                .flatten()
-                .unwrap_or_default();
-
-        let default_branch = crate::views::worktree::get_default_branch(&repository_root)
-            .unwrap_or_else(|_| "main".to_string());
+        let vcs_command = &self.settings.settings.vcs_command;
+        let conflicts = crate::views::worktree::check_merge_conflicts(
+            &repository_root,
+            &worktree_info,
+            vcs_command,
+        )
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+
+        let default_branch =
+            crate::views::worktree::get_default_branch(&repository_root, vcs_command)
+                .unwrap_or_else(|_| "main".to_string());
 
         let conflict_message = if conflicts.is_empty() {
             format!(
@@ -537,7 +1258,6 @@ Do not push to remote.";
             )
         };
 
-        let vcs_command = &self.settings.settings.vcs_command;
         if let Some(task_index) = self.tasks.find_task_index_by_id(task_id)
             && let Some(instance_id) = self.tasks.move_task_to_in_progress(task_index, vcs_command)
         {
@@ -552,3 +1272,71 @@ impl Default for App {
         Self::new()
     }
 }
+
+fn no_room_for_agent_message(title: &str) -> String {
+    format!(
+        "No room for another agent pane to start \"{title}\"; close a pane in the Instances tab first"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ColumnDefinition, ColumnWorkflow};
+
+    #[test]
+    fn test_invalid_column_settings_keep_the_board_as_it_is() {
+        let mut app = App::new();
+        app.tasks.apply_column_definitions(&[
+            ColumnDefinition::new("Planning"),
+            ColumnDefinition::new("In Progress").starts_agent(),
+            ColumnDefinition::new("QA"),
+            ColumnDefinition::new("Done").terminal(),
+        ]);
+        app.tasks
+            .add_task_to_planning("Testing".to_string(), String::new(), TaskType::Task);
+        let task = app.tasks.columns[0].tasks.remove(0);
+        let task_id = task.id;
+        app.tasks.columns[2].tasks.push(task);
+        // Workflows are not saved, so a board loaded from disk has none
+        for column in &mut app.tasks.columns {
+            column.workflow = ColumnWorkflow::default();
+        }
+
+        app.settings.settings.columns = vec![ColumnDefinition::new("Planning").terminal()];
+        app.apply_column_settings();
+
+        assert!(app.tasks.error_message.is_some());
+        let column_names: Vec<&str> = app
+            .tasks
+            .columns
+            .iter()
+            .map(|column| column.name.as_str())
+            .collect();
+        assert_eq!(column_names, ["Planning", "In Progress", "QA", "Done"]);
+        assert_eq!(app.tasks.find_task_location(task_id), Some((2, 0)));
+        assert_eq!(app.tasks.agent_column_index(), Some(1));
+    }
+
+    #[test]
+    fn test_permission_answers_reach_the_hook_without_a_pane() {
+        let mut app = App::new();
+        app.tasks
+            .add_task_to_planning("Testing".to_string(), String::new(), TaskType::Task);
+        let task_id = app.tasks.columns[0].tasks[0].id;
+        let event = crate::events::HookEvent {
+            event: "permission".to_string(),
+            worktree_id: task_id,
+            timestamp: 0,
+            hook_data: serde_json::json!({ "tool_name": "Bash" }),
+            awaits_decision: true,
+        };
+        let (responder, decisions) = std::sync::mpsc::channel();
+        app.permissions
+            .push(PermissionRequest::from_hook(&event, Some(responder)));
+
+        app.answer_permission(PermissionDecision::Allow, PermissionScope::Once);
+
+        assert_eq!(decisions.try_recv(), Ok(PermissionDecision::Allow));
+    }
+}
@@ -0,0 +1,191 @@
+use super::task::{QueuePriorityArgument, TaskKindArgument};
+use crate::events::{ControlCommand, ControlMessage, ControlRequest, control};
+use clap::Subcommand;
+
+#[derive(Subcommand)]
+pub enum CtlCommand {
+    /// List tasks on the live board
+    ListTasks {
+        /// Only list tasks in this column (name or index)
+        #[arg(long, short)]
+        column: Option<String>,
+    },
+
+    /// Show a single task
+    GetTask {
+        /// Task ID or unique ID prefix
+        task: String,
+    },
+
+    /// Add a task to the Planning column
+    CreateTask {
+        /// Task title
+        title: String,
+
+        /// Longer description passed to the agent as part of the prompt
+        #[arg(long, short, default_value = "")]
+        description: String,
+
+        /// Task type
+        #[arg(long, short, value_enum, default_value_t = TaskKindArgument::Task)]
+        kind: TaskKindArgument,
+    },
+
+    /// Move a task to another column, starting or stopping its agent as the board would
+    MoveTask {
+        /// Task ID or unique ID prefix
+        task: String,
+
+        /// Target column (name such as "in-progress", or index)
+        column: String,
+    },
+
+    /// Type into a task's agent terminal
+    SendInput {
+        /// Task ID or unique ID prefix
+        task: String,
+
+        /// Text to send
+        input: String,
+
+        /// Send the text without pressing Enter afterwards
+        #[arg(long)]
+        no_submit: bool,
+    },
+
+    /// Print the current screen of a task's agent terminal
+    Output {
+        /// Task ID or unique ID prefix
+        task: String,
+
+        /// Number of scrollback lines to include above the screen
+        #[arg(long, short, default_value_t = 0)]
+        scrollback: usize,
+    },
+
+    /// Set the dollar budget after which a task's agent is paused
+    SetBudget {
+        /// Task ID or unique ID prefix
+        task: String,
+
+        /// Budget in dollars; omit to remove the budget
+        budget: Option<f64>,
+    },
+
+    /// Queue a planned task for the auto-run queue
+    SetQueue {
+        /// Task ID or unique ID prefix
+        task: String,
+
+        /// Queue priority; omit to take the task out of the queue
+        #[arg(value_enum)]
+        priority: Option<QueuePriorityArgument>,
+    },
+
+    /// Set the tasks that must reach Done before a task can start
+    SetDependencies {
+        /// Task ID or unique ID prefix
+        task: String,
+
+        /// Blocking task IDs or prefixes; omit to remove every dependency
+        dependencies: Vec<String>,
+    },
+
+    /// Stream board and agent events as JSON lines until interrupted
+    Subscribe,
+
+    /// Send a raw JSON request line and print the response
+    Request {
+        /// Request line such as `{"method":"list_tasks"}`
+        json: String,
+    },
+}
+
+impl CtlCommand {
+    fn into_request(self) -> Result<ControlRequest, String> {
+        let command = match self {
+            Self::ListTasks { column } => ControlCommand::ListTasks { column },
+            Self::GetTask { task } => ControlCommand::GetTask { task },
+            Self::CreateTask {
+                title,
+                description,
+                kind,
+            } => ControlCommand::CreateTask {
+                title,
+                description,
+                kind: kind.into(),
+            },
+            Self::MoveTask { task, column } => ControlCommand::MoveTask { task, column },
+            Self::SendInput {
+                task,
+                input,
+                no_submit,
+            } => ControlCommand::SendInput {
+                task,
+                input,
+                submit: !no_submit,
+            },
+            Self::Output { task, scrollback } => ControlCommand::GetPaneOutput { task, scrollback },
+            Self::SetBudget { task, budget } => ControlCommand::SetBudget {
+                task,
+                budget_usd: budget,
+            },
+            Self::SetQueue { task, priority } => ControlCommand::SetQueue {
+                task,
+                priority: priority.map(Into::into),
+            },
+            Self::SetDependencies { task, dependencies } => {
+                ControlCommand::SetDependencies { task, dependencies }
+            }
+            Self::Subscribe => ControlCommand::Subscribe,
+            Self::Request { json } => {
+                return serde_json::from_str(&json)
+                    .map_err(|error| format!("Invalid request: {error}"));
+            }
+        };
+
+        Ok(ControlRequest {
+            id: Some(1),
+            command,
+        })
+    }
+}
+
+pub fn handle_ctl_command(command: CtlCommand) -> Result<(), String> {
+    let is_output = matches!(command, CtlCommand::Output { .. });
+    let request = command.into_request()?;
+
+    if matches!(request.command, ControlCommand::Subscribe) {
+        return control::subscribe(|message| print_line(message).is_ok())
+            .map_err(|error| format!("Failed to reach Chloe: {error}"));
+    }
+
+    let response = control::send_request(&request)
+        .map_err(|error| format!("Failed to reach Chloe: {error}"))?;
+
+    let ControlMessage::Response { result, error, .. } = response else {
+        return Err("Unexpected notification in place of a response".to_string());
+    };
+
+    if let Some(error) = error {
+        return Err(error);
+    }
+
+    let result = result.unwrap_or_default();
+    if is_output && let Some(output) = result.get("output").and_then(|value| value.as_str()) {
+        println!("{output}");
+        return Ok(());
+    }
+
+    let json = serde_json::to_string_pretty(&result)
+        .map_err(|error| format!("Failed to serialize output: {error}"))?;
+    println!("{json}");
+    Ok(())
+}
+
+fn print_line(message: &ControlMessage) -> Result<(), String> {
+    let json = serde_json::to_string(message)
+        .map_err(|error| format!("Failed to serialize output: {error}"))?;
+    println!("{json}");
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use crate::persistence::storage;
+use clap::Subcommand;
+use std::os::unix::process::CommandExt;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+const STARTUP_POLL_ATTEMPTS: u32 = 50;
+const STARTUP_POLL_DELAY_MS: u64 = 100;
+
+#[derive(Subcommand, Clone, Copy)]
+pub enum DaemonCommand {
+    /// Start the daemon that keeps agents running after the TUI exits
+    Start {
+        /// Stay attached to this terminal instead of running in the background
+        #[arg(long)]
+        foreground: bool,
+    },
+
+    /// Stop the daemon, ending every agent it owns
+    Stop,
+
+    /// Show whether the daemon is running and which agents it owns
+    Status,
+}
+
+pub fn handle_daemon_command(command: DaemonCommand) -> Result<(), String> {
+    let socket_path = crate::daemon::get_socket_path();
+
+    match command {
+        DaemonCommand::Start { foreground: true } => {
+            crate::daemon::run(&socket_path).map_err(|error| format!("{error:#}"))
+        }
+        DaemonCommand::Start { foreground: false } => {
+            if crate::daemon::is_running() {
+                return Err(format!(
+                    "A Chloe daemon is already running on {}",
+                    socket_path.display()
+                ));
+            }
+
+            let executable = std::env::current_exe()
+                .map_err(|error| format!("Failed to locate the chloe binary: {error}"))?;
+            Command::new(executable)
+                .args(["daemon", "start", "--foreground"])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .process_group(0)
+                .spawn()
+                .map_err(|error| format!("Failed to start daemon: {error}"))?;
+
+            for _ in 0..STARTUP_POLL_ATTEMPTS {
+                if crate::daemon::is_running() {
+                    println!("Chloe daemon listening on {}", socket_path.display());
+                    return Ok(());
+                }
+                thread::sleep(Duration::from_millis(STARTUP_POLL_DELAY_MS));
+            }
+            Err("Daemon did not come up in time".to_string())
+        }
+        DaemonCommand::Stop => {
+            crate::daemon::shutdown().map_err(|error| format!("Daemon is not running: {error}"))?;
+            println!("Chloe daemon stopped");
+            Ok(())
+        }
+        DaemonCommand::Status => {
+            let Ok(pane_ids) = crate::daemon::list_sessions() else {
+                println!("Chloe daemon is not running");
+                return Ok(());
+            };
+
+            println!("Chloe daemon listening on {}", socket_path.display());
+            let tasks = storage::load_state()
+                .map(|app| {
+                    app.tasks
+                        .columns
+                        .into_iter()
+                        .flat_map(|column| column.tasks)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+
+            println!("{} running agent(s)", pane_ids.len());
+            for pane_id in pane_ids {
+                let title = tasks
+                    .iter()
+                    .find(|task| task.instance_id == Some(pane_id))
+                    .map_or("(no task)", |task| task.title.as_str());
+                println!("  {pane_id}  {title}");
+            }
+            Ok(())
+        }
+    }
+}
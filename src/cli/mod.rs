@@ -1,7 +1,18 @@
+mod ctl;
+mod daemon;
+mod task;
+mod usage;
+
+pub use ctl::{CtlCommand, handle_ctl_command};
+pub use daemon::{DaemonCommand, handle_daemon_command};
+pub use task::{TaskCommand, handle_task_command};
+pub use usage::handle_usage_command;
+
+use crate::types::PermissionDecision;
 use clap::{Parser, Subcommand};
 use std::fs::{self, OpenOptions};
 use std::io::{Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 #[derive(Parser)]
@@ -11,6 +22,11 @@ use uuid::Uuid;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// Open the board without saving changes or starting agents, alongside another Chloe
+    /// running in this project
+    #[arg(long)]
+    pub read_only: bool,
 }
 
 #[derive(Subcommand)]
@@ -20,16 +36,62 @@ pub enum Commands {
 
     /// Handle Claude Code hook events (internal use)
     Notify {
-        /// Event type: start, end, permission
+        /// Event type: start, end, permission, tool-use, tool-result, notification
         event_type: String,
 
         /// Worktree ID associated with this event
         #[arg(long)]
         worktree_id: Uuid,
+
+        /// Socket of the Chloe instance to notify (defaults to the current project's)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+
+        /// Wait for the permission inbox to answer and print the decision as hook output
+        #[arg(long)]
+        await_decision: bool,
+    },
+
+    /// Manage tasks on the board without opening the TUI
+    Task {
+        #[command(subcommand)]
+        command: TaskCommand,
+
+        /// Print machine-readable JSON instead of human-readable text
+        #[arg(long, global = true)]
+        json: bool,
+    },
+
+    /// Drive a running Chloe session over its control socket
+    Ctl {
+        #[command(subcommand)]
+        command: CtlCommand,
+    },
+
+    /// Run the background daemon that keeps agents alive when the TUI exits
+    Daemon {
+        #[command(subcommand)]
+        command: DaemonCommand,
+    },
+
+    /// Summarize token usage and estimated cost per provider and per day
+    Usage {
+        /// Only show the most recent days
+        #[arg(long)]
+        days: Option<usize>,
+
+        /// Print machine-readable JSON instead of human-readable text
+        #[arg(long)]
+        json: bool,
     },
 }
 
-pub fn handle_notify_command(event_type: String, worktree_id: Uuid) -> Result<(), String> {
+pub fn handle_notify_command(
+    event_type: String,
+    worktree_id: Uuid,
+    socket: Option<PathBuf>,
+    await_decision: bool,
+) -> Result<(), String> {
     let mut hook_data = String::new();
     std::io::stdin()
         .read_to_string(&mut hook_data)
@@ -48,14 +110,43 @@ pub fn handle_notify_command(event_type: String, worktree_id: Uuid) -> Result<()
         worktree_id,
         timestamp,
         hook_data: hook_data_value,
+        awaits_decision: await_decision,
     };
 
+    let socket_path = socket.unwrap_or_else(crate::events::get_socket_path);
+
+    if await_decision {
+        // Without an answer the hook prints nothing and the agent asks in its own terminal.
+        if let Ok(Some(decision)) = crate::events::request_permission_decision(&event, &socket_path)
+        {
+            println!("{}", permission_hook_output(decision));
+        }
+        return Ok(());
+    }
+
     // Silently ignore errors - Chloe TUI may not be running
-    let _ = crate::events::send_event(&event);
+    let _ = crate::events::send_event(&event, &socket_path);
 
     Ok(())
 }
 
+/// Claude Code's `PermissionRequest` hook output for `decision`.
+fn permission_hook_output(decision: PermissionDecision) -> serde_json::Value {
+    let decision = match decision {
+        PermissionDecision::Allow => serde_json::json!({ "behavior": "allow" }),
+        PermissionDecision::Deny => serde_json::json!({
+            "behavior": "deny",
+            "message": "Denied from the Chloe permission inbox",
+        }),
+    };
+    serde_json::json!({
+        "hookSpecificOutput": {
+            "hookEventName": "PermissionRequest",
+            "decision": decision,
+        }
+    })
+}
+
 pub fn handle_init_command() -> Result<(), String> {
     let chloe_directory = Path::new(".chloe");
     let gitignore_path = Path::new(".gitignore");
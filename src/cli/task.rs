@@ -0,0 +1,514 @@
+use crate::helpers::text::format_token_count;
+use crate::persistence::lock::ProjectLock;
+use crate::persistence::storage;
+use crate::types::AgentProvider;
+use crate::views::settings::VcsCommand;
+use crate::views::tasks::state::{QueuePriority, TaskType, TasksState, WorktreeDeletionPurpose};
+use clap::{Subcommand, ValueEnum};
+use serde::Serialize;
+use uuid::Uuid;
+
+const SHORT_ID_LENGTH: usize = 8;
+
+#[derive(Subcommand)]
+pub enum TaskCommand {
+    /// Add a task to the Planning column
+    Add {
+        /// Task title
+        title: String,
+
+        /// Longer description passed to the agent as part of the prompt
+        #[arg(long, short, default_value = "")]
+        description: String,
+
+        /// Task type
+        #[arg(long, short, value_enum, default_value_t = TaskKindArgument::Task)]
+        kind: TaskKindArgument,
+
+        /// Task that must reach Done before this one can start (repeatable)
+        #[arg(long = "depends-on", value_name = "TASK")]
+        depends_on: Vec<String>,
+
+        /// Let the auto-run queue start the task, optionally with a priority
+        #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "normal")]
+        queue: Option<QueuePriorityArgument>,
+    },
+
+    /// List tasks, grouped by column
+    List {
+        /// Only list tasks in this column (name or index)
+        #[arg(long, short)]
+        column: Option<String>,
+    },
+
+    /// Show a single task
+    Show {
+        /// Task ID or unique ID prefix
+        task: String,
+    },
+
+    /// Move a task to another column
+    Move {
+        /// Task ID or unique ID prefix
+        task: String,
+
+        /// Target column (name such as "in-progress", or index)
+        column: String,
+    },
+
+    /// Delete a task
+    Delete {
+        /// Task ID or unique ID prefix
+        task: String,
+    },
+
+    /// Edit a task's title, description, type, budget, dependencies or queue priority
+    Edit {
+        /// Task ID or unique ID prefix
+        task: String,
+
+        /// New title
+        #[arg(long, short)]
+        title: Option<String>,
+
+        /// New description
+        #[arg(long, short)]
+        description: Option<String>,
+
+        /// New task type
+        #[arg(long, short, value_enum)]
+        kind: Option<TaskKindArgument>,
+
+        /// Pause the agent once the task has cost this many dollars
+        #[arg(long, conflicts_with = "no_budget", value_parser = parse_budget)]
+        budget: Option<f64>,
+
+        /// Remove the task's budget
+        #[arg(long)]
+        no_budget: bool,
+
+        /// Add a task that must reach Done before this one can start (repeatable)
+        #[arg(long = "depends-on", value_name = "TASK")]
+        depends_on: Vec<String>,
+
+        /// Stop depending on a task (repeatable)
+        #[arg(long = "remove-dependency", value_name = "TASK")]
+        remove_dependency: Vec<String>,
+
+        /// Let the auto-run queue start the task, optionally with a priority
+        #[arg(
+            long,
+            value_enum,
+            num_args = 0..=1,
+            default_missing_value = "normal",
+            conflicts_with = "unqueue"
+        )]
+        queue: Option<QueuePriorityArgument>,
+
+        /// Take the task out of the auto-run queue
+        #[arg(long)]
+        unqueue: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TaskKindArgument {
+    Feature,
+    Bug,
+    Chore,
+    Task,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum QueuePriorityArgument {
+    High,
+    Normal,
+    Low,
+}
+
+impl From<QueuePriorityArgument> for QueuePriority {
+    fn from(argument: QueuePriorityArgument) -> Self {
+        match argument {
+            QueuePriorityArgument::High => Self::High,
+            QueuePriorityArgument::Normal => Self::Normal,
+            QueuePriorityArgument::Low => Self::Low,
+        }
+    }
+}
+
+impl From<TaskKindArgument> for TaskType {
+    fn from(argument: TaskKindArgument) -> Self {
+        match argument {
+            TaskKindArgument::Feature => Self::Feature,
+            TaskKindArgument::Bug => Self::Bug,
+            TaskKindArgument::Chore => Self::Chore,
+            TaskKindArgument::Task => Self::Task,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DeletedRecord {
+    id: Uuid,
+    deleted: bool,
+}
+
+enum TaskOutput {
+    Task(Uuid),
+    List(Option<usize>),
+    Deleted(Uuid),
+}
+
+/// Reads a `--budget` amount, which has to be a positive number of dollars
+fn parse_budget(value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|budget| budget.is_finite() && *budget > 0.0)
+        .ok_or_else(|| "expected a number of dollars above 0".to_string())
+}
+
+pub fn handle_task_command(command: TaskCommand, json: bool) -> Result<(), String> {
+    let is_mutation = !matches!(command, TaskCommand::List { .. } | TaskCommand::Show { .. });
+    let _project_lock = is_mutation.then(lock_board).transpose()?;
+    let (mut app, vcs_command) = load_board()?;
+
+    let output = match command {
+        TaskCommand::Add {
+            title,
+            description,
+            kind,
+            depends_on,
+            queue,
+        } => {
+            app.tasks
+                .add_task_to_planning(title, description, kind.into());
+            let task_id = app.tasks.columns[0]
+                .tasks
+                .last()
+                .map(|task| task.id)
+                .ok_or_else(|| "Failed to add task".to_string())?;
+            update_dependencies(&mut app.tasks, task_id, &depends_on, &[])?;
+            if let Some(priority) = queue {
+                app.tasks
+                    .set_task_queue_priority(task_id, Some(priority.into()))?;
+            }
+            TaskOutput::Task(task_id)
+        }
+        TaskCommand::List { column } => {
+            let column_index = column
+                .map(|column| app.tasks.resolve_column_reference(&column))
+                .transpose()?;
+            TaskOutput::List(column_index)
+        }
+        TaskCommand::Show { task } => TaskOutput::Task(app.tasks.resolve_task_reference(&task)?),
+        TaskCommand::Move { task, column } => {
+            let task_id = app.tasks.resolve_task_reference(&task)?;
+            let column_index = app.tasks.resolve_column_reference(&column)?;
+            let move_result =
+                app.tasks
+                    .move_task_to_column(task_id, column_index, &vcs_command, |tasks| {
+                        process_pending_cleanup(tasks, &vcs_command);
+                    });
+            if move_result.is_err() {
+                // Earlier steps may already have created or removed a worktree
+                save_board(&app)?;
+            }
+            move_result?;
+            report_queued_dependents(&app.tasks, task_id, json);
+            TaskOutput::Task(task_id)
+        }
+        TaskCommand::Delete { task } => {
+            let task_id = app.tasks.resolve_task_reference(&task)?;
+            delete_task_worktree(&app.tasks, task_id, &vcs_command)?;
+            app.tasks.delete_task_by_id(task_id);
+            TaskOutput::Deleted(task_id)
+        }
+        TaskCommand::Edit {
+            task,
+            title,
+            description,
+            kind,
+            budget,
+            no_budget,
+            depends_on,
+            remove_dependency,
+            queue,
+            unqueue,
+        } => {
+            let task_id = app.tasks.resolve_task_reference(&task)?;
+            if let Some(title) = title {
+                app.tasks.update_task_title_by_id(task_id, title);
+            }
+            if let Some(description) = description {
+                app.tasks
+                    .update_task_description_by_id(task_id, description);
+            }
+            if let Some(kind) = kind {
+                app.tasks.update_task_kind_by_id(task_id, kind.into());
+            }
+            if budget.is_some() || no_budget {
+                app.tasks.set_task_budget(task_id, budget);
+            }
+            if !depends_on.is_empty() || !remove_dependency.is_empty() {
+                update_dependencies(&mut app.tasks, task_id, &depends_on, &remove_dependency)?;
+            }
+            if queue.is_some() || unqueue {
+                app.tasks
+                    .set_task_queue_priority(task_id, queue.map(Into::into))?;
+            }
+            TaskOutput::Task(task_id)
+        }
+    };
+
+    if is_mutation {
+        save_board(&app)?;
+    }
+
+    match output {
+        TaskOutput::Task(task_id) => print_task(&app.tasks, task_id, json),
+        TaskOutput::List(column_index) => print_task_list(&app.tasks, column_index, json),
+        TaskOutput::Deleted(task_id) => print_deleted(task_id, json),
+    }
+}
+
+fn load_board() -> Result<(crate::app::App, VcsCommand), String> {
+    let mut app =
+        storage::load_state().map_err(|error| format!("Failed to load task board: {error}"))?;
+    let settings =
+        storage::load_settings().map_err(|error| format!("Failed to load settings: {error}"))?;
+    app.tasks
+        .apply_column_definitions(settings.validated_columns()?);
+    Ok((app, settings.vcs_command))
+}
+
+/// Keeps a running Chloe from writing the board while this command changes it.
+fn lock_board() -> Result<ProjectLock, String> {
+    ProjectLock::try_acquire()
+        .map_err(|error| format!("Failed to lock the task board: {error}"))?
+        .ok_or_else(|| {
+            let holder = ProjectLock::holder_pid()
+                .map_or_else(String::new, |pid| format!(" (PID {pid})"));
+            format!(
+                "Chloe is running in this project{holder}; change its board with `chloe ctl` or close it first"
+            )
+        })
+}
+
+fn save_board(app: &crate::app::App) -> Result<(), String> {
+    storage::save_state(app).map_err(|error| format!("Failed to save task board: {error}"))
+}
+
+fn update_dependencies(
+    tasks: &mut TasksState,
+    task_id: Uuid,
+    added: &[String],
+    removed: &[String],
+) -> Result<(), String> {
+    let mut dependencies = tasks
+        .find_task_by_id(task_id)
+        .map(|task| task.dependencies.clone())
+        .unwrap_or_default();
+
+    for reference in removed {
+        let dependency_id = tasks.resolve_task_reference(reference)?;
+        dependencies.retain(|id| *id != dependency_id);
+    }
+    for reference in added {
+        dependencies.push(tasks.resolve_task_reference(reference)?);
+    }
+    tasks.set_task_dependencies(task_id, dependencies)
+}
+
+/// Tells the user which dependents of `task_id` can start now. They wait in the auto-run
+/// queue for a running Chloe to start them.
+fn report_queued_dependents(tasks: &TasksState, task_id: Uuid, json: bool) {
+    if json {
+        return;
+    }
+    for task in tasks.startable_queued_tasks() {
+        if task.dependencies.contains(&task_id) {
+            eprintln!("Queued unblocked task {}", task.id);
+        }
+    }
+}
+
+/// Removes the worktree Chloe created for the task, after its teardown command, so deleting
+/// the task does not leave it behind. Worktrees the user picked for the task are kept.
+fn delete_task_worktree(
+    tasks: &TasksState,
+    task_id: Uuid,
+    vcs_command: &VcsCommand,
+) -> Result<(), String> {
+    let Some(worktree_info) = tasks
+        .find_task_by_id(task_id)
+        .and_then(|task| task.worktree_info.as_ref())
+        .filter(|worktree_info| worktree_info.auto_created)
+    else {
+        return Ok(());
+    };
+
+    crate::views::worktree::delete_worktree(&repository_root()?, worktree_info, vcs_command)
+        .map_err(|error| format!("Could not delete the worktree, so the task was kept: {error:#}"))
+}
+
+fn repository_root() -> Result<std::path::PathBuf, String> {
+    std::env::current_dir()
+        .map_err(anyhow::Error::from)
+        .and_then(|directory| crate::views::worktree::find_repository_root(&directory))
+        .map_err(|error| error.to_string())
+}
+
+fn process_pending_cleanup(tasks: &mut TasksState, vcs_command: &VcsCommand) {
+    tasks.pending_instance_creation = None;
+    tasks.pending_instance_termination = None;
+
+    let deletions = std::mem::take(&mut tasks.pending_worktree_deletions);
+    if deletions.is_empty() {
+        return;
+    }
+
+    let repository_root = repository_root();
+    for deletion in deletions {
+        let result = repository_root.clone().and_then(|root| {
+            crate::views::worktree::delete_worktree(&root, &deletion.worktree_info, vcs_command)
+                .map_err(|error| format!("{error:#}"))
+        });
+        // The task goes back with its worktree, which stops the move
+        if let (
+            Err(error),
+            WorktreeDeletionPurpose::Moved {
+                source_column_index,
+            },
+        ) = (result, deletion.purpose)
+        {
+            tasks.error_message = Some(format!("Could not delete the worktree: {error}"));
+            tasks.restore_worktree(
+                deletion.task_id,
+                deletion.worktree_info,
+                source_column_index,
+            );
+        }
+    }
+}
+
+fn print_task(tasks: &TasksState, task_id: Uuid, json: bool) -> Result<(), String> {
+    let reference = tasks
+        .find_task_reference(task_id)
+        .ok_or_else(|| format!("No task with ID {task_id}"))?;
+
+    if json {
+        return print_json(&reference);
+    }
+
+    let task = reference.task;
+    println!("ID:          {}", task.id);
+    println!("Title:       {}", task.title);
+    println!("Type:        {}", task.kind.badge_text());
+    println!("Column:      {}", reference.column_name);
+    println!(
+        "Provider:    {}",
+        task.provider
+            .as_ref()
+            .map_or("(default)", AgentProvider::display_name)
+    );
+    if let Some(session_id) = &task.session_id {
+        println!("Session:     {session_id}");
+    }
+    if let Some(worktree_info) = &task.worktree_info {
+        println!(
+            "Worktree:    {} ({})",
+            worktree_info.branch_name,
+            worktree_info.worktree_path.display()
+        );
+    }
+    if !task.usage.is_empty() {
+        let usage = task.usage.total();
+        println!(
+            "Usage:       {} in, {} out, {} cached, ${:.2}",
+            format_token_count(usage.input_tokens + usage.cache_write_tokens),
+            format_token_count(usage.output_tokens),
+            format_token_count(usage.cache_read_tokens),
+            usage.cost_usd
+        );
+    }
+    if let Some(budget_usd) = task.budget_usd {
+        let paused = if task.is_paused { " (paused)" } else { "" };
+        println!("Budget:      ${budget_usd:.2}{paused}");
+    }
+    if let Some(priority) = task.queue_priority {
+        println!("Queued:      {priority:?} priority");
+    }
+    for (index, dependency_id) in task.dependencies.iter().enumerate() {
+        let label = if index == 0 { "Depends on:" } else { "" };
+        let dependency = tasks
+            .find_task_reference(*dependency_id)
+            .map_or_else(String::new, |dependency| {
+                format!(" {} ({})", dependency.task.title, dependency.column_name)
+            });
+        println!(
+            "{label:<12} {}{dependency}",
+            &dependency_id.to_string()[..SHORT_ID_LENGTH]
+        );
+    }
+    println!(
+        "Created:     {}",
+        task.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    if !task.description.is_empty() {
+        println!();
+        println!("{}", task.description);
+    }
+
+    Ok(())
+}
+
+fn print_task_list(
+    tasks: &TasksState,
+    column_filter: Option<usize>,
+    json: bool,
+) -> Result<(), String> {
+    if json {
+        return print_json(&tasks.list_task_references(column_filter));
+    }
+
+    let columns = tasks
+        .columns
+        .iter()
+        .enumerate()
+        .filter(|(column_index, _)| column_filter.is_none_or(|filter| filter == *column_index))
+        .map(|(_, column)| column);
+
+    for column in columns {
+        println!("{} ({})", column.name, column.tasks.len());
+        for task in &column.tasks {
+            println!(
+                "  {}  {:<5}  {}",
+                &task.id.to_string()[..SHORT_ID_LENGTH],
+                task.kind.badge_text(),
+                task.title
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn print_deleted(task_id: Uuid, json: bool) -> Result<(), String> {
+    if json {
+        return print_json(&DeletedRecord {
+            id: task_id,
+            deleted: true,
+        });
+    }
+
+    println!("Deleted task {task_id}");
+    Ok(())
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|error| format!("Failed to serialize output: {error}"))?;
+    println!("{json}");
+    Ok(())
+}
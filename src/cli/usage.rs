@@ -0,0 +1,70 @@
+use crate::helpers::text::format_token_count;
+use crate::persistence::storage;
+use crate::types::TokenUsage;
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Default)]
+struct UsageSummary {
+    total: TokenUsage,
+    by_provider: BTreeMap<String, TokenUsage>,
+    by_day: BTreeMap<NaiveDate, TokenUsage>,
+}
+
+pub fn handle_usage_command(days: Option<usize>, json: bool) -> Result<(), String> {
+    let app =
+        storage::load_state().map_err(|error| format!("Failed to load task board: {error}"))?;
+    let default_provider = storage::load_settings()
+        .map(|settings| settings.default_provider)
+        .unwrap_or_default();
+
+    let mut summary = UsageSummary::default();
+    for task in app.tasks.columns.iter().flat_map(|column| &column.tasks) {
+        let provider = task.provider.as_ref().unwrap_or(&default_provider);
+        let task_total = task.usage.total();
+        summary.total += task_total;
+        *summary
+            .by_provider
+            .entry(provider.display_name().to_string())
+            .or_default() += task_total;
+        for (day, usage) in task.usage.by_day() {
+            *summary.by_day.entry(day).or_default() += usage;
+        }
+    }
+
+    if let Some(days) = days {
+        let skipped = summary.by_day.len().saturating_sub(days);
+        summary.by_day = summary.by_day.into_iter().skip(skipped).collect();
+    }
+
+    if json {
+        let json = serde_json::to_string_pretty(&summary)
+            .map_err(|error| format!("Failed to serialize output: {error}"))?;
+        println!("{json}");
+        return Ok(());
+    }
+
+    println!("{}", usage_row("Total", &summary.total));
+    println!();
+    println!("By provider");
+    for (provider, usage) in &summary.by_provider {
+        println!("  {}", usage_row(provider, usage));
+    }
+    println!();
+    println!("By day");
+    for (day, usage) in &summary.by_day {
+        println!("  {}", usage_row(&day.to_string(), usage));
+    }
+    Ok(())
+}
+
+fn usage_row(label: &str, usage: &TokenUsage) -> String {
+    format!(
+        "{label:<14} {:>8} in  {:>8} out  {:>8} cached  {:>9}",
+        format_token_count(usage.input_tokens + usage.cache_write_tokens),
+        format_token_count(usage.output_tokens),
+        format_token_count(usage.cache_read_tokens),
+        format!("${:.2}", usage.cost_usd)
+    )
+}
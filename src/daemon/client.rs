@@ -0,0 +1,239 @@
+use super::protocol::{Message, SpawnRequest};
+use crate::events::AppEvent;
+use crate::views::instances::pty::EventProxy;
+use alacritty_terminal::term::Term;
+use alacritty_terminal::vte::ansi::{Processor, StdSyncHandler};
+use anyhow::anyhow;
+use std::collections::HashMap;
+use std::io::BufReader;
+use std::os::unix::net::UnixStream;
+use std::sync::{Arc, Mutex, mpsc as std_mpsc};
+use std::thread;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+const ACKNOWLEDGEMENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+type Panes = Arc<Mutex<HashMap<Uuid, PaneSink>>>;
+type Acknowledgements = Arc<Mutex<HashMap<Uuid, std_mpsc::Sender<Result<(), String>>>>>;
+
+/// The TUI's connection to the daemon.
+///
+/// Output for every attached pane arrives on one socket and is fed into that pane's local
+/// `Term`, then forwarded as `AppEvent::PtyOutput` exactly as a local PTY would.
+pub struct DaemonClient {
+    writer: Mutex<UnixStream>,
+    panes: Panes,
+    acknowledgements: Acknowledgements,
+}
+
+struct PaneSink {
+    term: Arc<Mutex<Term<EventProxy>>>,
+    processor: Processor<StdSyncHandler>,
+    event_sender: mpsc::UnboundedSender<AppEvent>,
+}
+
+impl DaemonClient {
+    /// Connects to the current project's daemon.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no daemon is listening.
+    pub fn connect() -> std::io::Result<Arc<Self>> {
+        let stream = UnixStream::connect(super::get_socket_path())?;
+        let reader = stream.try_clone()?;
+
+        let client = Arc::new(Self {
+            writer: Mutex::new(stream),
+            panes: Arc::default(),
+            acknowledgements: Arc::default(),
+        });
+
+        let panes = Arc::clone(&client.panes);
+        let acknowledgements = Arc::clone(&client.acknowledgements);
+        thread::spawn(move || receive(reader, &panes, &acknowledgements));
+
+        Ok(client)
+    }
+
+    /// Starts an agent in the daemon and attaches `term` to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the daemon cannot start the process or does not answer.
+    pub fn spawn(
+        &self,
+        request: SpawnRequest,
+        term: Arc<Mutex<Term<EventProxy>>>,
+        event_sender: mpsc::UnboundedSender<AppEvent>,
+    ) -> anyhow::Result<()> {
+        let pane_id = request.pane_id;
+        self.request(pane_id, &[Message::Spawn(request)], term, event_sender)
+    }
+
+    /// Attaches `term` to a session that is already running, replaying its current screen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the daemon has no such session or does not answer.
+    pub fn attach(
+        &self,
+        pane_id: Uuid,
+        rows: u16,
+        columns: u16,
+        term: Arc<Mutex<Term<EventProxy>>>,
+        event_sender: mpsc::UnboundedSender<AppEvent>,
+    ) -> anyhow::Result<()> {
+        let messages = [
+            Message::Resize {
+                pane_id,
+                rows,
+                columns,
+            },
+            Message::Attach { pane_id },
+        ];
+        self.request(pane_id, &messages, term, event_sender)
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the connection to the daemon is gone.
+    pub fn write_input(&self, pane_id: Uuid, data: &[u8]) -> anyhow::Result<()> {
+        self.send(&Message::Input {
+            pane_id,
+            data: data.to_vec(),
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the connection to the daemon is gone.
+    pub fn resize(&self, pane_id: Uuid, rows: u16, columns: u16) -> anyhow::Result<()> {
+        self.send(&Message::Resize {
+            pane_id,
+            rows,
+            columns,
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the connection to the daemon is gone.
+    pub fn kill(&self, pane_id: Uuid) -> anyhow::Result<()> {
+        self.send(&Message::Kill { pane_id })
+    }
+
+    fn send(&self, message: &Message) -> anyhow::Result<()> {
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|_| anyhow!("Daemon connection lock poisoned"))?;
+        message.write_to(&mut *writer)?;
+        drop(writer);
+        Ok(())
+    }
+
+    fn request(
+        &self,
+        pane_id: Uuid,
+        messages: &[Message],
+        term: Arc<Mutex<Term<EventProxy>>>,
+        event_sender: mpsc::UnboundedSender<AppEvent>,
+    ) -> anyhow::Result<()> {
+        let (sender, receiver) = std_mpsc::channel();
+        if let Ok(mut acknowledgements) = self.acknowledgements.lock() {
+            acknowledgements.insert(pane_id, sender);
+        }
+        if let Ok(mut panes) = self.panes.lock() {
+            panes.insert(
+                pane_id,
+                PaneSink {
+                    term,
+                    processor: Processor::new(),
+                    event_sender,
+                },
+            );
+        }
+
+        let result = messages
+            .iter()
+            .try_for_each(|message| self.send(message))
+            .and_then(|()| {
+                receiver.recv_timeout(ACKNOWLEDGEMENT_TIMEOUT).map_or_else(
+                    |_| Err(anyhow!("Chloe daemon did not respond")),
+                    |result| result.map_err(|message| anyhow!(message)),
+                )
+            });
+
+        if let Ok(mut acknowledgements) = self.acknowledgements.lock() {
+            acknowledgements.remove(&pane_id);
+        }
+        if result.is_err()
+            && let Ok(mut panes) = self.panes.lock()
+        {
+            panes.remove(&pane_id);
+        }
+
+        result
+    }
+}
+
+impl std::fmt::Debug for DaemonClient {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.debug_struct("DaemonClient").finish()
+    }
+}
+
+fn receive(stream: UnixStream, panes: &Panes, acknowledgements: &Acknowledgements) {
+    let mut reader = BufReader::new(stream);
+
+    while let Ok(Some(message)) = Message::read_from(&mut reader) {
+        match message {
+            Message::Output { pane_id, data } => {
+                if let Ok(mut panes) = panes.lock()
+                    && let Some(sink) = panes.get_mut(&pane_id)
+                {
+                    if let Ok(mut term) = sink.term.lock() {
+                        sink.processor.advance(&mut *term, &data);
+                    }
+                    let _ = sink
+                        .event_sender
+                        .send(AppEvent::PtyOutput { pane_id, data });
+                }
+            }
+            Message::Exit { pane_id } => {
+                let sink = panes
+                    .lock()
+                    .ok()
+                    .and_then(|mut panes| panes.remove(&pane_id));
+                if let Some(sink) = sink {
+                    let _ = sink.event_sender.send(AppEvent::PtyExit { pane_id });
+                }
+            }
+            Message::Spawned { pane_id } => acknowledge(acknowledgements, pane_id, Ok(())),
+            Message::Error { pane_id, message } => {
+                acknowledge(acknowledgements, pane_id, Err(message));
+            }
+            _ => {}
+        }
+    }
+
+    // The daemon went away, taking every agent with it.
+    if let Ok(mut panes) = panes.lock() {
+        for (pane_id, sink) in panes.drain() {
+            let _ = sink.event_sender.send(AppEvent::PtyExit { pane_id });
+        }
+    }
+    if let Ok(mut acknowledgements) = acknowledgements.lock() {
+        acknowledgements.clear();
+    }
+}
+
+fn acknowledge(acknowledgements: &Acknowledgements, pane_id: Uuid, result: Result<(), String>) {
+    if let Ok(acknowledgements) = acknowledgements.lock()
+        && let Some(sender) = acknowledgements.get(&pane_id)
+    {
+        let _ = sender.send(result);
+    }
+}
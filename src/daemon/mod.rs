@@ -0,0 +1,56 @@
+//! Background process that owns agent PTYs so they outlive the TUI.
+//!
+//! `chloe daemon start` runs a per-project server on a Unix socket next to the hook socket.
+//! While it is running, the TUI spawns agents inside it and attaches to their output instead
+//! of owning the processes, so quitting Chloe or losing an SSH session only detaches. The next
+//! TUI to start reattaches to every session that is still alive.
+
+mod client;
+mod protocol;
+mod server;
+mod snapshot;
+
+pub use client::DaemonClient;
+pub use protocol::{Message, SpawnRequest};
+pub use server::run;
+
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+#[must_use]
+pub fn get_socket_path() -> PathBuf {
+    crate::events::get_socket_path().with_extension("daemon.sock")
+}
+
+#[must_use]
+pub fn is_running() -> bool {
+    UnixStream::connect(get_socket_path()).is_ok()
+}
+
+/// Asks the daemon which panes it is keeping alive.
+///
+/// # Errors
+///
+/// Returns an error if the daemon is not running or replies with something unexpected.
+pub fn list_sessions() -> std::io::Result<Vec<Uuid>> {
+    match request(&Message::List)? {
+        Some(Message::Sessions(pane_ids)) => Ok(pane_ids),
+        _ => Err(std::io::Error::other("Unexpected reply from daemon")),
+    }
+}
+
+/// Stops the daemon and every agent it owns.
+///
+/// # Errors
+///
+/// Returns an error if the daemon is not running.
+pub fn shutdown() -> std::io::Result<()> {
+    request(&Message::Shutdown).map(|_| ())
+}
+
+fn request(message: &Message) -> std::io::Result<Option<Message>> {
+    let mut stream = UnixStream::connect(get_socket_path())?;
+    message.write_to(&mut stream)?;
+    Message::read_from(&mut stream)
+}
@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+const MAX_PAYLOAD_BYTES: usize = 16 * 1024 * 1024;
+
+/// Everything the daemon needs to start an agent, mirroring `pty::SpawnOptions`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpawnRequest {
+    pub pane_id: Uuid,
+    pub working_directory: PathBuf,
+    pub rows: u16,
+    pub columns: u16,
+    pub command: Option<String>,
+    pub arguments: Vec<String>,
+    pub environment: HashMap<String, String>,
+}
+
+/// A frame on the daemon socket.
+///
+/// Frames are a one byte tag, the 16 byte pane id, a big-endian `u32` payload length and the
+/// payload, so terminal output travels as raw bytes instead of being escaped into JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Spawn(SpawnRequest),
+    Attach {
+        pane_id: Uuid,
+    },
+    Input {
+        pane_id: Uuid,
+        data: Vec<u8>,
+    },
+    Resize {
+        pane_id: Uuid,
+        rows: u16,
+        columns: u16,
+    },
+    Kill {
+        pane_id: Uuid,
+    },
+    List,
+    Shutdown,
+    Spawned {
+        pane_id: Uuid,
+    },
+    Output {
+        pane_id: Uuid,
+        data: Vec<u8>,
+    },
+    Exit {
+        pane_id: Uuid,
+    },
+    Sessions(Vec<Uuid>),
+    Error {
+        pane_id: Uuid,
+        message: String,
+    },
+}
+
+mod tag {
+    pub const SPAWN: u8 = 1;
+    pub const ATTACH: u8 = 2;
+    pub const INPUT: u8 = 3;
+    pub const RESIZE: u8 = 4;
+    pub const KILL: u8 = 5;
+    pub const LIST: u8 = 6;
+    pub const SHUTDOWN: u8 = 7;
+    pub const SPAWNED: u8 = 64;
+    pub const OUTPUT: u8 = 65;
+    pub const EXIT: u8 = 66;
+    pub const SESSIONS: u8 = 67;
+    pub const ERROR: u8 = 68;
+}
+
+impl Message {
+    /// Writes the message as a single frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the writer fails.
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let (tag, pane_id, payload) = self.encode()?;
+        let length = u32::try_from(payload.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Frame too large"))?;
+
+        let mut frame = Vec::with_capacity(21 + payload.len());
+        frame.push(tag);
+        frame.extend_from_slice(pane_id.as_bytes());
+        frame.extend_from_slice(&length.to_be_bytes());
+        frame.extend_from_slice(&payload);
+
+        writer.write_all(&frame)?;
+        writer.flush()
+    }
+
+    /// Reads the next frame, or `None` once the stream is closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream fails or carries a malformed frame.
+    pub fn read_from(reader: &mut impl Read) -> io::Result<Option<Self>> {
+        let mut tag = [0u8; 1];
+        match reader.read_exact(&mut tag) {
+            Ok(()) => {}
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(error) => return Err(error),
+        }
+
+        let mut header = [0u8; 20];
+        reader.read_exact(&mut header)?;
+        let pane_id = Uuid::from_slice(&header[..16]).map_err(invalid_data)?;
+        let length = u32::from_be_bytes([header[16], header[17], header[18], header[19]]);
+        let length = usize::try_from(length).map_err(invalid_data)?;
+        if length > MAX_PAYLOAD_BYTES {
+            return Err(invalid_data("Frame too large"));
+        }
+
+        let mut payload = vec![0u8; length];
+        reader.read_exact(&mut payload)?;
+
+        Self::decode(tag[0], pane_id, payload).map(Some)
+    }
+
+    fn encode(&self) -> io::Result<(u8, Uuid, Vec<u8>)> {
+        let frame = match self {
+            Self::Spawn(request) => (
+                tag::SPAWN,
+                request.pane_id,
+                serde_json::to_vec(request).map_err(invalid_data)?,
+            ),
+            Self::Attach { pane_id } => (tag::ATTACH, *pane_id, Vec::new()),
+            Self::Input { pane_id, data } => (tag::INPUT, *pane_id, data.clone()),
+            Self::Resize {
+                pane_id,
+                rows,
+                columns,
+            } => {
+                let mut payload = rows.to_be_bytes().to_vec();
+                payload.extend_from_slice(&columns.to_be_bytes());
+                (tag::RESIZE, *pane_id, payload)
+            }
+            Self::Kill { pane_id } => (tag::KILL, *pane_id, Vec::new()),
+            Self::List => (tag::LIST, Uuid::nil(), Vec::new()),
+            Self::Shutdown => (tag::SHUTDOWN, Uuid::nil(), Vec::new()),
+            Self::Spawned { pane_id } => (tag::SPAWNED, *pane_id, Vec::new()),
+            Self::Output { pane_id, data } => (tag::OUTPUT, *pane_id, data.clone()),
+            Self::Exit { pane_id } => (tag::EXIT, *pane_id, Vec::new()),
+            Self::Sessions(pane_ids) => (
+                tag::SESSIONS,
+                Uuid::nil(),
+                serde_json::to_vec(pane_ids).map_err(invalid_data)?,
+            ),
+            Self::Error { pane_id, message } => (tag::ERROR, *pane_id, message.as_bytes().to_vec()),
+        };
+        Ok(frame)
+    }
+
+    fn decode(tag: u8, pane_id: Uuid, payload: Vec<u8>) -> io::Result<Self> {
+        let message = match tag {
+            tag::SPAWN => Self::Spawn(serde_json::from_slice(&payload).map_err(invalid_data)?),
+            tag::ATTACH => Self::Attach { pane_id },
+            tag::INPUT => Self::Input {
+                pane_id,
+                data: payload,
+            },
+            tag::RESIZE => {
+                let [rows_high, rows_low, columns_high, columns_low] = payload[..] else {
+                    return Err(invalid_data("Malformed resize frame"));
+                };
+                Self::Resize {
+                    pane_id,
+                    rows: u16::from_be_bytes([rows_high, rows_low]),
+                    columns: u16::from_be_bytes([columns_high, columns_low]),
+                }
+            }
+            tag::KILL => Self::Kill { pane_id },
+            tag::LIST => Self::List,
+            tag::SHUTDOWN => Self::Shutdown,
+            tag::SPAWNED => Self::Spawned { pane_id },
+            tag::OUTPUT => Self::Output {
+                pane_id,
+                data: payload,
+            },
+            tag::EXIT => Self::Exit { pane_id },
+            tag::SESSIONS => {
+                Self::Sessions(serde_json::from_slice(&payload).map_err(invalid_data)?)
+            }
+            tag::ERROR => Self::Error {
+                pane_id,
+                message: String::from_utf8_lossy(&payload).to_string(),
+            },
+            unknown => return Err(invalid_data(format!("Unknown frame tag {unknown}"))),
+        };
+        Ok(message)
+    }
+}
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(message: &Message) -> Message {
+        let mut buffer = Vec::new();
+        message.write_to(&mut buffer).unwrap();
+        Message::read_from(&mut buffer.as_slice()).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_frames_round_trip() {
+        let pane_id = Uuid::new_v4();
+        let messages = [
+            Message::Spawn(SpawnRequest {
+                pane_id,
+                working_directory: PathBuf::from("/tmp/task"),
+                rows: 24,
+                columns: 80,
+                command: Some("claude".to_string()),
+                arguments: vec!["Fix the bug".to_string()],
+                environment: HashMap::from([("TERM".to_string(), "xterm".to_string())]),
+            }),
+            Message::Input {
+                pane_id,
+                data: b"\x1b[A\r".to_vec(),
+            },
+            Message::Resize {
+                pane_id,
+                rows: 50,
+                columns: 160,
+            },
+            Message::Sessions(vec![pane_id]),
+            Message::Error {
+                pane_id,
+                message: "No such session".to_string(),
+            },
+            Message::Shutdown,
+        ];
+
+        for message in &messages {
+            assert_eq!(&round_trip(message), message);
+        }
+    }
+
+    #[test]
+    fn test_read_from_closed_stream() {
+        let empty: &[u8] = &[];
+        assert!(Message::read_from(&mut &*empty).unwrap().is_none());
+
+        let truncated: &[u8] = &[tag::OUTPUT, 0, 0];
+        assert!(Message::read_from(&mut &*truncated).is_err());
+    }
+}
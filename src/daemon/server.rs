@@ -0,0 +1,287 @@
+use super::protocol::{Message, SpawnRequest};
+use super::snapshot;
+use crate::views::instances::pty::{self, EventProxy};
+use alacritty_terminal::event::OnResize;
+use alacritty_terminal::term::Term;
+use alacritty_terminal::tty::{self, Options, Pty, Shell};
+use alacritty_terminal::vte::ansi::{Processor, StdSyncHandler};
+use anyhow::{Context, bail};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufReader, ErrorKind, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+use std::time::Duration;
+use uuid::Uuid;
+
+const READ_BUFFER_BYTES: usize = 4096;
+const READ_POLL_DELAY_MS: u64 = 10;
+
+type Sessions = Arc<Mutex<HashMap<Uuid, Arc<Session>>>>;
+
+/// An agent kept alive by the daemon, together with the terminal its output is replayed into
+/// so that a newly attached TUI can be shown the current screen.
+struct Session {
+    pty: Mutex<Option<Pty>>,
+    screen: Mutex<Screen>,
+}
+
+struct Screen {
+    term: Term<EventProxy>,
+    processor: Processor<StdSyncHandler>,
+    subscribers: Vec<mpsc::Sender<Message>>,
+}
+
+impl Session {
+    fn subscribe(&self, pane_id: Uuid, subscriber: &mpsc::Sender<Message>) {
+        let Ok(mut screen) = self.screen.lock() else {
+            return;
+        };
+        let data = snapshot::render(&screen.term);
+        if subscriber.send(Message::Output { pane_id, data }).is_ok() {
+            screen.subscribers.push(subscriber.clone());
+        }
+    }
+
+    fn write_input(&self, data: &[u8]) {
+        if let Ok(pty) = self.pty.lock()
+            && let Some(pty) = pty.as_ref()
+        {
+            let mut file = pty.file();
+            let _ = file.write_all(data).and_then(|()| file.flush());
+        }
+    }
+
+    fn resize(&self, rows: u16, columns: u16) {
+        if let Ok(mut pty) = self.pty.lock()
+            && let Some(pty) = pty.as_mut()
+        {
+            pty.on_resize(pty::window_size(rows, columns));
+        }
+        if let Ok(mut screen) = self.screen.lock() {
+            pty::resize_term(&mut screen.term, rows, columns);
+        }
+    }
+
+    /// Hangs up on the agent. The output pump notices the closed PTY and cleans up.
+    fn kill(&self) {
+        let pty = self.pty.lock().ok().and_then(|mut pty| pty.take());
+        drop(pty);
+    }
+}
+
+/// Serves the daemon socket until a client asks it to shut down.
+///
+/// # Errors
+///
+/// Returns an error if another daemon already owns the socket or it cannot be bound.
+pub fn run(socket_path: &Path) -> anyhow::Result<()> {
+    if UnixStream::connect(socket_path).is_ok() {
+        bail!(
+            "A Chloe daemon is already running on {}",
+            socket_path.display()
+        );
+    }
+    let _ = fs::remove_file(socket_path);
+
+    let listener = UnixListener::bind(socket_path)
+        .with_context(|| format!("Failed to bind {}", socket_path.display()))?;
+    tty::setup_env();
+
+    let sessions: Sessions = Arc::default();
+
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let sessions = Arc::clone(&sessions);
+        let socket_path = socket_path.to_path_buf();
+
+        thread::spawn(move || {
+            if matches!(serve_client(stream, &sessions), Ok(true)) {
+                shut_down(&sessions, &socket_path);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+fn shut_down(sessions: &Sessions, socket_path: &PathBuf) -> ! {
+    let sessions: Vec<Arc<Session>> = sessions
+        .lock()
+        .map(|mut sessions| sessions.drain().map(|(_, session)| session).collect())
+        .unwrap_or_default();
+    for session in sessions {
+        session.kill();
+    }
+    let _ = fs::remove_file(socket_path);
+    std::process::exit(0);
+}
+
+/// Handles one connection, returning `true` if the client asked the daemon to shut down.
+fn serve_client(stream: UnixStream, sessions: &Sessions) -> std::io::Result<bool> {
+    let (sender, receiver) = mpsc::channel::<Message>();
+    let mut writer = stream.try_clone()?;
+    thread::spawn(move || {
+        for message in receiver {
+            if message.write_to(&mut writer).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut reader = BufReader::new(stream);
+    while let Some(message) = Message::read_from(&mut reader)? {
+        match message {
+            Message::Spawn(request) => {
+                let pane_id = request.pane_id;
+                match spawn_session(request, sessions) {
+                    Ok(session) => {
+                        let _ = sender.send(Message::Spawned { pane_id });
+                        session.subscribe(pane_id, &sender);
+                    }
+                    Err(error) => {
+                        let _ = sender.send(Message::Error {
+                            pane_id,
+                            message: format!("{error:#}"),
+                        });
+                    }
+                }
+            }
+            Message::Attach { pane_id } => {
+                if let Some(session) = find_session(sessions, pane_id) {
+                    session.subscribe(pane_id, &sender);
+                    let _ = sender.send(Message::Spawned { pane_id });
+                } else {
+                    let _ = sender.send(Message::Error {
+                        pane_id,
+                        message: "No such session".to_string(),
+                    });
+                }
+            }
+            Message::Input { pane_id, data } => {
+                if let Some(session) = find_session(sessions, pane_id) {
+                    session.write_input(&data);
+                }
+            }
+            Message::Resize {
+                pane_id,
+                rows,
+                columns,
+            } => {
+                if let Some(session) = find_session(sessions, pane_id) {
+                    session.resize(rows, columns);
+                }
+            }
+            Message::Kill { pane_id } => {
+                if let Some(session) = find_session(sessions, pane_id) {
+                    session.kill();
+                }
+            }
+            Message::List => {
+                let pane_ids = sessions
+                    .lock()
+                    .map(|sessions| sessions.keys().copied().collect())
+                    .unwrap_or_default();
+                let _ = sender.send(Message::Sessions(pane_ids));
+            }
+            Message::Shutdown => return Ok(true),
+            Message::Spawned { .. }
+            | Message::Output { .. }
+            | Message::Exit { .. }
+            | Message::Sessions(_)
+            | Message::Error { .. } => {}
+        }
+    }
+
+    Ok(false)
+}
+
+fn find_session(sessions: &Sessions, pane_id: Uuid) -> Option<Arc<Session>> {
+    sessions.lock().ok()?.get(&pane_id).cloned()
+}
+
+fn spawn_session(request: SpawnRequest, sessions: &Sessions) -> anyhow::Result<Arc<Session>> {
+    let pane_id = request.pane_id;
+    if find_session(sessions, pane_id).is_some() {
+        bail!("Session {pane_id} is already running");
+    }
+
+    let options = Options {
+        shell: request
+            .command
+            .map(|command| Shell::new(command, request.arguments)),
+        working_directory: Some(request.working_directory),
+        env: request.environment,
+        drain_on_exit: true,
+    };
+    let pty = tty::new(&options, pty::window_size(request.rows, request.columns), 0)?;
+    let reader = pty.file().try_clone()?;
+
+    let session = Arc::new(Session {
+        pty: Mutex::new(Some(pty)),
+        screen: Mutex::new(Screen {
+            term: pty::new_term(request.rows, request.columns),
+            processor: Processor::new(),
+            subscribers: Vec::new(),
+        }),
+    });
+
+    if let Ok(mut sessions) = sessions.lock() {
+        sessions.insert(pane_id, Arc::clone(&session));
+    }
+
+    let pump_session = Arc::clone(&session);
+    let sessions = Arc::clone(sessions);
+    thread::spawn(move || pump_output(reader, pane_id, &pump_session, &sessions));
+
+    Ok(session)
+}
+
+/// Feeds agent output into the session's terminal and every attached client until the agent
+/// exits.
+fn pump_output(mut reader: fs::File, pane_id: Uuid, session: &Session, sessions: &Sessions) {
+    let mut buffer = [0u8; READ_BUFFER_BYTES];
+
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(bytes_read) => {
+                let data = &buffer[..bytes_read];
+                let Ok(mut screen) = session.screen.lock() else {
+                    break;
+                };
+                let Screen {
+                    term, processor, ..
+                } = &mut *screen;
+                processor.advance(term, data);
+                screen.subscribers.retain(|subscriber| {
+                    subscriber
+                        .send(Message::Output {
+                            pane_id,
+                            data: data.to_vec(),
+                        })
+                        .is_ok()
+                });
+            }
+            Err(error) if error.kind() == ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(READ_POLL_DELAY_MS));
+            }
+            Err(_) => break,
+        }
+    }
+
+    if let Ok(mut sessions) = sessions.lock() {
+        sessions.remove(&pane_id);
+    }
+    session.kill();
+
+    if let Ok(mut screen) = session.screen.lock() {
+        for subscriber in screen.subscribers.drain(..) {
+            let _ = subscriber.send(Message::Exit { pane_id });
+        }
+    }
+}
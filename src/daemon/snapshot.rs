@@ -0,0 +1,230 @@
+use alacritty_terminal::grid::Dimensions;
+use alacritty_terminal::index::{Column, Line};
+use alacritty_terminal::term::cell::{Cell, Flags};
+use alacritty_terminal::term::{Term, TermMode};
+use alacritty_terminal::vte::ansi::{Color, NamedColor};
+use std::fmt::Write;
+
+/// Renders the terminal as escape sequences that rebuild it when fed to an empty terminal of
+/// the same size.
+///
+/// Used when a TUI attaches to a running session: scrollback, screen contents, colors, the
+/// cursor position and the input modes agents rely on are carried over, so the attaching side
+/// does not have to replay the session's whole output.
+pub fn render<T>(term: &Term<T>) -> Vec<u8> {
+    let mode = *term.mode();
+    let grid = term.grid();
+    let mut output = String::from("\x1b[0m");
+
+    let is_alternate_screen = mode.contains(TermMode::ALT_SCREEN);
+    if is_alternate_screen {
+        output.push_str("\x1b[?1049h\x1b[H");
+    }
+
+    let history_lines = if is_alternate_screen {
+        0
+    } else {
+        grid.history_size()
+    };
+    let first_line = -i32::try_from(history_lines).unwrap_or(i32::MAX);
+    let last_line = i32::try_from(grid.screen_lines()).unwrap_or(i32::MAX);
+
+    let mut style = Style::default();
+    for line in first_line..last_line {
+        if line != first_line {
+            output.push_str("\r\n");
+        }
+
+        let row = &grid[Line(line)];
+        let used_columns = (0..grid.columns())
+            .rev()
+            .find(|&column| !is_blank(&row[Column(column)]))
+            .map_or(0, |column| column + 1);
+
+        for column in 0..used_columns {
+            let cell = &row[Column(column)];
+            if cell
+                .flags
+                .intersects(Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER)
+            {
+                continue;
+            }
+
+            let cell_style = Style::of(cell);
+            if cell_style != style {
+                cell_style.write_sgr(&mut output);
+                style = cell_style;
+            }
+            output.push(cell.c);
+            if let Some(zero_width) = cell.zerowidth() {
+                output.extend(zero_width);
+            }
+        }
+    }
+
+    output.push_str("\x1b[0m");
+
+    let cursor = grid.cursor.point;
+    let _ = write!(
+        output,
+        "\x1b[{};{}H",
+        cursor.line.0 + 1,
+        cursor.column.0 + 1
+    );
+
+    if !mode.contains(TermMode::SHOW_CURSOR) {
+        output.push_str("\x1b[?25l");
+    }
+    if mode.contains(TermMode::APP_CURSOR) {
+        output.push_str("\x1b[?1h");
+    }
+    if mode.contains(TermMode::APP_KEYPAD) {
+        output.push_str("\x1b=");
+    }
+    if mode.contains(TermMode::BRACKETED_PASTE) {
+        output.push_str("\x1b[?2004h");
+    }
+
+    output.into_bytes()
+}
+
+fn is_blank(cell: &Cell) -> bool {
+    cell.c == ' ' && Style::of(cell) == Style::default() && cell.zerowidth().is_none()
+}
+
+const STYLE_FLAGS: [(Flags, u8); 7] = [
+    (Flags::BOLD, 1),
+    (Flags::DIM, 2),
+    (Flags::ITALIC, 3),
+    (Flags::UNDERLINE, 4),
+    (Flags::INVERSE, 7),
+    (Flags::HIDDEN, 8),
+    (Flags::STRIKEOUT, 9),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Style {
+    foreground: Color,
+    background: Color,
+    flags: Flags,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            foreground: Color::Named(NamedColor::Foreground),
+            background: Color::Named(NamedColor::Background),
+            flags: Flags::empty(),
+        }
+    }
+}
+
+impl Style {
+    fn of(cell: &Cell) -> Self {
+        let style_flags = STYLE_FLAGS
+            .iter()
+            .fold(Flags::empty(), |flags, (flag, _)| flags | *flag);
+        Self {
+            foreground: cell.fg,
+            background: cell.bg,
+            flags: cell.flags & style_flags,
+        }
+    }
+
+    fn write_sgr(&self, output: &mut String) {
+        output.push_str("\x1b[0");
+        for (flag, code) in STYLE_FLAGS {
+            if self.flags.contains(flag) {
+                let _ = write!(output, ";{code}");
+            }
+        }
+        write_color(output, self.foreground, 30);
+        write_color(output, self.background, 40);
+        output.push('m');
+    }
+}
+
+/// Appends the SGR parameters for `color`, where `base` is 30 for foreground and 40 for
+/// background colors.
+fn write_color(output: &mut String, color: Color, base: u16) {
+    match color {
+        Color::Spec(rgb) => {
+            let _ = write!(output, ";{};2;{};{};{}", base + 8, rgb.r, rgb.g, rgb.b);
+        }
+        Color::Indexed(index) => {
+            let _ = write!(output, ";{};5;{index}", base + 8);
+        }
+        Color::Named(named) => {
+            let index = named as u16;
+            if index < 8 {
+                let _ = write!(output, ";{}", base + index);
+            } else if index < 16 {
+                let _ = write!(output, ";{}", base + 60 + index - 8);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::instances::pty;
+    use alacritty_terminal::vte::ansi::{Processor, StdSyncHandler};
+
+    fn feed(term: &mut Term<pty::EventProxy>, bytes: &[u8]) {
+        let mut processor: Processor<StdSyncHandler> = Processor::new();
+        processor.advance(term, bytes);
+    }
+
+    fn screen(term: &Term<pty::EventProxy>) -> Vec<String> {
+        let grid = term.grid();
+        (0..i32::try_from(grid.screen_lines()).unwrap())
+            .map(|line| {
+                let row = &grid[Line(line)];
+                (0..grid.columns())
+                    .map(|column| row[Column(column)].c)
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_snapshot_rebuilds_screen_and_cursor() {
+        let mut original = pty::new_term(5, 20);
+        feed(
+            &mut original,
+            b"line one\r\n\x1b[1;31mred bold\x1b[0m\r\n\x1b[?1h\x1b[?2004hprompt> ",
+        );
+
+        let mut restored = pty::new_term(5, 20);
+        feed(&mut restored, &render(&original));
+
+        assert_eq!(screen(&restored), screen(&original));
+        assert_eq!(restored.grid().cursor.point, original.grid().cursor.point);
+        assert_eq!(
+            restored.grid()[Line(1)][Column(0)].fg,
+            Color::Named(NamedColor::Red)
+        );
+        assert!(
+            restored.grid()[Line(1)][Column(0)]
+                .flags
+                .contains(Flags::BOLD)
+        );
+        assert!(restored.mode().contains(TermMode::APP_CURSOR));
+        assert!(restored.mode().contains(TermMode::BRACKETED_PASTE));
+    }
+
+    #[test]
+    fn test_snapshot_keeps_scrollback() {
+        let mut original = pty::new_term(3, 20);
+        feed(&mut original, b"one\r\ntwo\r\nthree\r\nfour\r\nfive");
+
+        let mut restored = pty::new_term(3, 20);
+        feed(&mut restored, &render(&original));
+
+        assert_eq!(restored.grid().history_size(), 2);
+        assert_eq!(screen(&restored), vec!["three", "four", "five"]);
+    }
+}
@@ -1,8 +1,9 @@
 use crate::views::roadmap::GeneratedRoadmap;
 use crate::views::tasks::ai_classifier::ClassifiedTask;
+use crate::views::tasks::state::WorktreeDeletion;
 use uuid::Uuid;
 
-use super::HookEvent;
+use super::{ControlRequest, ControlResponder, HookEvent, PermissionResponder};
 
 #[derive(Debug, Clone)]
 pub enum AppEvent {
@@ -23,5 +24,22 @@ pub enum AppEvent {
         result: Result<GeneratedRoadmap, String>,
     },
 
+    /// A task's worktree was deleted in the background, or could not be.
+    WorktreeDeleted {
+        deletion: WorktreeDeletion,
+        result: Result<(), String>,
+    },
+
     HookReceived(HookEvent),
+
+    /// A permission hook that is blocked until the inbox answers through `responder`.
+    PermissionRequested {
+        event: HookEvent,
+        responder: PermissionResponder,
+    },
+
+    ControlRequest {
+        request: ControlRequest,
+        responder: ControlResponder,
+    },
 }
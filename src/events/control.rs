@@ -0,0 +1,285 @@
+use super::{AppEvent, get_socket_path};
+use crate::views::tasks::TaskType;
+use crate::views::tasks::state::QueuePriority;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::sync::mpsc as std_mpsc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const RESPONSE_TIMEOUT_SECONDS: u64 = 10;
+
+/// A request sent over the hook socket by `chloe ctl`, editor plugins or scripts.
+///
+/// Requests are single JSON lines such as
+/// `{"id": 1, "method": "move_task", "task": "3f2a", "column": "review"}`; the running TUI
+/// answers each one with a single [`ControlMessage::Response`] line carrying the same `id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlRequest {
+    #[serde(default)]
+    pub id: Option<u64>,
+    #[serde(flatten)]
+    pub command: ControlCommand,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum ControlCommand {
+    ListTasks {
+        #[serde(default)]
+        column: Option<String>,
+    },
+    GetTask {
+        task: String,
+    },
+    CreateTask {
+        title: String,
+        #[serde(default)]
+        description: String,
+        #[serde(default)]
+        kind: TaskType,
+    },
+    MoveTask {
+        task: String,
+        column: String,
+    },
+    SendInput {
+        task: String,
+        input: String,
+        #[serde(default = "default_submit")]
+        submit: bool,
+    },
+    GetPaneOutput {
+        task: String,
+        #[serde(default)]
+        scrollback: usize,
+    },
+    /// Sets a task's budget in dollars, or removes it when `budget_usd` is omitted.
+    SetBudget {
+        task: String,
+        #[serde(default)]
+        budget_usd: Option<f64>,
+    },
+    /// Puts a planned task in the auto-run queue, or takes it out when `priority` is omitted.
+    SetQueue {
+        task: String,
+        #[serde(default)]
+        priority: Option<QueuePriority>,
+    },
+    /// Replaces the tasks that must reach Done before `task` can start.
+    SetDependencies {
+        task: String,
+        #[serde(default)]
+        dependencies: Vec<String>,
+    },
+    /// Turns the connection into a stream of [`ControlMessage::Notification`] lines. The
+    /// connection stops accepting further requests after subscribing.
+    Subscribe,
+}
+
+const fn default_submit() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ControlMessage {
+    Response {
+        #[serde(default)]
+        id: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        result: Option<serde_json::Value>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    Notification {
+        event: String,
+        #[serde(default)]
+        data: serde_json::Value,
+    },
+}
+
+impl ControlMessage {
+    #[must_use]
+    pub fn response(id: Option<u64>, result: Result<serde_json::Value, String>) -> Self {
+        match result {
+            Ok(value) => Self::Response {
+                id,
+                result: Some(value),
+                error: None,
+            },
+            Err(message) => Self::Response {
+                id,
+                result: None,
+                error: Some(message),
+            },
+        }
+    }
+
+    #[must_use]
+    pub fn notification(event: &str, data: serde_json::Value) -> Self {
+        Self::Notification {
+            event: event.to_string(),
+            data,
+        }
+    }
+}
+
+/// Channel the TUI answers a request on. Subscriptions keep it and push notifications
+/// through it until the client disconnects.
+pub type ControlResponder = std_mpsc::Sender<ControlMessage>;
+
+pub(super) fn serve_request(
+    stream: &mut UnixStream,
+    request: ControlRequest,
+    sender: &mpsc::UnboundedSender<AppEvent>,
+) -> std::io::Result<()> {
+    let request_id = request.id;
+    let is_subscription = matches!(request.command, ControlCommand::Subscribe);
+    let (responder, receiver) = std_mpsc::channel();
+
+    let sent = sender.send(AppEvent::ControlRequest { request, responder });
+    if sent.is_err() {
+        let message = ControlMessage::response(request_id, Err("Chloe is shutting down".into()));
+        return write_message(stream, &message);
+    }
+
+    if is_subscription {
+        for message in receiver {
+            write_message(stream, &message)?;
+        }
+        return Ok(());
+    }
+
+    let message = receiver
+        .recv_timeout(Duration::from_secs(RESPONSE_TIMEOUT_SECONDS))
+        .unwrap_or_else(|_| {
+            ControlMessage::response(request_id, Err("Timed out waiting for Chloe".into()))
+        });
+    write_message(stream, &message)
+}
+
+fn write_message(stream: &mut UnixStream, message: &ControlMessage) -> std::io::Result<()> {
+    let json = serde_json::to_string(message)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+    writeln!(stream, "{json}")?;
+    stream.flush()
+}
+
+fn connect_and_send(request: &ControlRequest) -> std::io::Result<BufReader<UnixStream>> {
+    let socket_path = get_socket_path();
+    let mut stream = UnixStream::connect(&socket_path)?;
+
+    let json = serde_json::to_string(request)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+    writeln!(stream, "{json}")?;
+    stream.flush()?;
+
+    Ok(BufReader::new(stream))
+}
+
+fn read_message(reader: &mut BufReader<UnixStream>) -> std::io::Result<Option<ControlMessage>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+
+    serde_json::from_str(&line)
+        .map(Some)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+}
+
+/// Sends a single request to the running TUI and waits for its response.
+///
+/// # Errors
+///
+/// Returns an error if Chloe is not running or the connection closes before a response.
+pub fn send_request(request: &ControlRequest) -> std::io::Result<ControlMessage> {
+    let mut reader = connect_and_send(request)?;
+    read_message(&mut reader)?.ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "Connection closed without a response",
+        )
+    })
+}
+
+/// Subscribes to the running TUI and calls `on_message` for every notification until the
+/// connection closes or `on_message` returns `false`.
+///
+/// # Errors
+///
+/// Returns an error if Chloe is not running or a message cannot be read.
+pub fn subscribe<F>(mut on_message: F) -> std::io::Result<()>
+where
+    F: FnMut(&ControlMessage) -> bool,
+{
+    let request = ControlRequest {
+        id: None,
+        command: ControlCommand::Subscribe,
+    };
+    let mut reader = connect_and_send(&request)?;
+
+    while let Some(message) = read_message(&mut reader)? {
+        if !on_message(&message) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_with_parameters() {
+        let line = r#"{"id":7,"method":"move_task","task":"3f2a","column":"review"}"#;
+        let request: ControlRequest = serde_json::from_str(line).unwrap();
+
+        assert_eq!(request.id, Some(7));
+        assert!(matches!(
+            request.command,
+            ControlCommand::MoveTask { ref task, ref column } if task == "3f2a" && column == "review"
+        ));
+    }
+
+    #[test]
+    fn test_parse_request_defaults() {
+        let request: ControlRequest = serde_json::from_str(r#"{"method":"list_tasks"}"#).unwrap();
+        assert!(matches!(
+            request.command,
+            ControlCommand::ListTasks { column: None }
+        ));
+
+        let line = r#"{"method":"send_input","task":"3f2a","input":"yes"}"#;
+        let request: ControlRequest = serde_json::from_str(line).unwrap();
+        assert!(matches!(
+            request.command,
+            ControlCommand::SendInput { submit: true, .. }
+        ));
+    }
+
+    #[test]
+    fn test_hook_event_is_not_a_control_request() {
+        let line = r#"{"event":"start","worktree_id":"00000000-0000-0000-0000-000000000000","timestamp":1}"#;
+        assert!(serde_json::from_str::<ControlRequest>(line).is_err());
+    }
+
+    #[test]
+    fn test_response_round_trip() {
+        let message = ControlMessage::response(Some(3), Err("No task matches 'zz'".to_string()));
+        let json = serde_json::to_string(&message).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"type":"response","id":3,"error":"No task matches 'zz'"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<ControlMessage>(&json).unwrap(),
+            message
+        );
+    }
+}
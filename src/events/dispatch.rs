@@ -1,10 +1,17 @@
 use crate::app::{App, Tab};
 use crate::events::{
-    AppAction, AppEvent, EventHandler, EventResult, PullRequestAction, RoadmapAction,
+    AppAction, AppEvent, ControlCommand, ControlMessage, ControlRequest, ControlResponder,
+    EventHandler, EventResult, PaletteAction, PermissionAction, PullRequestAction, RoadmapAction,
     SettingsAction, TerminalAction, WorktreeAction,
 };
+use crate::types::{KeyAction, KeyContext};
 use crate::views;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::views::instances::InstanceState;
+use crate::views::tasks::{TasksState, TasksViewMode};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use serde_json::json;
+use uuid::Uuid;
 
 pub fn handle_key_event(app: &mut App, key: KeyEvent) -> bool {
     if handle_exit_confirmation(app, key) {
@@ -15,6 +22,16 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> bool {
         return false;
     }
 
+    if app.permissions.is_open {
+        dispatch_permissions_event(app, key);
+        return false;
+    }
+
+    if app.palette.is_open {
+        dispatch_palette_event(app, key);
+        return false;
+    }
+
     let result = dispatch_key_event(app, key);
 
     if result.is_quit() {
@@ -24,14 +41,14 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> bool {
     false
 }
 
-const fn handle_exit_confirmation(app: &mut App, key: KeyEvent) -> bool {
+fn handle_exit_confirmation(app: &mut App, key: KeyEvent) -> bool {
     if !app.showing_exit_confirmation {
         return false;
     }
 
-    match key.code {
-        KeyCode::Char('y' | 'Y') => true,
-        KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+    match app.keymap.action(KeyContext::Dialog, key) {
+        Some(KeyAction::Confirm) => true,
+        Some(KeyAction::Cancel) => {
             app.showing_exit_confirmation = false;
             false
         }
@@ -49,14 +66,20 @@ fn is_terminal_focused(app: &App) -> bool {
     instances_focused || tasks_focused
 }
 
-fn is_typing_mode(app: &App) -> bool {
-    app.active_tab == Tab::Tasks && app.tasks.is_typing_mode()
+/// Whether a dialog, picker or text field on the active tab has the keyboard, so the global
+/// keys must not see its keys.
+const fn is_dialog_open(app: &App) -> bool {
+    match app.active_tab {
+        Tab::Tasks => app.tasks.captures_keys(),
+        Tab::Roadmap => app.roadmap.captures_keys(),
+        Tab::Worktree => app.worktree.captures_keys(),
+        Tab::Settings => app.settings.captures_keys(),
+        Tab::Instances | Tab::PullRequests => false,
+    }
 }
 
 fn dispatch_key_event(app: &mut App, key: KeyEvent) -> EventResult {
-    let terminal_focused = is_terminal_focused(app);
-    let typing_mode = is_typing_mode(app);
-    let can_handle_global = !terminal_focused && !typing_mode;
+    let can_handle_global = !is_terminal_focused(app) && !is_dialog_open(app);
 
     if let Some(result) = handle_global_key(app, key, can_handle_global) {
         return result;
@@ -70,48 +93,65 @@ fn handle_global_key(app: &mut App, key: KeyEvent, can_handle: bool) -> Option<E
         return None;
     }
 
-    match key.code {
-        KeyCode::Char('q' | 'Q') => Some(EventResult::Quit),
-        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            Some(EventResult::Quit)
+    let tab = match app.keymap.action(KeyContext::Global, key)? {
+        KeyAction::Quit => return Some(EventResult::Quit),
+        KeyAction::TogglePermissions => {
+            app.permissions.toggle();
+            return Some(EventResult::Consumed);
+        }
+        KeyAction::CommandPalette => {
+            views::palette::dispatch::open(app);
+            return Some(EventResult::Consumed);
         }
-        KeyCode::Tab => {
+        KeyAction::NextTab => {
             app.next_tab();
-            Some(EventResult::Consumed)
+            return Some(EventResult::Consumed);
         }
-        KeyCode::BackTab => {
+        KeyAction::PreviousTab => {
             app.previous_tab();
-            Some(EventResult::Consumed)
+            return Some(EventResult::Consumed);
         }
-        KeyCode::Char('1') => {
-            app.switch_tab(Tab::Tasks);
-            Some(EventResult::Consumed)
-        }
-        KeyCode::Char('2') => {
-            app.switch_tab(Tab::Instances);
-            Some(EventResult::Consumed)
-        }
-        KeyCode::Char('3') => {
-            app.switch_tab(Tab::Roadmap);
-            Some(EventResult::Consumed)
-        }
-        KeyCode::Char('4') => {
-            app.switch_tab(Tab::Worktree);
-            Some(EventResult::Consumed)
-        }
-        KeyCode::Char('5') => {
-            app.switch_tab(Tab::PullRequests);
-            Some(EventResult::Consumed)
+        KeyAction::ShowTasks => Tab::Tasks,
+        KeyAction::ShowInstances => Tab::Instances,
+        KeyAction::ShowRoadmap => Tab::Roadmap,
+        KeyAction::ShowWorktrees => Tab::Worktree,
+        KeyAction::ShowPullRequests => Tab::PullRequests,
+        KeyAction::ShowSettings => Tab::Settings,
+        _ => return None,
+    };
+
+    app.switch_tab(tab);
+    Some(EventResult::Consumed)
+}
+
+fn dispatch_permissions_event(app: &mut App, key: KeyEvent) {
+    match app.permissions.handle_key(key, &app.keymap) {
+        EventResult::Action(AppAction::Permission(PermissionAction::Answer {
+            decision,
+            scope,
+        })) => {
+            app.answer_permission(decision, scope);
         }
-        KeyCode::Char('6') => {
-            app.switch_tab(Tab::Settings);
-            Some(EventResult::Consumed)
+        EventResult::Action(AppAction::Permission(PermissionAction::OpenTerminal)) => {
+            app.open_permission_terminal();
         }
-        _ => None,
+        _ => {}
+    }
+}
+
+fn dispatch_palette_event(app: &mut App, key: KeyEvent) {
+    if let EventResult::Action(AppAction::Palette(PaletteAction::Execute(target))) =
+        app.palette.handle_key(key, &app.keymap)
+    {
+        views::palette::dispatch::execute(app, target);
     }
 }
 
 fn dispatch_to_active_tab(app: &mut App, key: KeyEvent) -> EventResult {
+    if app.read_only && !is_browsing_key(app, key) {
+        return EventResult::Consumed;
+    }
+
     match app.active_tab {
         Tab::Tasks => dispatch_tasks_event(app, key),
         Tab::Instances => dispatch_instances_event(app, key),
@@ -122,9 +162,31 @@ fn dispatch_to_active_tab(app: &mut App, key: KeyEvent) -> EventResult {
     }
 }
 
+/// Keys that only move around, the ones a read-only board still answers to.
+fn is_browsing_key(app: &App, key: KeyEvent) -> bool {
+    let contexts: &[KeyContext] = match app.active_tab {
+        Tab::Tasks => match app.tasks.view_mode {
+            TasksViewMode::Kanban => &[KeyContext::Tasks, KeyContext::Kanban],
+            TasksViewMode::Focus => &[KeyContext::Tasks, KeyContext::Focus],
+        },
+        Tab::Instances => &[KeyContext::Instances],
+        Tab::Roadmap => &[KeyContext::Roadmap],
+        Tab::Worktree => &[KeyContext::Worktree],
+        Tab::PullRequests => &[KeyContext::PullRequests],
+        Tab::Settings => &[KeyContext::Settings],
+    };
+
+    key.code == KeyCode::Esc
+        || contexts.iter().any(|context| {
+            app.keymap
+                .action(*context, key)
+                .is_some_and(KeyAction::is_browsing)
+        })
+}
+
 fn dispatch_tasks_event(app: &mut App, key: KeyEvent) -> EventResult {
-    let is_jump_to_instance =
-        app.tasks.is_normal_mode() && matches!(key.code, KeyCode::Char('t' | 'T'));
+    let is_jump_to_instance = app.tasks.is_normal_mode()
+        && app.keymap.action(KeyContext::Tasks, key) == Some(KeyAction::JumpToAgent);
 
     if is_jump_to_instance {
         app.jump_to_task_instance();
@@ -136,7 +198,7 @@ fn dispatch_tasks_event(app: &mut App, key: KeyEvent) -> EventResult {
 }
 
 fn dispatch_instances_event(app: &mut App, key: KeyEvent) -> EventResult {
-    let result = app.instances.handle_key(key);
+    let result = app.instances.handle_key(key, &app.keymap);
 
     if let EventResult::Action(action) = &result {
         process_instances_action(app, action);
@@ -156,7 +218,7 @@ fn process_instances_action(app: &mut App, action: &AppAction) {
 }
 
 fn dispatch_roadmap_event(app: &mut App, key: KeyEvent) -> EventResult {
-    let result = app.roadmap.handle_key(key);
+    let result = app.roadmap.handle_key(key, &app.keymap);
 
     if let EventResult::Action(action) = &result {
         process_roadmap_action(app, action);
@@ -172,16 +234,7 @@ fn process_roadmap_action(app: &mut App, action: &AppAction) {
             app.convert_roadmap_item_to_task(*index);
             app.active_tab = Tab::Tasks;
         }
-        AppAction::Roadmap(RoadmapAction::Generate) => {
-            if let Ok(current_directory) = std::env::current_dir()
-                && let Some(event_sender) = app.event_sender()
-            {
-                app.roadmap.start_generation(
-                    current_directory.to_string_lossy().to_string(),
-                    event_sender,
-                );
-            }
-        }
+        AppAction::Roadmap(RoadmapAction::Generate) => app.start_roadmap_generation(),
         AppAction::Settings(SettingsAction::SaveState) => {
             let _ = app.save();
         }
@@ -191,22 +244,16 @@ fn process_roadmap_action(app: &mut App, action: &AppAction) {
 
 fn dispatch_worktree_event(app: &mut App, key: KeyEvent) -> EventResult {
     let vcs_command = &app.settings.settings.vcs_command.clone();
-    app.worktree.handle_key_event(key, vcs_command);
+    if app.worktree.handle_key_event(key, &app.keymap, vcs_command) {
+        return EventResult::Consumed;
+    }
 
-    let result = app.worktree.handle_key(key);
+    let result = app.worktree.handle_key(key, &app.keymap);
 
     if let EventResult::Action(action) = &result {
         process_worktree_action(app, action);
     }
 
-    if let Some(worktree_index) = app.worktree.pending_ide_open.take() {
-        app.open_worktree_in_ide(worktree_index);
-    }
-
-    if let Some(worktree_index) = app.worktree.pending_terminal_open.take() {
-        app.open_worktree_in_terminal(worktree_index);
-    }
-
     EventResult::Consumed
 }
 
@@ -223,7 +270,7 @@ fn process_worktree_action(app: &App, action: &AppAction) {
 }
 
 fn dispatch_pull_requests_event(app: &mut App, key: KeyEvent) -> EventResult {
-    let result = app.pull_requests.handle_key(key);
+    let result = app.pull_requests.handle_key(key, &app.keymap);
 
     if let EventResult::Action(action) = &result {
         process_pull_requests_action(app, action);
@@ -249,7 +296,7 @@ fn process_pull_requests_action(app: &mut App, action: &AppAction) {
 }
 
 fn dispatch_settings_event(app: &mut App, key: KeyEvent) -> EventResult {
-    let result = app.settings.handle_key(key);
+    let result = app.settings.handle_key(key, &app.keymap);
 
     if matches!(
         &result,
@@ -262,6 +309,12 @@ fn dispatch_settings_event(app: &mut App, key: KeyEvent) -> EventResult {
     result
 }
 
+/// Sizes the agent terminals for the new window size right away, so agents in hidden tabs
+/// follow it too. Each terminal is resized once, to where the next frame will draw it.
+pub fn handle_resize(app: &mut App, columns: u16, rows: u16) {
+    views::fit_terminals(app, Rect::new(0, 0, columns, rows));
+}
+
 pub fn handle_app_event(app: &mut App, event: AppEvent) {
     match event {
         AppEvent::PtyOutput { pane_id, data } => {
@@ -269,6 +322,7 @@ pub fn handle_app_event(app: &mut App, event: AppEvent) {
         }
         AppEvent::PtyExit { pane_id } => {
             app.instances.handle_pty_exit(pane_id);
+            app.notify_control_subscribers("pane_exited", &json!({ "instance_id": pane_id }));
         }
         AppEvent::ClassificationCompleted { task_id, result } => {
             app.tasks.handle_classification_completed(task_id, result);
@@ -276,12 +330,173 @@ pub fn handle_app_event(app: &mut App, event: AppEvent) {
         AppEvent::RoadmapGenerationCompleted { result } => {
             app.roadmap.handle_generation_completed(result);
         }
+        AppEvent::WorktreeDeleted { deletion, result } => {
+            app.finish_worktree_deletion(deletion, result);
+        }
         AppEvent::HookReceived(hook_event) => {
-            app.process_hook_event(&hook_event);
+            app.process_hook_event(&hook_event, None);
+            app.notify_control_subscribers(
+                "hook",
+                &json!({ "task_id": hook_event.worktree_id, "event": hook_event.event }),
+            );
+        }
+        AppEvent::PermissionRequested { event, responder } => {
+            app.process_hook_event(&event, Some(responder));
+            app.notify_control_subscribers(
+                "hook",
+                &json!({ "task_id": event.worktree_id, "event": event.event }),
+            );
+        }
+        AppEvent::ControlRequest { request, responder } => {
+            handle_control_request(app, request, &responder);
         }
     }
 }
 
+fn handle_control_request(app: &mut App, request: ControlRequest, responder: &ControlResponder) {
+    let result = execute_control_command(app, request.command, responder);
+    let _ = responder.send(ControlMessage::response(request.id, result));
+}
+
+fn execute_control_command(
+    app: &mut App,
+    command: ControlCommand,
+    responder: &ControlResponder,
+) -> Result<serde_json::Value, String> {
+    match command {
+        ControlCommand::ListTasks { column } => {
+            let column_index = column
+                .map(|column| app.tasks.resolve_column_reference(&column))
+                .transpose()?;
+            to_json_value(&app.tasks.list_task_references(column_index))
+        }
+        ControlCommand::GetTask { task } => {
+            let task_id = app.tasks.resolve_task_reference(&task)?;
+            task_json(&app.tasks, task_id)
+        }
+        ControlCommand::CreateTask {
+            title,
+            description,
+            kind,
+        } => {
+            app.tasks.add_task_to_planning(title, description, kind);
+            let task_id = app.tasks.columns[0]
+                .tasks
+                .last()
+                .map(|task| task.id)
+                .ok_or_else(|| "Failed to add task".to_string())?;
+            let _ = app.save();
+
+            let task = task_json(&app.tasks, task_id)?;
+            app.notify_control_subscribers("task_created", &task);
+            Ok(task)
+        }
+        ControlCommand::MoveTask { task, column } => {
+            let task_id = app.tasks.resolve_task_reference(&task)?;
+            let column_index = app.tasks.resolve_column_reference(&column)?;
+            let vcs_command = app.settings.settings.vcs_command.clone();
+
+            let instances = &mut app.instances;
+            let move_result =
+                app.tasks
+                    .move_task_to_column(task_id, column_index, &vcs_command, |tasks| {
+                        process_pending_moves(tasks, instances);
+                    });
+            app.delete_pending_worktrees();
+            app.sync_task_instances();
+            let _ = app.save();
+            move_result?;
+
+            let task = task_json(&app.tasks, task_id)?;
+            app.notify_control_subscribers("task_moved", &task);
+            Ok(task)
+        }
+        ControlCommand::SendInput {
+            task,
+            input,
+            submit,
+        } => {
+            let instance_id = task_instance_id(&app.tasks, &task)?;
+            let is_sent = if submit {
+                app.instances.send_input_to_instance(instance_id, &input)
+            } else {
+                app.instances
+                    .send_raw_input_to_instance(instance_id, input.as_bytes())
+            };
+
+            if !is_sent {
+                return Err("Failed to write to the agent terminal".to_string());
+            }
+            Ok(json!({ "sent": true }))
+        }
+        ControlCommand::GetPaneOutput { task, scrollback } => {
+            let instance_id = task_instance_id(&app.tasks, &task)?;
+            let output = app
+                .instances
+                .find_pane(instance_id)
+                .and_then(|pane| pane.screen_text(scrollback))
+                .ok_or_else(|| "The agent terminal is not running".to_string())?;
+            Ok(json!({ "instance_id": instance_id, "output": output }))
+        }
+        ControlCommand::SetBudget { task, budget_usd } => {
+            let task_id = app.tasks.resolve_task_reference(&task)?;
+            app.tasks.set_task_budget(task_id, budget_usd);
+            save_and_notify_task_updated(app, task_id)
+        }
+        ControlCommand::SetQueue { task, priority } => {
+            let task_id = app.tasks.resolve_task_reference(&task)?;
+            app.tasks.set_task_queue_priority(task_id, priority)?;
+            save_and_notify_task_updated(app, task_id)
+        }
+        ControlCommand::SetDependencies { task, dependencies } => {
+            let task_id = app.tasks.resolve_task_reference(&task)?;
+            let dependencies = dependencies
+                .iter()
+                .map(|reference| app.tasks.resolve_task_reference(reference))
+                .collect::<Result<Vec<_>, _>>()?;
+            app.tasks.set_task_dependencies(task_id, dependencies)?;
+            save_and_notify_task_updated(app, task_id)
+        }
+        ControlCommand::Subscribe => {
+            app.add_control_subscriber(responder.clone());
+            Ok(json!({ "subscribed": true }))
+        }
+    }
+}
+
+fn save_and_notify_task_updated(app: &mut App, task_id: Uuid) -> Result<serde_json::Value, String> {
+    let _ = app.save();
+
+    let task = task_json(&app.tasks, task_id)?;
+    app.notify_control_subscribers("task_updated", &task);
+    Ok(task)
+}
+
+fn process_pending_moves(tasks: &mut TasksState, instances: &mut InstanceState) {
+    if let Some(instance_id) = tasks.pending_instance_termination.take() {
+        instances.close_pane_by_id(instance_id);
+    }
+}
+
+fn task_instance_id(tasks: &TasksState, reference: &str) -> Result<Uuid, String> {
+    let task_id = tasks.resolve_task_reference(reference)?;
+    tasks
+        .find_task_by_id(task_id)
+        .and_then(|task| task.instance_id)
+        .ok_or_else(|| "Task has no running agent".to_string())
+}
+
+fn task_json(tasks: &TasksState, task_id: Uuid) -> Result<serde_json::Value, String> {
+    let reference = tasks
+        .find_task_reference(task_id)
+        .ok_or_else(|| format!("No task with ID {task_id}"))?;
+    to_json_value(&reference)
+}
+
+fn to_json_value<T: serde::Serialize>(value: &T) -> Result<serde_json::Value, String> {
+    serde_json::to_value(value).map_err(|error| format!("Failed to serialize response: {error}"))
+}
+
 pub fn handle_tick(app: &mut App) {
     if app.active_tab == Tab::Tasks && app.tasks.has_pending_classifications() {
         app.tasks.advance_spinner();
@@ -302,4 +517,7 @@ pub fn handle_tick(app: &mut App) {
     }
 
     app.auto_transition_completed_tasks();
+    app.start_queued_tasks();
+    app.sync_tasks_with_base_if_due();
+    app.autosave_if_due();
 }
@@ -1,6 +1,6 @@
 use crate::app::App;
 use crate::events::AppEvent;
-use crate::events::dispatch;
+use crate::events::{dispatch, mouse};
 use crate::views;
 use crossterm::event::{Event, EventStream};
 use futures::StreamExt;
@@ -49,11 +49,20 @@ impl EventLoop {
                 biased;
 
                 maybe_crossterm_event = event_stream.next() => {
-                    if let Some(Ok(Event::Key(key))) = maybe_crossterm_event {
-                        let should_exit = dispatch::handle_key_event(app, key);
-                        if should_exit {
-                            return Ok(());
+                    match maybe_crossterm_event {
+                        Some(Ok(Event::Key(key))) => {
+                            let should_exit = dispatch::handle_key_event(app, key);
+                            if should_exit {
+                                return Ok(());
+                            }
                         }
+                        Some(Ok(Event::Mouse(mouse_event))) => {
+                            mouse::handle_mouse_event(app, mouse_event);
+                        }
+                        Some(Ok(Event::Resize(columns, rows))) => {
+                            dispatch::handle_resize(app, columns, rows);
+                        }
+                        _ => {}
                     }
                 }
 
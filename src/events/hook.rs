@@ -1,12 +1,28 @@
 use super::AppEvent;
+use super::control::{self, ControlMessage, ControlRequest};
+use crate::types::PermissionDecision;
 use serde::{Deserialize, Serialize};
 use std::io::{BufRead, BufReader, Write};
 use std::os::unix::net::{UnixListener, UnixStream};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc as std_mpsc;
 use std::thread;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
+/// How long a blocking permission hook waits for an answer from the inbox before the agent
+/// falls back to asking in its own terminal.
+pub const PERMISSION_DECISION_TIMEOUT_SECONDS: u64 = 300;
+
+/// Delivers the inbox's answer to the hook process that is waiting for it.
+pub type PermissionResponder = std_mpsc::Sender<PermissionDecision>;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct PermissionReply {
+    decision: PermissionDecision,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HookEvent {
     pub event: String,
@@ -14,6 +30,9 @@ pub struct HookEvent {
     pub timestamp: u128,
     #[serde(default)]
     pub hook_data: serde_json::Value,
+    /// Set by hooks that keep the connection open for a [`PermissionDecision`].
+    #[serde(default)]
+    pub awaits_decision: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -21,6 +40,11 @@ pub enum EventType {
     Start,
     End,
     Permission,
+    /// The agent is about to run a tool; the payload names the tool and its input.
+    ToolUse,
+    /// A tool finished; the payload carries its response.
+    ToolResult,
+    Notification,
     Unknown(String),
 }
 
@@ -30,25 +54,74 @@ impl From<&str> for EventType {
             "start" => Self::Start,
             "end" => Self::End,
             "permission" => Self::Permission,
+            "tool-use" => Self::ToolUse,
+            "tool-result" => Self::ToolResult,
+            "notification" => Self::Notification,
             other => Self::Unknown(other.to_string()),
         }
     }
 }
 
+/// Keys providers use for their conversation id in hook payloads.
+const SESSION_ID_KEYS: [&str; 4] = ["session_id", "sessionId", "sessionID", "thread_id"];
+
 impl HookEvent {
     #[must_use]
     pub fn event_type(&self) -> EventType {
         EventType::from(self.event.as_str())
     }
+
+    /// The provider's conversation id, when the hook payload carries one.
+    #[must_use]
+    pub fn session_id(&self) -> Option<&str> {
+        SESSION_ID_KEYS
+            .iter()
+            .find_map(|key| self.hook_data.get(key)?.as_str())
+            .filter(|session_id| !session_id.is_empty())
+    }
 }
 
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Returns the socket of the Chloe instance that owns the `.chloe/` directory of the current
+/// project, so instances running in different repositories never share a socket.
 #[must_use]
 pub fn get_socket_path() -> PathBuf {
-    std::env::temp_dir().join("chloe.sock")
+    let config_directory = crate::persistence::paths::get_config_dir();
+    let project_directory = config_directory
+        .parent()
+        .map_or_else(|| config_directory.clone(), Path::to_path_buf);
+    socket_path_for_project(&project_directory)
+}
+
+fn socket_path_for_project(project_directory: &Path) -> PathBuf {
+    let canonical_directory = project_directory
+        .canonicalize()
+        .unwrap_or_else(|_| project_directory.to_path_buf());
+    let project_hash = hash_path(&canonical_directory);
+
+    let runtime_directory = std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .filter(|directory| directory.is_dir())
+        .unwrap_or_else(std::env::temp_dir);
+
+    runtime_directory.join(format!("chloe-{project_hash:016x}.sock"))
+}
+
+/// FNV-1a, chosen over `DefaultHasher` because the result is baked into generated hook
+/// files and must stay stable across Rust releases.
+fn hash_path(path: &Path) -> u64 {
+    path.as_os_str()
+        .as_encoded_bytes()
+        .iter()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+        })
 }
 
 pub struct EventListener {
-    _marker: (),
+    socket_path: PathBuf,
 }
 
 impl EventListener {
@@ -66,14 +139,13 @@ impl EventListener {
             run_listener(&listener, event_sender.as_ref());
         });
 
-        Ok(Self { _marker: () })
+        Ok(Self { socket_path })
     }
 }
 
 impl Drop for EventListener {
     fn drop(&mut self) {
-        let socket_path = get_socket_path();
-        let _ = std::fs::remove_file(socket_path);
+        let _ = std::fs::remove_file(&self.socket_path);
     }
 }
 
@@ -81,7 +153,12 @@ fn run_listener(listener: &UnixListener, event_sender: Option<&mpsc::UnboundedSe
     loop {
         match listener.accept() {
             Ok((stream, _)) => {
-                handle_connection(stream, event_sender);
+                let Some(sender) = event_sender.cloned() else {
+                    continue;
+                };
+                // Control connections wait for the TUI to answer, so each one gets its own
+                // thread instead of blocking hook events behind it.
+                thread::spawn(move || handle_connection(stream, &sender));
             }
             Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => {
                 thread::sleep(std::time::Duration::from_millis(50));
@@ -93,11 +170,10 @@ fn run_listener(listener: &UnixListener, event_sender: Option<&mpsc::UnboundedSe
     }
 }
 
-fn handle_connection(stream: UnixStream, event_sender: Option<&mpsc::UnboundedSender<AppEvent>>) {
-    let Some(sender) = event_sender else {
+fn handle_connection(stream: UnixStream, sender: &mpsc::UnboundedSender<AppEvent>) {
+    let Ok(mut writer) = stream.try_clone() else {
         return;
     };
-
     let reader = BufReader::new(stream);
 
     for line in reader.lines() {
@@ -110,14 +186,82 @@ fn handle_connection(stream: UnixStream, event_sender: Option<&mpsc::UnboundedSe
         }
 
         if let Ok(event) = serde_json::from_str::<HookEvent>(&line) {
+            if event.awaits_decision {
+                let _ = serve_permission_request(&mut writer, event, sender);
+                break;
+            }
             let _ = sender.send(AppEvent::HookReceived(event));
+            continue;
+        }
+
+        let result = match serde_json::from_str::<ControlRequest>(&line) {
+            Ok(request) => control::serve_request(&mut writer, request, sender),
+            Err(error) => {
+                let message =
+                    ControlMessage::response(None, Err(format!("Invalid request: {error}")));
+                let json = serde_json::to_string(&message).unwrap_or_default();
+                writeln!(writer, "{json}")
+            }
+        };
+
+        if result.is_err() {
+            break;
         }
     }
 }
 
-pub fn send_event(event: &HookEvent) -> std::io::Result<()> {
-    let socket_path = get_socket_path();
-    let mut stream = UnixStream::connect(&socket_path)?;
+/// Holds the hook's connection until the inbox answers. Closing it without a reply lets the
+/// agent ask in its own terminal instead.
+fn serve_permission_request(
+    writer: &mut UnixStream,
+    event: HookEvent,
+    sender: &mpsc::UnboundedSender<AppEvent>,
+) -> std::io::Result<()> {
+    let (responder, receiver) = std_mpsc::channel();
+    if sender
+        .send(AppEvent::PermissionRequested { event, responder })
+        .is_err()
+    {
+        return Ok(());
+    }
+
+    let Ok(decision) =
+        receiver.recv_timeout(Duration::from_secs(PERMISSION_DECISION_TIMEOUT_SECONDS))
+    else {
+        return Ok(());
+    };
+
+    let json = serde_json::to_string(&PermissionReply { decision })
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+    writeln!(writer, "{json}")?;
+    writer.flush()
+}
+
+/// Sends a permission event and waits for the user's answer, returning `None` if Chloe
+/// closes the connection without deciding.
+pub fn request_permission_decision(
+    event: &HookEvent,
+    socket_path: &Path,
+) -> std::io::Result<Option<PermissionDecision>> {
+    let mut stream = UnixStream::connect(socket_path)?;
+    stream.set_read_timeout(Some(Duration::from_secs(
+        PERMISSION_DECISION_TIMEOUT_SECONDS + 1,
+    )))?;
+
+    let json = serde_json::to_string(event)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+    writeln!(stream, "{json}")?;
+    stream.flush()?;
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    Ok(serde_json::from_str::<PermissionReply>(&line)
+        .ok()
+        .map(|reply| reply.decision))
+}
+
+pub fn send_event(event: &HookEvent, socket_path: &Path) -> std::io::Result<()> {
+    let mut stream = UnixStream::connect(socket_path)?;
 
     let json = serde_json::to_string(event)
         .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
@@ -127,3 +271,44 @@ pub fn send_event(event: &HookEvent) -> std::io::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_socket_path_differs_per_project() {
+        let first = socket_path_for_project(Path::new("/nonexistent/project-a"));
+        let second = socket_path_for_project(Path::new("/nonexistent/project-b"));
+
+        assert_ne!(first, second);
+        assert_eq!(
+            first,
+            socket_path_for_project(Path::new("/nonexistent/project-a"))
+        );
+    }
+
+    #[test]
+    fn test_hash_path_is_stable() {
+        assert_eq!(hash_path(Path::new("")), FNV_OFFSET_BASIS);
+        assert_eq!(hash_path(Path::new("a")), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_session_id_from_hook_data() {
+        let mut event = HookEvent {
+            event: "start".to_string(),
+            worktree_id: Uuid::nil(),
+            timestamp: 0,
+            hook_data: serde_json::json!({ "session_id": "abc-123", "cwd": "/tmp" }),
+            awaits_decision: false,
+        };
+        assert_eq!(event.session_id(), Some("abc-123"));
+
+        event.hook_data = serde_json::json!({ "session_id": null });
+        assert_eq!(event.session_id(), None);
+
+        event.hook_data = serde_json::Value::String("not json".to_string());
+        assert_eq!(event.session_id(), None);
+    }
+}
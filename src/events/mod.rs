@@ -1,17 +1,26 @@
 mod app;
+pub mod control;
 pub mod dispatch;
 mod event_loop;
 mod hook;
+mod mouse;
 
 pub use crate::views::instances::TerminalAction;
+pub use crate::views::palette::PaletteAction;
+pub use crate::views::permissions::PermissionAction;
 pub use crate::views::pull_requests::PullRequestAction;
 pub use crate::views::roadmap::RoadmapAction;
 pub use crate::views::settings::SettingsAction;
 pub use crate::views::worktree::WorktreeAction;
 pub use app::AppEvent;
+pub use control::{ControlCommand, ControlMessage, ControlRequest, ControlResponder};
 pub use event_loop::EventLoop;
-pub use hook::{EventListener, EventType, HookEvent, get_socket_path, send_event};
+pub use hook::{
+    EventListener, EventType, HookEvent, PERMISSION_DECISION_TIMEOUT_SECONDS, PermissionResponder,
+    get_socket_path, request_permission_decision, send_event,
+};
 
+use crate::types::Keymap;
 use crossterm::event::KeyEvent;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -30,12 +39,15 @@ impl EventResult {
 }
 
 pub trait EventHandler {
-    fn handle_key(&mut self, key: KeyEvent) -> EventResult;
+    /// Handles `key`, looking up what it does in `keymap` outside of dialogs and text fields.
+    fn handle_key(&mut self, key: KeyEvent, keymap: &Keymap) -> EventResult;
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AppAction {
     Terminal(TerminalAction),
+    Permission(PermissionAction),
+    Palette(PaletteAction),
     Roadmap(RoadmapAction),
     PullRequest(PullRequestAction),
     Worktree(WorktreeAction),
@@ -0,0 +1,177 @@
+use crate::app::{App, Tab};
+use crate::types::KeyAction;
+use crate::views;
+use crate::views::mouse::MouseTarget;
+use crate::views::pull_requests::state::PullRequestsMode;
+use crate::views::roadmap::RoadmapMode;
+use crate::views::tasks::state::{ReviewPanel, TasksMode, TasksState};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+
+/// Acts on a click, drag or wheel notch over whatever the last frame drew there.
+pub fn handle_mouse_event(app: &mut App, event: MouseEvent) {
+    if app.showing_exit_confirmation || app.permissions.is_open {
+        return;
+    }
+
+    if app.palette.is_open {
+        match event.kind {
+            MouseEventKind::ScrollUp => app.palette.select_previous(),
+            MouseEventKind::ScrollDown => app.palette.select_next(),
+            _ => {}
+        }
+        return;
+    }
+
+    let target = app.mouse_regions.target_at(event.column, event.row);
+    if event.kind == MouseEventKind::Down(MouseButton::Left)
+        && let Some((_, MouseTarget::Tab(tab))) = target
+    {
+        app.switch_tab(tab);
+        return;
+    }
+
+    match app.active_tab {
+        Tab::Tasks => handle_tasks_mouse(app, event, target),
+        Tab::Instances => app.instances.handle_mouse(event),
+        Tab::Roadmap | Tab::Worktree | Tab::PullRequests | Tab::Settings => {
+            move_selection_with_wheel(app, event.kind);
+        }
+    }
+}
+
+/// Moves the selection one step for a wheel notch, wherever a selection is shown.
+fn move_selection_with_wheel(app: &mut App, kind: MouseEventKind) {
+    let action = match kind {
+        MouseEventKind::ScrollUp => KeyAction::MoveUp,
+        MouseEventKind::ScrollDown => KeyAction::MoveDown,
+        _ => return,
+    };
+    let is_down = action == KeyAction::MoveDown;
+
+    match app.active_tab {
+        Tab::Tasks => views::tasks::events::move_selection(
+            &mut app.tasks,
+            action,
+            &app.settings.settings.default_provider,
+        ),
+        Tab::Roadmap if app.roadmap.mode == RoadmapMode::Normal => {
+            if is_down {
+                app.roadmap.select_next();
+            } else {
+                app.roadmap.select_previous();
+            }
+        }
+        Tab::Worktree if !app.worktree.captures_keys() => {
+            if is_down {
+                app.worktree.select_next();
+            } else {
+                app.worktree.select_previous();
+            }
+        }
+        Tab::PullRequests if app.pull_requests.mode == PullRequestsMode::Normal => {
+            if is_down {
+                app.pull_requests.select_next();
+            } else {
+                app.pull_requests.select_previous();
+            }
+        }
+        Tab::Settings => views::settings::events::move_selection(&mut app.settings, action),
+        Tab::Instances | Tab::Roadmap | Tab::Worktree | Tab::PullRequests => {}
+    }
+}
+
+fn handle_tasks_mouse(app: &mut App, event: MouseEvent, target: Option<(Rect, MouseTarget)>) {
+    if handle_task_terminal_mouse(app, event, target) {
+        return;
+    }
+
+    match (event.kind, target) {
+        (MouseEventKind::Down(MouseButton::Left), Some((_, target))) => {
+            click_tasks_target(&mut app.tasks, target);
+        }
+        (MouseEventKind::ScrollUp | MouseEventKind::ScrollDown, _) => {
+            if let Some((_, MouseTarget::ReviewPanel(panel))) = target {
+                focus_review_panel(&mut app.tasks, panel);
+            }
+            move_selection_with_wheel(app, event.kind);
+        }
+        _ => {}
+    }
+}
+
+/// Sends the mouse to the program in the focus view's terminal when it asked for it, and
+/// otherwise focuses the terminal on a click and scrolls it back with the wheel.
+fn handle_task_terminal_mouse(
+    app: &mut App,
+    event: MouseEvent,
+    target: Option<(Rect, MouseTarget)>,
+) -> bool {
+    let Some(terminal_area) = app.mouse_regions.area_of(MouseTarget::TaskTerminal) else {
+        return false;
+    };
+    let Some(instance_id) = app.tasks.selected_instance_id() else {
+        return false;
+    };
+    let Some(pane) = app.instances.find_pane_mut(instance_id) else {
+        return false;
+    };
+
+    let is_over_terminal = matches!(target, Some((_, MouseTarget::TaskTerminal)));
+    let is_focused = app.tasks.mode == TasksMode::TerminalFocused;
+    // Drags and releases that wander off the terminal still belong to the press that began them
+    let is_continued = matches!(event.kind, MouseEventKind::Drag(_) | MouseEventKind::Up(_));
+    if is_focused && (is_over_terminal || is_continued) && pane.forward_mouse(event, terminal_area)
+    {
+        return true;
+    }
+
+    let is_browsing = app.tasks.is_normal_mode() || app.tasks.is_terminal_focused();
+    if !is_over_terminal || !is_browsing {
+        return false;
+    }
+
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            pane.scroll_to_bottom();
+            app.tasks.enter_terminal_mode();
+            true
+        }
+        MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+            if pane.scroll_with_wheel(event.kind) {
+                app.tasks.enter_terminal_scroll_mode();
+            } else if app.tasks.mode == TasksMode::TerminalScroll {
+                app.tasks.exit_terminal_scroll_mode();
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+fn click_tasks_target(state: &mut TasksState, target: MouseTarget) {
+    match target {
+        MouseTarget::KanbanTask { column, task } if state.is_normal_mode() => {
+            state.select_kanban_task(column, task);
+        }
+        MouseTarget::KanbanColumn(column)
+            if state.is_normal_mode() && column != state.kanban_selected_column =>
+        {
+            state.select_kanban_task(column, 0);
+        }
+        MouseTarget::FocusTask { panel, index }
+            if state.is_normal_mode() || state.is_terminal_focused() =>
+        {
+            state.mode = TasksMode::Normal;
+            state.select_focus_task(panel, index);
+        }
+        MouseTarget::ReviewPanel(panel) => focus_review_panel(state, panel),
+        _ => {}
+    }
+}
+
+const fn focus_review_panel(state: &mut TasksState, panel: ReviewPanel) {
+    if let TasksMode::ReviewPopup { focused_panel, .. } = &mut state.mode {
+        *focused_panel = panel;
+    }
+}
@@ -0,0 +1,74 @@
+const MATCH_SCORE: i64 = 16;
+const CONSECUTIVE_BONUS: i64 = 24;
+const WORD_START_BONUS: i64 = 32;
+const GAP_PENALTY: i64 = 1;
+
+/// Scores `candidate` against `query`, or `None` unless every character of the query appears
+/// in it in order, ignoring case.
+///
+/// Runs of consecutive characters and matches at the start of a word score higher, so `nt`
+/// ranks "New task" above "Open terminal".
+#[must_use]
+pub fn score(query: &str, candidate: &str) -> Option<i64> {
+    let mut query_characters = query
+        .chars()
+        .filter(|character| !character.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+
+    let mut total = 0;
+    let mut previous: Option<char> = None;
+    let mut is_previous_matched = false;
+    let mut gap = 0;
+
+    for character in candidate.chars() {
+        let Some(&wanted) = query_characters.peek() else {
+            break;
+        };
+
+        let is_match = character.to_lowercase().eq(std::iter::once(wanted));
+        if is_match {
+            total += MATCH_SCORE - gap * GAP_PENALTY;
+            if is_previous_matched {
+                total += CONSECUTIVE_BONUS;
+            }
+            if previous.is_none_or(|previous| !previous.is_alphanumeric()) {
+                total += WORD_START_BONUS;
+            }
+            query_characters.next();
+            gap = 0;
+        } else {
+            gap += 1;
+        }
+
+        is_previous_matched = is_match;
+        previous = Some(character);
+    }
+
+    query_characters.peek().is_none().then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_subsequence_ignoring_case() {
+        assert!(score("", "anything").is_some());
+        assert!(score("gr", "Generate roadmap").is_some());
+        assert!(score("GEN road", "Generate roadmap").is_some());
+        assert!(score("rg", "Generate roadmap").is_none());
+        assert!(score("tasks", "Task").is_none());
+    }
+
+    #[test]
+    fn test_ranks_word_starts_and_runs_higher() {
+        let new_task = score("nt", "New task").unwrap();
+        let open_terminal = score("nt", "Open terminal").unwrap();
+        assert!(new_task > open_terminal);
+
+        let run = score("pull", "Refresh pull requests").unwrap();
+        let scattered = score("pull", "Open pane: ultra long label").unwrap();
+        assert!(run > scattered);
+    }
+}
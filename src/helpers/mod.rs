@@ -1 +1,2 @@
+pub mod fuzzy;
 pub mod text;
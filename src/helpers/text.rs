@@ -1,3 +1,65 @@
+/// Quotes `argument` for a POSIX shell, leaving plain words and paths untouched.
+#[must_use]
+pub fn escape_shell_arg(argument: &str) -> String {
+    if argument
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.' || c == '/')
+    {
+        argument.to_string()
+    } else {
+        format!("'{}'", argument.replace('\'', "'\\''"))
+    }
+}
+
+/// Splits `input` into words the way a POSIX shell would, honouring single quotes, double
+/// quotes and backslash escapes. The inverse of joining words quoted by [`escape_shell_arg`].
+#[must_use]
+pub fn split_shell_words(input: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut characters = input.chars();
+
+    while let Some(character) = characters.next() {
+        match character {
+            '\'' => {
+                in_word = true;
+                current.extend(characters.by_ref().take_while(|&c| c != '\''));
+            }
+            '"' => {
+                in_word = true;
+                while let Some(quoted) = characters.next() {
+                    match quoted {
+                        '"' => break,
+                        '\\' => current.extend(characters.next()),
+                        _ => current.push(quoted),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                current.extend(characters.next());
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+
+    if in_word {
+        words.push(current);
+    }
+
+    words
+}
+
 #[must_use]
 pub fn truncate(text: &str, max_length: usize) -> String {
     if text.len() <= max_length {
@@ -50,3 +112,42 @@ pub fn wrap(text: &str, max_width: usize) -> Vec<String> {
 
     lines
 }
+
+/// Shortens a token count to a few digits, such as `950`, `12.3k` or `1.2M`.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn format_token_count(count: u64) -> String {
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", count as f64 / 1_000.0),
+        _ => format!("{:.1}M", count as f64 / 1_000_000.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_token_count() {
+        assert_eq!(format_token_count(950), "950");
+        assert_eq!(format_token_count(12_345), "12.3k");
+        assert_eq!(format_token_count(1_200_000), "1.2M");
+    }
+
+    #[test]
+    fn test_split_shell_words_round_trips_escaped_arguments() {
+        let arguments = ["--model", "opus 4", "it's"];
+        let joined = arguments
+            .iter()
+            .map(|argument| escape_shell_arg(argument))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        assert_eq!(split_shell_words(&joined), arguments);
+        assert_eq!(
+            split_shell_words(r#"  a "b \"c\"" d\ e "#),
+            vec!["a", r#"b "c""#, "d e"]
+        );
+    }
+}
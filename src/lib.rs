@@ -8,6 +8,7 @@
 
 pub mod app;
 pub mod cli;
+pub mod daemon;
 pub mod events;
 pub mod helpers;
 pub mod persistence;
@@ -14,6 +14,7 @@
 
 mod app;
 mod cli;
+mod daemon;
 pub mod events;
 mod helpers;
 mod persistence;
@@ -26,12 +27,14 @@ use app::App;
 use clap::Parser;
 use cli::{Cli, Commands};
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use events::EventLoop;
+use persistence::lock::ProjectLock;
 use ratatui::{Terminal, backend::CrosstermBackend};
-use std::io;
+use std::io::{self, Write};
 
 #[tokio::main]
 async fn main() -> Result<(), io::Error> {
@@ -48,35 +51,91 @@ async fn main() -> Result<(), io::Error> {
         Some(Commands::Notify {
             event_type,
             worktree_id,
+            socket,
+            await_decision,
         }) => {
-            if let Err(error) = cli::handle_notify_command(event_type, worktree_id) {
+            if let Err(error) =
+                cli::handle_notify_command(event_type, worktree_id, socket, await_decision)
+            {
                 eprintln!("Error handling notify command: {error}");
                 std::process::exit(1);
             }
             Ok(())
         }
-        None => run_tui().await,
+        Some(Commands::Task { command, json }) => {
+            if let Err(error) = cli::handle_task_command(command, json) {
+                eprintln!("Error: {error}");
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Some(Commands::Ctl { command }) => {
+            if let Err(error) = cli::handle_ctl_command(command) {
+                eprintln!("Error: {error}");
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Some(Commands::Daemon { command }) => {
+            if let Err(error) = cli::handle_daemon_command(command) {
+                eprintln!("Error: {error}");
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Some(Commands::Usage { days, json }) => {
+            if let Err(error) = cli::handle_usage_command(days, json) {
+                eprintln!("Error: {error}");
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        None => run_tui(cli.read_only).await,
     }
 }
 
 #[allow(clippy::future_not_send)]
-async fn run_tui() -> Result<(), io::Error> {
+async fn run_tui(read_only: bool) -> Result<(), io::Error> {
+    let project_lock = lock_project(read_only)?;
+    let mut app = match App::load() {
+        Ok(app) => app,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            std::process::exit(1);
+        }
+    };
+    app.read_only = project_lock.is_none();
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::load_or_default();
     let mut event_loop = EventLoop::new();
 
     app.set_event_sender(event_loop.event_sender());
-    let _event_listener = events::EventListener::start(app.event_sender())?;
+    // The hook and control socket belongs to the instance that owns the project
+    let _event_listener = if app.read_only {
+        None
+    } else {
+        Some(events::EventListener::start(app.event_sender())?)
+    };
+    if !app.read_only
+        && let Ok(daemon) = daemon::DaemonClient::connect()
+    {
+        app.instances.set_daemon(daemon);
+    }
+    app.restore_task_instances();
 
     let result = event_loop.run(&mut terminal, &mut app).await;
 
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
     terminal.show_cursor()?;
 
     if let Err(save_error) = app.save() {
@@ -89,3 +148,28 @@ async fn run_tui() -> Result<(), io::Error> {
 
     Ok(())
 }
+
+/// Takes the lock on the project's `.chloe/` directory. When another Chloe holds it, asks
+/// whether to open the board read-only instead and returns `None` if so.
+fn lock_project(read_only: bool) -> io::Result<Option<ProjectLock>> {
+    if read_only {
+        return Ok(None);
+    }
+    if let Some(lock) = ProjectLock::try_acquire()? {
+        return Ok(Some(lock));
+    }
+
+    let holder = ProjectLock::holder_pid().map_or_else(String::new, |pid| format!(" (PID {pid})"));
+    eprintln!("Chloe is already running in this project{holder}.");
+    eprintln!("Drive that session with `chloe ctl`, or browse the board here without saving.");
+    eprint!("Open the board read-only? [y/N] ");
+    io::stderr().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    if answer.trim().eq_ignore_ascii_case("y") {
+        Ok(None)
+    } else {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,301 @@
+use crate::views::settings::state::Settings;
+use serde_json::{Map, Value};
+
+/// The layer a setting's value comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingSource {
+    Default,
+    Global,
+    Project,
+}
+
+impl SettingSource {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Global => "global",
+            Self::Project => "project",
+        }
+    }
+}
+
+/// Keys every project `settings.json` holds that was written before the settings were layered,
+/// since those versions wrote out all of the settings.
+const FULL_FILE_KEYS: [&str; 9] = [
+    "default_shell",
+    "auto_save_interval_seconds",
+    "ide_command",
+    "terminal_command",
+    "vcs_command",
+    "default_provider",
+    "skip_provider_selection",
+    "provider_registry",
+    "permission_configs",
+];
+
+/// The user-level and project `settings.json` files as written. Either may hold any subset of
+/// the settings: project values override global ones, which override the built-in defaults.
+#[derive(Debug, Clone, Default)]
+pub struct SettingsLayers {
+    pub global: Map<String, Value>,
+    pub project: Map<String, Value>,
+}
+
+impl SettingsLayers {
+    /// The settings in effect: the defaults with the global and then the project layer merged
+    /// over them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a layer holds a value of the wrong type.
+    pub fn merged(&self) -> serde_json::Result<Settings> {
+        let mut merged = serde_json::to_value(Settings::default())?;
+        merge(&mut merged, &self.global);
+        merge(&mut merged, &self.project);
+        serde_json::from_value(merged)
+    }
+
+    /// The layer the value at `path`, a list of keys into `settings.json`, comes from.
+    #[must_use]
+    pub fn source(&self, path: &[&str]) -> SettingSource {
+        if lookup(&self.project, path).is_some() {
+            SettingSource::Project
+        } else if lookup(&self.global, path).is_some() {
+            SettingSource::Global
+        } else {
+            SettingSource::Default
+        }
+    }
+
+    /// Unpins the values a project file written before the settings were layered holds only
+    /// because those versions wrote out every setting: the ones equal to the defaults, which
+    /// the global layer now gets to override. Returns whether the project layer was such a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the default settings cannot be serialized.
+    pub fn unpin_full_project_file(&mut self) -> serde_json::Result<bool> {
+        if !FULL_FILE_KEYS
+            .iter()
+            .all(|key| self.project.contains_key(*key))
+        {
+            return Ok(false);
+        }
+
+        let defaults = serde_json::to_value(Settings::default())?;
+        let project = Value::Object(std::mem::take(&mut self.project));
+        if let Some(Value::Object(layer)) = difference(&defaults, &project, None) {
+            self.project = layer;
+        }
+        Ok(true)
+    }
+
+    /// Drops the project value at `path`, so the setting falls back to the global config or
+    /// the default. Objects left empty are dropped with it.
+    pub fn remove_project_value(&mut self, path: &[&str]) {
+        remove(&mut self.project, path);
+    }
+
+    /// What the project file has to hold for the settings in effect to be `settings`: the keys
+    /// it already held, so values pinned to the project stay pinned, and every value that
+    /// differs from what the defaults and the global layer give.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the settings cannot be serialized.
+    pub fn project_layer(&self, settings: &Settings) -> serde_json::Result<Map<String, Value>> {
+        let mut base = serde_json::to_value(Settings::default())?;
+        merge(&mut base, &self.global);
+        // Registered the same way as the settings in effect, so only project providers differ
+        let mut base: Settings = serde_json::from_value(base)?;
+        base.provider_registry
+            .register_custom_providers(&base.custom_providers);
+
+        let base = serde_json::to_value(base)?;
+        let current = serde_json::to_value(settings)?;
+        let previous = Value::Object(self.project.clone());
+        let layer = difference(&base, &current, Some(&previous));
+
+        Ok(match layer {
+            Some(Value::Object(layer)) => layer,
+            _ => Map::new(),
+        })
+    }
+}
+
+/// Merges `layer` into `target` key by key. Objects are merged recursively; anything else,
+/// lists included, replaces the value below it.
+fn merge(target: &mut Value, layer: &Map<String, Value>) {
+    let Value::Object(target) = target else {
+        *target = Value::Object(layer.clone());
+        return;
+    };
+
+    for (key, value) in layer {
+        match (target.get_mut(key), value) {
+            (Some(existing), Value::Object(nested)) if existing.is_object() => {
+                merge(existing, nested);
+            }
+            _ => {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Removes the value at `path` from `layer` and the objects on the way that it leaves empty.
+fn remove(layer: &mut Map<String, Value>, path: &[&str]) {
+    let Some((first, rest)) = path.split_first() else {
+        return;
+    };
+
+    if rest.is_empty() {
+        layer.remove(*first);
+        return;
+    }
+
+    if let Some(Value::Object(nested)) = layer.get_mut(*first) {
+        remove(nested, rest);
+        if nested.is_empty() {
+            layer.remove(*first);
+        }
+    }
+}
+
+fn lookup<'a>(layer: &'a Map<String, Value>, path: &[&str]) -> Option<&'a Value> {
+    let (first, rest) = path.split_first()?;
+    rest.iter()
+        .try_fold(layer.get(*first)?, |value, key| value.get(key))
+}
+
+/// The parts of `current` that are not already given by `base`, plus everything `previous`
+/// held a key for. `None` when nothing needs to be written.
+fn difference(base: &Value, current: &Value, previous: Option<&Value>) -> Option<Value> {
+    match (base, current) {
+        (Value::Object(base), Value::Object(current)) => {
+            let mut layer = Map::new();
+            for (key, value) in current {
+                let previous_value = previous.and_then(|previous| previous.get(key));
+                let nested = base.get(key).map_or_else(
+                    || Some(value.clone()),
+                    |base_value| difference(base_value, value, previous_value),
+                );
+                if let Some(nested) = nested {
+                    layer.insert(key.clone(), nested);
+                }
+            }
+
+            (!layer.is_empty() || previous.is_some()).then_some(Value::Object(layer))
+        }
+        _ => (previous.is_some() || base != current).then(|| current.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn layer(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => panic!("layer must be an object"),
+        }
+    }
+
+    #[test]
+    fn test_project_overrides_global() {
+        let layers = SettingsLayers {
+            global: layer(json!({ "default_shell": "/bin/zsh", "max_running_agents": 4 })),
+            project: layer(json!({ "max_running_agents": 1 })),
+        };
+
+        let settings = layers.merged().unwrap();
+        assert_eq!(settings.default_shell, "/bin/zsh");
+        assert_eq!(settings.max_running_agents, 1);
+
+        assert_eq!(
+            layers.source(&["max_running_agents"]),
+            SettingSource::Project
+        );
+        assert_eq!(layers.source(&["default_shell"]), SettingSource::Global);
+        assert_eq!(
+            layers.source(&["auto_save_interval_seconds"]),
+            SettingSource::Default
+        );
+    }
+
+    #[test]
+    fn test_project_layer_keeps_only_overrides() {
+        let layers = SettingsLayers {
+            global: layer(json!({ "default_shell": "/bin/zsh" })),
+            project: layer(json!({ "max_running_agents": 1 })),
+        };
+
+        let mut settings = layers.merged().unwrap();
+        settings.auto_save_interval_seconds = 5;
+
+        let project = layers.project_layer(&settings).unwrap();
+        assert_eq!(
+            Value::Object(project),
+            json!({ "auto_save_interval_seconds": 5, "max_running_agents": 1 })
+        );
+    }
+
+    #[test]
+    fn test_full_project_file_is_unpinned() {
+        let mut project = serde_json::to_value(Settings::default()).unwrap();
+        project["default_shell"] = json!("/bin/fish");
+        let mut layers = SettingsLayers {
+            global: layer(json!({ "auto_save_interval_seconds": 60 })),
+            project: layer(project),
+        };
+
+        assert!(layers.unpin_full_project_file().unwrap());
+        assert_eq!(
+            Value::Object(layers.project.clone()),
+            json!({ "default_shell": "/bin/fish" })
+        );
+        assert_eq!(layers.merged().unwrap().auto_save_interval_seconds, 60);
+    }
+
+    #[test]
+    fn test_partial_project_file_is_kept() {
+        let mut layers = SettingsLayers {
+            global: Map::new(),
+            project: layer(json!({ "auto_save_interval_seconds": 30 })),
+        };
+
+        assert!(!layers.unpin_full_project_file().unwrap());
+        assert_eq!(
+            Value::Object(layers.project),
+            json!({ "auto_save_interval_seconds": 30 })
+        );
+    }
+
+    #[test]
+    fn test_removed_project_value_falls_back_to_global() {
+        let mut layers = SettingsLayers {
+            global: layer(json!({ "max_running_agents": 4 })),
+            project: layer(json!({
+                "max_running_agents": 1,
+                "provider_registry": { "configs": { "claude": { "max_running_agents": 1 } } },
+            })),
+        };
+
+        layers.remove_project_value(&["max_running_agents"]);
+        layers.remove_project_value(&[
+            "provider_registry",
+            "configs",
+            "claude",
+            "max_running_agents",
+        ]);
+        assert!(layers.project.is_empty());
+        assert_eq!(layers.merged().unwrap().max_running_agents, 4);
+        assert_eq!(
+            layers.source(&["max_running_agents"]),
+            SettingSource::Global
+        );
+    }
+}
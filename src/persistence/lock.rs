@@ -0,0 +1,50 @@
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, Write};
+
+/// An advisory lock on the project's `.chloe/` directory, held by the process that writes its
+/// state. Dropping it releases the lock, and so does the process exiting or crashing.
+pub struct ProjectLock {
+    _file: File,
+}
+
+impl ProjectLock {
+    /// Takes the lock without waiting. Returns `None` when another process holds it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the lock file cannot be opened.
+    pub fn try_acquire() -> io::Result<Option<Self>> {
+        let path = super::paths::get_lock_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Truncating before holding the lock would erase the holder's PID
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(&path)?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Ok(None),
+            Err(TryLockError::Error(error)) => return Err(error),
+        }
+
+        file.set_len(0)?;
+        write!(file, "{}", std::process::id())?;
+        Ok(Some(Self { _file: file }))
+    }
+
+    /// The PID of the process holding the lock, as it recorded it.
+    #[must_use]
+    pub fn holder_pid() -> Option<u32> {
+        fs::read_to_string(super::paths::get_lock_path())
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+    }
+}
@@ -0,0 +1,86 @@
+use serde_json::Value;
+
+/// The schema version `state.json` is written with.
+pub const CURRENT_VERSION: u32 = 1;
+
+type Migration = fn(Value) -> Result<Value, String>;
+
+/// `MIGRATIONS[n]` upgrades the state of a version `n` file to version `n + 1`.
+const MIGRATIONS: [Migration; CURRENT_VERSION as usize] = [migrate_unversioned];
+
+/// Splits a `state.json` document into its schema version and the state it holds. Files
+/// written before the state was versioned hold the state directly and count as version 0.
+#[must_use]
+pub fn split_envelope(document: Value) -> (u32, Value) {
+    let version = document
+        .get("version")
+        .and_then(Value::as_u64)
+        .and_then(|version| u32::try_from(version).ok());
+
+    match (version, document) {
+        (Some(version), Value::Object(mut envelope)) if envelope.contains_key("state") => {
+            let state = envelope.remove("state").unwrap_or(Value::Null);
+            (version, state)
+        }
+        (_, document) => (0, document),
+    }
+}
+
+/// Runs the migrations that bring `state` from `version` up to [`CURRENT_VERSION`], in order.
+///
+/// # Errors
+///
+/// Returns an error if `version` is newer than this build understands or a migration fails.
+pub fn migrate(mut state: Value, version: u32) -> Result<Value, String> {
+    if version > CURRENT_VERSION {
+        return Err(format!(
+            "it was written by a newer version of Chloe (schema version {version}, this \
+             version reads up to {CURRENT_VERSION}); upgrade Chloe to open it"
+        ));
+    }
+
+    for (from_version, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        state = migration(state).map_err(|error| {
+            format!(
+                "migrating from schema version {from_version} to {} failed: {error}",
+                from_version + 1
+            )
+        })?;
+    }
+    Ok(state)
+}
+
+/// Version 1 wrapped the state in a versioned envelope and left the state itself unchanged.
+#[allow(clippy::unnecessary_wraps)]
+const fn migrate_unversioned(state: Value) -> Result<Value, String> {
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_unversioned_state_is_migrated() {
+        let document = json!({ "active_tab": "Tasks", "tasks": {} });
+
+        let (version, state) = split_envelope(document.clone());
+        assert_eq!(version, 0);
+        assert_eq!(migrate(state, version), Ok(document));
+    }
+
+    #[test]
+    fn test_versioned_state_is_unwrapped() {
+        let document = json!({ "version": CURRENT_VERSION, "state": { "active_tab": "Tasks" } });
+
+        let (version, state) = split_envelope(document);
+        assert_eq!(version, CURRENT_VERSION);
+        assert_eq!(state, json!({ "active_tab": "Tasks" }));
+    }
+
+    #[test]
+    fn test_newer_version_is_refused() {
+        assert!(migrate(json!({}), CURRENT_VERSION + 1).is_err());
+    }
+}
@@ -1,4 +1,5 @@
+pub mod config;
+pub mod lock;
+pub mod migrations;
 pub mod paths;
 pub mod storage;
-
-// pub mod migrations;  // TODO: Add later for schema versioning
@@ -1,11 +1,27 @@
 use std::env;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// The directory the project's `.chloe/` lives in.
+///
+/// This is the root of the repository the current directory belongs to, so launching Chloe
+/// from a subdirectory opens the same board. Outside a repository it is the current directory.
+#[must_use]
+pub fn get_project_dir() -> PathBuf {
+    static PROJECT_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+    PROJECT_DIR
+        .get_or_init(|| {
+            let current_directory = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            crate::views::worktree::find_repository_root(&current_directory)
+                .unwrap_or(current_directory)
+        })
+        .clone()
+}
 
 #[must_use]
 pub fn get_config_dir() -> PathBuf {
-    env::current_dir()
-        .unwrap_or_else(|_| PathBuf::from("."))
-        .join(".chloe")
+    get_project_dir().join(".chloe")
 }
 
 #[must_use]
@@ -17,3 +33,24 @@ pub fn get_state_path() -> PathBuf {
 pub fn get_settings_path() -> PathBuf {
     get_config_dir().join("settings.json")
 }
+
+#[must_use]
+pub fn get_lock_path() -> PathBuf {
+    get_config_dir().join("chloe.lock")
+}
+
+/// The user-level config directory, `$XDG_CONFIG_HOME/chloe` or `~/.config/chloe`.
+#[must_use]
+pub fn get_global_config_dir() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_home.join("chloe"))
+}
+
+#[must_use]
+pub fn get_global_settings_path() -> Option<PathBuf> {
+    get_global_config_dir().map(|directory| directory.join("settings.json"))
+}
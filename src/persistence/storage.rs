@@ -1,7 +1,36 @@
+use super::config::SettingsLayers;
+use super::migrations;
 use crate::app::App;
-use crate::types::Result;
+use crate::types::{AppError, Result};
 use crate::views::settings::state::Settings;
-use std::fs;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+/// Writes `contents` to a temporary file next to `path` and renames it over `path`, so a crash
+/// or another reader never sees a half-written file.
+fn write_atomically(path: &Path, contents: &str) -> Result<()> {
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("state");
+    let temporary_path = path.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()));
+
+    let mut file = File::create(&temporary_path)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&temporary_path, path)?;
+
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct StateEnvelope<'a> {
+    version: u32,
+    state: &'a App,
+}
 
 pub fn save_state(app: &App) -> Result<()> {
     let path = super::paths::get_state_path();
@@ -10,12 +39,23 @@ pub fn save_state(app: &App) -> Result<()> {
         fs::create_dir_all(parent)?;
     }
 
-    let json = serde_json::to_string_pretty(app)?;
-    fs::write(path, json)?;
+    let envelope = StateEnvelope {
+        version: migrations::CURRENT_VERSION,
+        state: app,
+    };
+    let json = serde_json::to_string_pretty(&envelope)?;
+    write_atomically(&path, &json)?;
 
     Ok(())
 }
 
+/// Loads the board, migrating files written by older versions of Chloe after copying them to
+/// a backup next to the original.
+///
+/// # Errors
+///
+/// Returns an error instead of an empty board when the file exists but cannot be read, so
+/// that saving does not overwrite it.
 pub fn load_state() -> Result<App> {
     let path = super::paths::get_state_path();
 
@@ -23,34 +63,73 @@ pub fn load_state() -> Result<App> {
         return Ok(App::default());
     }
 
-    let json = fs::read_to_string(path)?;
-    let app: App = serde_json::from_str(&json)?;
+    let json = fs::read_to_string(&path)?;
+    let document: Value = serde_json::from_str(&json).map_err(|error| {
+        AppError::State(format!("{} is not valid JSON: {error}", path.display()))
+    })?;
+
+    let (version, mut state) = migrations::split_envelope(document);
+    if version != migrations::CURRENT_VERSION {
+        if version < migrations::CURRENT_VERSION {
+            let backup_path = path.with_extension(format!("json.v{version}.bak"));
+            fs::copy(&path, &backup_path)?;
+        }
+        state = migrations::migrate(state, version)
+            .map_err(|error| AppError::State(format!("Cannot load {}: {error}", path.display())))?;
+    }
 
-    Ok(app)
+    serde_json::from_value(state).map_err(|error| {
+        AppError::State(format!(
+            "Cannot load {}: {error}. Fix or move the file to start with an empty board",
+            path.display()
+        ))
+    })
 }
 
-pub fn save_settings(settings: &Settings) -> Result<()> {
+/// Writes the project's `settings.json` with what `settings` sets on top of the defaults and the
+/// global config, and records it as the new project layer.
+pub fn save_settings(settings: &Settings, layers: &mut SettingsLayers) -> Result<()> {
     let path = super::paths::get_settings_path();
 
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
 
-    let json = serde_json::to_string_pretty(settings)?;
-    fs::write(path, json)?;
+    let project = layers.project_layer(settings)?;
+    let json = serde_json::to_string_pretty(&project)?;
+    write_atomically(&path, &json)?;
+    layers.project = project;
 
     Ok(())
 }
 
+/// The settings in effect, with the project config merged over the global one.
 pub fn load_settings() -> Result<Settings> {
-    let path = super::paths::get_settings_path();
+    Ok(load_settings_layers()?.merged()?)
+}
 
+/// Reads the global and project `settings.json` files, either of which may be missing.
+///
+/// A project file written out in full by an older version keeps only the values that differ
+/// from the defaults, and is saved that way with the next change.
+pub fn load_settings_layers() -> Result<SettingsLayers> {
+    let global = match super::paths::get_global_settings_path() {
+        Some(path) => read_settings_layer(&path)?,
+        None => Map::new(),
+    };
+    let project = read_settings_layer(&super::paths::get_settings_path())?;
+
+    let mut layers = SettingsLayers { global, project };
+    layers.unpin_full_project_file()?;
+    Ok(layers)
+}
+
+fn read_settings_layer(path: &Path) -> Result<Map<String, Value>> {
     if !path.exists() {
-        return Ok(Settings::default());
+        return Ok(Map::new());
     }
 
     let json = fs::read_to_string(path)?;
-    let settings: Settings = serde_json::from_str(&json)?;
-
-    Ok(settings)
+    serde_json::from_str(&json)
+        .map_err(|error| AppError::Config(format!("Cannot read {}: {error}", path.display())))
 }
@@ -1,22 +1,27 @@
-use super::{GeneratedFile, OneShotPromptStyle, PromptStyle, ProviderSpec};
+use super::{
+    GeneratedFile, OneShotPromptStyle, PromptStyle, ProviderSpec, ResumeStyle, notify_command,
+};
 use crate::types::PermissionConfig;
+use std::borrow::Cow;
 use std::path::Path;
 use uuid::Uuid;
 
-pub static SPEC: ProviderSpec = ProviderSpec {
-    command: "amp",
-    prompt_style: PromptStyle::Direct,
-    oneshot_style: OneShotPromptStyle::Direct,
+pub static SPEC: ProviderSpec = ProviderSpec::built_in(
+    "amp",
+    PromptStyle::Direct,
+    OneShotPromptStyle::Direct,
+    ResumeStyle::Subcommand(Cow::Borrowed("threads continue")),
     generate_files,
-};
+);
 
 fn generate_files(
     task_id: Uuid,
     working_directory: &Path,
+    socket_path: &Path,
     _permission_config: &PermissionConfig,
 ) -> Vec<GeneratedFile> {
-    let notify_start = format!("chloe notify start --worktree-id {task_id}");
-    let notify_end = format!("chloe notify end --worktree-id {task_id}");
+    let notify_start = notify_command("start", task_id, socket_path);
+    let notify_end = notify_command("end", task_id, socket_path);
 
     let settings = serde_json::json!({
         "amp.hooks": [
@@ -64,9 +69,10 @@ mod tests {
     fn test_generate_files_creates_settings() {
         let task_id = Uuid::new_v4();
         let working_dir = Path::new("/tmp/test");
+        let socket_path = Path::new("/tmp/chloe-test.sock");
         let permission_config = PermissionConfig::default();
 
-        let files = generate_files(task_id, working_dir, &permission_config);
+        let files = generate_files(task_id, working_dir, socket_path, &permission_config);
 
         assert_eq!(files.len(), 1);
         assert_eq!(
@@ -1,23 +1,33 @@
-use super::{GeneratedFile, OneShotPromptStyle, PromptStyle, ProviderSpec};
+use super::{
+    GeneratedFile, OneShotPromptStyle, PERMISSION_HOOK_TIMEOUT_SECONDS, PromptStyle, ProviderSpec,
+    ResumeStyle, notify_command, permission_decision_command,
+};
 use crate::types::PermissionConfig;
+use std::borrow::Cow;
 use std::path::Path;
 use uuid::Uuid;
 
-pub static SPEC: ProviderSpec = ProviderSpec {
-    command: "claude",
-    prompt_style: PromptStyle::Direct,
-    oneshot_style: OneShotPromptStyle::Flag("-p"),
+pub static SPEC: ProviderSpec = ProviderSpec::built_in(
+    "claude",
+    PromptStyle::Direct,
+    OneShotPromptStyle::Flag(Cow::Borrowed("-p")),
+    ResumeStyle::Flag(Cow::Borrowed("--resume")),
     generate_files,
-};
+)
+.with_tool_activity();
 
 fn generate_files(
     task_id: Uuid,
     working_directory: &Path,
+    socket_path: &Path,
     permission_config: &PermissionConfig,
 ) -> Vec<GeneratedFile> {
-    let notify_start = format!("chloe notify start --worktree-id {task_id}");
-    let notify_end = format!("chloe notify end --worktree-id {task_id}");
-    let notify_permission = format!("chloe notify permission --worktree-id {task_id}");
+    let notify_start = notify_command("start", task_id, socket_path);
+    let notify_end = notify_command("end", task_id, socket_path);
+    let notify_permission = permission_decision_command(task_id, socket_path);
+    let notify_tool_use = notify_command("tool-use", task_id, socket_path);
+    let notify_tool_result = notify_command("tool-result", task_id, socket_path);
+    let notify_notification = notify_command("notification", task_id, socket_path);
 
     let allowed_tools = permission_config.to_provider_tool_list();
 
@@ -43,7 +53,34 @@ fn generate_files(
                 {
                     "matcher": "*",
                     "hooks": [
-                        { "type": "command", "command": notify_permission }
+                        {
+                            "type": "command",
+                            "command": notify_permission,
+                            "timeout": PERMISSION_HOOK_TIMEOUT_SECONDS
+                        }
+                    ]
+                }
+            ],
+            "PreToolUse": [
+                {
+                    "matcher": "*",
+                    "hooks": [
+                        { "type": "command", "command": notify_tool_use }
+                    ]
+                }
+            ],
+            "PostToolUse": [
+                {
+                    "matcher": "*",
+                    "hooks": [
+                        { "type": "command", "command": notify_tool_result }
+                    ]
+                }
+            ],
+            "Notification": [
+                {
+                    "hooks": [
+                        { "type": "command", "command": notify_notification }
                     ]
                 }
             ],
@@ -78,9 +115,10 @@ mod tests {
     fn test_generate_files_creates_settings() {
         let task_id = Uuid::new_v4();
         let working_dir = Path::new("/tmp/test");
+        let socket_path = Path::new("/tmp/chloe-test.sock");
         let permission_config = PermissionConfig::default();
 
-        let files = generate_files(task_id, working_dir, &permission_config);
+        let files = generate_files(task_id, working_dir, socket_path, &permission_config);
 
         assert_eq!(files.len(), 1);
         assert_eq!(
@@ -89,5 +127,8 @@ mod tests {
         );
         assert!(files[0].content.contains("hooks"));
         assert!(files[0].content.contains(&task_id.to_string()));
+        assert!(files[0].content.contains("--socket /tmp/chloe-test.sock"));
+        assert!(files[0].content.contains("PreToolUse"));
+        assert!(files[0].content.contains("chloe notify tool-result"));
     }
 }
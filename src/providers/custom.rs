@@ -0,0 +1,288 @@
+use super::{
+    FileGenerator, GeneratedFile, OneShotPromptStyle, PromptStyle, ProviderSpec, ResumeStyle,
+    notify_command,
+};
+use crate::types::{AgentProvider, DetectedProvider, ProviderConfig};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use uuid::Uuid;
+
+/// An agent declared in settings rather than compiled into Chloe.
+///
+/// The name identifies the provider everywhere a built-in one would be used, so it must not
+/// collide with a built-in provider name such as `ClaudeCode`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomProvider {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub arguments: Vec<String>,
+    #[serde(default)]
+    pub prompt_style: PromptStyle,
+    #[serde(default)]
+    pub oneshot_style: OneShotPromptStyle,
+    #[serde(default)]
+    pub resume_style: ResumeStyle,
+    #[serde(default)]
+    pub environment: HashMap<String, String>,
+    #[serde(default)]
+    pub files: Vec<FileTemplate>,
+}
+
+/// A hook or config file written into the task's working directory before the agent starts.
+///
+/// Both `path` and `content` may use the placeholders `{task_id}`, `{working_directory}`,
+/// `{socket_path}`, `{notify_start}`, `{notify_end}` and `{notify_permission}`; the
+/// `notify_*` ones expand to complete `chloe notify` shell commands.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileTemplate {
+    pub path: String,
+    pub content: String,
+}
+
+impl CustomProvider {
+    #[must_use]
+    pub fn with_command(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            command: name.to_string(),
+            arguments: Vec::new(),
+            prompt_style: PromptStyle::default(),
+            oneshot_style: OneShotPromptStyle::default(),
+            resume_style: ResumeStyle::default(),
+            environment: HashMap::new(),
+            files: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn provider(&self) -> AgentProvider {
+        AgentProvider::Custom(self.name.clone())
+    }
+
+    /// Whether the name can be told apart from the built-in providers when read back from
+    /// settings or state.
+    #[must_use]
+    pub fn has_valid_name(&self) -> bool {
+        !self.name.trim().is_empty()
+            && matches!(
+                AgentProvider::from_key(&self.name),
+                AgentProvider::Custom(_)
+            )
+    }
+
+    #[must_use]
+    pub fn to_spec(&self) -> ProviderSpec {
+        let mut environment: Vec<(String, String)> = self
+            .environment
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        environment.sort();
+
+        ProviderSpec {
+            command: Cow::Owned(self.command.clone()),
+            prompt_style: self.prompt_style.clone(),
+            oneshot_style: self.oneshot_style.clone(),
+            resume_style: self.resume_style.clone(),
+            arguments: self.arguments.clone(),
+            environment,
+            working_directory_argument: None,
+            reports_tool_activity: false,
+            file_generator: FileGenerator::Templates(self.files.clone()),
+        }
+    }
+
+    /// The registry entry for this provider. Arguments and environment stay empty because the
+    /// definition's own ones are already part of its spec.
+    #[must_use]
+    pub fn default_config(&self) -> ProviderConfig {
+        ProviderConfig {
+            command: PathBuf::from(&self.command),
+            arguments: Vec::new(),
+            environment: HashMap::new(),
+            working_directory_argument: None,
+            supports_worktree: true,
+            max_running_agents: None,
+        }
+    }
+
+    #[must_use]
+    pub fn detect(&self) -> Option<DetectedProvider> {
+        AgentProvider::detect_command(self.provider(), &self.command)
+    }
+}
+
+/// Renders the provider's file templates into files under `working_directory`.
+///
+/// # Errors
+///
+/// Returns an error if a rendered path is absolute or leaves the working directory.
+pub(super) fn render_templates(
+    templates: &[FileTemplate],
+    task_id: Uuid,
+    working_directory: &Path,
+    socket_path: &Path,
+) -> Result<Vec<GeneratedFile>, String> {
+    let placeholders = [
+        ("task_id", task_id.to_string()),
+        (
+            "working_directory",
+            working_directory.to_string_lossy().to_string(),
+        ),
+        ("socket_path", socket_path.to_string_lossy().to_string()),
+        (
+            "notify_start",
+            notify_command("start", task_id, socket_path),
+        ),
+        ("notify_end", notify_command("end", task_id, socket_path)),
+        (
+            "notify_permission",
+            notify_command("permission", task_id, socket_path),
+        ),
+    ];
+
+    let render = |template: &str| {
+        placeholders
+            .iter()
+            .fold(template.to_string(), |rendered, (name, value)| {
+                rendered.replace(&format!("{{{name}}}"), value)
+            })
+    };
+
+    templates
+        .iter()
+        .map(|template| {
+            let relative_path = PathBuf::from(render(&template.path));
+            let stays_inside = relative_path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+            if !stays_inside {
+                return Err(format!(
+                    "Provider file paths must be relative to the working directory: {}",
+                    relative_path.display()
+                ));
+            }
+
+            Ok(GeneratedFile {
+                path: working_directory.join(relative_path),
+                content: render(&template.content),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PermissionConfig;
+
+    fn aider() -> CustomProvider {
+        CustomProvider {
+            name: "Aider".to_string(),
+            command: "aider".to_string(),
+            arguments: vec!["--no-auto-commits".to_string()],
+            prompt_style: PromptStyle::Flag(Cow::Borrowed("--message")),
+            oneshot_style: OneShotPromptStyle::Flag(Cow::Borrowed("--message")),
+            resume_style: ResumeStyle::Unsupported,
+            environment: HashMap::from([("AIDER_DARK_MODE".to_string(), "1".to_string())]),
+            files: vec![FileTemplate {
+                path: ".aider/{task_id}.sh".to_string(),
+                content: "{notify_end}".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_parse_from_settings() {
+        let json = r#"{
+            "name": "Codex",
+            "command": "codex",
+            "prompt_style": { "flag": "--prompt" },
+            "oneshot_style": { "subcommand": "exec" }
+        }"#;
+
+        let provider: CustomProvider = serde_json::from_str(json).unwrap();
+
+        assert_eq!(provider.prompt_style, PromptStyle::Flag("--prompt".into()));
+        assert_eq!(
+            provider.oneshot_style,
+            OneShotPromptStyle::Subcommand("exec".into())
+        );
+        assert!(provider.files.is_empty());
+    }
+
+    #[test]
+    fn test_spec_builds_commands() {
+        let spec = aider().to_spec();
+
+        let command = spec.build_command("Fix the bug");
+        assert_eq!(command.program, "aider");
+        assert_eq!(
+            command.arguments,
+            vec!["--no-auto-commits", "--message", "Fix the bug"]
+        );
+        assert_eq!(
+            command.environment.get("AIDER_DARK_MODE"),
+            Some(&"1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_spec_renders_file_templates() {
+        let task_id = Uuid::new_v4();
+        let working_dir = Path::new("/tmp/test");
+        let socket_path = Path::new("/tmp/chloe-test.sock");
+
+        let files = aider()
+            .to_spec()
+            .build_files(
+                task_id,
+                working_dir,
+                socket_path,
+                &PermissionConfig::default(),
+            )
+            .unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(
+            files[0].path,
+            working_dir.join(".aider").join(format!("{task_id}.sh"))
+        );
+        assert_eq!(
+            files[0].content,
+            notify_command("end", task_id, socket_path)
+        );
+    }
+
+    #[test]
+    fn test_file_templates_must_stay_in_the_working_directory() {
+        let working_dir = Path::new("/tmp/test");
+        let socket_path = Path::new("/tmp/chloe-test.sock");
+
+        for path in [
+            "../outside.sh",
+            "/home/user/.bashrc",
+            "hooks/../../outside.sh",
+        ] {
+            let mut provider = aider();
+            provider.files[0].path = path.to_string();
+            let result = provider.to_spec().build_files(
+                Uuid::new_v4(),
+                working_dir,
+                socket_path,
+                &PermissionConfig::default(),
+            );
+            assert!(result.is_err(), "{path} was accepted");
+        }
+    }
+
+    #[test]
+    fn test_name_must_not_shadow_built_in() {
+        assert!(aider().has_valid_name());
+        assert!(!CustomProvider::with_command("Gemini").has_valid_name());
+        assert!(!CustomProvider::with_command(" ").has_valid_name());
+    }
+}
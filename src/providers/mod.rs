@@ -1,31 +1,71 @@
 mod amp;
 mod claude_code;
+mod custom;
 mod gemini;
 mod opencode;
+mod usage;
 
-use crate::types::{AgentProvider, PermissionConfig};
+pub use custom::{CustomProvider, FileTemplate};
+pub use usage::{TranscriptReader, UsageReport};
+
+use crate::helpers::text::escape_shell_arg;
+use crate::types::{AgentProvider, PermissionConfig, ProviderConfig};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
+type FileGeneratorFunction = fn(Uuid, &Path, &Path, &PermissionConfig) -> Vec<GeneratedFile>;
+
+#[derive(Debug, Clone)]
 pub struct ProviderSpec {
-    pub command: &'static str,
+    pub command: Cow<'static, str>,
     pub prompt_style: PromptStyle,
     pub oneshot_style: OneShotPromptStyle,
-    generate_files: fn(Uuid, &Path, &PermissionConfig) -> Vec<GeneratedFile>,
+    pub resume_style: ResumeStyle,
+    pub arguments: Vec<String>,
+    pub environment: Vec<(String, String)>,
+    pub working_directory_argument: Option<String>,
+    /// Whether the generated hooks report every tool call, so the activity feed can be built
+    /// from them instead of scraped from terminal output.
+    pub reports_tool_activity: bool,
+    file_generator: FileGenerator,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
+enum FileGenerator {
+    BuiltIn(FileGeneratorFunction),
+    Templates(Vec<FileTemplate>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
 pub enum PromptStyle {
+    #[default]
     Direct,
-    Flag(&'static str),
+    Flag(Cow<'static, str>),
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
 pub enum OneShotPromptStyle {
+    #[default]
     Direct,
-    Flag(&'static str),
-    Subcommand(&'static str),
+    Flag(Cow<'static, str>),
+    Subcommand(Cow<'static, str>),
+}
+
+/// How to reopen a conversation whose session id was reported by the provider's hooks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ResumeStyle {
+    #[default]
+    Unsupported,
+    /// `<flag> <session_id>`
+    Flag(Cow<'static, str>),
+    /// Whitespace-separated subcommand words followed by the session id.
+    Subcommand(Cow<'static, str>),
 }
 
 #[derive(Debug, Clone)]
@@ -41,12 +81,106 @@ pub struct ProviderCommand {
 }
 
 impl ProviderSpec {
+    const fn built_in(
+        command: &'static str,
+        prompt_style: PromptStyle,
+        oneshot_style: OneShotPromptStyle,
+        resume_style: ResumeStyle,
+        generate_files: FileGeneratorFunction,
+    ) -> Self {
+        Self {
+            command: Cow::Borrowed(command),
+            prompt_style,
+            oneshot_style,
+            resume_style,
+            arguments: Vec::new(),
+            environment: Vec::new(),
+            working_directory_argument: None,
+            reports_tool_activity: false,
+            file_generator: FileGenerator::BuiltIn(generate_files),
+        }
+    }
+
+    const fn with_tool_activity(mut self) -> Self {
+        self.reports_tool_activity = true;
+        self
+    }
+
+    /// Layers a `ProviderRegistry` entry on top of the spec.
+    ///
+    /// The configured command replaces the spec's one, its arguments come after the spec's own
+    /// and its environment variables win over the spec's.
+    #[must_use]
+    pub fn with_config(mut self, config: &ProviderConfig) -> Self {
+        if !config.command.as_os_str().is_empty() {
+            self.command = Cow::Owned(config.command.to_string_lossy().to_string());
+        }
+        self.arguments.extend(config.arguments.iter().cloned());
+
+        let mut environment: Vec<(String, String)> = config
+            .environment
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        environment.sort();
+        self.environment.extend(environment);
+
+        self.working_directory_argument
+            .clone_from(&config.working_directory_argument);
+        self
+    }
+
     #[must_use]
     pub fn build_command(&self, prompt: &str) -> ProviderCommand {
-        let mut arguments = Vec::new();
+        self.build_command_with_arguments(self.arguments.clone(), prompt)
+    }
 
+    /// Like [`Self::build_command`], but also passes `working_directory` through the
+    /// provider's working directory flag when one is configured.
+    #[must_use]
+    pub fn build_command_in(&self, prompt: &str, working_directory: &Path) -> ProviderCommand {
+        let arguments = self.arguments_in(working_directory);
+        self.build_command_with_arguments(arguments, prompt)
+    }
+
+    /// Builds the command that reopens `session_id` instead of starting from the task prompt,
+    /// or `None` when the provider cannot resume sessions.
+    #[must_use]
+    pub fn build_resume_command(
+        &self,
+        session_id: &str,
+        working_directory: &Path,
+    ) -> Option<ProviderCommand> {
+        let mut arguments = self.arguments_in(working_directory);
+
+        match &self.resume_style {
+            ResumeStyle::Unsupported => return None,
+            ResumeStyle::Flag(flag) => arguments.push(flag.to_string()),
+            ResumeStyle::Subcommand(subcommand) => {
+                arguments.extend(subcommand.split_whitespace().map(str::to_string));
+            }
+        }
+        arguments.push(session_id.to_string());
+
+        Some(self.command_with_arguments(arguments))
+    }
+
+    fn arguments_in(&self, working_directory: &Path) -> Vec<String> {
+        let mut arguments = self.arguments.clone();
+        if let Some(flag) = &self.working_directory_argument {
+            arguments.push(flag.clone());
+            arguments.push(working_directory.to_string_lossy().to_string());
+        }
+        arguments
+    }
+
+    fn build_command_with_arguments(
+        &self,
+        mut arguments: Vec<String>,
+        prompt: &str,
+    ) -> ProviderCommand {
         if !prompt.is_empty() {
-            match self.prompt_style {
+            match &self.prompt_style {
                 PromptStyle::Direct => arguments.push(prompt.to_string()),
                 PromptStyle::Flag(flag) => {
                     arguments.push(flag.to_string());
@@ -55,18 +189,14 @@ impl ProviderSpec {
             }
         }
 
-        ProviderCommand {
-            program: self.command.to_string(),
-            arguments,
-            environment: HashMap::new(),
-        }
+        self.command_with_arguments(arguments)
     }
 
     #[must_use]
     pub fn build_oneshot_command(&self, prompt: &str) -> ProviderCommand {
-        let mut arguments = Vec::new();
+        let mut arguments = self.arguments.clone();
 
-        match self.oneshot_style {
+        match &self.oneshot_style {
             OneShotPromptStyle::Direct => {
                 arguments.push(prompt.to_string());
             }
@@ -80,31 +210,92 @@ impl ProviderSpec {
             }
         }
 
+        self.command_with_arguments(arguments)
+    }
+
+    fn command_with_arguments(&self, arguments: Vec<String>) -> ProviderCommand {
         ProviderCommand {
             program: self.command.to_string(),
             arguments,
-            environment: HashMap::new(),
+            environment: self.environment.iter().cloned().collect(),
         }
     }
 
-    #[must_use]
+    /// The files the provider needs in `working_directory` to report back to Chloe.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a custom provider's file template would write outside
+    /// `working_directory`.
     pub fn build_files(
         &self,
         task_id: Uuid,
         working_directory: &Path,
+        socket_path: &Path,
         permission_config: &PermissionConfig,
-    ) -> Vec<GeneratedFile> {
-        (self.generate_files)(task_id, working_directory, permission_config)
+    ) -> Result<Vec<GeneratedFile>, String> {
+        match &self.file_generator {
+            FileGenerator::BuiltIn(generate_files) => Ok(generate_files(
+                task_id,
+                working_directory,
+                socket_path,
+                permission_config,
+            )),
+            FileGenerator::Templates(templates) => {
+                custom::render_templates(templates, task_id, working_directory, socket_path)
+            }
+        }
     }
 }
 
+/// Builds the hook command that reports `event_type` for `task_id`.
+///
+/// The socket path is baked in because hooks run inside the task's worktree, where Chloe
+/// would otherwise derive a different project socket.
+#[must_use]
+pub fn notify_command(event_type: &str, task_id: Uuid, socket_path: &Path) -> String {
+    let socket_argument = escape_shell_arg(&socket_path.to_string_lossy());
+    format!("chloe notify {event_type} --worktree-id {task_id} --socket {socket_argument}")
+}
+
+/// A permission hook that blocks until the request is answered from Chloe's permission inbox.
+#[must_use]
+pub fn permission_decision_command(task_id: Uuid, socket_path: &Path) -> String {
+    format!(
+        "{} --await-decision",
+        notify_command("permission", task_id, socket_path)
+    )
+}
+
+/// Hook timeout for [`permission_decision_command`], slightly longer than Chloe waits so the
+/// hook exits on its own instead of being killed.
+pub const PERMISSION_HOOK_TIMEOUT_SECONDS: u64 =
+    crate::events::PERMISSION_DECISION_TIMEOUT_SECONDS + 10;
+
+/// Returns the spec for `provider`, building custom ones from `custom_providers`.
+///
+/// A custom provider whose definition was removed falls back to running its name as a
+/// command with the prompt as the only argument.
 #[must_use]
-pub fn get_spec(provider: AgentProvider) -> &'static ProviderSpec {
+pub fn get_spec(
+    provider: &AgentProvider,
+    custom_providers: &[CustomProvider],
+) -> Cow<'static, ProviderSpec> {
     match provider {
-        AgentProvider::ClaudeCode => &claude_code::SPEC,
-        AgentProvider::Gemini => &gemini::SPEC,
-        AgentProvider::Amp => &amp::SPEC,
-        AgentProvider::OpenCode => &opencode::SPEC,
+        AgentProvider::ClaudeCode => Cow::Borrowed(&claude_code::SPEC),
+        AgentProvider::Gemini => Cow::Borrowed(&gemini::SPEC),
+        AgentProvider::Amp => Cow::Borrowed(&amp::SPEC),
+        AgentProvider::OpenCode => Cow::Borrowed(&opencode::SPEC),
+        AgentProvider::Custom(name) => {
+            let spec = custom_providers
+                .iter()
+                .find(|custom_provider| custom_provider.name == *name)
+                .map_or_else(
+                    || CustomProvider::with_command(name).to_spec(),
+                    CustomProvider::to_spec,
+                );
+            Cow::Owned(spec)
+        }
     }
 }
 
@@ -114,7 +305,7 @@ mod tests {
 
     #[test]
     fn test_build_command_direct_prompt() {
-        let spec = get_spec(AgentProvider::ClaudeCode);
+        let spec = get_spec(&AgentProvider::ClaudeCode, &[]);
         let command = spec.build_command("Fix the bug");
 
         assert_eq!(command.program, "claude");
@@ -123,7 +314,7 @@ mod tests {
 
     #[test]
     fn test_build_command_flag_prompt() {
-        let spec = get_spec(AgentProvider::OpenCode);
+        let spec = get_spec(&AgentProvider::OpenCode, &[]);
         let command = spec.build_command("Fix the bug");
 
         assert_eq!(command.program, "opencode");
@@ -132,10 +323,70 @@ mod tests {
 
     #[test]
     fn test_build_command_empty_prompt() {
-        let spec = get_spec(AgentProvider::ClaudeCode);
+        let spec = get_spec(&AgentProvider::ClaudeCode, &[]);
         let command = spec.build_command("");
 
         assert_eq!(command.program, "claude");
         assert!(command.arguments.is_empty());
     }
+
+    #[test]
+    fn test_config_overrides_command_arguments_and_environment() {
+        let config = ProviderConfig {
+            command: PathBuf::from("/opt/claude/bin/claude"),
+            arguments: vec!["--model".to_string(), "opus".to_string()],
+            environment: HashMap::from([("ANTHROPIC_LOG".to_string(), "debug".to_string())]),
+            working_directory_argument: Some("--cwd".to_string()),
+            supports_worktree: true,
+            max_running_agents: None,
+        };
+        let spec = get_spec(&AgentProvider::ClaudeCode, &[])
+            .into_owned()
+            .with_config(&config);
+
+        let command = spec.build_command_in("Fix the bug", Path::new("/tmp/task"));
+
+        assert_eq!(command.program, "/opt/claude/bin/claude");
+        assert_eq!(
+            command.arguments,
+            vec!["--model", "opus", "--cwd", "/tmp/task", "Fix the bug"]
+        );
+        assert_eq!(
+            command.environment.get("ANTHROPIC_LOG"),
+            Some(&"debug".to_string())
+        );
+    }
+
+    #[test]
+    fn test_build_resume_command() {
+        let working_directory = Path::new("/tmp/task");
+
+        let claude = get_spec(&AgentProvider::ClaudeCode, &[]);
+        let command = claude
+            .build_resume_command("abc-123", working_directory)
+            .unwrap();
+        assert_eq!(command.arguments, vec!["--resume", "abc-123"]);
+
+        let amp = get_spec(&AgentProvider::Amp, &[]);
+        let command = amp.build_resume_command("T-42", working_directory).unwrap();
+        assert_eq!(command.arguments, vec!["threads", "continue", "T-42"]);
+
+        let custom = get_spec(&AgentProvider::Custom("aider".to_string()), &[]);
+        assert!(
+            custom
+                .build_resume_command("abc-123", working_directory)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_notify_command_quotes_socket_path() {
+        let task_id = Uuid::nil();
+        let command = notify_command("start", task_id, Path::new("/tmp/my dir/chloe.sock"));
+
+        assert_eq!(
+            command,
+            format!("chloe notify start --worktree-id {task_id} --socket '/tmp/my dir/chloe.sock'")
+        );
+    }
 }
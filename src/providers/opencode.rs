@@ -1,22 +1,28 @@
-use super::{GeneratedFile, OneShotPromptStyle, PromptStyle, ProviderSpec};
+use super::{GeneratedFile, OneShotPromptStyle, PromptStyle, ProviderSpec, ResumeStyle};
 use crate::types::PermissionConfig;
+use std::borrow::Cow;
 use std::path::Path;
 use uuid::Uuid;
 
-pub static SPEC: ProviderSpec = ProviderSpec {
-    command: "opencode",
-    prompt_style: PromptStyle::Flag("--prompt"),
-    oneshot_style: OneShotPromptStyle::Subcommand("run"),
+pub static SPEC: ProviderSpec = ProviderSpec::built_in(
+    "opencode",
+    PromptStyle::Flag(Cow::Borrowed("--prompt")),
+    OneShotPromptStyle::Subcommand(Cow::Borrowed("run")),
+    ResumeStyle::Flag(Cow::Borrowed("--session")),
     generate_files,
-};
+)
+.with_tool_activity();
 
 fn generate_files(
     task_id: Uuid,
     working_directory: &Path,
+    socket_path: &Path,
     permission_config: &PermissionConfig,
 ) -> Vec<GeneratedFile> {
     let log_path = working_directory.join(".opencode").join("chloe-plugin.log");
     let log_path_str = log_path.to_string_lossy();
+    let socket_path_literal =
+        serde_json::to_string(&socket_path.to_string_lossy()).unwrap_or_default();
 
     let plugin_content = format!(
         r#"// Chloe integration plugin for OpenCode
@@ -27,12 +33,38 @@ const log = (msg) => appendFileSync("{log_path_str}", new Date().toISOString() +
 export const ChloeNotifier = async () => {{
   const {{ spawn }} = await import("child_process");
   const taskId = "{task_id}";
+  const socketPath = {socket_path_literal};
 
-  const notify = (type) => {{
-    spawn("chloe", ["notify", type, "--worktree-id", taskId], {{
+  const sessionIdOf = (event) => event.properties?.sessionID ?? event.properties?.info?.id;
+
+  const notify = (type, sessionId, payload = {{}}) => {{
+    const child = spawn("chloe", ["notify", type, "--worktree-id", taskId, "--socket", socketPath], {{
       detached: true,
-      stdio: "ignore",
-    }}).unref();
+      stdio: ["pipe", "ignore", "ignore"],
+    }});
+    child.stdin.end(JSON.stringify({{ session_id: sessionId ?? null, ...payload }}));
+    child.unref();
+  }};
+
+  // Token counts of every assistant message, per session, reported as running totals.
+  const usage = new Map();
+
+  const recordUsage = (info) => {{
+    const messages = usage.get(info.sessionID) ?? new Map();
+    messages.set(info.id, {{ tokens: info.tokens, cost: info.cost }});
+    usage.set(info.sessionID, messages);
+  }};
+
+  const totalUsage = (sessionId) => {{
+    const total = {{ input: 0, output: 0, cache: {{ read: 0, write: 0 }}, cost: 0 }};
+    for (const {{ tokens, cost }} of usage.get(sessionId)?.values() ?? []) {{
+      total.input += tokens?.input ?? 0;
+      total.output += (tokens?.output ?? 0) + (tokens?.reasoning ?? 0);
+      total.cache.read += tokens?.cache?.read ?? 0;
+      total.cache.write += tokens?.cache?.write ?? 0;
+      total.cost += cost ?? 0;
+    }}
+    return total;
   }};
 
   log("Plugin loaded, taskId: " + taskId);
@@ -41,16 +73,29 @@ export const ChloeNotifier = async () => {{
     event: async ({{ event }}) => {{
       log("event: " + event.type);
       if (event.type === "permission.updated") {{
-        notify("permission");
+        notify("permission", sessionIdOf(event));
       }}
       if (event.type === "session.updated") {{
-        notify("start");
+        notify("start", sessionIdOf(event));
+      }}
+      if (event.type === "message.updated" && event.properties?.info?.role === "assistant") {{
+        recordUsage(event.properties.info);
       }}
       if (event.type === "session.idle") {{
         log("session.idle detected, notifying end");
-        notify("end");
+        const sessionId = sessionIdOf(event);
+        notify("end", sessionId, {{ usage: totalUsage(sessionId) }});
       }}
     }},
+    "tool.execute.before": async (input, output) => {{
+      notify("tool-use", input.sessionID, {{ tool_name: input.tool, tool_input: output.args }});
+    }},
+    "tool.execute.after": async (input, output) => {{
+      notify("tool-result", input.sessionID, {{
+        tool_name: input.tool,
+        tool_response: {{ title: output.title, output: output.output, metadata: output.metadata }},
+      }});
+    }},
   }};
 }};
 "#
@@ -100,9 +145,10 @@ mod tests {
     fn test_generate_files_creates_plugin() {
         let task_id = Uuid::new_v4();
         let working_dir = Path::new("/tmp/test");
+        let socket_path = Path::new("/tmp/chloe-test.sock");
         let permission_config = PermissionConfig::default();
 
-        let files = generate_files(task_id, working_dir, &permission_config);
+        let files = generate_files(task_id, working_dir, socket_path, &permission_config);
 
         assert_eq!(files.len(), 2);
         assert_eq!(
@@ -114,6 +160,11 @@ mod tests {
         );
         assert!(files[0].content.contains("ChloeNotifier"));
         assert!(files[0].content.contains(&task_id.to_string()));
+        assert!(
+            files[0]
+                .content
+                .contains(r#"const socketPath = "/tmp/chloe-test.sock";"#)
+        );
 
         assert_eq!(
             files[1].path,
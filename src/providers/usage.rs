@@ -0,0 +1,318 @@
+use crate::events::HookEvent;
+use crate::types::TokenUsage;
+use chrono::{DateTime, Local, NaiveDate};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+const TOKENS_PER_PRICE_UNIT: f64 = 1_000_000.0;
+
+/// Dollars per million tokens for input, output, cache writes and cache reads, matched
+/// against the model name in order, so more specific names come first.
+const MODEL_PRICES: [(&str, [f64; 4]); 7] = [
+    ("opus-4-5", [5.0, 25.0, 6.25, 0.5]),
+    ("opus", [15.0, 75.0, 18.75, 1.5]),
+    ("sonnet", [3.0, 15.0, 3.75, 0.3]),
+    ("haiku-4", [1.0, 5.0, 1.25, 0.1]),
+    ("haiku", [0.8, 4.0, 1.0, 0.08]),
+    ("gemini-2.5-pro", [1.25, 10.0, 0.0, 0.31]),
+    ("gemini-2.5-flash", [0.3, 2.5, 0.0, 0.075]),
+];
+
+/// Usage found in a hook payload.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UsageReport {
+    /// The whole session, recomputed from its transcript.
+    Transcript(BTreeMap<NaiveDate, TokenUsage>),
+    /// The running total the provider reported for the session.
+    SessionTotal(TokenUsage),
+}
+
+/// What has been read of each transcript, so a hook only parses the lines added to it since
+/// the previous one.
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptReader {
+    transcripts: HashMap<PathBuf, TranscriptProgress>,
+}
+
+impl TranscriptReader {
+    /// Reads the session's usage from the transcript named in the payload, or from a `usage`
+    /// object for providers that report totals themselves.
+    pub fn usage_from_hook(&mut self, event: &HookEvent) -> Option<UsageReport> {
+        let data = &event.hook_data;
+
+        if let Some(path) = data.get("transcript_path").and_then(Value::as_str)
+            && let Some(by_day) = self.read_transcript(Path::new(path))
+        {
+            return Some(UsageReport::Transcript(by_day));
+        }
+
+        let model = data
+            .get("model")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        data.get("usage")
+            .map(|usage| parse_usage(usage, model))
+            .filter(|usage| !usage.is_empty() || usage.cost_usd > 0.0)
+            .map(UsageReport::SessionTotal)
+    }
+
+    fn read_transcript(&mut self, path: &Path) -> Option<BTreeMap<NaiveDate, TokenUsage>> {
+        let mut file = File::open(path).ok()?;
+        let length = file.metadata().ok()?.len();
+        let progress = self.transcripts.entry(path.to_path_buf()).or_default();
+        // A transcript that got shorter was rewritten, so it is read again from the start
+        if length < progress.offset {
+            *progress = TranscriptProgress::default();
+        }
+
+        file.seek(SeekFrom::Start(progress.offset)).ok()?;
+        let mut added = String::new();
+        file.read_to_string(&mut added).ok()?;
+
+        // A one-line JSONL transcript is also a valid JSON document, so look for the chat
+        // shape. Chat documents are rewritten as a whole, so they are parsed whole every time.
+        if progress.offset == 0
+            && let Ok(transcript) = serde_json::from_str::<Value>(&added)
+            && transcript.get("messages").is_some()
+        {
+            let by_day = parse_chat_transcript(&transcript);
+            return (!by_day.is_empty()).then_some(by_day);
+        }
+
+        // A line still being written is read with the next hook
+        let complete_length = added.rfind('\n').map_or(0, |end| end + 1);
+        progress.read_lines(&added[..complete_length]);
+        progress.offset += complete_length as u64;
+
+        let by_day = progress.by_day();
+        (!by_day.is_empty()).then_some(by_day)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct TranscriptProgress {
+    /// Bytes read so far, up to the end of the last complete line.
+    offset: u64,
+    lines_read: usize,
+    messages: BTreeMap<String, (NaiveDate, TokenUsage)>,
+}
+
+impl TranscriptProgress {
+    /// Claude Code writes one JSON object per line. A response split into several content
+    /// blocks repeats its message id, so only the last line for each id is counted.
+    fn read_lines(&mut self, lines: &str) {
+        for line in lines.lines() {
+            let line_number = self.lines_read;
+            self.lines_read += 1;
+
+            let Ok(entry) = serde_json::from_str::<Value>(line) else {
+                continue;
+            };
+            let Some(message) = entry.get("message") else {
+                continue;
+            };
+            let Some(usage) = message.get("usage") else {
+                continue;
+            };
+
+            let model = message
+                .get("model")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let message_id = message
+                .get("id")
+                .and_then(Value::as_str)
+                .map_or_else(|| format!("line-{line_number}"), str::to_string);
+            self.messages
+                .insert(message_id, (entry_day(&entry), parse_usage(usage, model)));
+        }
+    }
+
+    fn by_day(&self) -> BTreeMap<NaiveDate, TokenUsage> {
+        let mut by_day = BTreeMap::<NaiveDate, TokenUsage>::new();
+        for (day, usage) in self.messages.values() {
+            *by_day.entry(*day).or_default() += *usage;
+        }
+        by_day
+    }
+}
+
+/// Gemini CLI keeps the whole chat in one JSON document with token counts per message.
+fn parse_chat_transcript(transcript: &Value) -> BTreeMap<NaiveDate, TokenUsage> {
+    let mut by_day = BTreeMap::<NaiveDate, TokenUsage>::new();
+    let Some(messages) = transcript.get("messages").and_then(Value::as_array) else {
+        return by_day;
+    };
+
+    for message in messages {
+        let Some(tokens) = message.get("tokens") else {
+            continue;
+        };
+        let model = message
+            .get("model")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        *by_day.entry(entry_day(message)).or_default() += parse_usage(tokens, model);
+    }
+    by_day
+}
+
+fn entry_day(entry: &Value) -> NaiveDate {
+    entry
+        .get("timestamp")
+        .and_then(Value::as_str)
+        .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+        .map_or_else(
+            || Local::now().date_naive(),
+            |timestamp| timestamp.with_timezone(&Local).date_naive(),
+        )
+}
+
+/// Reads token counts under the names the supported providers use, estimating the cost from
+/// `model` when the provider did not report one.
+fn parse_usage(usage: &Value, model: &str) -> TokenUsage {
+    let count = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| usage.get(key)?.as_u64())
+            .unwrap_or_default()
+    };
+    let cache = usage.get("cache");
+    let cache_count = |key: &str| {
+        cache
+            .and_then(|cache| cache.get(key)?.as_u64())
+            .unwrap_or_default()
+    };
+
+    let mut token_usage = TokenUsage {
+        input_tokens: count(&["input_tokens", "inputTokens", "input"]),
+        output_tokens: count(&["output_tokens", "outputTokens", "output"]),
+        cache_read_tokens: count(&["cache_read_input_tokens", "cache_read_tokens", "cached"])
+            + cache_count("read"),
+        cache_write_tokens: count(&["cache_creation_input_tokens", "cache_write_tokens"])
+            + cache_count("write"),
+        cost_usd: 0.0,
+    };
+
+    token_usage.cost_usd = ["cost_usd", "total_cost_usd", "cost"]
+        .iter()
+        .find_map(|key| usage.get(key)?.as_f64())
+        .unwrap_or_else(|| estimate_cost(&token_usage, model));
+    token_usage
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn estimate_cost(usage: &TokenUsage, model: &str) -> f64 {
+    let Some((_, [input, output, cache_write, cache_read])) =
+        MODEL_PRICES.iter().find(|(name, _)| model.contains(name))
+    else {
+        return 0.0;
+    };
+
+    (usage.input_tokens as f64 * input
+        + usage.output_tokens as f64 * output
+        + usage.cache_write_tokens as f64 * cache_write
+        + usage.cache_read_tokens as f64 * cache_read)
+        / TOKENS_PER_PRICE_UNIT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_jsonl_transcript_counts_each_message_once() {
+        let contents = [
+            r#"{"type":"user","message":{"role":"user","content":"hi"}}"#,
+            r#"{"type":"assistant","timestamp":"2026-03-02T10:00:00Z","message":{"id":"msg_1","model":"claude-sonnet-4-5","usage":{"input_tokens":1000,"output_tokens":10,"cache_read_input_tokens":2000}}}"#,
+            r#"{"type":"assistant","timestamp":"2026-03-02T10:00:01Z","message":{"id":"msg_1","model":"claude-sonnet-4-5","usage":{"input_tokens":1000,"output_tokens":200,"cache_read_input_tokens":2000}}}"#,
+            "not json",
+        ]
+        .join("\n");
+
+        let mut progress = TranscriptProgress::default();
+        progress.read_lines(&contents);
+        let by_day = progress.by_day();
+        let total = by_day.values().next().unwrap();
+
+        assert_eq!(by_day.len(), 1);
+        assert_eq!(total.input_tokens, 1000);
+        assert_eq!(total.output_tokens, 200);
+        assert_eq!(total.cache_read_tokens, 2000);
+        // 1000 input at $3, 200 output at $15 and 2000 cache reads at $0.30 per million.
+        assert!((total.cost_usd - 0.0066).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_reported_usage_keeps_provider_cost() {
+        let event = HookEvent {
+            event: "end".to_string(),
+            worktree_id: uuid::Uuid::nil(),
+            timestamp: 0,
+            hook_data: serde_json::json!({
+                "usage": { "input": 50, "output": 5, "cache": { "read": 7, "write": 3 }, "cost": 0.25 },
+            }),
+            awaits_decision: false,
+        };
+
+        let Some(UsageReport::SessionTotal(usage)) =
+            TranscriptReader::default().usage_from_hook(&event)
+        else {
+            panic!("expected a session total");
+        };
+        assert_eq!(usage.input_tokens, 50);
+        assert_eq!(usage.cache_read_tokens, 7);
+        assert_eq!(usage.cache_write_tokens, 3);
+        assert!((usage.cost_usd - 0.25).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_transcript_is_read_where_the_last_hook_left_off() {
+        let path =
+            std::env::temp_dir().join(format!("chloe-transcript-{}.jsonl", uuid::Uuid::new_v4()));
+        let line = |id: &str, output: u64| {
+            format!(
+                r#"{{"timestamp":"2026-03-02T10:00:00Z","message":{{"id":"{id}","usage":{{"input_tokens":10,"output_tokens":{output}}}}}}}"#
+            )
+        };
+        let event = HookEvent {
+            event: "stop".to_string(),
+            worktree_id: uuid::Uuid::nil(),
+            timestamp: 0,
+            hook_data: serde_json::json!({ "transcript_path": path }),
+            awaits_decision: false,
+        };
+        let output_tokens = |report: Option<UsageReport>| match report {
+            Some(UsageReport::Transcript(by_day)) => by_day
+                .values()
+                .map(|usage| usage.output_tokens)
+                .sum::<u64>(),
+            other => panic!("expected a transcript report, got {other:?}"),
+        };
+        let mut reader = TranscriptReader::default();
+
+        // The second line is still being written
+        fs::write(
+            &path,
+            format!("{}\n{}", line("msg_1", 5), &line("msg_2", 7)[..20]),
+        )
+        .unwrap();
+        assert_eq!(output_tokens(reader.usage_from_hook(&event)), 5);
+
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            format!("{}\n{}\n", &line("msg_2", 7)[20..], line("msg_1", 9)).as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(output_tokens(reader.usage_from_hook(&event)), 16);
+
+        fs::write(&path, format!("{}\n", line("msg_3", 1))).unwrap();
+        assert_eq!(output_tokens(reader.usage_from_hook(&event)), 1);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -5,6 +5,7 @@ pub enum AppError {
     Io(std::io::Error),
     Serialization(serde_json::Error),
     Config(String),
+    State(String),
 }
 
 impl fmt::Display for AppError {
@@ -13,6 +14,7 @@ impl fmt::Display for AppError {
             Self::Io(e) => write!(f, "IO error: {e}"),
             Self::Serialization(e) => write!(f, "Serialization error: {e}"),
             Self::Config(message) => write!(f, "Configuration error: {message}"),
+            Self::State(message) => write!(f, "State error: {message}"),
         }
     }
 }
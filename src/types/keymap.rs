@@ -0,0 +1,969 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// A key together with the modifiers held with it, such as `ctrl+s`.
+///
+/// Characters carry their case, so the Shift modifier is dropped from them: `Q` and
+/// `shift+q` are the same chord.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    #[must_use]
+    pub const fn key(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[must_use]
+    pub const fn char(character: char) -> Self {
+        Self::key(KeyCode::Char(character))
+    }
+
+    #[must_use]
+    pub const fn ctrl(character: char) -> Self {
+        Self {
+            code: KeyCode::Char(character),
+            modifiers: KeyModifiers::CONTROL,
+        }
+    }
+
+    #[must_use]
+    pub const fn shift(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::SHIFT,
+        }
+    }
+
+    /// The chord a key press matches.
+    #[must_use]
+    pub fn from_event(key: KeyEvent) -> Self {
+        // Some terminals send Ctrl and a letter as the bare control character
+        if let KeyCode::Char(control @ '\x01'..='\x1a') = key.code
+            && let Some(letter) = char::from_u32(u32::from(control) - 1 + u32::from('a'))
+        {
+            let key = KeyEvent::new(KeyCode::Char(letter), key.modifiers | KeyModifiers::CONTROL);
+            return Self::from_event(key);
+        }
+
+        let mut modifiers = key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if key.modifiers.contains(KeyModifiers::SHIFT)
+            && !matches!(key.code, KeyCode::Char(_) | KeyCode::BackTab)
+        {
+            modifiers |= KeyModifiers::SHIFT;
+        }
+        Self {
+            code: key.code,
+            modifiers,
+        }
+    }
+
+    /// Parses a chord written as modifiers and a key joined by `+`, like `ctrl+s`, `shift+tab`,
+    /// `G` or `enter`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the part that is not a known key or modifier.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let (modifier_names, key_name) = match text.rsplit_once('+') {
+            // A trailing `+` is the plus key itself
+            Some((modifiers, "")) => (modifiers.strip_suffix('+').unwrap_or(modifiers), "+"),
+            Some((modifiers, key)) => (modifiers, key),
+            None => ("", text),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_names.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match name.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("Unknown modifier \"{name}\" in \"{text}\"")),
+            };
+        }
+
+        let code = parse_key_code(key_name)
+            .ok_or_else(|| format!("Unknown key \"{key_name}\" in \"{text}\""))?;
+
+        let shifted = modifiers.contains(KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(character) if shifted => KeyCode::Char(character.to_ascii_uppercase()),
+            KeyCode::Tab if shifted => KeyCode::BackTab,
+            code => code,
+        };
+
+        Ok(Self::from_event(KeyEvent::new(code, modifiers)))
+    }
+}
+
+fn parse_key_code(name: &str) -> Option<KeyCode> {
+    let mut characters = name.chars();
+    if let (Some(character), None) = (characters.next(), characters.next()) {
+        return Some(KeyCode::Char(character));
+    }
+
+    let code = match name.to_ascii_lowercase().as_str() {
+        "space" => KeyCode::Char(' '),
+        "plus" => KeyCode::Char('+'),
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        name => {
+            let number = name.strip_prefix('f')?.parse().ok()?;
+            KeyCode::F(number)
+        }
+    };
+    Some(code)
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(formatter, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(formatter, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(formatter, "Shift+")?;
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(formatter, "Space"),
+            KeyCode::Char(character) => write!(formatter, "{character}"),
+            KeyCode::Enter => write!(formatter, "Enter"),
+            KeyCode::Esc => write!(formatter, "Esc"),
+            KeyCode::Tab => write!(formatter, "Tab"),
+            KeyCode::BackTab => write!(formatter, "Shift+Tab"),
+            KeyCode::Backspace => write!(formatter, "Backspace"),
+            KeyCode::Delete => write!(formatter, "Del"),
+            KeyCode::Insert => write!(formatter, "Ins"),
+            KeyCode::Home => write!(formatter, "Home"),
+            KeyCode::End => write!(formatter, "End"),
+            KeyCode::PageUp => write!(formatter, "PgUp"),
+            KeyCode::PageDown => write!(formatter, "PgDn"),
+            KeyCode::Up => write!(formatter, "↑"),
+            KeyCode::Down => write!(formatter, "↓"),
+            KeyCode::Left => write!(formatter, "←"),
+            KeyCode::Right => write!(formatter, "→"),
+            KeyCode::F(number) => write!(formatter, "F{number}"),
+            code => write!(formatter, "{code:?}"),
+        }
+    }
+}
+
+/// Where a binding applies. Each tab has its own context, the terminal contexts apply while a
+/// pane has the keyboard, and the dialog contexts while a dialog is open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum KeyContext {
+    /// Tab switching and quitting, everywhere except in a focused terminal or a text field.
+    Global,
+    /// The Tasks tab in either view.
+    Tasks,
+    Kanban,
+    Focus,
+    /// A focused agent terminal. Keys bound here never reach the agent.
+    Terminal,
+    /// Scrolling back through a terminal or an activity summary.
+    Scroll,
+    Instances,
+    Roadmap,
+    Worktree,
+    PullRequests,
+    Settings,
+    /// A yes or no question, such as a delete or exit confirmation.
+    Dialog,
+    /// A list to choose one entry from, such as the settings selectors and the provider,
+    /// worktree and blocker pickers.
+    Picker,
+    /// The review popup of a finished task.
+    Review,
+    /// The merge dialog. While the squash subject has focus, keys that type text go into it.
+    Merge,
+    /// The permission request inbox.
+    Permissions,
+    /// The command palette. Keys not bound here type into its search.
+    Palette,
+}
+
+impl KeyContext {
+    pub const ALL: [Self; 17] = [
+        Self::Global,
+        Self::Tasks,
+        Self::Kanban,
+        Self::Focus,
+        Self::Terminal,
+        Self::Scroll,
+        Self::Instances,
+        Self::Roadmap,
+        Self::Worktree,
+        Self::PullRequests,
+        Self::Settings,
+        Self::Dialog,
+        Self::Picker,
+        Self::Review,
+        Self::Merge,
+        Self::Permissions,
+        Self::Palette,
+    ];
+
+    /// The name used for the context in `settings.json`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Global => "global",
+            Self::Tasks => "tasks",
+            Self::Kanban => "kanban",
+            Self::Focus => "focus",
+            Self::Terminal => "terminal",
+            Self::Scroll => "scroll",
+            Self::Instances => "instances",
+            Self::Roadmap => "roadmap",
+            Self::Worktree => "worktree",
+            Self::PullRequests => "pull_requests",
+            Self::Settings => "settings",
+            Self::Dialog => "dialog",
+            Self::Picker => "picker",
+            Self::Review => "review",
+            Self::Merge => "merge",
+            Self::Permissions => "permissions",
+            Self::Palette => "palette",
+        }
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|context| context.name() == name)
+    }
+
+    /// The contexts whose bindings are looked up before this one's, so a chord bound there
+    /// can never reach an action here. Dialogs have the keyboard to themselves.
+    #[must_use]
+    pub const fn shadowed_by(self) -> &'static [Self] {
+        match self {
+            Self::Global
+            | Self::Terminal
+            | Self::Scroll
+            | Self::Dialog
+            | Self::Picker
+            | Self::Review
+            | Self::Merge
+            | Self::Permissions
+            | Self::Palette => &[],
+            Self::Kanban | Self::Focus => &[Self::Global, Self::Tasks],
+            Self::Tasks
+            | Self::Instances
+            | Self::Roadmap
+            | Self::Worktree
+            | Self::PullRequests
+            | Self::Settings => &[Self::Global],
+        }
+    }
+
+    /// The actions available in the context and the chords they are bound to by default.
+    #[must_use]
+    pub const fn default_bindings(self) -> &'static [(KeyAction, &'static [KeyChord])] {
+        match self {
+            Self::Global => GLOBAL_BINDINGS,
+            Self::Tasks => TASKS_BINDINGS,
+            Self::Kanban => KANBAN_BINDINGS,
+            Self::Focus => FOCUS_BINDINGS,
+            Self::Terminal => TERMINAL_BINDINGS,
+            Self::Scroll => SCROLL_BINDINGS,
+            Self::Instances => INSTANCES_BINDINGS,
+            Self::Roadmap => ROADMAP_BINDINGS,
+            Self::Worktree => WORKTREE_BINDINGS,
+            Self::PullRequests => PULL_REQUESTS_BINDINGS,
+            Self::Settings => SETTINGS_BINDINGS,
+            Self::Dialog => DIALOG_BINDINGS,
+            Self::Picker => PICKER_BINDINGS,
+            Self::Review => REVIEW_BINDINGS,
+            Self::Merge => MERGE_BINDINGS,
+            Self::Permissions => PERMISSIONS_BINDINGS,
+            Self::Palette => PALETTE_BINDINGS,
+        }
+    }
+
+    fn has_action(self, action: KeyAction) -> bool {
+        self.default_bindings()
+            .iter()
+            .any(|(available, _)| *available == action)
+    }
+}
+
+/// Something a key can be bound to. Actions are looked up within a [`KeyContext`], so the same
+/// action can be bound differently in each context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyAction {
+    Quit,
+    TogglePermissions,
+    CommandPalette,
+    NextTab,
+    PreviousTab,
+    ShowTasks,
+    ShowInstances,
+    ShowRoadmap,
+    ShowWorktrees,
+    ShowPullRequests,
+    ShowSettings,
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    GoToTop,
+    GoToBottom,
+    HalfPageUp,
+    HalfPageDown,
+    PageUp,
+    PageDown,
+    SwitchPanel,
+    Open,
+    Add,
+    Edit,
+    Delete,
+    MoveBack,
+    Start,
+    ToggleView,
+    JumpToAgent,
+    ToggleQueue,
+    CyclePriority,
+    SelectBlockers,
+    ExitTerminal,
+    SendEscape,
+    ScrollMode,
+    Exit,
+    NewPane,
+    ClosePane,
+    NextPane,
+    PreviousPane,
+    ShowActivity,
+    ConvertToTask,
+    Generate,
+    OpenInIde,
+    OpenInTerminal,
+    Refresh,
+    Confirm,
+    Cancel,
+    Allow,
+    AllowForTask,
+    AllowTool,
+    Deny,
+    DenyForTask,
+    DenyTool,
+    ResetToGlobal,
+}
+
+impl KeyAction {
+    const ALL: [Self; 56] = [
+        Self::Quit,
+        Self::TogglePermissions,
+        Self::CommandPalette,
+        Self::NextTab,
+        Self::PreviousTab,
+        Self::ShowTasks,
+        Self::ShowInstances,
+        Self::ShowRoadmap,
+        Self::ShowWorktrees,
+        Self::ShowPullRequests,
+        Self::ShowSettings,
+        Self::MoveUp,
+        Self::MoveDown,
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::GoToTop,
+        Self::GoToBottom,
+        Self::HalfPageUp,
+        Self::HalfPageDown,
+        Self::PageUp,
+        Self::PageDown,
+        Self::SwitchPanel,
+        Self::Open,
+        Self::Add,
+        Self::Edit,
+        Self::Delete,
+        Self::MoveBack,
+        Self::Start,
+        Self::ToggleView,
+        Self::JumpToAgent,
+        Self::ToggleQueue,
+        Self::CyclePriority,
+        Self::SelectBlockers,
+        Self::ExitTerminal,
+        Self::SendEscape,
+        Self::ScrollMode,
+        Self::Exit,
+        Self::NewPane,
+        Self::ClosePane,
+        Self::NextPane,
+        Self::PreviousPane,
+        Self::ShowActivity,
+        Self::ConvertToTask,
+        Self::Generate,
+        Self::OpenInIde,
+        Self::OpenInTerminal,
+        Self::Refresh,
+        Self::Confirm,
+        Self::Cancel,
+        Self::Allow,
+        Self::AllowForTask,
+        Self::AllowTool,
+        Self::Deny,
+        Self::DenyForTask,
+        Self::DenyTool,
+        Self::ResetToGlobal,
+    ];
+
+    /// The name used for the action in `settings.json`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::TogglePermissions => "toggle_permissions",
+            Self::CommandPalette => "command_palette",
+            Self::NextTab => "next_tab",
+            Self::PreviousTab => "previous_tab",
+            Self::ShowTasks => "show_tasks",
+            Self::ShowInstances => "show_instances",
+            Self::ShowRoadmap => "show_roadmap",
+            Self::ShowWorktrees => "show_worktrees",
+            Self::ShowPullRequests => "show_pull_requests",
+            Self::ShowSettings => "show_settings",
+            Self::MoveUp => "move_up",
+            Self::MoveDown => "move_down",
+            Self::MoveLeft => "move_left",
+            Self::MoveRight => "move_right",
+            Self::GoToTop => "go_to_top",
+            Self::GoToBottom => "go_to_bottom",
+            Self::HalfPageUp => "half_page_up",
+            Self::HalfPageDown => "half_page_down",
+            Self::PageUp => "page_up",
+            Self::PageDown => "page_down",
+            Self::SwitchPanel => "switch_panel",
+            Self::Open => "open",
+            Self::Add => "add",
+            Self::Edit => "edit",
+            Self::Delete => "delete",
+            Self::MoveBack => "move_back",
+            Self::Start => "start",
+            Self::ToggleView => "toggle_view",
+            Self::JumpToAgent => "jump_to_agent",
+            Self::ToggleQueue => "toggle_queue",
+            Self::CyclePriority => "cycle_priority",
+            Self::SelectBlockers => "select_blockers",
+            Self::ExitTerminal => "exit_terminal",
+            Self::SendEscape => "send_escape",
+            Self::ScrollMode => "scroll_mode",
+            Self::Exit => "exit",
+            Self::NewPane => "new_pane",
+            Self::ClosePane => "close_pane",
+            Self::NextPane => "next_pane",
+            Self::PreviousPane => "previous_pane",
+            Self::ShowActivity => "show_activity",
+            Self::ConvertToTask => "convert_to_task",
+            Self::Generate => "generate",
+            Self::OpenInIde => "open_in_ide",
+            Self::OpenInTerminal => "open_in_terminal",
+            Self::Refresh => "refresh",
+            Self::Confirm => "confirm",
+            Self::Cancel => "cancel",
+            Self::Allow => "allow",
+            Self::AllowForTask => "allow_for_task",
+            Self::AllowTool => "allow_tool",
+            Self::Deny => "deny",
+            Self::DenyForTask => "deny_for_task",
+            Self::DenyTool => "deny_tool",
+            Self::ResetToGlobal => "reset_to_global",
+        }
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+
+    /// Actions that only move around or change what is shown, the ones a read-only board still
+    /// answers to.
+    #[must_use]
+    pub const fn is_browsing(self) -> bool {
+        matches!(
+            self,
+            Self::MoveUp
+                | Self::MoveDown
+                | Self::MoveLeft
+                | Self::MoveRight
+                | Self::GoToTop
+                | Self::GoToBottom
+                | Self::HalfPageUp
+                | Self::HalfPageDown
+                | Self::SwitchPanel
+                | Self::ToggleView
+                | Self::Exit
+        )
+    }
+}
+
+const UP: KeyChord = KeyChord::key(KeyCode::Up);
+const DOWN: KeyChord = KeyChord::key(KeyCode::Down);
+const LEFT: KeyChord = KeyChord::key(KeyCode::Left);
+const RIGHT: KeyChord = KeyChord::key(KeyCode::Right);
+const ENTER: KeyChord = KeyChord::key(KeyCode::Enter);
+const ESC: KeyChord = KeyChord::key(KeyCode::Esc);
+const BACKSPACE: KeyChord = KeyChord::key(KeyCode::Backspace);
+const TAB: KeyChord = KeyChord::key(KeyCode::Tab);
+const BACKTAB: KeyChord = KeyChord::key(KeyCode::BackTab);
+
+const GLOBAL_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (
+        KeyAction::Quit,
+        &[
+            KeyChord::char('q'),
+            KeyChord::char('Q'),
+            KeyChord::ctrl('c'),
+        ],
+    ),
+    (KeyAction::TogglePermissions, &[KeyChord::char('!')]),
+    (KeyAction::CommandPalette, &[KeyChord::ctrl('p')]),
+    (KeyAction::NextTab, &[TAB]),
+    (KeyAction::PreviousTab, &[BACKTAB]),
+    (KeyAction::ShowTasks, &[KeyChord::char('1')]),
+    (KeyAction::ShowInstances, &[KeyChord::char('2')]),
+    (KeyAction::ShowRoadmap, &[KeyChord::char('3')]),
+    (KeyAction::ShowWorktrees, &[KeyChord::char('4')]),
+    (KeyAction::ShowPullRequests, &[KeyChord::char('5')]),
+    (KeyAction::ShowSettings, &[KeyChord::char('6')]),
+];
+
+const TASKS_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::ToggleView, &[KeyChord::char('/')]),
+    (
+        KeyAction::JumpToAgent,
+        &[KeyChord::char('t'), KeyChord::char('T')],
+    ),
+];
+
+const KANBAN_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::MoveLeft, &[KeyChord::char('h'), LEFT]),
+    (KeyAction::MoveRight, &[KeyChord::char('l'), RIGHT]),
+    (KeyAction::MoveUp, &[KeyChord::char('k'), UP]),
+    (KeyAction::MoveDown, &[KeyChord::char('j'), DOWN]),
+    (KeyAction::Add, &[KeyChord::char('a')]),
+    (KeyAction::Edit, &[KeyChord::char('e')]),
+    (KeyAction::Delete, &[KeyChord::char('d')]),
+    (KeyAction::ToggleQueue, &[KeyChord::char('r')]),
+    (KeyAction::CyclePriority, &[KeyChord::char('R')]),
+    (KeyAction::SelectBlockers, &[KeyChord::char('b')]),
+    (KeyAction::Open, &[ENTER]),
+    (KeyAction::MoveBack, &[BACKSPACE]),
+];
+
+const FOCUS_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::MoveUp, &[KeyChord::char('k'), UP]),
+    (KeyAction::MoveDown, &[KeyChord::char('j'), DOWN]),
+    (KeyAction::SwitchPanel, &[KeyChord::char('w')]),
+    (KeyAction::GoToTop, &[KeyChord::char('g')]),
+    (KeyAction::GoToBottom, &[KeyChord::char('G')]),
+    (KeyAction::Add, &[KeyChord::char('a')]),
+    (KeyAction::Edit, &[KeyChord::char('e')]),
+    (KeyAction::Delete, &[KeyChord::char('d')]),
+    (KeyAction::Start, &[KeyChord::char('s')]),
+    (KeyAction::Open, &[ENTER]),
+    (KeyAction::MoveBack, &[BACKSPACE]),
+];
+
+const TERMINAL_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::ExitTerminal, &[ESC]),
+    (KeyAction::SendEscape, &[KeyChord::shift(KeyCode::Esc)]),
+    (KeyAction::ScrollMode, &[KeyChord::ctrl('s')]),
+];
+
+const SCROLL_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::MoveUp, &[KeyChord::char('k'), UP]),
+    (KeyAction::MoveDown, &[KeyChord::char('j'), DOWN]),
+    (KeyAction::HalfPageUp, &[KeyChord::ctrl('u')]),
+    (KeyAction::HalfPageDown, &[KeyChord::ctrl('d')]),
+    (KeyAction::GoToTop, &[KeyChord::char('g')]),
+    (KeyAction::GoToBottom, &[KeyChord::char('G')]),
+    (KeyAction::Exit, &[ESC, KeyChord::char('q')]),
+];
+
+const INSTANCES_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::MoveLeft, &[KeyChord::char('h'), LEFT]),
+    (KeyAction::MoveRight, &[KeyChord::char('l'), RIGHT]),
+    (KeyAction::MoveUp, &[KeyChord::char('k'), UP]),
+    (KeyAction::MoveDown, &[KeyChord::char('j'), DOWN]),
+    (KeyAction::Open, &[ENTER]),
+    (KeyAction::NewPane, &[KeyChord::char('c')]),
+    (KeyAction::ClosePane, &[KeyChord::char('x')]),
+    (KeyAction::NextPane, &[KeyChord::char('n')]),
+    (KeyAction::PreviousPane, &[KeyChord::char('N')]),
+    (KeyAction::ShowActivity, &[KeyChord::char('A')]),
+];
+
+const ROADMAP_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::MoveUp, &[KeyChord::char('k'), UP]),
+    (KeyAction::MoveDown, &[KeyChord::char('j'), DOWN]),
+    (KeyAction::Add, &[KeyChord::char('a')]),
+    (KeyAction::Edit, &[KeyChord::char('e')]),
+    (KeyAction::Delete, &[KeyChord::char('d')]),
+    (KeyAction::ConvertToTask, &[KeyChord::char('t')]),
+    (KeyAction::CyclePriority, &[KeyChord::char('p')]),
+    (
+        KeyAction::Generate,
+        &[KeyChord::char('g'), KeyChord::char('G')],
+    ),
+];
+
+const WORKTREE_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::MoveUp, &[KeyChord::char('k'), UP]),
+    (KeyAction::MoveDown, &[KeyChord::char('j'), DOWN]),
+    (KeyAction::OpenInIde, &[KeyChord::char('o')]),
+    (KeyAction::OpenInTerminal, &[KeyChord::char('t')]),
+    (KeyAction::Delete, &[KeyChord::char('d')]),
+];
+
+const PULL_REQUESTS_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::MoveUp, &[KeyChord::char('k'), UP]),
+    (KeyAction::MoveDown, &[KeyChord::char('j'), DOWN]),
+    (KeyAction::GoToTop, &[KeyChord::char('g')]),
+    (KeyAction::GoToBottom, &[KeyChord::char('G')]),
+    (KeyAction::Open, &[ENTER, KeyChord::char('o')]),
+    (
+        KeyAction::Refresh,
+        &[KeyChord::char('r'), KeyChord::char('R')],
+    ),
+    (KeyAction::Exit, &[ESC]),
+];
+
+const SETTINGS_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::SwitchPanel, &[KeyChord::char('w')]),
+    (KeyAction::MoveUp, &[KeyChord::char('k'), UP]),
+    (KeyAction::MoveDown, &[KeyChord::char('j'), DOWN]),
+    (KeyAction::MoveLeft, &[KeyChord::char('h'), LEFT]),
+    (KeyAction::MoveRight, &[KeyChord::char('l'), RIGHT]),
+    (KeyAction::GoToTop, &[KeyChord::char('g')]),
+    (KeyAction::GoToBottom, &[KeyChord::char('G')]),
+    (KeyAction::Open, &[ENTER, KeyChord::char(' ')]),
+    (KeyAction::ResetToGlobal, &[KeyChord::char('r')]),
+];
+
+const DIALOG_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (
+        KeyAction::Confirm,
+        &[KeyChord::char('y'), KeyChord::char('Y')],
+    ),
+    (
+        KeyAction::Cancel,
+        &[KeyChord::char('n'), KeyChord::char('N'), ESC],
+    ),
+];
+
+const PICKER_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::MoveUp, &[KeyChord::char('k'), UP]),
+    (KeyAction::MoveDown, &[KeyChord::char('j'), DOWN]),
+    (KeyAction::Open, &[ENTER, KeyChord::char(' ')]),
+    (KeyAction::Exit, &[ESC, KeyChord::char('q')]),
+];
+
+const REVIEW_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::NextPane, &[TAB]),
+    (KeyAction::PreviousPane, &[BACKTAB]),
+    (KeyAction::MoveUp, &[KeyChord::char('k'), UP]),
+    (KeyAction::MoveDown, &[KeyChord::char('j'), DOWN]),
+    (KeyAction::PageUp, &[KeyChord::key(KeyCode::PageUp)]),
+    (KeyAction::PageDown, &[KeyChord::key(KeyCode::PageDown)]),
+    (KeyAction::MoveLeft, &[KeyChord::char('h'), LEFT]),
+    (KeyAction::MoveRight, &[KeyChord::char('l'), RIGHT]),
+    (KeyAction::Open, &[ENTER]),
+    (KeyAction::Exit, &[ESC, KeyChord::char('q')]),
+];
+
+const MERGE_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::NextPane, &[TAB]),
+    (KeyAction::PreviousPane, &[BACKTAB]),
+    (KeyAction::MoveUp, &[KeyChord::char('k'), UP]),
+    (KeyAction::MoveDown, &[KeyChord::char('j'), DOWN]),
+    (KeyAction::Open, &[ENTER]),
+    (KeyAction::Exit, &[ESC, KeyChord::char('q')]),
+];
+
+const PERMISSIONS_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::MoveUp, &[KeyChord::char('k'), UP]),
+    (KeyAction::MoveDown, &[KeyChord::char('j'), DOWN]),
+    (KeyAction::Allow, &[KeyChord::char('y')]),
+    (KeyAction::AllowForTask, &[KeyChord::char('Y')]),
+    (KeyAction::AllowTool, &[KeyChord::char('a')]),
+    (KeyAction::Deny, &[KeyChord::char('n')]),
+    (KeyAction::DenyForTask, &[KeyChord::char('N')]),
+    (KeyAction::DenyTool, &[KeyChord::char('d')]),
+    (KeyAction::Open, &[ENTER]),
+    (
+        KeyAction::Exit,
+        &[ESC, KeyChord::char('!'), KeyChord::char('q')],
+    ),
+];
+
+const PALETTE_BINDINGS: &[(KeyAction, &[KeyChord])] = &[
+    (KeyAction::MoveUp, &[UP, BACKTAB, KeyChord::ctrl('p')]),
+    (KeyAction::MoveDown, &[DOWN, TAB, KeyChord::ctrl('n')]),
+    (KeyAction::Open, &[ENTER]),
+    (KeyAction::Exit, &[ESC]),
+];
+
+/// One chord or a list of them, as written for an action in `settings.json`. An empty list
+/// unbinds the action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyChordList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyChordList {
+    fn chords(&self) -> &[String] {
+        match self {
+            Self::One(chord) => std::slice::from_ref(chord),
+            Self::Many(chords) => chords,
+        }
+    }
+}
+
+/// The `keybindings` setting: chords by action name, grouped by context name.
+pub type KeyBindings = BTreeMap<String, BTreeMap<String, KeyChordList>>;
+
+/// The chords every action is bound to, in every context.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<(KeyContext, KeyAction), Vec<KeyChord>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = KeyContext::ALL
+            .into_iter()
+            .flat_map(|context| {
+                context
+                    .default_bindings()
+                    .iter()
+                    .map(move |(action, chords)| ((context, *action), chords.to_vec()))
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// The default bindings with `overrides` applied. An action listed in `overrides` keeps
+    /// only the chords listed there.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for an unknown context, action or key, and when a chord would be
+    /// bound to two actions that can be reached at the same time.
+    pub fn with_overrides(overrides: &KeyBindings) -> Result<Self, String> {
+        let mut keymap = Self::default();
+
+        for (context_name, actions) in overrides {
+            let context = KeyContext::from_name(context_name)
+                .ok_or_else(|| format!("Unknown keybinding context \"{context_name}\""))?;
+
+            for (action_name, chords) in actions {
+                let action = KeyAction::from_name(action_name)
+                    .filter(|action| context.has_action(*action))
+                    .ok_or_else(|| format!("\"{context_name}\" has no \"{action_name}\" action"))?;
+                let chords = chords
+                    .chords()
+                    .iter()
+                    .map(|chord| KeyChord::parse(chord))
+                    .collect::<Result<Vec<_>, _>>()?;
+                keymap.bindings.insert((context, action), chords);
+            }
+        }
+
+        keymap.check_conflicts()?;
+        Ok(keymap)
+    }
+
+    /// The action `key` triggers in `context`, if any.
+    #[must_use]
+    pub fn action(&self, context: KeyContext, key: KeyEvent) -> Option<KeyAction> {
+        let chord = KeyChord::from_event(key);
+        context
+            .default_bindings()
+            .iter()
+            .map(|(action, _)| *action)
+            .find(|action| self.chords(context, *action).contains(&chord))
+    }
+
+    /// The chords bound to `action` in `context`, the first one being the one shown in hints.
+    #[must_use]
+    pub fn chords(&self, context: KeyContext, action: KeyAction) -> &[KeyChord] {
+        self.bindings
+            .get(&(context, action))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// A footer hint like `hjkl:navigate` showing the first chord of each action, or `None`
+    /// when none of them is bound.
+    #[must_use]
+    pub fn hint(&self, context: KeyContext, actions: &[KeyAction], label: &str) -> Option<String> {
+        let keys: Vec<String> = actions
+            .iter()
+            .filter_map(|action| self.chords(context, *action).first())
+            .map(ToString::to_string)
+            .collect();
+        if keys.is_empty() {
+            return None;
+        }
+
+        let is_compact = keys.iter().all(|key| key.chars().count() == 1);
+        let separator = if is_compact { "" } else { "/" };
+        Some(format!("{}:{label}", keys.join(separator)))
+    }
+
+    /// Footer help built from [`Self::hint`]s, skipping the ones with nothing bound.
+    #[must_use]
+    pub fn help(&self, context: KeyContext, hints: &[(&[KeyAction], &str)]) -> String {
+        hints
+            .iter()
+            .filter_map(|(actions, label)| self.hint(context, actions, label))
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+
+    fn check_conflicts(&self) -> Result<(), String> {
+        for context in KeyContext::ALL {
+            let reachable = context.shadowed_by().iter().copied().chain([context]);
+            let mut owners: HashMap<KeyChord, (KeyContext, KeyAction)> = HashMap::new();
+
+            for owner_context in reachable {
+                for (action, _) in owner_context.default_bindings() {
+                    for chord in self.chords(owner_context, *action) {
+                        if let Some((other_context, other_action)) =
+                            owners.insert(*chord, (owner_context, *action))
+                        {
+                            return Err(format!(
+                                "{chord} is bound to both {}.{} and {}.{}",
+                                other_context.name(),
+                                other_action.name(),
+                                owner_context.name(),
+                                action.name()
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bindings(json: &str) -> KeyBindings {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_parse_chords() {
+        assert_eq!(KeyChord::parse("ctrl+s"), Ok(KeyChord::ctrl('s')));
+        assert_eq!(KeyChord::parse("shift+q"), Ok(KeyChord::char('Q')));
+        assert_eq!(
+            KeyChord::parse("shift+tab"),
+            Ok(KeyChord::key(KeyCode::BackTab))
+        );
+        assert_eq!(
+            KeyChord::parse("Shift+Esc"),
+            Ok(KeyChord::shift(KeyCode::Esc))
+        );
+        assert_eq!(KeyChord::parse("ctrl++"), Ok(KeyChord::ctrl('+')));
+        assert_eq!(KeyChord::parse("f5"), Ok(KeyChord::key(KeyCode::F(5))));
+        assert!(KeyChord::parse("hyper+x").is_err());
+        assert!(KeyChord::parse("enterr").is_err());
+    }
+
+    #[test]
+    fn test_control_characters_match_ctrl_chords() {
+        let press = |character| KeyEvent::new(KeyCode::Char(character), KeyModifiers::NONE);
+
+        assert_eq!(KeyChord::from_event(press('\x04')), KeyChord::ctrl('d'));
+        assert_eq!(KeyChord::from_event(press('\x15')), KeyChord::ctrl('u'));
+        assert_eq!(
+            Keymap::default().action(KeyContext::Scroll, press('\x04')),
+            Some(KeyAction::HalfPageDown)
+        );
+    }
+
+    #[test]
+    fn test_default_keymap_has_no_conflicts() {
+        assert!(Keymap::default().check_conflicts().is_ok());
+    }
+
+    #[test]
+    fn test_overrides_replace_default_chords() {
+        let keymap = Keymap::with_overrides(&bindings(
+            r#"{ "kanban": { "add": "n", "move_down": ["ctrl+n", "down"] } }"#,
+        ))
+        .unwrap();
+
+        let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(
+            keymap.action(KeyContext::Kanban, press(KeyCode::Char('n'))),
+            Some(KeyAction::Add)
+        );
+        assert_eq!(
+            keymap.action(KeyContext::Kanban, press(KeyCode::Char('a'))),
+            None
+        );
+        assert_eq!(
+            keymap.action(
+                KeyContext::Kanban,
+                KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL)
+            ),
+            Some(KeyAction::MoveDown)
+        );
+        assert_eq!(
+            keymap.hint(KeyContext::Kanban, &[KeyAction::MoveDown], "down"),
+            Some("Ctrl+n:down".to_string())
+        );
+    }
+
+    #[test]
+    fn test_conflicting_overrides_are_refused() {
+        assert!(Keymap::with_overrides(&bindings(r#"{ "kanban": { "add": "e" } }"#)).is_err());
+        // Global bindings are looked up first, so they shadow the tab's
+        assert!(Keymap::with_overrides(&bindings(r#"{ "global": { "quit": "a" } }"#)).is_err());
+        // Contexts that are never active together may share chords
+        assert!(
+            Keymap::with_overrides(&bindings(r#"{ "terminal": { "scroll_mode": "ctrl+a" } }"#))
+                .is_ok()
+        );
+        // Dialogs have the keyboard to themselves, so global chords do not reach them
+        assert!(Keymap::with_overrides(&bindings(r#"{ "dialog": { "confirm": "q" } }"#)).is_ok());
+        assert!(
+            Keymap::with_overrides(&bindings(r#"{ "permissions": { "allow": "d" } }"#)).is_err()
+        );
+        assert!(Keymap::with_overrides(&bindings(r#"{ "kanban": { "launch": "x" } }"#)).is_err());
+    }
+}
@@ -1,7 +1,13 @@
 pub mod errors;
+pub mod keymap;
 pub mod permissions;
 pub mod provider;
+pub mod usage;
+pub mod workflow;
 
 pub use errors::{AppError, Result};
-pub use permissions::{PermissionConfig, PermissionPreset};
-pub use provider::{AgentProvider, DetectedProvider, ProviderRegistry};
+pub use keymap::{KeyAction, KeyBindings, KeyContext, Keymap};
+pub use permissions::{PermissionConfig, PermissionDecision, PermissionPreset, PermissionRule};
+pub use provider::{AgentProvider, DetectedProvider, ProviderConfig, ProviderRegistry};
+pub use usage::{TaskUsage, TokenUsage};
+pub use workflow::{ColumnDefinition, ColumnWorkflow, PLANNING_COLUMN_INDEX};
@@ -1,7 +1,7 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use std::collections::HashSet;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ToolPermission {
     Read,
     Edit,
@@ -154,10 +154,21 @@ impl Default for SandboxConfig {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PermissionConfig {
+    #[serde(serialize_with = "serialize_sorted")]
     pub allowed_tools: HashSet<ToolPermission>,
     pub sandbox: SandboxConfig,
 }
 
+/// Writes the set in a stable order, so unchanged settings serialize the same way every time.
+fn serialize_sorted<S: Serializer>(
+    tools: &HashSet<ToolPermission>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut tools: Vec<_> = tools.iter().collect();
+    tools.sort();
+    tools.serialize(serializer)
+}
+
 impl PermissionConfig {
     #[must_use]
     pub fn restrictive() -> Self {
@@ -226,6 +237,38 @@ impl Default for PermissionConfig {
     }
 }
 
+/// An answer to an agent asking to use a tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionDecision {
+    Allow,
+    Deny,
+}
+
+/// A standing answer for every request to use `tool`, recorded from the permission inbox.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PermissionRule {
+    pub tool: String,
+    pub decision: PermissionDecision,
+}
+
+impl PermissionRule {
+    /// The decision recorded for `tool`, if any.
+    #[must_use]
+    pub fn find_decision(rules: &[Self], tool: &str) -> Option<PermissionDecision> {
+        rules
+            .iter()
+            .find(|rule| rule.tool == tool)
+            .map(|rule| rule.decision)
+    }
+
+    /// Adds `rule`, replacing any earlier rule for the same tool.
+    pub fn upsert(rules: &mut Vec<Self>, rule: Self) {
+        rules.retain(|existing| existing.tool != rule.tool);
+        rules.push(rule);
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PermissionPreset {
     Restrictive,
@@ -375,4 +418,30 @@ mod tests {
         assert!(tool_list.contains(&"Read".to_string()));
         assert!(tool_list.contains(&"Bash".to_string()));
     }
+
+    #[test]
+    fn test_permission_rule_upsert_replaces_tool() {
+        let mut rules = Vec::new();
+        PermissionRule::upsert(
+            &mut rules,
+            PermissionRule {
+                tool: "Bash".to_string(),
+                decision: PermissionDecision::Allow,
+            },
+        );
+        PermissionRule::upsert(
+            &mut rules,
+            PermissionRule {
+                tool: "Bash".to_string(),
+                decision: PermissionDecision::Deny,
+            },
+        );
+
+        assert_eq!(rules.len(), 1);
+        assert_eq!(
+            PermissionRule::find_decision(&rules, "Bash"),
+            Some(PermissionDecision::Deny)
+        );
+        assert_eq!(PermissionRule::find_decision(&rules, "Edit"), None);
+    }
 }
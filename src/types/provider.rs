@@ -1,14 +1,20 @@
-use serde::{Deserialize, Serialize};
+use crate::providers::CustomProvider;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+/// An agent Chloe can run in a task pane.
+///
+/// Serialized as a plain string (the variant name for built-ins, the provider name for custom
+/// ones) so it can key the maps in `settings.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub enum AgentProvider {
     #[default]
     ClaudeCode,
     Gemini,
     Amp,
     OpenCode,
+    Custom(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -19,34 +25,64 @@ pub struct DetectedProvider {
 
 impl AgentProvider {
     #[must_use]
-    pub const fn display_name(self) -> &'static str {
+    pub fn display_name(&self) -> &str {
         match self {
             Self::ClaudeCode => "Claude Code",
             Self::Gemini => "Gemini",
             Self::Amp => "Amp",
             Self::OpenCode => "OpenCode",
+            Self::Custom(name) => name,
         }
     }
 
     #[must_use]
-    pub const fn command_name(self) -> &'static str {
+    pub fn command_name(&self) -> &str {
         match self {
             Self::ClaudeCode => "claude",
             Self::Gemini => "gemini",
             Self::Amp => "amp",
             Self::OpenCode => "opencode",
+            Self::Custom(name) => name,
         }
     }
 
+    #[must_use]
+    pub fn key(&self) -> &str {
+        match self {
+            Self::ClaudeCode => "ClaudeCode",
+            Self::Gemini => "Gemini",
+            Self::Amp => "Amp",
+            Self::OpenCode => "OpenCode",
+            Self::Custom(name) => name,
+        }
+    }
+
+    #[must_use]
+    pub fn from_key(key: &str) -> Self {
+        match key {
+            "ClaudeCode" => Self::ClaudeCode,
+            "Gemini" => Self::Gemini,
+            "Amp" => Self::Amp,
+            "OpenCode" => Self::OpenCode,
+            name => Self::Custom(name.to_string()),
+        }
+    }
+
+    /// The providers compiled into Chloe. Custom ones live in `Settings::custom_providers`.
     #[must_use]
     pub const fn all() -> &'static [Self] {
         &[Self::ClaudeCode, Self::Gemini, Self::Amp, Self::OpenCode]
     }
 
     #[must_use]
-    pub fn detect(self) -> Option<DetectedProvider> {
+    pub fn detect(&self) -> Option<DetectedProvider> {
+        Self::detect_command(self.clone(), self.command_name())
+    }
+
+    #[must_use]
+    pub fn detect_command(provider: Self, command: &str) -> Option<DetectedProvider> {
         let output = std::process::Command::new("which")
-            .arg(self.command_name())
+            .arg(command)
             .output()
             .ok()?;
 
@@ -58,25 +94,24 @@ impl AgentProvider {
         let path = PathBuf::from(path_string.trim());
 
         if path.exists() {
-            Some(DetectedProvider {
-                provider: self,
-                path,
-            })
+            Some(DetectedProvider { provider, path })
         } else {
             None
         }
     }
 
     #[must_use]
-    pub fn detect_all_available() -> Vec<DetectedProvider> {
-        Self::all()
+    pub fn detect_all_available(custom_providers: &[CustomProvider]) -> Vec<DetectedProvider> {
+        let built_in = Self::all().iter().filter_map(Self::detect);
+        let custom = custom_providers
             .iter()
-            .filter_map(|provider| provider.detect())
-            .collect()
+            .filter(|custom_provider| custom_provider.has_valid_name())
+            .filter_map(CustomProvider::detect);
+        built_in.chain(custom).collect()
     }
 
     #[must_use]
-    pub fn default_config(self) -> ProviderConfig {
+    pub fn default_config(&self) -> ProviderConfig {
         match self {
             Self::ClaudeCode => ProviderConfig {
                 command: "claude".into(),
@@ -84,6 +119,7 @@ impl AgentProvider {
                 environment: HashMap::new(),
                 working_directory_argument: None,
                 supports_worktree: true,
+                max_running_agents: None,
             },
             Self::Gemini => ProviderConfig {
                 command: "gemini".into(),
@@ -91,6 +127,7 @@ impl AgentProvider {
                 environment: HashMap::new(),
                 working_directory_argument: None,
                 supports_worktree: true,
+                max_running_agents: None,
             },
             Self::Amp => ProviderConfig {
                 command: "amp".into(),
@@ -98,6 +135,7 @@ impl AgentProvider {
                 environment: HashMap::new(),
                 working_directory_argument: None,
                 supports_worktree: true,
+                max_running_agents: None,
             },
             Self::OpenCode => ProviderConfig {
                 command: "opencode".into(),
@@ -105,11 +143,26 @@ impl AgentProvider {
                 environment: HashMap::new(),
                 working_directory_argument: None,
                 supports_worktree: true,
+                max_running_agents: None,
             },
+            Self::Custom(name) => CustomProvider::with_command(name).default_config(),
         }
     }
 }
 
+impl Serialize for AgentProvider {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.key())
+    }
+}
+
+impl<'de> Deserialize<'de> for AgentProvider {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let key = String::deserialize(deserializer)?;
+        Ok(Self::from_key(&key))
+    }
+}
+
 impl std::fmt::Display for AgentProvider {
     fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(formatter, "{}", self.display_name())
@@ -123,6 +176,10 @@ pub struct ProviderConfig {
     pub environment: HashMap<String, String>,
     pub working_directory_argument: Option<String>,
     pub supports_worktree: bool,
+    /// Most of this agent's sessions the auto-run queue starts at once, on top of the global
+    /// limit.
+    #[serde(default)]
+    pub max_running_agents: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -135,8 +192,60 @@ impl ProviderRegistry {
     pub fn new() -> Self {
         let mut configs = HashMap::new();
         for provider in AgentProvider::all() {
-            configs.insert(*provider, provider.default_config());
+            configs.insert(provider.clone(), provider.default_config());
         }
         Self { configs }
     }
+
+    /// Adds a default config for every custom provider that does not have one yet, so they
+    /// can be overridden like the built-in ones.
+    pub fn register_custom_providers(&mut self, custom_providers: &[CustomProvider]) {
+        for custom_provider in custom_providers {
+            if !custom_provider.has_valid_name() {
+                continue;
+            }
+            self.configs
+                .entry(custom_provider.provider())
+                .or_insert_with(|| custom_provider.default_config());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_as_plain_string() {
+        let providers = vec![
+            AgentProvider::ClaudeCode,
+            AgentProvider::Custom("Aider".to_string()),
+        ];
+        let json = serde_json::to_string(&providers).unwrap();
+
+        assert_eq!(json, r#"["ClaudeCode","Aider"]"#);
+        assert_eq!(
+            serde_json::from_str::<Vec<AgentProvider>>(&json).unwrap(),
+            providers
+        );
+    }
+
+    #[test]
+    fn test_custom_provider_can_key_settings_maps() {
+        let mut registry = ProviderRegistry::new();
+        registry.register_custom_providers(&[CustomProvider::with_command("goose")]);
+
+        let json = serde_json::to_string(&registry).unwrap();
+        let restored: ProviderRegistry = serde_json::from_str(&json).unwrap();
+
+        let goose = AgentProvider::Custom("goose".to_string());
+        assert_eq!(
+            restored
+                .configs
+                .get(&goose)
+                .map(|config| config.command.clone()),
+            Some(PathBuf::from("goose"))
+        );
+        assert!(restored.configs.contains_key(&AgentProvider::Gemini));
+    }
 }
@@ -0,0 +1,167 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+
+/// Tokens an agent consumed and what they are estimated to have cost.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub cache_read_tokens: u64,
+    #[serde(default)]
+    pub cache_write_tokens: u64,
+    #[serde(default)]
+    pub cost_usd: f64,
+}
+
+impl TokenUsage {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.input_tokens == 0
+            && self.output_tokens == 0
+            && self.cache_read_tokens == 0
+            && self.cache_write_tokens == 0
+    }
+
+    /// What `self` has on top of `earlier`, never negative.
+    #[must_use]
+    pub fn saturating_sub(&self, earlier: &Self) -> Self {
+        Self {
+            input_tokens: self.input_tokens.saturating_sub(earlier.input_tokens),
+            output_tokens: self.output_tokens.saturating_sub(earlier.output_tokens),
+            cache_read_tokens: self
+                .cache_read_tokens
+                .saturating_sub(earlier.cache_read_tokens),
+            cache_write_tokens: self
+                .cache_write_tokens
+                .saturating_sub(earlier.cache_write_tokens),
+            cost_usd: (self.cost_usd - earlier.cost_usd).max(0.0),
+        }
+    }
+}
+
+impl AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cache_write_tokens += other.cache_write_tokens;
+        self.cost_usd += other.cost_usd;
+    }
+}
+
+/// Usage of a task's agent sessions, per session and day.
+///
+/// Each session is stored whole so that re-reading a transcript, or a provider reporting its
+/// running total again, replaces what was recorded before instead of counting it twice.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaskUsage {
+    #[serde(default)]
+    pub sessions: BTreeMap<String, BTreeMap<NaiveDate, TokenUsage>>,
+}
+
+impl TaskUsage {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    #[must_use]
+    pub fn total(&self) -> TokenUsage {
+        let mut total = TokenUsage::default();
+        for usage in self.sessions.values().flat_map(BTreeMap::values) {
+            total += *usage;
+        }
+        total
+    }
+
+    #[must_use]
+    pub fn by_day(&self) -> BTreeMap<NaiveDate, TokenUsage> {
+        let mut days = BTreeMap::<NaiveDate, TokenUsage>::new();
+        for (day, usage) in self.sessions.values().flat_map(|session| session.iter()) {
+            *days.entry(*day).or_default() += *usage;
+        }
+        days
+    }
+
+    /// Replaces a session's usage with one recomputed from its transcript. Returns whether
+    /// anything changed.
+    pub fn replace_session(
+        &mut self,
+        session_id: &str,
+        by_day: BTreeMap<NaiveDate, TokenUsage>,
+    ) -> bool {
+        if by_day.is_empty() || self.sessions.get(session_id) == Some(&by_day) {
+            return false;
+        }
+        self.sessions.insert(session_id.to_string(), by_day);
+        true
+    }
+
+    /// Records the running total a provider reported for a session, attributing whatever it
+    /// grew by since the last report to `today`. Returns whether anything changed.
+    pub fn record_session_total(
+        &mut self,
+        session_id: &str,
+        total: TokenUsage,
+        today: NaiveDate,
+    ) -> bool {
+        let session = self.sessions.entry(session_id.to_string()).or_default();
+        let mut recorded = TokenUsage::default();
+        for usage in session.values() {
+            recorded += *usage;
+        }
+
+        let growth = total.saturating_sub(&recorded);
+        if growth.is_empty() && growth.cost_usd == 0.0 {
+            return false;
+        }
+        *session.entry(today).or_default() += growth;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(input_tokens: u64, cost_usd: f64) -> TokenUsage {
+        TokenUsage {
+            input_tokens,
+            cost_usd,
+            ..TokenUsage::default()
+        }
+    }
+
+    #[test]
+    fn test_session_totals_are_not_counted_twice() {
+        let monday = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let tuesday = NaiveDate::from_ymd_opt(2026, 3, 3).unwrap();
+        let mut task_usage = TaskUsage::default();
+
+        assert!(task_usage.record_session_total("a", usage(100, 1.0), monday));
+        assert!(!task_usage.record_session_total("a", usage(100, 1.0), monday));
+        assert!(task_usage.record_session_total("a", usage(250, 1.5), tuesday));
+
+        assert_eq!(task_usage.total(), usage(250, 1.5));
+        assert_eq!(task_usage.by_day()[&monday], usage(100, 1.0));
+        assert_eq!(task_usage.by_day()[&tuesday], usage(150, 0.5));
+    }
+
+    #[test]
+    fn test_replace_session_keeps_other_sessions() {
+        let day = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let mut task_usage = TaskUsage::default();
+
+        task_usage.replace_session("a", BTreeMap::from([(day, usage(10, 0.1))]));
+        task_usage.replace_session("b", BTreeMap::from([(day, usage(5, 0.2))]));
+        assert!(task_usage.replace_session("a", BTreeMap::from([(day, usage(30, 0.3))])));
+
+        assert_eq!(task_usage.total().input_tokens, 35);
+        assert!((task_usage.total().cost_usd - 0.5).abs() < f64::EPSILON);
+    }
+}
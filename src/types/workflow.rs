@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The rules a kanban column applies to the tasks that enter it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnWorkflow {
+    /// The most tasks the column holds. Moving a task into a full column is refused.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wip_limit: Option<usize>,
+    /// Tasks entering the column get a worktree and an agent working in it.
+    #[serde(default)]
+    pub starts_agent: bool,
+    /// Tasks entering the column have their agent stopped and their auto-created worktree
+    /// removed.
+    #[serde(default)]
+    pub removes_worktree: bool,
+    /// Tasks in the column are finished: their agent is stopped and tasks depending on them
+    /// can start.
+    #[serde(default)]
+    pub is_terminal: bool,
+}
+
+/// The column new tasks are added to and started from.
+/// [`ColumnDefinition::validate`] keeps it from starting agents or being terminal.
+pub const PLANNING_COLUMN_INDEX: usize = 0;
+
+/// A kanban column as configured in the project settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnDefinition {
+    pub name: String,
+    #[serde(flatten)]
+    pub workflow: ColumnWorkflow,
+}
+
+impl ColumnDefinition {
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            workflow: ColumnWorkflow::default(),
+        }
+    }
+
+    #[must_use]
+    pub const fn starts_agent(mut self) -> Self {
+        self.workflow.starts_agent = true;
+        self
+    }
+
+    #[must_use]
+    pub const fn terminal(mut self) -> Self {
+        self.workflow.is_terminal = true;
+        self
+    }
+
+    /// Planning, In Progress, Review and Done.
+    #[must_use]
+    pub fn defaults() -> Vec<Self> {
+        vec![
+            Self::new("Planning"),
+            Self::new("In Progress").starts_agent(),
+            Self::new("Review"),
+            Self::new("Done").terminal(),
+        ]
+    }
+
+    /// Checks that `columns` describe a board tasks can flow through: new tasks land in the
+    /// first column, so it can neither start an agent nor be terminal, and the board needs a
+    /// column that starts agents and a terminal column after it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first problem found.
+    pub fn validate(columns: &[Self]) -> Result<(), String> {
+        let Some(first) = columns.first() else {
+            return Err("The board needs at least one column".to_string());
+        };
+        if first.workflow.starts_agent || first.workflow.is_terminal {
+            return Err(format!(
+                "New tasks are added to \"{}\", so it can neither start an agent nor be terminal",
+                first.name
+            ));
+        }
+
+        // Columns are looked up ignoring case and punctuation, see `resolve_column_reference`
+        let mut names = HashSet::new();
+        for column in columns {
+            let normalized_name: String = column
+                .name
+                .chars()
+                .filter(|character| character.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect();
+            if normalized_name.is_empty() {
+                return Err("Column names need at least one letter or digit".to_string());
+            }
+            if !names.insert(normalized_name) {
+                return Err(format!("There is more than one \"{}\" column", column.name));
+            }
+            if column.workflow.wip_limit == Some(0) {
+                return Err(format!(
+                    "The WIP limit of \"{}\" must be above 0",
+                    column.name
+                ));
+            }
+        }
+
+        let Some(agent_column_index) = columns
+            .iter()
+            .position(|column| column.workflow.starts_agent)
+        else {
+            return Err("No column starts agents".to_string());
+        };
+        let has_terminal_column = columns[agent_column_index..]
+            .iter()
+            .any(|column| column.workflow.is_terminal);
+        if !has_terminal_column {
+            return Err(format!(
+                "No terminal column comes after \"{}\"",
+                columns[agent_column_index].name
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_columns() {
+        assert_eq!(
+            ColumnDefinition::validate(&ColumnDefinition::defaults()),
+            Ok(())
+        );
+
+        let mut columns = ColumnDefinition::defaults();
+        columns.insert(3, ColumnDefinition::new("QA"));
+        assert_eq!(ColumnDefinition::validate(&columns), Ok(()));
+
+        columns[3].workflow.wip_limit = Some(0);
+        assert!(ColumnDefinition::validate(&columns).is_err());
+
+        let without_agents = vec![
+            ColumnDefinition::new("Todo"),
+            ColumnDefinition::new("Done").terminal(),
+        ];
+        assert!(ColumnDefinition::validate(&without_agents).is_err());
+
+        let duplicated = vec![
+            ColumnDefinition::new("Todo"),
+            ColumnDefinition::new("Doing").starts_agent(),
+            ColumnDefinition::new("doing").terminal(),
+        ];
+        assert!(ColumnDefinition::validate(&duplicated).is_err());
+    }
+
+    #[test]
+    fn test_column_definition_reads_flat_rules() {
+        let column: ColumnDefinition =
+            serde_json::from_str(r#"{"name": "QA", "wip_limit": 2, "starts_agent": true}"#)
+                .unwrap();
+
+        assert_eq!(column.name, "QA");
+        assert_eq!(column.workflow.wip_limit, Some(2));
+        assert!(column.workflow.starts_agent);
+        assert!(!column.workflow.is_terminal);
+    }
+}
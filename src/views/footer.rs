@@ -1,3 +1,4 @@
+use crate::types::{KeyAction, KeyContext, Keymap};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -60,3 +61,102 @@ pub fn render_footer(frame: &mut Frame, area: Rect, content: StatusBarContent) {
         frame.render_widget(version, chunks[1]);
     }
 }
+
+/// Help for a focused terminal, where every key but the bound ones goes to the agent.
+#[must_use]
+pub fn terminal_help(keymap: &Keymap, is_wide: bool) -> String {
+    let (scroll_label, exit_label) = if is_wide {
+        ("scroll-mode", "back-to-navigation")
+    } else {
+        ("scroll", "back")
+    };
+
+    keymap.help(
+        KeyContext::Terminal,
+        &[
+            (&[KeyAction::ScrollMode], scroll_label),
+            (&[KeyAction::ExitTerminal], exit_label),
+        ],
+    )
+}
+
+/// Help for scrolling back through a terminal or an activity summary.
+#[must_use]
+pub fn scroll_help(keymap: &Keymap, is_wide: bool) -> String {
+    let labels = if is_wide {
+        ["scroll-line", "half-page", "top/bottom", "exit-scroll"]
+    } else {
+        ["line", "page", "top/bottom", "exit"]
+    };
+
+    keymap.help(
+        KeyContext::Scroll,
+        &[
+            (&[KeyAction::MoveDown, KeyAction::MoveUp], labels[0]),
+            (&[KeyAction::HalfPageDown, KeyAction::HalfPageUp], labels[1]),
+            (&[KeyAction::GoToTop, KeyAction::GoToBottom], labels[2]),
+            (&[KeyAction::Exit], labels[3]),
+        ],
+    )
+}
+
+/// Help for the keys that work on every tab.
+#[must_use]
+pub fn global_help(keymap: &Keymap) -> String {
+    keymap.help(
+        KeyContext::Global,
+        &[
+            (&[KeyAction::NextTab], "switch-tabs"),
+            (&[KeyAction::Quit], "quit"),
+        ],
+    )
+}
+
+/// Joins help texts built for different contexts, skipping empty ones.
+#[must_use]
+pub fn join_help<const N: usize>(parts: [String; N]) -> String {
+    parts
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+/// Help for a yes or no question.
+#[must_use]
+pub fn confirm_help(keymap: &Keymap) -> String {
+    keymap.help(
+        KeyContext::Dialog,
+        &[(&[KeyAction::Confirm], "yes"), (&[KeyAction::Cancel], "no")],
+    )
+}
+
+/// The keys answering a yes or no question, like `(y/n)`, for the end of the question itself.
+#[must_use]
+pub fn confirm_keys(keymap: &Keymap) -> String {
+    let first_chord = |action| {
+        keymap
+            .chords(KeyContext::Dialog, action)
+            .first()
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    };
+    format!(
+        "({}/{})",
+        first_chord(KeyAction::Confirm),
+        first_chord(KeyAction::Cancel)
+    )
+}
+
+/// Help for a list to choose one entry from, with `open_label` describing the choice.
+#[must_use]
+pub fn picker_help(keymap: &Keymap, open_label: &str) -> String {
+    keymap.help(
+        KeyContext::Picker,
+        &[
+            (&[KeyAction::MoveDown, KeyAction::MoveUp], "select"),
+            (&[KeyAction::Open], open_label),
+            (&[KeyAction::Exit], "cancel"),
+        ],
+    )
+}
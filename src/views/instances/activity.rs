@@ -1,6 +1,127 @@
 use super::state::{ActivityEventType, InstancePane};
+use crate::events::{EventType, HookEvent};
 use regex::Regex;
+use serde_json::Value;
 
+/// Tools, lowercased, whose input is a shell command.
+const COMMAND_TOOLS: [&str; 3] = ["bash", "run_shell_command", "shell"];
+
+/// Tools, lowercased, that write to the file named in their input.
+const FILE_TOOLS: [&str; 7] = [
+    "edit",
+    "multiedit",
+    "write",
+    "notebookedit",
+    "write_file",
+    "replace",
+    "patch",
+];
+
+/// Keys providers use in tool input for the file being written.
+const FILE_PATH_KEYS: [&str; 4] = ["file_path", "filePath", "notebook_path", "path"];
+
+/// Records what the agent did from a provider hook's payload.
+///
+/// A pane that has reported a tool call through hooks stops being scraped by
+/// [`detect_and_log_activity`], so its feed only holds what really happened.
+pub fn log_hook_activity(pane: &mut InstancePane, event: &HookEvent) {
+    let data = &event.hook_data;
+
+    match event.event_type() {
+        EventType::ToolUse => {
+            pane.has_structured_activity = true;
+            log_tool_use(pane, data);
+        }
+        EventType::ToolResult => {
+            pane.has_structured_activity = true;
+            log_tool_failure(pane, data);
+        }
+        EventType::Notification => {
+            if let Some(message) = string_field(data, &["message", "title"]) {
+                pane.add_activity_event(
+                    ActivityEventType::ProviderNotification,
+                    message.to_string(),
+                    None,
+                );
+            }
+        }
+        EventType::End if pane.has_structured_activity => {
+            pane.add_activity_event(
+                ActivityEventType::TaskCompleted,
+                "Agent finished its turn".to_string(),
+                None,
+            );
+        }
+        EventType::Start | EventType::End | EventType::Permission | EventType::Unknown(_) => {}
+    }
+}
+
+fn log_tool_use(pane: &mut InstancePane, data: &Value) {
+    let Some(tool) = string_field(data, &["tool_name", "tool"]) else {
+        return;
+    };
+    let tool_input = data.get("tool_input").unwrap_or(&Value::Null);
+    let tool_key = tool.to_lowercase();
+
+    if COMMAND_TOOLS.contains(&tool_key.as_str())
+        && let Some(command) = string_field(tool_input, &["command"])
+    {
+        pane.add_activity_event(
+            ActivityEventType::CommandExecuted,
+            format!("Executed: {command}"),
+            Some(tool.to_string()),
+        );
+    } else if FILE_TOOLS.contains(&tool_key.as_str())
+        && let Some(file_path) = string_field(tool_input, &FILE_PATH_KEYS)
+    {
+        pane.add_activity_event(
+            ActivityEventType::FileChanged,
+            format!("Modified: {file_path}"),
+            Some(tool.to_string()),
+        );
+    }
+}
+
+/// Logs a tool whose response reports that it failed. Successful results add nothing the
+/// matching tool use did not already record.
+fn log_tool_failure(pane: &mut InstancePane, data: &Value) {
+    let tool = string_field(data, &["tool_name", "tool"]).unwrap_or("Tool");
+    let Some(response) = data.get("tool_response") else {
+        return;
+    };
+
+    let is_error = ["is_error", "isError"]
+        .iter()
+        .any(|key| response.get(key).and_then(Value::as_bool) == Some(true));
+    let exit_code = ["exit_code", "exitCode"]
+        .iter()
+        .find_map(|key| response.get(key)?.as_i64())
+        .filter(|code| *code != 0);
+    let error = string_field(response, &["error"]);
+
+    let description = match (error, exit_code) {
+        (Some(error), _) => format!("Error: {tool}: {error}"),
+        (None, Some(code)) => format!("Error: {tool} exited with code {code}"),
+        (None, None) if is_error => format!("Error: {tool} failed"),
+        (None, None) => return,
+    };
+
+    pane.add_activity_event(
+        ActivityEventType::ErrorOccurred,
+        description,
+        Some(tool.to_string()),
+    );
+}
+
+fn string_field<'a>(value: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .find_map(|key| value.get(key)?.as_str())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+/// Guesses activity from raw terminal output, for panes whose provider reports nothing
+/// through hooks.
 pub fn detect_and_log_activity(pane: &mut InstancePane, output: &str) {
     if let Some(command) = detect_command_execution(output) {
         pane.add_activity_event(
@@ -156,3 +277,108 @@ fn detect_provider_notification(output: &str) -> Option<String> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn hook_event(event: &str, hook_data: Value) -> HookEvent {
+        HookEvent {
+            event: event.to_string(),
+            worktree_id: Uuid::nil(),
+            timestamp: 0,
+            hook_data,
+            awaits_decision: false,
+        }
+    }
+
+    fn pane() -> InstancePane {
+        InstancePane::new(PathBuf::from("/tmp"), 24, 80)
+    }
+
+    #[test]
+    fn test_tool_use_logs_commands_and_file_changes() {
+        let mut pane = pane();
+        let bash = hook_event(
+            "tool-use",
+            serde_json::json!({
+                "hook_event_name": "PreToolUse",
+                "tool_name": "Bash",
+                "tool_input": { "command": "cargo test" },
+            }),
+        );
+        let edit = hook_event(
+            "tool-use",
+            serde_json::json!({
+                "tool_name": "edit",
+                "tool_input": { "filePath": "src/main.rs" },
+            }),
+        );
+        let read = hook_event(
+            "tool-use",
+            serde_json::json!({ "tool_name": "Read", "tool_input": { "file_path": "a.rs" } }),
+        );
+        log_hook_activity(&mut pane, &bash);
+        log_hook_activity(&mut pane, &edit);
+        log_hook_activity(&mut pane, &read);
+
+        assert!(pane.has_structured_activity);
+        let events: Vec<_> = pane
+            .activity_events
+            .iter()
+            .map(|event| (event.event_type.clone(), event.description.as_str()))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                (ActivityEventType::CommandExecuted, "Executed: cargo test"),
+                (ActivityEventType::FileChanged, "Modified: src/main.rs"),
+            ]
+        );
+        assert_eq!(pane.activity_events[0].metadata.as_deref(), Some("Bash"));
+    }
+
+    #[test]
+    fn test_tool_result_logs_only_failures() {
+        let mut pane = pane();
+        let success = hook_event(
+            "tool-result",
+            serde_json::json!({
+                "tool_name": "Bash",
+                "tool_response": { "stdout": "done", "exit_code": 0 },
+            }),
+        );
+        let failure = hook_event(
+            "tool-result",
+            serde_json::json!({
+                "tool_name": "Bash",
+                "tool_response": { "stderr": "boom", "exit_code": 101 },
+            }),
+        );
+        log_hook_activity(&mut pane, &success);
+        log_hook_activity(&mut pane, &failure);
+
+        assert_eq!(pane.activity_events.len(), 1);
+        assert_eq!(
+            pane.activity_events[0].description,
+            "Error: Bash exited with code 101"
+        );
+    }
+
+    #[test]
+    fn test_turn_end_counts_only_for_structured_panes() {
+        let mut pane = pane();
+        let end = hook_event("end", serde_json::json!({}));
+        log_hook_activity(&mut pane, &end);
+        assert!(pane.activity_events.is_empty());
+
+        pane.has_structured_activity = true;
+        log_hook_activity(&mut pane, &end);
+        assert_eq!(
+            pane.activity_events[0].event_type,
+            ActivityEventType::TaskCompleted
+        );
+    }
+}
@@ -2,66 +2,69 @@ use super::TerminalAction;
 use super::operations::NavigationDirection;
 use super::state::{InstanceMode, InstanceState};
 use crate::events::{AppAction, EventHandler, EventResult};
+use crate::types::{KeyAction, KeyContext, Keymap};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-const DEFAULT_PTY_ROWS: u16 = 24;
-const DEFAULT_PTY_COLUMNS: u16 = 80;
 const SCROLL_LINES_SINGLE: usize = 1;
 const SCROLL_LINES_HALF_PAGE: usize = 12;
 
 impl EventHandler for InstanceState {
-    fn handle_key(&mut self, key: KeyEvent) -> EventResult {
+    fn handle_key(&mut self, key: KeyEvent, keymap: &Keymap) -> EventResult {
         match self.mode {
-            InstanceMode::Normal => self.handle_navigation_mode(key),
-            InstanceMode::Focused => self.handle_focused_mode(key),
-            InstanceMode::Scroll => self.handle_scroll_mode(key),
-            InstanceMode::ActivitySummary => self.handle_activity_summary_mode(key),
+            InstanceMode::Normal => {
+                self.handle_navigation_mode(keymap.action(KeyContext::Instances, key))
+            }
+            InstanceMode::Focused => self.handle_focused_mode(key, keymap),
+            InstanceMode::Scroll => self.handle_scroll_mode(key, keymap),
+            InstanceMode::ActivitySummary => {
+                self.handle_activity_summary_mode(keymap.action(KeyContext::Scroll, key))
+            }
         }
     }
 }
 
 impl InstanceState {
-    fn handle_navigation_mode(&mut self, key: KeyEvent) -> EventResult {
-        match key.code {
-            KeyCode::Char('h') | KeyCode::Left => {
+    fn handle_navigation_mode(&mut self, action: Option<KeyAction>) -> EventResult {
+        match action {
+            Some(KeyAction::MoveLeft) => {
                 self.navigate_to_pane_in_direction(NavigationDirection::Left);
                 EventResult::Consumed
             }
-            KeyCode::Char('j') | KeyCode::Down => {
+            Some(KeyAction::MoveDown) => {
                 self.navigate_to_pane_in_direction(NavigationDirection::Down);
                 EventResult::Consumed
             }
-            KeyCode::Char('k') | KeyCode::Up => {
+            Some(KeyAction::MoveUp) => {
                 self.navigate_to_pane_in_direction(NavigationDirection::Up);
                 EventResult::Consumed
             }
-            KeyCode::Char('l') | KeyCode::Right => {
+            Some(KeyAction::MoveRight) => {
                 self.navigate_to_pane_in_direction(NavigationDirection::Right);
                 EventResult::Consumed
             }
-            KeyCode::Enter => {
+            Some(KeyAction::Open) => {
                 if self.pane_count() > 0 {
                     self.mode = InstanceMode::Focused;
                 }
                 EventResult::Consumed
             }
-            KeyCode::Char('c') => {
-                self.create_pane(DEFAULT_PTY_ROWS, DEFAULT_PTY_COLUMNS);
+            Some(KeyAction::NewPane) => {
+                self.create_pane();
                 EventResult::Consumed
             }
-            KeyCode::Char('x') => {
+            Some(KeyAction::ClosePane) => {
                 self.close_pane();
                 EventResult::Consumed
             }
-            KeyCode::Tab => {
+            Some(KeyAction::NextPane) => {
                 self.next_pane();
                 EventResult::Consumed
             }
-            KeyCode::BackTab => {
+            Some(KeyAction::PreviousPane) => {
                 self.previous_pane();
                 EventResult::Consumed
             }
-            KeyCode::Char('A') => {
+            Some(KeyAction::ShowActivity) => {
                 if self.selected_pane_id.is_some() {
                     self.activity_summary_scroll_offset = 0;
                     self.mode = InstanceMode::ActivitySummary;
@@ -72,23 +75,18 @@ impl InstanceState {
         }
     }
 
-    fn handle_focused_mode(&mut self, key: KeyEvent) -> EventResult {
-        let is_shift_escape =
-            key.code == KeyCode::Esc && key.modifiers.contains(KeyModifiers::SHIFT);
-        if is_shift_escape {
-            return self.send_escape_to_terminal();
-        }
-
-        if key.code == KeyCode::Esc {
-            self.mode = InstanceMode::Normal;
-            return EventResult::Consumed;
-        }
-
-        let is_ctrl_s =
-            key.code == KeyCode::Char('s') && key.modifiers.contains(KeyModifiers::CONTROL);
-        if is_ctrl_s {
-            self.mode = InstanceMode::Scroll;
-            return EventResult::Consumed;
+    fn handle_focused_mode(&mut self, key: KeyEvent, keymap: &Keymap) -> EventResult {
+        match keymap.action(KeyContext::Terminal, key) {
+            Some(KeyAction::SendEscape) => return self.send_escape_to_terminal(),
+            Some(KeyAction::ExitTerminal) => {
+                self.mode = InstanceMode::Normal;
+                return EventResult::Consumed;
+            }
+            Some(KeyAction::ScrollMode) => {
+                self.mode = InstanceMode::Scroll;
+                return EventResult::Consumed;
+            }
+            _ => {}
         }
 
         if let Some(pane) = self.selected_pane_mut() {
@@ -98,8 +96,11 @@ impl InstanceState {
         self.send_input_to_terminal(key)
     }
 
-    fn handle_scroll_mode(&mut self, key: KeyEvent) -> EventResult {
-        if key.code == KeyCode::Esc || key.code == KeyCode::Char('q') {
+    fn handle_scroll_mode(&mut self, key: KeyEvent, keymap: &Keymap) -> EventResult {
+        let Some(action) = keymap.action(KeyContext::Scroll, key) else {
+            return EventResult::Ignored;
+        };
+        if action == KeyAction::Exit {
             if let Some(pane) = self.selected_pane_mut() {
                 pane.scroll_to_bottom();
             }
@@ -107,98 +108,55 @@ impl InstanceState {
             return EventResult::Consumed;
         }
 
-        let has_ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let Some(pane) = self.selected_pane_mut() else {
+            return EventResult::Consumed;
+        };
+        let max_scrollback = pane.scrollback_len();
+        match action {
+            KeyAction::MoveDown => pane.scroll_down(SCROLL_LINES_SINGLE),
+            KeyAction::MoveUp => pane.scroll_up(SCROLL_LINES_SINGLE, max_scrollback),
+            KeyAction::HalfPageDown => pane.scroll_down(SCROLL_LINES_HALF_PAGE),
+            KeyAction::HalfPageUp => pane.scroll_up(SCROLL_LINES_HALF_PAGE, max_scrollback),
+            KeyAction::GoToTop => pane.scroll_offset = max_scrollback,
+            KeyAction::GoToBottom => pane.scroll_to_bottom(),
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed
+    }
 
-        match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
-                if let Some(pane) = self.selected_pane_mut() {
-                    pane.scroll_down(SCROLL_LINES_SINGLE);
-                }
-                EventResult::Consumed
-            }
-            KeyCode::Char('k') | KeyCode::Up => {
-                if let Some(pane) = self.selected_pane_mut() {
-                    let max_scrollback = pane.scrollback_len();
-                    pane.scroll_up(SCROLL_LINES_SINGLE, max_scrollback);
-                }
-                EventResult::Consumed
-            }
-            KeyCode::Char('d') if has_ctrl => {
-                if let Some(pane) = self.selected_pane_mut() {
-                    pane.scroll_down(SCROLL_LINES_HALF_PAGE);
-                }
-                EventResult::Consumed
-            }
-            KeyCode::Char('u') if has_ctrl => {
-                if let Some(pane) = self.selected_pane_mut() {
-                    let max_scrollback = pane.scrollback_len();
-                    pane.scroll_up(SCROLL_LINES_HALF_PAGE, max_scrollback);
-                }
-                EventResult::Consumed
-            }
-            KeyCode::Char('\x04') => {
-                if let Some(pane) = self.selected_pane_mut() {
-                    pane.scroll_down(SCROLL_LINES_HALF_PAGE);
-                }
-                EventResult::Consumed
-            }
-            KeyCode::Char('\x15') => {
-                if let Some(pane) = self.selected_pane_mut() {
-                    let max_scrollback = pane.scrollback_len();
-                    pane.scroll_up(SCROLL_LINES_HALF_PAGE, max_scrollback);
-                }
-                EventResult::Consumed
-            }
-            KeyCode::Char('g') => {
+    fn handle_activity_summary_mode(&mut self, action: Option<KeyAction>) -> EventResult {
+        match action {
+            Some(KeyAction::Exit) => {
                 if let Some(pane) = self.selected_pane_mut() {
-                    pane.scroll_offset = pane.scrollback_len();
-                }
-                EventResult::Consumed
-            }
-            KeyCode::Char('G') => {
-                if let Some(pane) = self.selected_pane_mut() {
-                    pane.scroll_to_bottom();
+                    pane.mark_viewed();
                 }
+                self.activity_summary_scroll_offset = 0;
+                self.mode = InstanceMode::Normal;
                 EventResult::Consumed
             }
-            _ => EventResult::Ignored,
-        }
-    }
-
-    fn handle_activity_summary_mode(&mut self, key: KeyEvent) -> EventResult {
-        if key.code == KeyCode::Esc || key.code == KeyCode::Char('q') {
-            if let Some(pane) = self.selected_pane_mut() {
-                pane.mark_viewed();
-            }
-            self.activity_summary_scroll_offset = 0;
-            self.mode = InstanceMode::Normal;
-            return EventResult::Consumed;
-        }
-
-        match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
+            Some(KeyAction::MoveDown) => {
                 self.activity_summary_scroll_offset =
                     self.activity_summary_scroll_offset.saturating_add(1);
                 EventResult::Consumed
             }
-            KeyCode::Char('k') | KeyCode::Up => {
+            Some(KeyAction::MoveUp) => {
                 self.activity_summary_scroll_offset =
                     self.activity_summary_scroll_offset.saturating_sub(1);
                 EventResult::Consumed
             }
-            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(KeyAction::HalfPageDown) => {
                 self.activity_summary_scroll_offset = self
                     .activity_summary_scroll_offset
                     .saturating_add(SCROLL_LINES_HALF_PAGE);
                 EventResult::Consumed
             }
-            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(KeyAction::HalfPageUp) => {
                 self.activity_summary_scroll_offset = self
                     .activity_summary_scroll_offset
                     .saturating_sub(SCROLL_LINES_HALF_PAGE);
                 EventResult::Consumed
             }
-            KeyCode::Char('g') => {
+            Some(KeyAction::GoToTop) => {
                 self.activity_summary_scroll_offset = 0;
                 EventResult::Consumed
             }
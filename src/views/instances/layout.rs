@@ -1,11 +1,23 @@
 use super::state::{PaneNode, SplitDirection};
-use ratatui::layout::Rect;
+use ratatui::layout::{Position, Rect};
 use uuid::Uuid;
 
 const MINIMUM_PANE_WIDTH: u16 = 40;
 const MINIMUM_PANE_HEIGHT: u16 = 10;
 const ASPECT_RATIO_THRESHOLD: f32 = 1.5;
 const DEFAULT_SPLIT_RATIO: f32 = 0.5;
+const MINIMUM_SPLIT_RATIO: f32 = 0.1;
+const MAXIMUM_SPLIT_RATIO: f32 = 0.9;
+
+/// The border between the two sides of a split, as found under the mouse to be dragged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitBorder {
+    /// The side taken at each split on the way down from the root, `true` for the second.
+    pub path: Vec<bool>,
+    pub direction: SplitDirection,
+    /// The area the split divides.
+    pub area: Rect,
+}
 
 #[must_use]
 pub fn calculate_pane_areas(area: Rect, root: &PaneNode) -> Vec<(Uuid, Rect)> {
@@ -32,6 +44,78 @@ fn calculate_areas_recursive(area: Rect, node: &PaneNode, result: &mut Vec<(Uuid
     }
 }
 
+/// The split whose border is under `column` and `row`, where the outer split wins when two
+/// borders meet.
+#[must_use]
+pub fn find_split_border(
+    area: Rect,
+    root: &PaneNode,
+    column: u16,
+    row: u16,
+) -> Option<SplitBorder> {
+    let mut path = Vec::new();
+    find_border_recursive(area, root, Position::new(column, row), &mut path)
+}
+
+fn find_border_recursive(
+    area: Rect,
+    node: &PaneNode,
+    position: Position,
+    path: &mut Vec<bool>,
+) -> Option<SplitBorder> {
+    let PaneNode::Split {
+        direction,
+        ratio,
+        first,
+        second,
+    } = node
+    else {
+        return None;
+    };
+    if !area.contains(position) {
+        return None;
+    }
+
+    let (first_area, second_area) = split_area(area, *direction, *ratio);
+    let (offset, border) = match direction {
+        SplitDirection::Horizontal => (position.x, second_area.x),
+        SplitDirection::Vertical => (position.y, second_area.y),
+    };
+    if offset == border || offset + 1 == border {
+        return Some(SplitBorder {
+            path: path.clone(),
+            direction: *direction,
+            area,
+        });
+    }
+
+    for (is_second, child_area, child) in [(false, first_area, first), (true, second_area, second)]
+    {
+        path.push(is_second);
+        if let Some(border) = find_border_recursive(child_area, child, position, path) {
+            return Some(border);
+        }
+        path.pop();
+    }
+    None
+}
+
+/// The ratio that moves the border of a split dividing `area` to `column` or `row`, kept
+/// away from the edges so neither side disappears.
+#[must_use]
+pub fn split_ratio_at(area: Rect, direction: SplitDirection, column: u16, row: u16) -> f32 {
+    let (offset, length) = match direction {
+        SplitDirection::Horizontal => (column.saturating_sub(area.x), area.width),
+        SplitDirection::Vertical => (row.saturating_sub(area.y), area.height),
+    };
+    if length == 0 {
+        return DEFAULT_SPLIT_RATIO;
+    }
+
+    // Aim for the middle of the cell so truncating in `split_area` lands on it
+    ((f32::from(offset) + 0.5) / f32::from(length)).clamp(MINIMUM_SPLIT_RATIO, MAXIMUM_SPLIT_RATIO)
+}
+
 fn split_area(area: Rect, direction: SplitDirection, ratio: f32) -> (Rect, Rect) {
     match direction {
         SplitDirection::Horizontal => {
@@ -107,3 +191,84 @@ pub fn find_biggest_pane_id(pane_areas: &[(Uuid, Rect)]) -> Option<Uuid> {
         .max_by_key(|(_, area)| u32::from(area.width) * u32::from(area.height))
         .map(|(id, _)| *id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::instances::InstancePane;
+    use std::path::PathBuf;
+
+    fn leaf() -> PaneNode {
+        PaneNode::Leaf(Box::new(InstancePane::new(PathBuf::from("/tmp"), 24, 80)))
+    }
+
+    fn split(direction: SplitDirection, first: PaneNode, second: PaneNode) -> PaneNode {
+        PaneNode::Split {
+            direction,
+            ratio: DEFAULT_SPLIT_RATIO,
+            first: Box::new(first),
+            second: Box::new(second),
+        }
+    }
+
+    #[test]
+    fn test_finds_the_border_under_the_mouse() {
+        let area = Rect::new(0, 0, 100, 40);
+        let root = split(
+            SplitDirection::Horizontal,
+            leaf(),
+            split(SplitDirection::Vertical, leaf(), leaf()),
+        );
+
+        let outer = find_split_border(area, &root, 50, 10).unwrap();
+        assert!(outer.path.is_empty());
+        assert_eq!(outer.area, area);
+        assert_eq!(
+            find_split_border(area, &root, 49, 10).unwrap().path,
+            Vec::<bool>::new()
+        );
+
+        let inner = find_split_border(area, &root, 70, 20).unwrap();
+        assert_eq!(inner.path, vec![true]);
+        assert_eq!(inner.direction, SplitDirection::Vertical);
+        assert_eq!(inner.area, Rect::new(50, 0, 50, 40));
+
+        assert!(find_split_border(area, &root, 20, 20).is_none());
+        assert!(find_split_border(area, &root, 70, 5).is_none());
+    }
+
+    #[test]
+    fn test_dragged_ratio_lands_on_the_mouse_and_stays_in_bounds() {
+        let area = Rect::new(10, 0, 100, 40);
+        for column in [30, 47, 61, 93] {
+            let ratio = split_ratio_at(area, SplitDirection::Horizontal, column, 0);
+            let (_, second) = split_area(area, SplitDirection::Horizontal, ratio);
+            assert_eq!(second.x, column);
+        }
+
+        let ratio = split_ratio_at(area, SplitDirection::Horizontal, 0, 0);
+        assert!((ratio - MINIMUM_SPLIT_RATIO).abs() < f32::EPSILON);
+        let ratio = split_ratio_at(area, SplitDirection::Vertical, 0, 200);
+        assert!((ratio - MAXIMUM_SPLIT_RATIO).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_sets_the_ratio_at_a_path() {
+        let mut root = split(
+            SplitDirection::Horizontal,
+            leaf(),
+            split(SplitDirection::Vertical, leaf(), leaf()),
+        );
+        assert!(root.set_split_ratio(&[true], 0.25));
+        assert!(!root.set_split_ratio(&[false], 0.25));
+
+        let PaneNode::Split { ratio, second, .. } = &root else {
+            unreachable!();
+        };
+        assert!((ratio - DEFAULT_SPLIT_RATIO).abs() < f32::EPSILON);
+        let PaneNode::Split { ratio, .. } = second.as_ref() else {
+            unreachable!();
+        };
+        assert!((ratio - 0.25).abs() < f32::EPSILON);
+    }
+}
@@ -2,6 +2,7 @@ mod action;
 pub mod activity;
 pub mod events;
 pub mod layout;
+pub mod mouse;
 pub mod operations;
 pub mod pty;
 pub mod state;
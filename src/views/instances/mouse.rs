@@ -0,0 +1,386 @@
+use super::layout;
+use super::state::{InstanceMode, InstancePane, InstanceState};
+use alacritty_terminal::term::TermMode;
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use ratatui::widgets::{Block, Borders};
+use uuid::Uuid;
+
+/// Lines of scrollback one notch of the wheel moves.
+pub const WHEEL_SCROLL_LINES: usize = 3;
+
+const LEFT_BUTTON_CODE: u8 = 0;
+const MIDDLE_BUTTON_CODE: u8 = 1;
+const RIGHT_BUTTON_CODE: u8 = 2;
+const RELEASE_CODE: u8 = 3;
+const SHIFT_FLAG: u8 = 4;
+const ALT_FLAG: u8 = 8;
+const CONTROL_FLAG: u8 = 16;
+const MOTION_FLAG: u8 = 32;
+const WHEEL_UP_CODE: u8 = 64;
+const WHEEL_DOWN_CODE: u8 = 65;
+const WHEEL_LEFT_CODE: u8 = 66;
+const WHEEL_RIGHT_CODE: u8 = 67;
+const X10_OFFSET: u8 = 32;
+
+/// Encodes a mouse event at `column` and `row` of a terminal, counted from its top-left cell,
+/// the way the program in it asked for with `mode`.
+///
+/// Returns `None` when the program did not ask for this kind of event, or when the position
+/// cannot be expressed in the legacy encoding.
+#[must_use]
+pub fn encode_mouse_event(
+    kind: MouseEventKind,
+    modifiers: KeyModifiers,
+    column: u16,
+    row: u16,
+    mode: TermMode,
+) -> Option<Vec<u8>> {
+    if !mode.intersects(TermMode::MOUSE_MODE) {
+        return None;
+    }
+
+    let reports_motion = mode.contains(TermMode::MOUSE_MOTION);
+    let reports_drag = reports_motion || mode.contains(TermMode::MOUSE_DRAG);
+    let is_sgr = mode.contains(TermMode::SGR_MOUSE);
+
+    let button = match kind {
+        MouseEventKind::Down(button) => button_code(button),
+        MouseEventKind::Up(button) if is_sgr => button_code(button),
+        MouseEventKind::Up(_) => RELEASE_CODE,
+        MouseEventKind::Drag(button) if reports_drag => button_code(button) + MOTION_FLAG,
+        MouseEventKind::Moved if reports_motion => RELEASE_CODE + MOTION_FLAG,
+        MouseEventKind::ScrollUp => WHEEL_UP_CODE,
+        MouseEventKind::ScrollDown => WHEEL_DOWN_CODE,
+        MouseEventKind::ScrollLeft => WHEEL_LEFT_CODE,
+        MouseEventKind::ScrollRight => WHEEL_RIGHT_CODE,
+        MouseEventKind::Drag(_) | MouseEventKind::Moved => return None,
+    };
+    let code = button | modifier_flags(modifiers);
+
+    if is_sgr {
+        let suffix = if matches!(kind, MouseEventKind::Up(_)) {
+            'm'
+        } else {
+            'M'
+        };
+        let report = format!("\x1b[<{code};{};{}{suffix}", column + 1, row + 1);
+        return Some(report.into_bytes());
+    }
+
+    let is_utf8 = mode.contains(TermMode::UTF8_MOUSE);
+    let mut report = b"\x1b[M".to_vec();
+    report.push(code + X10_OFFSET);
+    for coordinate in [column, row] {
+        push_x10_coordinate(
+            &mut report,
+            u32::from(coordinate) + 1 + u32::from(X10_OFFSET),
+            is_utf8,
+        )?;
+    }
+    Some(report)
+}
+
+const fn button_code(button: MouseButton) -> u8 {
+    match button {
+        MouseButton::Left => LEFT_BUTTON_CODE,
+        MouseButton::Middle => MIDDLE_BUTTON_CODE,
+        MouseButton::Right => RIGHT_BUTTON_CODE,
+    }
+}
+
+const fn modifier_flags(modifiers: KeyModifiers) -> u8 {
+    let mut flags = 0;
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        flags |= SHIFT_FLAG;
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        flags |= ALT_FLAG;
+    }
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        flags |= CONTROL_FLAG;
+    }
+    flags
+}
+
+fn push_x10_coordinate(report: &mut Vec<u8>, value: u32, is_utf8: bool) -> Option<()> {
+    if is_utf8 {
+        let character = char::from_u32(value)?;
+        let mut buffer = [0; 4];
+        report.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+    } else {
+        report.push(u8::try_from(value).ok()?);
+    }
+    Some(())
+}
+
+/// The arrow keys a full-screen program gets for a wheel notch when it takes no mouse reports
+/// but asked for the wheel to scroll it.
+#[must_use]
+pub fn alternate_scroll_input(kind: MouseEventKind, mode: TermMode) -> Option<Vec<u8>> {
+    if !mode.contains(TermMode::ALT_SCREEN | TermMode::ALTERNATE_SCROLL) {
+        return None;
+    }
+
+    let is_application_cursor = mode.contains(TermMode::APP_CURSOR);
+    let arrow: &[u8] = match (kind, is_application_cursor) {
+        (MouseEventKind::ScrollUp, true) => b"\x1bOA",
+        (MouseEventKind::ScrollUp, false) => b"\x1b[A",
+        (MouseEventKind::ScrollDown, true) => b"\x1bOB",
+        (MouseEventKind::ScrollDown, false) => b"\x1b[B",
+        _ => return None,
+    };
+    Some(arrow.repeat(WHEEL_SCROLL_LINES))
+}
+
+impl InstancePane {
+    /// Hands a mouse event over the terminal drawn in `inner_area` to the program in it, if
+    /// it asked for the mouse or for wheel scrolling. Returns whether it took the event.
+    #[must_use]
+    pub fn forward_mouse(&self, event: MouseEvent, inner_area: Rect) -> bool {
+        let Some(session) = &self.pty_session else {
+            return false;
+        };
+
+        let mode = self.terminal_mode();
+        let column = event
+            .column
+            .saturating_sub(inner_area.x)
+            .min(inner_area.width.saturating_sub(1));
+        let row = event
+            .row
+            .saturating_sub(inner_area.y)
+            .min(inner_area.height.saturating_sub(1));
+
+        let input = encode_mouse_event(event.kind, event.modifiers, column, row, mode)
+            .or_else(|| alternate_scroll_input(event.kind, mode));
+        let Some(input) = input else {
+            return false;
+        };
+
+        let _ = session.write_input(&input);
+        true
+    }
+
+    /// Moves the scrollback by a wheel notch, returning whether it is now scrolled back.
+    pub fn scroll_with_wheel(&mut self, kind: MouseEventKind) -> bool {
+        match kind {
+            MouseEventKind::ScrollUp => {
+                let max_scrollback = self.scrollback_len();
+                self.scroll_up(WHEEL_SCROLL_LINES, max_scrollback);
+            }
+            MouseEventKind::ScrollDown => self.scroll_down(WHEEL_SCROLL_LINES),
+            _ => {}
+        }
+        self.scroll_offset > 0
+    }
+}
+
+impl InstanceState {
+    /// Clicking a pane selects and focuses it, dragging a border between panes resizes them
+    /// and the wheel scrolls a pane back. Over the focused pane, the program in it gets the
+    /// mouse instead when it asked for it.
+    pub fn handle_mouse(&mut self, event: MouseEvent) {
+        if self.mode == InstanceMode::ActivitySummary {
+            return;
+        }
+
+        match event.kind {
+            MouseEventKind::Drag(MouseButton::Left) if self.dragged_split.is_some() => {
+                self.drag_split(event.column, event.row);
+                return;
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.dragged_split.take().is_some() => {
+                return;
+            }
+            MouseEventKind::Down(MouseButton::Left)
+                if self.begin_split_drag(event.column, event.row) =>
+            {
+                return;
+            }
+            _ => {}
+        }
+
+        let position = Position::new(event.column, event.row);
+        if self.forward_to_focused_pane(event, position) {
+            return;
+        }
+
+        let Some(pane_id) = self.pane_at(position) else {
+            return;
+        };
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.select_pane_by_id(pane_id);
+            }
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                self.scroll_pane_with_wheel(pane_id, event.kind);
+            }
+            _ => {}
+        }
+    }
+
+    fn forward_to_focused_pane(&self, event: MouseEvent, position: Position) -> bool {
+        if self.mode != InstanceMode::Focused {
+            return false;
+        }
+        let Some(pane_id) = self.selected_pane_id else {
+            return false;
+        };
+        let Some(area) = self.get_pane_area(pane_id) else {
+            return false;
+        };
+
+        // Drags and releases that wander off the pane still belong to the press that began them
+        let inner_area = Block::default().borders(Borders::ALL).inner(area);
+        let is_over_pane = inner_area.contains(position)
+            || matches!(event.kind, MouseEventKind::Drag(_) | MouseEventKind::Up(_));
+
+        is_over_pane
+            && self
+                .find_pane(pane_id)
+                .is_some_and(|pane| pane.forward_mouse(event, inner_area))
+    }
+
+    fn pane_at(&self, position: Position) -> Option<Uuid> {
+        self.pane_areas
+            .iter()
+            .find(|(_, area)| area.contains(position))
+            .map(|(pane_id, _)| *pane_id)
+    }
+
+    fn scroll_pane_with_wheel(&mut self, pane_id: Uuid, kind: MouseEventKind) {
+        let Some(pane) = self.find_pane_mut(pane_id) else {
+            return;
+        };
+
+        if pane.scroll_with_wheel(kind) {
+            self.selected_pane_id = Some(pane_id);
+            self.mode = InstanceMode::Scroll;
+        } else if self.mode == InstanceMode::Scroll && self.selected_pane_id == Some(pane_id) {
+            self.mode = InstanceMode::Focused;
+        }
+    }
+
+    fn begin_split_drag(&mut self, column: u16, row: u16) -> bool {
+        let (Some(root), Some(area)) = (&self.root, self.last_render_area) else {
+            return false;
+        };
+
+        self.dragged_split = layout::find_split_border(area, root, column, row);
+        self.dragged_split.is_some()
+    }
+
+    fn drag_split(&mut self, column: u16, row: u16) {
+        let (Some(border), Some(root)) = (&self.dragged_split, &mut self.root) else {
+            return;
+        };
+
+        let ratio = layout::split_ratio_at(border.area, border.direction, column, row);
+        root.set_split_ratio(&border.path, ratio);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLICKS: TermMode = TermMode::MOUSE_REPORT_CLICK;
+
+    #[test]
+    fn test_encodes_sgr_reports() {
+        let mode = CLICKS | TermMode::SGR_MOUSE;
+        let press = encode_mouse_event(
+            MouseEventKind::Down(MouseButton::Left),
+            KeyModifiers::NONE,
+            4,
+            9,
+            mode,
+        );
+        assert_eq!(press.as_deref(), Some(b"\x1b[<0;5;10M".as_slice()));
+
+        let release = encode_mouse_event(
+            MouseEventKind::Up(MouseButton::Right),
+            KeyModifiers::CONTROL,
+            4,
+            9,
+            mode,
+        );
+        assert_eq!(release.as_deref(), Some(b"\x1b[<18;5;10m".as_slice()));
+
+        let wheel = encode_mouse_event(MouseEventKind::ScrollDown, KeyModifiers::NONE, 0, 0, mode);
+        assert_eq!(wheel.as_deref(), Some(b"\x1b[<65;1;1M".as_slice()));
+    }
+
+    #[test]
+    fn test_encodes_legacy_reports() {
+        let press = encode_mouse_event(
+            MouseEventKind::Down(MouseButton::Middle),
+            KeyModifiers::SHIFT,
+            0,
+            2,
+            CLICKS,
+        );
+        assert_eq!(press.as_deref(), Some(b"\x1b[M%!#".as_slice()));
+
+        let release = encode_mouse_event(
+            MouseEventKind::Up(MouseButton::Middle),
+            KeyModifiers::NONE,
+            0,
+            2,
+            CLICKS,
+        );
+        assert_eq!(release.as_deref(), Some(b"\x1b[M#!#".as_slice()));
+
+        let far_away = encode_mouse_event(
+            MouseEventKind::Down(MouseButton::Left),
+            KeyModifiers::NONE,
+            300,
+            0,
+            CLICKS | TermMode::UTF8_MOUSE,
+        );
+        assert_eq!(
+            far_away.as_deref(),
+            Some("\x1b[M \u{14d}!".as_bytes()),
+            "UTF-8 reports reach past column 223"
+        );
+    }
+
+    #[test]
+    fn test_reports_only_what_the_program_asked_for() {
+        let drag = MouseEventKind::Drag(MouseButton::Left);
+        assert!(encode_mouse_event(drag, KeyModifiers::NONE, 1, 1, TermMode::NONE).is_none());
+        assert!(encode_mouse_event(drag, KeyModifiers::NONE, 1, 1, CLICKS).is_none());
+        assert_eq!(
+            encode_mouse_event(drag, KeyModifiers::NONE, 1, 1, TermMode::MOUSE_DRAG).as_deref(),
+            Some(b"\x1b[M@\"\"".as_slice())
+        );
+        assert!(
+            encode_mouse_event(
+                MouseEventKind::Moved,
+                KeyModifiers::NONE,
+                1,
+                1,
+                TermMode::MOUSE_DRAG
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_alternate_scroll_sends_arrows_on_the_alternate_screen() {
+        let mode = TermMode::ALT_SCREEN | TermMode::ALTERNATE_SCROLL;
+        assert_eq!(
+            alternate_scroll_input(MouseEventKind::ScrollUp, mode).as_deref(),
+            Some(b"\x1b[A\x1b[A\x1b[A".as_slice())
+        );
+        assert_eq!(
+            alternate_scroll_input(MouseEventKind::ScrollDown, mode | TermMode::APP_CURSOR)
+                .as_deref(),
+            Some(b"\x1bOB\x1bOB\x1bOB".as_slice())
+        );
+        assert!(
+            alternate_scroll_input(MouseEventKind::ScrollUp, TermMode::ALTERNATE_SCROLL).is_none()
+        );
+    }
+}
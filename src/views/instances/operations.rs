@@ -1,12 +1,16 @@
 use super::state::{InstancePane, InstanceState, PaneNode, SplitDirection};
 use super::{layout, pty};
-use crate::providers::{self, GeneratedFile};
+use crate::providers::{GeneratedFile, ProviderSpec};
 use crate::types::{AgentProvider, PermissionConfig};
+use crate::views;
 use crate::views::settings::VcsCommand;
 use ratatui::layout::Rect;
+use ratatui::widgets::{Block, Borders};
+use std::borrow::Cow;
 use std::env;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 use uuid::Uuid;
 
 pub struct TaskPaneConfig {
@@ -15,53 +19,80 @@ pub struct TaskPaneConfig {
     pub description: String,
     pub working_directory: Option<PathBuf>,
     pub pane_name: Option<String>,
+    pub session_id: Option<String>,
     pub provider: AgentProvider,
+    pub spec: Cow<'static, ProviderSpec>,
     pub vcs_command: VcsCommand,
-    pub rows: u16,
-    pub columns: u16,
     pub permission_config: PermissionConfig,
+    /// The worktree's setup command, run in the pane before the agent starts
+    pub setup_command: Option<String>,
 }
 
 impl InstanceState {
-    pub fn create_pane(&mut self, rows: u16, columns: u16) {
+    pub fn create_pane(&mut self) {
         let working_directory = env::current_dir().unwrap_or_else(|_| PathBuf::from("/"));
 
-        let mut pane = InstancePane::new(working_directory.clone(), rows, columns);
-        let pane_id = pane.id;
+        let pane = InstancePane::new(working_directory.clone(), 0, 0);
+        let Some((pane_id, rows, columns)) = self.insert_pane(pane) else {
+            return;
+        };
 
-        if let Some(event_sender) = self.event_sender() {
-            match pty::PtySession::spawn(pane_id, &working_directory, rows, columns, event_sender) {
-                Ok(session) => {
-                    pane.pty_session = Some(session);
-                }
-                Err(error) => {
-                    pane.pty_spawn_error = Some(error.to_string());
-                }
-            }
-        } else {
-            pane.pty_spawn_error = Some("Event sender not initialized".to_string());
-        }
+        let spawned = self.event_sender().map(|event_sender| {
+            let spawn_options =
+                pty::SpawnOptions::new(pane_id, working_directory, rows, columns, event_sender)
+                    .with_daemon(self.daemon());
+            pty::PtySession::spawn_with_options(spawn_options)
+        });
+        self.attach_spawned_session(pane_id, spawned);
+    }
 
+    /// Adds `pane` to the layout and sizes it to the area it gets there, so its program starts
+    /// at the size it is shown at. Returns its id and size, or `None` when there was no pane
+    /// big enough to split for it.
+    fn insert_pane(&mut self, pane: InstancePane) -> Option<(Uuid, u16, u16)> {
+        let pane_id = pane.id;
         if self.root.is_none() {
             self.root = Some(PaneNode::Leaf(Box::new(pane)));
             self.selected_pane_id = Some(pane_id);
-            return;
+        } else if !self.split_biggest_pane_with_new_pane(pane) {
+            return None;
         }
 
-        self.split_biggest_pane_with_new_pane(pane);
+        self.fit_panes_to_layout();
+        let pane = self.find_pane(pane_id)?;
+        Some((pane_id, pane.rows, pane.columns))
     }
 
-    fn split_biggest_pane_with_new_pane(&mut self, new_pane: InstancePane) {
-        let Some(target_id) = layout::find_biggest_pane_id(&self.pane_areas) else {
+    /// Stores the outcome of spawning a pane's program, where `None` means there was no event
+    /// sender to spawn it with.
+    fn attach_spawned_session(
+        &mut self,
+        pane_id: Uuid,
+        spawned: Option<anyhow::Result<pty::PtySession>>,
+    ) {
+        let Some(pane) = self.find_pane_mut(pane_id) else {
             return;
         };
 
+        match spawned {
+            Some(Ok(session)) => pane.pty_session = Some(session),
+            Some(Err(error)) => pane.pty_spawn_error = Some(error.to_string()),
+            None => pane.pty_spawn_error = Some("Event sender not initialized".to_string()),
+        }
+    }
+
+    fn split_biggest_pane_with_new_pane(&mut self, new_pane: InstancePane) -> bool {
+        self.refresh_pane_areas();
+        let Some(target_id) = layout::find_biggest_pane_id(&self.pane_areas) else {
+            return false;
+        };
+
         let Some(target_area) = self.get_pane_area(target_id) else {
-            return;
+            return false;
         };
 
         let Some(direction) = layout::choose_split_direction(target_area) else {
-            return;
+            return false;
         };
 
         let new_pane_id = new_pane.id;
@@ -76,68 +107,130 @@ impl InstanceState {
             ));
             self.selected_pane_id = Some(new_pane_id);
         }
+        true
+    }
+
+    /// Whether the layout has room to split off a pane for another agent.
+    pub fn has_room_for_pane(&mut self) -> bool {
+        if self.root.is_none() {
+            return true;
+        }
+        self.refresh_pane_areas();
+        layout::find_biggest_pane_id(&self.pane_areas)
+            .and_then(|pane_id| self.get_pane_area(pane_id))
+            .is_some_and(|area| layout::choose_split_direction(area).is_some())
     }
 
-    pub fn create_pane_for_task(&mut self, config: TaskPaneConfig) -> Uuid {
+    /// Lays the panes out in `area`, the Instances tab's content area, and sizes their
+    /// terminals to fit, except the one in `shown_elsewhere`, which is sized to the area it
+    /// is drawn in outside the tab.
+    pub fn set_layout_area(&mut self, area: Rect, shown_elsewhere: Option<(Uuid, Rect)>) {
+        self.last_render_area = Some(area);
+        self.shown_elsewhere = shown_elsewhere;
+        self.fit_panes_to_layout();
+    }
+
+    /// Marks the pane drawn outside the Instances tab, if any, and sizes the panes again when
+    /// that changes.
+    pub fn show_pane_elsewhere(&mut self, shown_elsewhere: Option<(Uuid, Rect)>) {
+        if self.shown_elsewhere != shown_elsewhere {
+            self.shown_elsewhere = shown_elsewhere;
+            self.fit_panes_to_layout();
+        }
+    }
+
+    /// Sizes every pane's terminal to the inside of its area in the layout, or to the area it
+    /// is drawn in outside the Instances tab.
+    pub fn fit_panes_to_layout(&mut self) {
+        self.refresh_pane_areas();
+        for (pane_id, area) in self.pane_areas.clone() {
+            let inner_area = match self.shown_elsewhere {
+                Some((shown_pane_id, shown_area)) if shown_pane_id == pane_id => shown_area,
+                _ => Block::default().borders(Borders::ALL).inner(area),
+            };
+            if let Some(pane) = self.find_pane_mut(pane_id) {
+                pane.fit_to(inner_area);
+            }
+        }
+    }
+
+    /// Lays the panes out again without drawing them. Panes are otherwise only laid out when
+    /// the Instances tab is drawn, so agents started from another tab would find no pane to
+    /// split. Before the first draw the tab's area is worked out from the terminal's size.
+    fn refresh_pane_areas(&mut self) {
+        let Some(root) = &self.root else {
+            return;
+        };
+        let area = self.last_render_area.unwrap_or_else(|| {
+            let (columns, rows) = crossterm::terminal::size().unwrap_or((0, 0));
+            views::content_area(Rect::new(0, 0, columns, rows))
+        });
+        self.pane_areas = layout::calculate_pane_areas(area, root);
+    }
+
+    /// Starts the agent for a task in a new pane, returning its id, or `None` when there is no
+    /// room left in the layout for another pane.
+    pub fn create_pane_for_task(&mut self, config: TaskPaneConfig) -> Option<Uuid> {
         let working_directory = config
             .working_directory
             .unwrap_or_else(|| env::current_dir().unwrap_or_else(|_| PathBuf::from("/")));
 
-        let mut pane = InstancePane::with_provider(
-            working_directory.clone(),
-            config.rows,
-            config.columns,
-            config.provider,
-        );
-        let pane_id = pane.id;
-
+        let spec = config.spec;
+        let mut pane =
+            InstancePane::with_provider(working_directory.clone(), 0, 0, config.provider);
         pane.name = config.pane_name;
+        pane.has_structured_activity = spec.reports_tool_activity;
+        pane.claude_state = super::ClaudeState::Running;
 
-        let spec = providers::get_spec(config.provider);
+        let (pane_id, rows, columns) = self.insert_pane(pane)?;
 
         let generated_files = spec.build_files(
             config.task_id,
             &working_directory,
+            &crate::events::get_socket_path(),
             &config.permission_config,
         );
-        write_generated_files(&generated_files);
-
-        let prompt = build_task_prompt(&config.title, &config.description, &config.vcs_command);
-        let command = spec.build_command(&prompt);
-
-        if let Some(event_sender) = self.event_sender() {
-            let shell_command = build_shell_wrapped_command(&command);
-            let spawn_options = pty::SpawnOptions::new(
-                pane_id,
-                working_directory,
-                config.rows,
-                config.columns,
-                event_sender,
-            )
-            .with_command(shell_command.0, shell_command.1)
-            .with_environment(command.environment);
-
-            match pty::PtySession::spawn_with_options(spawn_options) {
-                Ok(session) => {
-                    pane.claude_state = super::ClaudeState::Running;
-                    pane.pty_session = Some(session);
-                }
-                Err(error) => {
-                    pane.pty_spawn_error = Some(error.to_string());
-                }
-            }
-        } else {
-            pane.pty_spawn_error = Some("Event sender not initialized".to_string());
-        }
 
-        if self.root.is_none() {
-            self.root = Some(PaneNode::Leaf(Box::new(pane)));
-            self.selected_pane_id = Some(pane_id);
-        } else {
-            self.split_biggest_pane_with_new_pane(pane);
+        let command = config
+            .session_id
+            .as_deref()
+            .and_then(|session_id| spec.build_resume_command(session_id, &working_directory))
+            .unwrap_or_else(|| {
+                let prompt =
+                    build_task_prompt(&config.title, &config.description, &config.vcs_command);
+                spec.build_command_in(&prompt, &working_directory)
+            });
+
+        // An agent that cannot get its files is not started, and its pane shows why
+        let spawned = match generated_files {
+            Ok(generated_files) => {
+                write_generated_files(&generated_files);
+                self.event_sender().map(|event_sender| {
+                    let shell_command =
+                        build_shell_wrapped_command(&command, config.setup_command.as_deref());
+                    let spawn_options = pty::SpawnOptions::new(
+                        pane_id,
+                        working_directory,
+                        rows,
+                        columns,
+                        event_sender,
+                    )
+                    .with_command(shell_command.0, shell_command.1)
+                    .with_environment(command.environment)
+                    .with_daemon(self.daemon());
+                    pty::PtySession::spawn_with_options(spawn_options)
+                })
+            }
+            Err(error) => Some(Err(anyhow::anyhow!(error))),
+        };
+        if !matches!(spawned, Some(Ok(_)))
+            && let Some(pane) = self.find_pane_mut(pane_id)
+        {
+            pane.claude_state = super::ClaudeState::Idle;
         }
+        self.attach_spawned_session(pane_id, spawned);
 
-        pane_id
+        Some(pane_id)
     }
 
     pub fn select_pane_by_id(&mut self, instance_id: Uuid) -> bool {
@@ -170,16 +263,59 @@ impl InstanceState {
             return false;
         }
 
+        if let Some(session) = root
+            .find_pane(instance_id)
+            .and_then(|pane| pane.pty_session.as_ref())
+        {
+            session.terminate();
+        }
+
         let new_root = remove_pane_from_tree(root, instance_id);
         self.root = new_root;
 
         if self.selected_pane_id == Some(instance_id) {
             self.selected_pane_id = self.root.as_ref().map(PaneNode::first_pane_id);
         }
+        self.fit_panes_to_layout();
 
         true
     }
 
+    /// Reconnects restored panes to the daemon sessions that kept running while Chloe was
+    /// closed, returning the ids of the panes that are live again.
+    pub fn attach_daemon_sessions(&mut self) -> Vec<Uuid> {
+        let (Some(daemon), Some(event_sender)) = (self.daemon(), self.event_sender()) else {
+            return Vec::new();
+        };
+        let Ok(live_sessions) = crate::daemon::list_sessions() else {
+            return Vec::new();
+        };
+        // Attach at the size the panes are shown at rather than the one they were saved with
+        self.fit_panes_to_layout();
+        let Some(root) = &mut self.root else {
+            return Vec::new();
+        };
+
+        let mut attached = Vec::new();
+        root.for_each_pane_mut(&mut |pane| {
+            if pane.pty_session.is_some() || !live_sessions.contains(&pane.id) {
+                return;
+            }
+            if let Ok(session) = pty::PtySession::attach(
+                Arc::clone(&daemon),
+                pane.id,
+                pane.rows,
+                pane.columns,
+                event_sender.clone(),
+            ) {
+                pane.pty_session = Some(session);
+                pane.pty_spawn_error = None;
+                attached.push(pane.id);
+            }
+        });
+        attached
+    }
+
     fn collect_pane_ids_from_node(node: &PaneNode) -> Vec<Uuid> {
         node.collect_panes().iter().map(|p| p.id).collect()
     }
@@ -304,32 +440,29 @@ fn build_task_prompt(title: &str, description: &str, vcs_command: &VcsCommand) -
     )
 }
 
+/// The script a task pane runs: the setup command, whose output stays in the pane and whose
+/// failure does not keep the agent from starting, then the agent, then a shell.
 fn build_shell_wrapped_command(
     command: &crate::providers::ProviderCommand,
+    setup_command: Option<&str>,
 ) -> (String, Vec<String>) {
-    let mut full_command = escape_shell_arg(&command.program);
+    let mut full_command = crate::helpers::text::escape_shell_arg(&command.program);
 
     for arg in &command.arguments {
         full_command.push(' ');
-        full_command.push_str(&escape_shell_arg(arg));
+        full_command.push_str(&crate::helpers::text::escape_shell_arg(arg));
     }
 
-    let shell_script = format!("{full_command}; exec $SHELL");
+    let setup_script = setup_command.map_or_else(String::new, |setup_command| {
+        format!(
+            "({setup_command}\n) || printf '\\nWorktree setup failed with status %s\\n' \"$?\"; "
+        )
+    });
+    let shell_script = format!("{setup_script}{full_command}; exec $SHELL");
 
     ("bash".to_string(), vec!["-c".to_string(), shell_script])
 }
 
-fn escape_shell_arg(arg: &str) -> String {
-    if arg
-        .chars()
-        .all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '.' || c == '/')
-    {
-        arg.to_string()
-    } else {
-        format!("'{}'", arg.replace('\'', "'\\''"))
-    }
-}
-
 fn send_input_with_enter(session: &crate::views::instances::pty::PtySession, input: &str) -> bool {
     if session.write_input(input.as_bytes()).is_err() {
         return false;
@@ -508,3 +641,68 @@ fn find_nearest_pane_in_direction(
 
     best_candidate.map(|(id, _)| id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pane_sizes(state: &InstanceState) -> Vec<(u16, u16)> {
+        state
+            .collect_all_pane_ids()
+            .into_iter()
+            .filter_map(|pane_id| state.find_pane(pane_id))
+            .map(|pane| (pane.rows, pane.columns))
+            .collect()
+    }
+
+    #[test]
+    fn test_panes_are_sized_to_their_area_in_the_layout() {
+        let mut state = InstanceState::new();
+        state.set_layout_area(Rect::new(0, 3, 120, 30), None);
+
+        state.create_pane();
+        assert_eq!(pane_sizes(&state), [(28, 118)]);
+
+        state.create_pane();
+        assert_eq!(pane_sizes(&state), [(28, 58), (28, 58)]);
+
+        state.set_layout_area(Rect::new(0, 3, 80, 20), None);
+        assert_eq!(pane_sizes(&state), [(18, 38), (18, 38)]);
+    }
+
+    #[test]
+    fn test_pane_shown_elsewhere_keeps_its_own_size() {
+        let mut state = InstanceState::new();
+        state.set_layout_area(Rect::new(0, 3, 120, 30), None);
+        state.create_pane();
+        state.create_pane();
+        let shown_pane_id = state.collect_all_pane_ids()[0];
+
+        state.set_layout_area(
+            Rect::new(0, 3, 80, 20),
+            Some((shown_pane_id, Rect::new(30, 10, 50, 12))),
+        );
+        assert_eq!(pane_sizes(&state), [(12, 50), (18, 38)]);
+
+        state.show_pane_elsewhere(None);
+        assert_eq!(pane_sizes(&state), [(18, 38), (18, 38)]);
+    }
+
+    #[test]
+    fn test_setup_command_runs_before_the_agent() {
+        let command = crate::providers::ProviderCommand {
+            program: "claude".to_string(),
+            arguments: vec!["fix it".to_string()],
+            environment: std::collections::HashMap::new(),
+        };
+
+        let (_, arguments) = build_shell_wrapped_command(&command, None);
+        assert_eq!(arguments[1], "claude 'fix it'; exec $SHELL");
+
+        let (_, arguments) = build_shell_wrapped_command(&command, Some("npm ci # deps"));
+        assert_eq!(
+            arguments[1],
+            "(npm ci # deps\n) || printf '\\nWorktree setup failed with status %s\\n' \"$?\"; claude 'fix it'; exec $SHELL"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::daemon::{DaemonClient, SpawnRequest};
 use crate::events::AppEvent;
 use alacritty_terminal::event::{Event, EventListener, OnResize, WindowSize};
 use alacritty_terminal::grid::Dimensions;
@@ -5,7 +6,6 @@ use alacritty_terminal::term::{Config, Term};
 use alacritty_terminal::tty::{self, Options, Pty, Shell};
 use alacritty_terminal::vte::ansi::{Processor, StdSyncHandler};
 use std::io::{Read, Write};
-use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
@@ -41,9 +41,50 @@ impl EventListener for EventProxy {
     fn send_event(&self, _event: Event) {}
 }
 
+/// Creates an empty terminal of the given size with Chloe's scrollback limit.
+#[must_use]
+pub fn new_term(rows: u16, columns: u16) -> Term<EventProxy> {
+    let config = Config {
+        scrolling_history: DEFAULT_SCROLLBACK_LINES,
+        ..Config::default()
+    };
+    Term::new(config, &terminal_size(rows, columns), EventProxy)
+}
+
+pub fn resize_term(term: &mut Term<EventProxy>, rows: u16, columns: u16) {
+    term.resize(terminal_size(rows, columns));
+}
+
+const fn terminal_size(rows: u16, columns: u16) -> TerminalSize {
+    TerminalSize {
+        columns: columns as usize,
+        screen_lines: rows as usize,
+    }
+}
+
+#[must_use]
+pub const fn window_size(rows: u16, columns: u16) -> WindowSize {
+    WindowSize {
+        cell_width: 1,
+        cell_height: 1,
+        num_cols: columns,
+        num_lines: rows,
+    }
+}
+
 pub struct PtySession {
     term: Arc<Mutex<Term<EventProxy>>>,
-    pty: Pty,
+    backend: PtyBackend,
+}
+
+/// Where the agent process lives. Daemon sessions survive the TUI exiting, so dropping them
+/// only detaches; [`PtySession::terminate`] ends them explicitly.
+enum PtyBackend {
+    Local(Pty),
+    Daemon {
+        client: Arc<DaemonClient>,
+        pane_id: Uuid,
+    },
 }
 
 pub struct SpawnOptions {
@@ -55,6 +96,7 @@ pub struct SpawnOptions {
     pub arguments: Vec<String>,
     pub environment: std::collections::HashMap<String, String>,
     pub event_sender: mpsc::UnboundedSender<AppEvent>,
+    pub daemon: Option<Arc<DaemonClient>>,
 }
 
 impl SpawnOptions {
@@ -75,6 +117,7 @@ impl SpawnOptions {
             arguments: Vec::new(),
             environment: std::collections::HashMap::new(),
             event_sender,
+            daemon: None,
         }
     }
 
@@ -93,27 +136,21 @@ impl SpawnOptions {
         self.environment = environment;
         self
     }
-}
 
-impl PtySession {
-    pub fn spawn(
-        pane_id: Uuid,
-        working_directory: &Path,
-        rows: u16,
-        columns: u16,
-        event_sender: mpsc::UnboundedSender<AppEvent>,
-    ) -> anyhow::Result<Self> {
-        let options = SpawnOptions::new(
-            pane_id,
-            working_directory.to_path_buf(),
-            rows,
-            columns,
-            event_sender,
-        );
-        Self::spawn_with_options(options)
+    /// Spawns through the daemon instead of as a child of the TUI when one is connected.
+    #[must_use]
+    pub fn with_daemon(mut self, daemon: Option<Arc<DaemonClient>>) -> Self {
+        self.daemon = daemon;
+        self
     }
+}
 
+impl PtySession {
     pub fn spawn_with_options(options: SpawnOptions) -> anyhow::Result<Self> {
+        if let Some(client) = options.daemon.clone() {
+            return Self::spawn_in_daemon(client, options);
+        }
+
         tty::setup_env();
 
         let shell = options
@@ -128,27 +165,9 @@ impl PtySession {
             drain_on_exit: true,
         };
 
-        let window_size = WindowSize {
-            cell_width: 1,
-            cell_height: 1,
-            num_cols: options.columns,
-            num_lines: options.rows,
-        };
-
-        let pty = tty::new(&tty_options, window_size, 0)?;
-
-        let config = Config {
-            scrolling_history: DEFAULT_SCROLLBACK_LINES,
-            ..Config::default()
-        };
+        let pty = tty::new(&tty_options, window_size(options.rows, options.columns), 0)?;
 
-        let term_size = TerminalSize {
-            columns: usize::from(options.columns),
-            screen_lines: usize::from(options.rows),
-        };
-        let term = Term::new(config, &term_size, EventProxy);
-
-        let term = Arc::new(Mutex::new(term));
+        let term = Arc::new(Mutex::new(new_term(options.rows, options.columns)));
 
         let reader = pty.file().try_clone()?;
         let pane_id = options.pane_id;
@@ -192,24 +211,67 @@ impl PtySession {
             }
         });
 
-        Ok(Self { term, pty })
+        Ok(Self {
+            term,
+            backend: PtyBackend::Local(pty),
+        })
     }
 
-    pub fn resize(&mut self, rows: u16, columns: u16) {
-        let window_size = WindowSize {
-            cell_width: 1,
-            cell_height: 1,
-            num_cols: columns,
-            num_lines: rows,
+    fn spawn_in_daemon(client: Arc<DaemonClient>, options: SpawnOptions) -> anyhow::Result<Self> {
+        let pane_id = options.pane_id;
+        let term = Arc::new(Mutex::new(new_term(options.rows, options.columns)));
+
+        let request = SpawnRequest {
+            pane_id,
+            working_directory: options.working_directory,
+            rows: options.rows,
+            columns: options.columns,
+            command: options.command,
+            arguments: options.arguments,
+            environment: options.environment,
         };
-        self.pty.on_resize(window_size);
+        client.spawn(request, Arc::clone(&term), options.event_sender)?;
+
+        Ok(Self {
+            term,
+            backend: PtyBackend::Daemon { client, pane_id },
+        })
+    }
+
+    /// Reconnects to a session the daemon kept running while no TUI was attached.
+    pub fn attach(
+        client: Arc<DaemonClient>,
+        pane_id: Uuid,
+        rows: u16,
+        columns: u16,
+        event_sender: mpsc::UnboundedSender<AppEvent>,
+    ) -> anyhow::Result<Self> {
+        let term = Arc::new(Mutex::new(new_term(rows, columns)));
+        client.attach(pane_id, rows, columns, Arc::clone(&term), event_sender)?;
+
+        Ok(Self {
+            term,
+            backend: PtyBackend::Daemon { client, pane_id },
+        })
+    }
+
+    /// Ends the agent. Local sessions end when dropped, so this only matters for daemon ones.
+    pub fn terminate(&self) {
+        if let PtyBackend::Daemon { client, pane_id } = &self.backend {
+            let _ = client.kill(*pane_id);
+        }
+    }
+
+    pub fn resize(&mut self, rows: u16, columns: u16) {
+        match &mut self.backend {
+            PtyBackend::Local(pty) => pty.on_resize(window_size(rows, columns)),
+            PtyBackend::Daemon { client, pane_id } => {
+                let _ = client.resize(*pane_id, rows, columns);
+            }
+        }
 
         if let Ok(mut term) = self.term.lock() {
-            let term_size = TerminalSize {
-                columns: usize::from(columns),
-                screen_lines: usize::from(rows),
-            };
-            term.resize(term_size);
+            resize_term(&mut term, rows, columns);
         }
     }
 
@@ -219,9 +281,14 @@ impl PtySession {
     }
 
     pub fn write_input(&self, data: &[u8]) -> anyhow::Result<()> {
-        let mut writer = self.pty.file().try_clone()?;
-        writer.write_all(data)?;
-        writer.flush()?;
+        match &self.backend {
+            PtyBackend::Local(pty) => {
+                let mut writer = pty.file().try_clone()?;
+                writer.write_all(data)?;
+                writer.flush()?;
+            }
+            PtyBackend::Daemon { client, pane_id } => client.write_input(*pane_id, data)?,
+        }
         Ok(())
     }
 }
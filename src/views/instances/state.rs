@@ -1,11 +1,15 @@
+use super::layout::SplitBorder;
+use crate::daemon::DaemonClient;
 use crate::events::AppEvent;
 use crate::types::AgentProvider;
 use alacritty_terminal::grid::Dimensions;
+use alacritty_terminal::term::TermMode;
 use chrono::{DateTime, Utc};
 use ratatui::layout::Rect;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::path::PathBuf;
+use std::sync::Arc;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
@@ -79,6 +83,29 @@ impl PaneNode {
         }
     }
 
+    /// Sets the ratio of the split reached by following `path`, as returned with a
+    /// [`SplitBorder`](super::layout::SplitBorder).
+    pub fn set_split_ratio(&mut self, path: &[bool], new_ratio: f32) -> bool {
+        let Self::Split {
+            ratio,
+            first,
+            second,
+            ..
+        } = self
+        else {
+            return false;
+        };
+
+        match path.split_first() {
+            None => {
+                *ratio = new_ratio;
+                true
+            }
+            Some((false, rest)) => first.set_split_ratio(rest, new_ratio),
+            Some((true, rest)) => second.set_split_ratio(rest, new_ratio),
+        }
+    }
+
     pub fn for_each_pane_mut<F>(&mut self, function: &mut F)
     where
         F: FnMut(&mut InstancePane),
@@ -104,8 +131,17 @@ pub struct InstanceState {
     pub pane_areas: Vec<(Uuid, Rect)>,
     #[serde(skip, default)]
     pub activity_summary_scroll_offset: usize,
+    /// The split border being dragged with the mouse, if any.
+    #[serde(skip)]
+    pub dragged_split: Option<SplitBorder>,
+    /// A pane drawn outside the Instances tab, such as in the task focus view, and the area
+    /// its terminal is drawn in there. Laying out the panes keeps it at that size.
+    #[serde(skip)]
+    pub shown_elsewhere: Option<(Uuid, Rect)>,
     #[serde(skip)]
     event_sender: Option<mpsc::UnboundedSender<AppEvent>>,
+    #[serde(skip)]
+    daemon: Option<Arc<DaemonClient>>,
 }
 
 impl InstanceState {
@@ -118,7 +154,10 @@ impl InstanceState {
             last_render_area: None,
             pane_areas: Vec::new(),
             activity_summary_scroll_offset: 0,
+            dragged_split: None,
+            shown_elsewhere: None,
             event_sender: None,
+            daemon: None,
         }
     }
 
@@ -131,11 +170,24 @@ impl InstanceState {
         self.event_sender.clone()
     }
 
+    /// Routes new panes through the daemon so their processes outlive this TUI.
+    pub fn set_daemon(&mut self, daemon: Arc<DaemonClient>) {
+        self.daemon = Some(daemon);
+    }
+
+    #[must_use]
+    pub fn daemon(&self) -> Option<Arc<DaemonClient>> {
+        self.daemon.clone()
+    }
+
     pub fn process_pty_output(&mut self, pane_id: Uuid, data: &[u8]) {
         let Some(pane) = self.find_pane_mut(pane_id) else {
             return;
         };
 
+        if pane.has_structured_activity {
+            return;
+        }
         if let Ok(text) = String::from_utf8(data.to_vec()) {
             super::activity::detect_and_log_activity(pane, &text);
         }
@@ -227,6 +279,10 @@ pub struct InstancePane {
     pub last_viewed_at: Option<DateTime<Utc>>,
     #[serde(default)]
     pub activity_events: VecDeque<ActivityEvent>,
+    /// Set once the agent reports its tool calls through hooks, after which its output is no
+    /// longer scraped for activity.
+    #[serde(default)]
+    pub has_structured_activity: bool,
 }
 
 impl InstancePane {
@@ -255,6 +311,7 @@ impl InstancePane {
             scroll_offset: 0,
             last_viewed_at: Some(Utc::now()),
             activity_events: VecDeque::new(),
+            has_structured_activity: false,
         }
     }
 
@@ -282,6 +339,60 @@ impl InstancePane {
         term.grid().history_size()
     }
 
+    /// Sizes the terminal to `area`, letting the program in it know when that changes its
+    /// size. Empty areas, such as those of panes squeezed out of view, are ignored.
+    pub fn fit_to(&mut self, area: Rect) {
+        if area.is_empty() || (self.rows == area.height && self.columns == area.width) {
+            return;
+        }
+
+        if let Some(session) = &mut self.pty_session {
+            session.resize(area.height, area.width);
+        }
+        self.rows = area.height;
+        self.columns = area.width;
+    }
+
+    /// The modes the program in the terminal has switched on, such as mouse reporting.
+    #[must_use]
+    pub fn terminal_mode(&self) -> TermMode {
+        let Some(session) = &self.pty_session else {
+            return TermMode::NONE;
+        };
+        let term_mutex = session.term();
+        let Ok(term) = term_mutex.lock() else {
+            return TermMode::NONE;
+        };
+        *term.mode()
+    }
+
+    /// Returns the visible screen as plain text, preceded by up to `scrollback_lines` lines of
+    /// history, with trailing whitespace trimmed from every line.
+    #[must_use]
+    pub fn screen_text(&self, scrollback_lines: usize) -> Option<String> {
+        let session = self.pty_session.as_ref()?;
+        let term_mutex = session.term();
+        let term = term_mutex.lock().ok()?;
+        let grid = term.grid();
+
+        let history_lines = scrollback_lines.min(grid.history_size());
+        let first_line = -i32::try_from(history_lines).unwrap_or(i32::MAX);
+        let last_line = i32::try_from(grid.screen_lines()).unwrap_or(i32::MAX);
+
+        let lines: Vec<String> = (first_line..last_line)
+            .map(|line| {
+                let row = &grid[alacritty_terminal::index::Line(line)];
+                let text: String = (0..grid.columns())
+                    .map(|column| row[alacritty_terminal::index::Column(column)].c)
+                    .collect();
+                text.trim_end().to_string()
+            })
+            .collect();
+        drop(term);
+
+        Some(lines.join("\n").trim_end().to_string())
+    }
+
     pub fn mark_viewed(&mut self) {
         self.last_viewed_at = Some(Utc::now());
     }
@@ -1,6 +1,6 @@
-use super::layout;
 use super::state::{InstancePane, InstanceState};
-use crate::views::StatusBarContent;
+use crate::types::{KeyAction, KeyContext, Keymap};
+use crate::views::{StatusBarContent, scroll_help, terminal_help};
 use crate::widgets::activity_summary::ActivitySummaryWidget;
 use crate::widgets::claude_indicator;
 use crate::widgets::terminal::{AlacrittyScreen, Cursor, PseudoTerminal};
@@ -15,14 +15,14 @@ use ratatui::{
 const STATUS_BAR_WIDTH_THRESHOLD: u16 = 80;
 
 pub fn render(f: &mut Frame, state: &mut InstanceState, area: Rect) {
-    state.last_render_area = Some(area);
+    state.set_layout_area(area, None);
 
     if state.root.is_none() {
         render_empty_state(f, area);
         return;
     }
 
-    render_panes(f, state, area);
+    render_panes(f, state);
 
     if state.mode == super::state::InstanceMode::ActivitySummary {
         render_activity_summary(f, state, area);
@@ -56,20 +56,11 @@ fn render_empty_state(f: &mut Frame, area: Rect) {
     f.render_widget(block, area);
 }
 
-fn render_panes(f: &mut Frame, state: &mut InstanceState, area: Rect) {
-    let Some(root) = &state.root else {
-        return;
-    };
-
-    let pane_areas = layout::calculate_pane_areas(area, root);
-    state.pane_areas.clone_from(&pane_areas);
-
-    resize_panes_to_match_areas(state, &pane_areas);
-
+fn render_panes(f: &mut Frame, state: &InstanceState) {
     let selected_id = state.selected_pane_id;
     let mode = state.mode;
 
-    for (index, (pane_id, pane_area)) in pane_areas.iter().enumerate() {
+    for (index, (pane_id, pane_area)) in state.pane_areas.iter().enumerate() {
         let Some(pane) = state.find_pane(*pane_id) else {
             continue;
         };
@@ -80,26 +71,6 @@ fn render_panes(f: &mut Frame, state: &mut InstanceState, area: Rect) {
     }
 }
 
-fn resize_panes_to_match_areas(state: &mut InstanceState, pane_areas: &[(uuid::Uuid, Rect)]) {
-    for (pane_id, pane_area) in pane_areas {
-        let inner_area = Block::default().borders(Borders::ALL).inner(*pane_area);
-        let desired_rows = inner_area.height;
-        let desired_columns = inner_area.width;
-
-        let Some(pane) = state.find_pane_mut(*pane_id) else {
-            continue;
-        };
-
-        if pane.rows != desired_rows || pane.columns != desired_columns {
-            if let Some(session) = &mut pane.pty_session {
-                session.resize(desired_rows, desired_columns);
-            }
-            pane.rows = desired_rows;
-            pane.columns = desired_columns;
-        }
-    }
-}
-
 fn render_pane(
     f: &mut Frame,
     pane: &InstancePane,
@@ -231,7 +202,11 @@ fn render_activity_summary(f: &mut Frame, state: &InstanceState, area: Rect) {
 }
 
 #[must_use]
-pub fn get_status_bar_content(state: &InstanceState, width: u16) -> StatusBarContent {
+pub fn get_status_bar_content(
+    state: &InstanceState,
+    keymap: &Keymap,
+    width: u16,
+) -> StatusBarContent {
     let (mode_text, mode_color) = match state.mode {
         super::InstanceMode::Normal => ("NAVIGATE", Color::Cyan),
         super::InstanceMode::Focused => ("FOCUSED", Color::Green),
@@ -241,36 +216,12 @@ pub fn get_status_bar_content(state: &InstanceState, width: u16) -> StatusBarCon
 
     let pane_count = state.pane_count();
 
+    let is_wide = width >= STATUS_BAR_WIDTH_THRESHOLD;
     let help_text = match state.mode {
-        super::InstanceMode::Normal => {
-            if pane_count == 0 {
-                "c:create"
-            } else if width < STATUS_BAR_WIDTH_THRESHOLD {
-                "h/j/k/l:nav  Enter:focus  A:activity  c:create  x:close"
-            } else {
-                "h/j/k/l:navigate  Enter:focus  A:activity-summary  c:create-pane  x:close-pane"
-            }
-        }
-        super::InstanceMode::Focused => {
-            if width < STATUS_BAR_WIDTH_THRESHOLD {
-                "Ctrl+s:scroll  Esc:unfocus"
-            } else {
-                "Ctrl+s:scroll-mode  Esc:back-to-navigation"
-            }
-        }
-        super::InstanceMode::Scroll => {
-            if width < STATUS_BAR_WIDTH_THRESHOLD {
-                "j/k:line  Ctrl+d/u:page  g/G:top/bottom  q:exit"
-            } else {
-                "j/k:scroll-line  Ctrl+d/u:half-page  g/G:top/bottom  q/Esc:exit-scroll"
-            }
-        }
-        super::InstanceMode::ActivitySummary => {
-            if width < STATUS_BAR_WIDTH_THRESHOLD {
-                "j/k:scroll  Ctrl+d/u:page  g:top  q:close"
-            } else {
-                "j/k:scroll  Ctrl+d/u:half-page  g:top  q/Esc:close-summary"
-            }
+        super::InstanceMode::Normal => normal_help(keymap, pane_count, is_wide),
+        super::InstanceMode::Focused => terminal_help(keymap, is_wide),
+        super::InstanceMode::Scroll | super::InstanceMode::ActivitySummary => {
+            scroll_help(keymap, is_wide)
         }
     };
 
@@ -278,6 +229,40 @@ pub fn get_status_bar_content(state: &InstanceState, width: u16) -> StatusBarCon
         mode_text: mode_text.to_string(),
         mode_color,
         extra_info: Some(format!("Panes: {pane_count}  ")),
-        help_text: help_text.to_string(),
+        help_text,
     }
 }
+
+fn normal_help(keymap: &Keymap, pane_count: usize, is_wide: bool) -> String {
+    let create_label = if is_wide { "create-pane" } else { "create" };
+    if pane_count == 0 {
+        return keymap.help(
+            KeyContext::Instances,
+            &[(&[KeyAction::NewPane], create_label)],
+        );
+    }
+
+    let labels = if is_wide {
+        ["navigate", "focus", "activity-summary", "close-pane"]
+    } else {
+        ["nav", "focus", "activity", "close"]
+    };
+    keymap.help(
+        KeyContext::Instances,
+        &[
+            (
+                &[
+                    KeyAction::MoveLeft,
+                    KeyAction::MoveDown,
+                    KeyAction::MoveUp,
+                    KeyAction::MoveRight,
+                ],
+                labels[0],
+            ),
+            (&[KeyAction::Open], labels[1]),
+            (&[KeyAction::ShowActivity], labels[2]),
+            (&[KeyAction::NewPane], create_label),
+            (&[KeyAction::ClosePane], labels[3]),
+        ],
+    )
+}
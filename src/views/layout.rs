@@ -1,10 +1,15 @@
-use super::{footer, instances, pull_requests, roadmap, settings, tab_bar, tasks, worktree};
+use super::mouse::MouseRegions;
+use super::{
+    footer, instances, palette, permissions, pull_requests, roadmap, settings, tab_bar, tasks,
+    worktree,
+};
 use crate::app::{App, Tab};
 use crate::views::tasks::TasksViewMode;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
 };
+use uuid::Uuid;
 
 const TAB_BAR_HEIGHT: u16 = 3;
 const FOOTER_HEIGHT: u16 = 3;
@@ -32,16 +37,52 @@ fn calculate_layout(area: Rect) -> AppLayout {
     }
 }
 
+/// The area between the tab bar and the footer of a terminal of the given size, where the
+/// tabs draw their content.
+#[must_use]
+pub fn content_area(area: Rect) -> Rect {
+    calculate_layout(area).content
+}
+
+/// Sizes every agent terminal for a window of the given size: the one the Tasks tab draws to
+/// its area there, the rest to their place in the Instances tab.
+pub fn fit_terminals(app: &mut App, area: Rect) {
+    let content = content_area(area);
+    let shown_elsewhere = tasks_terminal(app, content);
+    app.instances.set_layout_area(content, shown_elsewhere);
+}
+
+/// The agent terminal drawn by the Tasks tab, if it is active, and the area it is drawn in:
+/// the review popup's output section, or else the focus view's terminal panel.
+fn tasks_terminal(app: &App, content: Rect) -> Option<(Uuid, Rect)> {
+    if app.active_tab != Tab::Tasks {
+        return None;
+    }
+    tasks::dialogs::review_terminal(app, content).or_else(|| match app.tasks.view_mode {
+        TasksViewMode::Focus => tasks::views::focus::view::shown_terminal(app, content),
+        TasksViewMode::Kanban => None,
+    })
+}
+
 pub fn render(frame: &mut Frame, app: &mut App) {
     let layout = calculate_layout(frame.area());
 
-    tab_bar::render(frame, app, layout.tab_bar);
+    let mut regions = MouseRegions::default();
+    tab_bar::render(frame, app, layout.tab_bar, &mut regions);
 
     match app.active_tab {
-        Tab::Tasks => match app.tasks.view_mode {
-            TasksViewMode::Focus => tasks::views::focus::view::render(frame, app, layout.content),
-            TasksViewMode::Kanban => tasks::views::kanban::view::render(frame, app, layout.content),
-        },
+        Tab::Tasks => {
+            let shown_elsewhere = tasks_terminal(app, layout.content);
+            app.instances.show_pane_elsewhere(shown_elsewhere);
+            match app.tasks.view_mode {
+                TasksViewMode::Focus => {
+                    tasks::views::focus::view::render(frame, app, layout.content, &mut regions);
+                }
+                TasksViewMode::Kanban => {
+                    tasks::views::kanban::view::render(frame, app, layout.content, &mut regions);
+                }
+            }
+        }
         Tab::Instances => instances::view::render(frame, &mut app.instances, layout.content),
         Tab::Roadmap => roadmap::view::render(frame, app, layout.content),
         Tab::Worktree => worktree::view::render(
@@ -63,20 +104,45 @@ pub fn render(frame: &mut Frame, app: &mut App) {
                 tasks::views::kanban::view::get_status_bar_content(app, layout.footer.width)
             }
         },
-        Tab::Instances => {
-            instances::view::get_status_bar_content(&app.instances, layout.footer.width)
+        Tab::Instances => instances::view::get_status_bar_content(
+            &app.instances,
+            &app.keymap,
+            layout.footer.width,
+        ),
+        Tab::Roadmap => {
+            roadmap::view::get_status_bar_content(&app.roadmap, &app.keymap, layout.footer.width)
         }
-        Tab::Roadmap => roadmap::view::get_status_bar_content(&app.roadmap, layout.footer.width),
-        Tab::Worktree => worktree::view::get_status_bar_content(&app.worktree, layout.footer.width),
-        Tab::PullRequests => {
-            pull_requests::view::get_status_bar_content(&app.pull_requests, layout.footer.width)
+        Tab::Worktree => {
+            worktree::view::get_status_bar_content(&app.worktree, &app.keymap, layout.footer.width)
+        }
+        Tab::PullRequests => pull_requests::view::get_status_bar_content(
+            &app.pull_requests,
+            &app.keymap,
+            layout.footer.width,
+        ),
+        Tab::Settings => {
+            settings::view::get_status_bar_content(&app.settings, &app.keymap, layout.footer.width)
         }
-        Tab::Settings => settings::view::get_status_bar_content(&app.settings, layout.footer.width),
     };
 
     footer::render_footer(frame, layout.footer, status_content);
+    app.mouse_regions = regions;
+
+    if app.permissions.is_open {
+        permissions::view::render(
+            frame,
+            frame.area(),
+            &app.permissions,
+            &app.tasks,
+            &app.keymap,
+        );
+    }
+
+    if app.palette.is_open {
+        palette::view::render(frame, frame.area(), &app.palette);
+    }
 
     if app.showing_exit_confirmation {
-        tasks::dialogs::render_exit_confirmation_dialog(frame, frame.area());
+        tasks::dialogs::render_exit_confirmation_dialog(frame, frame.area(), &app.keymap);
     }
 }
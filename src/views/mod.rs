@@ -3,11 +3,17 @@ mod layout;
 mod tab_bar;
 
 pub mod instances;
+pub mod mouse;
+pub mod palette;
+pub mod permissions;
 pub mod pull_requests;
 pub mod roadmap;
 pub mod settings;
 pub mod tasks;
 pub mod worktree;
 
-pub use footer::StatusBarContent;
-pub use layout::render;
+pub use footer::{
+    StatusBarContent, confirm_help, confirm_keys, global_help, join_help, picker_help, scroll_help,
+    terminal_help,
+};
+pub use layout::{content_area, fit_terminals, render};
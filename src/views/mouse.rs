@@ -0,0 +1,111 @@
+use crate::app::Tab;
+use crate::views::tasks::state::{FocusPanel, ReviewPanel};
+use ratatui::layout::{Position, Rect};
+
+/// Something on screen a click or the wheel can act on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseTarget {
+    Tab(Tab),
+    KanbanColumn(usize),
+    KanbanTask {
+        column: usize,
+        task: usize,
+    },
+    FocusTask {
+        panel: FocusPanel,
+        index: usize,
+    },
+    /// The terminal of the task selected in the focus view, without its border.
+    TaskTerminal,
+    ReviewPanel(ReviewPanel),
+}
+
+/// Where the last frame drew each [`MouseTarget`], so mouse events can be matched to them.
+#[derive(Debug, Clone, Default)]
+pub struct MouseRegions {
+    regions: Vec<(Rect, MouseTarget)>,
+}
+
+impl MouseRegions {
+    pub fn push(&mut self, area: Rect, target: MouseTarget) {
+        self.regions.push((area, target));
+    }
+
+    /// Records the rows of a list drawn top to bottom from the top of `area`, given the
+    /// height of each item and what it stands for. Rows past the bottom are cut off.
+    pub fn push_rows(
+        &mut self,
+        area: Rect,
+        rows: impl IntoIterator<Item = (usize, Option<MouseTarget>)>,
+    ) {
+        let mut y = area.y;
+        for (height, target) in rows {
+            if y >= area.bottom() {
+                break;
+            }
+            let height = u16::try_from(height)
+                .unwrap_or(u16::MAX)
+                .min(area.bottom() - y);
+            if let Some(target) = target {
+                self.push(Rect { y, height, ..area }, target);
+            }
+            y += height;
+        }
+    }
+
+    /// Where `target` was drawn, if it was.
+    #[must_use]
+    pub fn area_of(&self, target: MouseTarget) -> Option<Rect> {
+        self.regions
+            .iter()
+            .find(|(_, region_target)| *region_target == target)
+            .map(|(area, _)| *area)
+    }
+
+    /// The target under `column` and `row`, where whatever was drawn last wins.
+    #[must_use]
+    pub fn target_at(&self, column: u16, row: u16) -> Option<(Rect, MouseTarget)> {
+        let position = Position::new(column, row);
+        self.regions
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(position))
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_later_regions_cover_earlier_ones() {
+        let mut regions = MouseRegions::default();
+        regions.push(Rect::new(0, 0, 20, 20), MouseTarget::KanbanColumn(0));
+        regions.push_rows(
+            Rect::new(1, 1, 18, 4),
+            [
+                (1, None),
+                (2, Some(MouseTarget::KanbanTask { column: 0, task: 0 })),
+                (3, Some(MouseTarget::KanbanTask { column: 0, task: 1 })),
+            ],
+        );
+
+        let target = |column, row| regions.target_at(column, row).map(|(_, target)| target);
+        assert_eq!(target(5, 1), Some(MouseTarget::KanbanColumn(0)));
+        assert_eq!(
+            target(5, 3),
+            Some(MouseTarget::KanbanTask { column: 0, task: 0 })
+        );
+        assert_eq!(
+            target(5, 4),
+            Some(MouseTarget::KanbanTask { column: 0, task: 1 })
+        );
+        assert_eq!(
+            target(5, 5),
+            Some(MouseTarget::KanbanColumn(0)),
+            "rows past the list are cut off"
+        );
+        assert_eq!(target(30, 5), None);
+    }
+}
@@ -0,0 +1,6 @@
+use super::PaletteTarget;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteAction {
+    Execute(PaletteTarget),
+}
@@ -0,0 +1,138 @@
+use super::{PaletteCommand, PaletteEntry, PaletteTarget};
+use crate::app::{App, Tab};
+use crate::views;
+
+/// Opens the palette over the board as it is now.
+pub fn open(app: &mut App) {
+    let vcs_command = app.settings.settings.vcs_command.clone();
+    app.worktree.poll_worktrees(&vcs_command);
+
+    let entries = collect_entries(app);
+    app.palette.open(entries);
+}
+
+fn collect_entries(app: &App) -> Vec<PaletteEntry> {
+    let commands = PaletteCommand::ALL
+        .into_iter()
+        .filter(|command| !(app.read_only && command.is_editing()))
+        .map(|command| {
+            let (context, action) = command.key_action();
+            PaletteEntry {
+                target: PaletteTarget::Command(command),
+                label: command.label().to_string(),
+                detail: app
+                    .keymap
+                    .chords(context, action)
+                    .first()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+            }
+        });
+
+    let tasks = app
+        .tasks
+        .list_task_references(None)
+        .into_iter()
+        .map(|reference| PaletteEntry {
+            target: PaletteTarget::Task(reference.task.id),
+            label: reference.task.title.clone(),
+            detail: reference.column_name.to_string(),
+        });
+
+    let panes = app.instances.collect_panes().into_iter().map(|pane| {
+        let working_directory = pane.working_directory.display().to_string();
+        PaletteEntry {
+            target: PaletteTarget::Pane(pane.id),
+            label: pane
+                .name
+                .clone()
+                .unwrap_or_else(|| pane.provider.display_name().to_string()),
+            detail: working_directory,
+        }
+    });
+
+    let worktrees = app
+        .worktree
+        .worktrees
+        .iter()
+        .enumerate()
+        .map(|(index, worktree)| PaletteEntry {
+            target: PaletteTarget::Worktree(index),
+            label: worktree.branch_name.clone(),
+            detail: worktree.path.display().to_string(),
+        });
+
+    let pull_requests =
+        app.pull_requests
+            .pull_requests
+            .iter()
+            .enumerate()
+            .map(|(index, pull_request)| PaletteEntry {
+                target: PaletteTarget::PullRequest(index),
+                label: format!("#{} {}", pull_request.number, pull_request.title),
+                detail: pull_request.author.clone(),
+            });
+
+    commands
+        .chain(tasks)
+        .chain(panes)
+        .chain(worktrees)
+        .chain(pull_requests)
+        .collect()
+}
+
+/// Runs the chosen command, or shows the chosen entity on its tab.
+pub fn execute(app: &mut App, target: PaletteTarget) {
+    match target {
+        PaletteTarget::Command(command) => execute_command(app, command),
+        PaletteTarget::Task(task_id) => {
+            if app.tasks.select_task_by_id(task_id) {
+                app.switch_tab(Tab::Tasks);
+            }
+        }
+        PaletteTarget::Pane(pane_id) => {
+            if app.instances.select_pane_by_id(pane_id) {
+                app.switch_tab(Tab::Instances);
+            }
+        }
+        PaletteTarget::Worktree(index) => {
+            app.switch_tab(Tab::Worktree);
+            app.worktree.selected_index = Some(index);
+        }
+        PaletteTarget::PullRequest(index) => {
+            app.switch_tab(Tab::PullRequests);
+            app.pull_requests.selected_index = Some(index);
+        }
+    }
+}
+
+fn execute_command(app: &mut App, command: PaletteCommand) {
+    if app.read_only && command.is_editing() {
+        return;
+    }
+
+    match command {
+        PaletteCommand::NewTask => {
+            app.switch_tab(Tab::Tasks);
+            app.tasks.begin_add_task();
+        }
+        PaletteCommand::ToggleTaskView => {
+            app.switch_tab(Tab::Tasks);
+            app.tasks.toggle_view_mode();
+        }
+        PaletteCommand::GenerateRoadmap => {
+            app.switch_tab(Tab::Roadmap);
+            app.start_roadmap_generation();
+        }
+        PaletteCommand::RefreshPullRequests => {
+            app.switch_tab(Tab::PullRequests);
+            views::pull_requests::refresh(&mut app.pull_requests);
+        }
+        PaletteCommand::NewPane => {
+            app.switch_tab(Tab::Instances);
+            app.instances.create_pane();
+        }
+        PaletteCommand::OpenPermissions => app.permissions.is_open = true,
+        PaletteCommand::ShowTab(tab) => app.switch_tab(tab),
+    }
+}
@@ -0,0 +1,40 @@
+use super::{PaletteAction, PaletteState};
+use crate::events::{AppAction, EventHandler, EventResult};
+use crate::types::{KeyAction, KeyContext, Keymap};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+impl EventHandler for PaletteState {
+    fn handle_key(&mut self, key: KeyEvent, keymap: &Keymap) -> EventResult {
+        match keymap.action(KeyContext::Palette, key) {
+            Some(KeyAction::Exit) => {
+                self.close();
+                return EventResult::Consumed;
+            }
+            Some(KeyAction::Open) => {
+                let target = self.selected().map(|entry| entry.target);
+                self.close();
+                return target.map_or(EventResult::Consumed, |target| {
+                    EventResult::Action(AppAction::Palette(PaletteAction::Execute(target)))
+                });
+            }
+            Some(KeyAction::MoveDown) => {
+                self.select_next();
+                return EventResult::Consumed;
+            }
+            Some(KeyAction::MoveUp) => {
+                self.select_previous();
+                return EventResult::Consumed;
+            }
+            _ => {}
+        }
+
+        match key.code {
+            KeyCode::Backspace => self.pop_char(),
+            KeyCode::Char(character) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.push_char(character);
+            }
+            _ => {}
+        }
+        EventResult::Consumed
+    }
+}
@@ -0,0 +1,8 @@
+mod action;
+pub mod dispatch;
+pub mod events;
+pub mod state;
+pub mod view;
+
+pub use action::PaletteAction;
+pub use state::{PaletteCommand, PaletteEntry, PaletteState, PaletteTarget};
@@ -0,0 +1,252 @@
+use crate::app::Tab;
+use crate::helpers::fuzzy;
+use crate::types::{KeyAction, KeyContext};
+use uuid::Uuid;
+
+/// Something the palette can do without leaving it for a tab first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteCommand {
+    NewTask,
+    ToggleTaskView,
+    GenerateRoadmap,
+    RefreshPullRequests,
+    NewPane,
+    OpenPermissions,
+    ShowTab(Tab),
+}
+
+impl PaletteCommand {
+    pub const ALL: [Self; 12] = [
+        Self::NewTask,
+        Self::ToggleTaskView,
+        Self::GenerateRoadmap,
+        Self::RefreshPullRequests,
+        Self::NewPane,
+        Self::OpenPermissions,
+        Self::ShowTab(Tab::Tasks),
+        Self::ShowTab(Tab::Instances),
+        Self::ShowTab(Tab::Roadmap),
+        Self::ShowTab(Tab::Worktree),
+        Self::ShowTab(Tab::PullRequests),
+        Self::ShowTab(Tab::Settings),
+    ];
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::NewTask => "New task",
+            Self::ToggleTaskView => "Switch task view",
+            Self::GenerateRoadmap => "Generate roadmap",
+            Self::RefreshPullRequests => "Refresh pull requests",
+            Self::NewPane => "New terminal pane",
+            Self::OpenPermissions => "Open permission inbox",
+            Self::ShowTab(Tab::Tasks) => "Go to Tasks",
+            Self::ShowTab(Tab::Instances) => "Go to Instances",
+            Self::ShowTab(Tab::Roadmap) => "Go to Roadmap",
+            Self::ShowTab(Tab::Worktree) => "Go to Worktrees",
+            Self::ShowTab(Tab::PullRequests) => "Go to Pull requests",
+            Self::ShowTab(Tab::Settings) => "Go to Settings",
+        }
+    }
+
+    /// The keymap entry that does the same, shown next to the command so it can be learned.
+    #[must_use]
+    pub const fn key_action(self) -> (KeyContext, KeyAction) {
+        match self {
+            Self::NewTask => (KeyContext::Kanban, KeyAction::Add),
+            Self::ToggleTaskView => (KeyContext::Tasks, KeyAction::ToggleView),
+            Self::GenerateRoadmap => (KeyContext::Roadmap, KeyAction::Generate),
+            Self::RefreshPullRequests => (KeyContext::PullRequests, KeyAction::Refresh),
+            Self::NewPane => (KeyContext::Instances, KeyAction::NewPane),
+            Self::OpenPermissions => (KeyContext::Global, KeyAction::TogglePermissions),
+            Self::ShowTab(Tab::Tasks) => (KeyContext::Global, KeyAction::ShowTasks),
+            Self::ShowTab(Tab::Instances) => (KeyContext::Global, KeyAction::ShowInstances),
+            Self::ShowTab(Tab::Roadmap) => (KeyContext::Global, KeyAction::ShowRoadmap),
+            Self::ShowTab(Tab::Worktree) => (KeyContext::Global, KeyAction::ShowWorktrees),
+            Self::ShowTab(Tab::PullRequests) => (KeyContext::Global, KeyAction::ShowPullRequests),
+            Self::ShowTab(Tab::Settings) => (KeyContext::Global, KeyAction::ShowSettings),
+        }
+    }
+
+    /// Whether the command changes the board or starts processes, which a read-only board
+    /// must not do.
+    #[must_use]
+    pub const fn is_editing(self) -> bool {
+        matches!(self, Self::NewTask | Self::GenerateRoadmap | Self::NewPane)
+    }
+}
+
+/// What choosing a palette entry jumps to or runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteTarget {
+    Command(PaletteCommand),
+    Task(Uuid),
+    Pane(Uuid),
+    Worktree(usize),
+    PullRequest(usize),
+}
+
+impl PaletteTarget {
+    #[must_use]
+    pub const fn kind(self) -> &'static str {
+        match self {
+            Self::Command(_) => "Action",
+            Self::Task(_) => "Task",
+            Self::Pane(_) => "Pane",
+            Self::Worktree(_) => "Worktree",
+            Self::PullRequest(_) => "PR",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteEntry {
+    pub target: PaletteTarget,
+    pub label: String,
+    pub detail: String,
+}
+
+/// The `Ctrl-P` overlay: every action and entity of the board, narrowed down by a fuzzy query.
+#[derive(Debug, Clone, Default)]
+pub struct PaletteState {
+    pub is_open: bool,
+    pub query: String,
+    pub selected_index: usize,
+    entries: Vec<PaletteEntry>,
+    matches: Vec<usize>,
+}
+
+impl PaletteState {
+    /// Opens the palette over `entries` with an empty query.
+    pub fn open(&mut self, entries: Vec<PaletteEntry>) {
+        self.entries = entries;
+        self.query.clear();
+        self.is_open = true;
+        self.update_matches();
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.query.clear();
+        self.entries.clear();
+        self.matches.clear();
+    }
+
+    pub fn push_char(&mut self, character: char) {
+        self.query.push(character);
+        self.update_matches();
+    }
+
+    pub fn pop_char(&mut self) {
+        self.query.pop();
+        self.update_matches();
+    }
+
+    pub const fn select_next(&mut self) {
+        if self.selected_index + 1 < self.matches.len() {
+            self.selected_index += 1;
+        }
+    }
+
+    pub const fn select_previous(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    /// The entries matching the query, best match first.
+    pub fn matches(&self) -> impl Iterator<Item = &PaletteEntry> {
+        self.matches.iter().map(|index| &self.entries[*index])
+    }
+
+    #[must_use]
+    pub const fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    #[must_use]
+    pub fn selected(&self) -> Option<&PaletteEntry> {
+        self.matches().nth(self.selected_index)
+    }
+
+    /// Ranks the entries against the query. Labels count more than details, and ties keep
+    /// the order the entries were given in, so an empty query lists them unchanged.
+    fn update_matches(&mut self) {
+        let mut scored: Vec<(i64, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                let label_score = fuzzy::score(&self.query, &entry.label);
+                let full_text = format!("{} {} {}", entry.target.kind(), entry.label, entry.detail);
+                label_score
+                    .map(|score| score * 2)
+                    .or_else(|| fuzzy::score(&self.query, &full_text))
+                    .map(|score| (score, index))
+            })
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.selected_index = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(target: PaletteTarget, label: &str, detail: &str) -> PaletteEntry {
+        PaletteEntry {
+            target,
+            label: label.to_string(),
+            detail: detail.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_query_narrows_and_ranks_entries() {
+        let task_id = Uuid::new_v4();
+        let mut state = PaletteState::default();
+        state.open(vec![
+            entry(
+                PaletteTarget::Command(PaletteCommand::NewTask),
+                "New task",
+                "",
+            ),
+            entry(
+                PaletteTarget::Command(PaletteCommand::RefreshPullRequests),
+                "Refresh pull requests",
+                "",
+            ),
+            entry(PaletteTarget::Task(task_id), "Fix login redirect", "Review"),
+        ]);
+        assert_eq!(state.match_count(), 3);
+
+        for character in "login".chars() {
+            state.push_char(character);
+        }
+        assert_eq!(state.match_count(), 1);
+        assert_eq!(
+            state.selected().map(|entry| entry.target),
+            Some(PaletteTarget::Task(task_id))
+        );
+
+        state.query.clear();
+        for character in "review".chars() {
+            state.push_char(character);
+        }
+        assert_eq!(
+            state.selected().map(|entry| entry.target),
+            Some(PaletteTarget::Task(task_id)),
+            "details are searched too"
+        );
+
+        state.query.clear();
+        for character in "pull".chars() {
+            state.push_char(character);
+        }
+        assert_eq!(
+            state.selected().map(|entry| entry.target),
+            Some(PaletteTarget::Command(PaletteCommand::RefreshPullRequests))
+        );
+    }
+}
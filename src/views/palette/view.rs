@@ -0,0 +1,20 @@
+use super::PaletteState;
+use crate::widgets::dialogs::{PaletteDialog, PaletteItem};
+use ratatui::{Frame, layout::Rect};
+
+pub fn render(frame: &mut Frame, area: Rect, state: &PaletteState) {
+    let title = format!(" Command Palette ({}) ", state.match_count());
+    let items = state
+        .matches()
+        .map(|entry| PaletteItem {
+            kind: entry.target.kind(),
+            label: &entry.label,
+            detail: &entry.detail,
+        })
+        .collect();
+
+    frame.render_widget(
+        PaletteDialog::new(&title, &state.query, items, state.selected_index),
+        area,
+    );
+}
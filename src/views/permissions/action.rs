@@ -0,0 +1,11 @@
+use super::state::PermissionScope;
+use crate::types::PermissionDecision;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionAction {
+    Answer {
+        decision: PermissionDecision,
+        scope: PermissionScope,
+    },
+    OpenTerminal,
+}
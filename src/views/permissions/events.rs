@@ -0,0 +1,43 @@
+use super::PermissionAction;
+use super::state::{PermissionScope, PermissionsState};
+use crate::events::{AppAction, EventHandler, EventResult};
+use crate::types::{KeyAction, KeyContext, Keymap, PermissionDecision};
+use crossterm::event::KeyEvent;
+
+impl EventHandler for PermissionsState {
+    fn handle_key(&mut self, key: KeyEvent, keymap: &Keymap) -> EventResult {
+        let answer = |decision, scope| {
+            EventResult::Action(AppAction::Permission(PermissionAction::Answer {
+                decision,
+                scope,
+            }))
+        };
+
+        match keymap.action(KeyContext::Permissions, key) {
+            Some(KeyAction::MoveDown) => {
+                self.select_next();
+                EventResult::Consumed
+            }
+            Some(KeyAction::MoveUp) => {
+                self.select_previous();
+                EventResult::Consumed
+            }
+            Some(KeyAction::Allow) => answer(PermissionDecision::Allow, PermissionScope::Once),
+            Some(KeyAction::AllowForTask) => {
+                answer(PermissionDecision::Allow, PermissionScope::Task)
+            }
+            Some(KeyAction::AllowTool) => answer(PermissionDecision::Allow, PermissionScope::Tool),
+            Some(KeyAction::Deny) => answer(PermissionDecision::Deny, PermissionScope::Once),
+            Some(KeyAction::DenyForTask) => answer(PermissionDecision::Deny, PermissionScope::Task),
+            Some(KeyAction::DenyTool) => answer(PermissionDecision::Deny, PermissionScope::Tool),
+            Some(KeyAction::Open) => {
+                EventResult::Action(AppAction::Permission(PermissionAction::OpenTerminal))
+            }
+            Some(KeyAction::Exit) => {
+                self.close();
+                EventResult::Consumed
+            }
+            _ => EventResult::Consumed,
+        }
+    }
+}
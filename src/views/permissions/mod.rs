@@ -0,0 +1,7 @@
+mod action;
+pub mod events;
+pub mod state;
+pub mod view;
+
+pub use action::PermissionAction;
+pub use state::{PermissionRequest, PermissionScope, PermissionsState};
@@ -0,0 +1,212 @@
+use crate::events::{HookEvent, PermissionResponder};
+use crate::types::PermissionDecision;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Keys tools use in their input for the thing they act on, most specific first.
+const DETAIL_KEYS: [&str; 6] = [
+    "command",
+    "file_path",
+    "notebook_path",
+    "path",
+    "url",
+    "pattern",
+];
+
+/// How far an inbox answer reaches beyond the request it was given for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionScope {
+    Once,
+    Task,
+    Tool,
+}
+
+/// An agent waiting for permission to use a tool.
+#[derive(Debug, Clone)]
+pub struct PermissionRequest {
+    pub task_id: Uuid,
+    pub tool: String,
+    pub detail: Option<String>,
+    pub received_at: DateTime<Utc>,
+    responder: Option<PermissionResponder>,
+}
+
+impl PermissionRequest {
+    /// Parses the tool and what it acts on from a permission hook's payload.
+    #[must_use]
+    pub fn from_hook(event: &HookEvent, responder: Option<PermissionResponder>) -> Self {
+        let data = &event.hook_data;
+        let tool = ["tool_name", "tool", "type"]
+            .iter()
+            .find_map(|key| data.get(key)?.as_str())
+            .unwrap_or("Unknown tool")
+            .to_string();
+
+        let tool_input = data.get("tool_input").unwrap_or(data);
+        let detail = DETAIL_KEYS
+            .iter()
+            .find_map(|key| tool_input.get(key)?.as_str())
+            .or_else(|| data.get("title")?.as_str())
+            .map(str::to_string);
+
+        Self {
+            task_id: event.worktree_id,
+            tool,
+            detail,
+            received_at: Utc::now(),
+            responder,
+        }
+    }
+
+    /// Answers through the hook that is waiting for it. Returns `false` when no hook is
+    /// listening, in which case the answer has to be typed into the agent's terminal.
+    #[must_use]
+    pub fn answer_through_hook(self, decision: PermissionDecision) -> bool {
+        self.responder
+            .is_some_and(|responder| responder.send(decision).is_ok())
+    }
+
+    fn is_same_request(&self, other: &Self) -> bool {
+        self.task_id == other.task_id && self.tool == other.tool && self.detail == other.detail
+    }
+}
+
+/// Pending permission requests from every task, answered from one overlay.
+#[derive(Debug, Clone, Default)]
+pub struct PermissionsState {
+    pub requests: Vec<PermissionRequest>,
+    pub selected_index: usize,
+    pub is_open: bool,
+}
+
+impl PermissionsState {
+    /// Queues `request`, replacing an identical one the agent asked again for.
+    pub fn push(&mut self, request: PermissionRequest) {
+        self.requests
+            .retain(|existing| !existing.is_same_request(&request));
+        self.requests.push(request);
+    }
+
+    /// Removes the selected request so it can be answered.
+    pub fn take_selected(&mut self) -> Option<PermissionRequest> {
+        if self.selected_index >= self.requests.len() {
+            return None;
+        }
+        let request = self.requests.remove(self.selected_index);
+        self.clamp_selection();
+        Some(request)
+    }
+
+    /// Empties the inbox, handing back every pending request.
+    pub fn take_all(&mut self) -> Vec<PermissionRequest> {
+        self.selected_index = 0;
+        std::mem::take(&mut self.requests)
+    }
+
+    /// Drops a task's requests once its agent has moved on. Dropping them releases any hook
+    /// still waiting, so the agent is never left blocked on an answer nobody will give.
+    pub fn clear_task(&mut self, task_id: Uuid) {
+        self.requests.retain(|request| request.task_id != task_id);
+        self.clamp_selection();
+    }
+
+    #[must_use]
+    pub const fn pending_count(&self) -> usize {
+        self.requests.len()
+    }
+
+    pub const fn toggle(&mut self) {
+        self.is_open = !self.is_open;
+    }
+
+    pub const fn close(&mut self) {
+        self.is_open = false;
+    }
+
+    pub const fn select_next(&mut self) {
+        if self.selected_index + 1 < self.requests.len() {
+            self.selected_index += 1;
+        }
+    }
+
+    pub const fn select_previous(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+    }
+
+    fn clamp_selection(&mut self) {
+        self.selected_index = self
+            .selected_index
+            .min(self.requests.len().saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook_event(hook_data: serde_json::Value) -> HookEvent {
+        HookEvent {
+            event: "permission".to_string(),
+            worktree_id: Uuid::nil(),
+            timestamp: 0,
+            hook_data,
+            awaits_decision: false,
+        }
+    }
+
+    #[test]
+    fn test_request_from_claude_hook() {
+        let event = hook_event(serde_json::json!({
+            "hook_event_name": "PermissionRequest",
+            "tool_name": "Bash",
+            "tool_input": { "command": "cargo test", "description": "Run tests" },
+        }));
+        let request = PermissionRequest::from_hook(&event, None);
+
+        assert_eq!(request.tool, "Bash");
+        assert_eq!(request.detail.as_deref(), Some("cargo test"));
+
+        let event = hook_event(serde_json::json!({
+            "tool_name": "Edit",
+            "tool_input": { "file_path": "src/main.rs", "old_string": "a" },
+        }));
+        let request = PermissionRequest::from_hook(&event, None);
+        assert_eq!(request.detail.as_deref(), Some("src/main.rs"));
+    }
+
+    #[test]
+    fn test_request_from_payload_without_tool() {
+        let request = PermissionRequest::from_hook(&hook_event(serde_json::json!({})), None);
+        assert_eq!(request.tool, "Unknown tool");
+        assert_eq!(request.detail, None);
+    }
+
+    #[test]
+    fn test_push_replaces_repeated_request() {
+        let mut state = PermissionsState::default();
+        let event = hook_event(serde_json::json!({
+            "tool_name": "Bash",
+            "tool_input": { "command": "ls" },
+        }));
+        state.push(PermissionRequest::from_hook(&event, None));
+        state.push(PermissionRequest::from_hook(&event, None));
+        assert_eq!(state.pending_count(), 1);
+
+        assert!(state.take_selected().is_some());
+        assert!(state.take_selected().is_none());
+        assert_eq!(state.selected_index, 0);
+    }
+
+    #[test]
+    fn test_answer_reaches_waiting_hook() {
+        let (responder, receiver) = std::sync::mpsc::channel();
+        let event = hook_event(serde_json::json!({ "tool_name": "Bash" }));
+        let request = PermissionRequest::from_hook(&event, Some(responder));
+
+        assert!(request.answer_through_hook(PermissionDecision::Deny));
+        assert_eq!(receiver.recv().unwrap(), PermissionDecision::Deny);
+
+        let request = PermissionRequest::from_hook(&event, None);
+        assert!(!request.answer_through_hook(PermissionDecision::Allow));
+    }
+}
@@ -0,0 +1,161 @@
+use super::state::{PermissionRequest, PermissionsState};
+use crate::helpers::text;
+use crate::types::{KeyAction, KeyContext, Keymap};
+use crate::views::tasks::TasksState;
+use crate::views::tasks::dialogs::{centered_rect, render_popup_background};
+use chrono::Utc;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+};
+
+pub const PERMISSION_COLOR: Color = Color::Rgb(138, 43, 226);
+
+const INBOX_WIDTH_PERCENT: u16 = 70;
+const INBOX_HEIGHT_PERCENT: u16 = 60;
+const HELP_HEIGHT: u16 = 3;
+
+const HELP_ACTIONS: [(KeyAction, &str); 8] = [
+    (KeyAction::Allow, "allow"),
+    (KeyAction::AllowForTask, "allow for task"),
+    (KeyAction::AllowTool, "allow tool everywhere"),
+    (KeyAction::Deny, "deny"),
+    (KeyAction::DenyForTask, "deny for task"),
+    (KeyAction::DenyTool, "deny tool everywhere"),
+    (KeyAction::Open, "answer in terminal"),
+    (KeyAction::Exit, "close"),
+];
+
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    state: &PermissionsState,
+    tasks: &TasksState,
+    keymap: &Keymap,
+) {
+    let dialog_area = centered_rect(INBOX_WIDTH_PERCENT, INBOX_HEIGHT_PERCENT, area);
+    render_popup_background(frame, dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(PERMISSION_COLOR))
+        .title(Span::styled(
+            format!(" Permission Requests ({}) ", state.pending_count()),
+            Style::default()
+                .fg(PERMISSION_COLOR)
+                .add_modifier(Modifier::BOLD),
+        ));
+    let inner_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(HELP_HEIGHT)])
+        .split(inner_area);
+
+    if state.requests.is_empty() {
+        let empty_text = Paragraph::new("No agent is waiting for permission.")
+            .style(Style::default().fg(Color::Gray));
+        frame.render_widget(empty_text, layout[0]);
+    } else {
+        let available_width = usize::from(layout[0].width);
+        let items: Vec<ListItem> = state
+            .requests
+            .iter()
+            .enumerate()
+            .map(|(index, request)| {
+                create_list_item(
+                    request,
+                    tasks,
+                    index == state.selected_index,
+                    available_width,
+                )
+            })
+            .collect();
+        frame.render_widget(List::new(items), layout[0]);
+    }
+
+    frame.render_widget(Paragraph::new(help_lines(keymap)), layout[1]);
+}
+
+fn create_list_item(
+    request: &PermissionRequest,
+    tasks: &TasksState,
+    is_selected: bool,
+    available_width: usize,
+) -> ListItem<'static> {
+    let task_title = tasks
+        .find_task_by_id(request.task_id)
+        .map_or("Unknown task", |task| task.title.as_str());
+    let waited_seconds = Utc::now()
+        .signed_duration_since(request.received_at)
+        .num_seconds();
+
+    let header = Line::from(vec![
+        Span::styled(
+            format!(" {} ", request.tool),
+            Style::default()
+                .fg(PERMISSION_COLOR)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            text::truncate(task_title, available_width.saturating_sub(24)),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled(
+            format!("  {waited_seconds}s ago"),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]);
+
+    let detail = request.detail.as_deref().unwrap_or("(no details)");
+    let detail_line = Line::from(Span::styled(
+        format!(
+            "   {}",
+            text::truncate(detail, available_width.saturating_sub(4))
+        ),
+        Style::default().fg(Color::Gray),
+    ));
+
+    let item = ListItem::new(vec![header, detail_line]);
+    if is_selected {
+        item.style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        item
+    }
+}
+
+fn help_lines(keymap: &Keymap) -> Vec<Line<'static>> {
+    let spans = |actions: &[(KeyAction, &'static str)]| {
+        let mut spans = Vec::new();
+        for (action, description) in actions {
+            let Some(chord) = keymap.chords(KeyContext::Permissions, *action).first() else {
+                continue;
+            };
+            spans.push(Span::styled(
+                chord.to_string(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::styled(
+                format!(" {description}  "),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+        Line::from(spans)
+    };
+
+    vec![
+        Line::from(""),
+        spans(&HELP_ACTIONS[..3]),
+        spans(&HELP_ACTIONS[3..]),
+    ]
+}
@@ -1,50 +1,55 @@
 use super::PullRequestAction;
 use super::state::{PullRequestsMode, PullRequestsState};
 use crate::events::{AppAction, EventHandler, EventResult};
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::types::{KeyAction, KeyContext, Keymap};
+use crossterm::event::KeyEvent;
 
 impl EventHandler for PullRequestsState {
-    fn handle_key(&mut self, key: KeyEvent) -> EventResult {
+    fn handle_key(&mut self, key: KeyEvent, keymap: &Keymap) -> EventResult {
         match self.mode {
-            PullRequestsMode::Normal => self.handle_normal_mode_event(key),
-            PullRequestsMode::Viewing => self.handle_viewing_mode_event(key),
+            PullRequestsMode::Normal => {
+                self.handle_normal_mode_event(keymap.action(KeyContext::PullRequests, key))
+            }
+            PullRequestsMode::Viewing => {
+                self.handle_viewing_mode_event(keymap.action(KeyContext::PullRequests, key))
+            }
         }
     }
 }
 
 impl PullRequestsState {
-    fn handle_normal_mode_event(&mut self, key: KeyEvent) -> EventResult {
-        match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
+    fn handle_normal_mode_event(&mut self, action: Option<KeyAction>) -> EventResult {
+        match action {
+            Some(KeyAction::MoveDown) => {
                 self.select_next();
                 EventResult::Consumed
             }
-            KeyCode::Char('k') | KeyCode::Up => {
+            Some(KeyAction::MoveUp) => {
                 self.select_previous();
                 EventResult::Consumed
             }
-            KeyCode::Char('g') => {
+            Some(KeyAction::GoToTop) => {
                 self.select_first();
                 EventResult::Consumed
             }
-            KeyCode::Char('G') => {
+            Some(KeyAction::GoToBottom) => {
                 self.select_last();
                 EventResult::Consumed
             }
-            KeyCode::Char('r' | 'R') => {
+            Some(KeyAction::Refresh) => {
                 self.mark_needs_refresh();
                 EventResult::Action(AppAction::PullRequest(PullRequestAction::Refresh))
             }
-            KeyCode::Enter | KeyCode::Char('o') => {
+            Some(KeyAction::Open) => {
                 EventResult::Action(AppAction::PullRequest(PullRequestAction::OpenInBrowser))
             }
             _ => EventResult::Ignored,
         }
     }
 
-    const fn handle_viewing_mode_event(&mut self, key: KeyEvent) -> EventResult {
-        match key.code {
-            KeyCode::Esc | KeyCode::Char('q') => {
+    const fn handle_viewing_mode_event(&mut self, action: Option<KeyAction>) -> EventResult {
+        match action {
+            Some(KeyAction::Exit) => {
                 self.mode = PullRequestsMode::Normal;
                 EventResult::Consumed
             }
@@ -1,6 +1,7 @@
 use super::state::{PullRequest, PullRequestStatusState, PullRequestsMode, PullRequestsState};
 use crate::helpers::text;
-use crate::views::StatusBarContent;
+use crate::types::{KeyAction, KeyContext, Keymap};
+use crate::views::{StatusBarContent, global_help, join_help};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -282,7 +283,11 @@ fn render_details(frame: &mut Frame, area: Rect, state: &PullRequestsState) {
 }
 
 #[must_use]
-pub fn get_status_bar_content(state: &PullRequestsState, width: u16) -> StatusBarContent {
+pub fn get_status_bar_content(
+    state: &PullRequestsState,
+    keymap: &Keymap,
+    width: u16,
+) -> StatusBarContent {
     let mode_color = match state.mode {
         PullRequestsMode::Normal => Color::Cyan,
         PullRequestsMode::Viewing => Color::Yellow,
@@ -293,17 +298,15 @@ pub fn get_status_bar_content(state: &PullRequestsState, width: u16) -> StatusBa
         PullRequestsMode::Viewing => "VIEWING",
     };
 
-    let help_text = if width < STATUS_BAR_WIDTH_THRESHOLD {
-        match state.mode {
-            PullRequestsMode::Normal => "jk:navigate  o:open  r:refresh",
-            PullRequestsMode::Viewing => "Esc:back",
-        }
-    } else {
-        match state.mode {
-            PullRequestsMode::Normal => {
-                "jk/arrows:navigate  o/Enter:open in browser  r:refresh  Tab:switch-tabs  q:quit"
-            }
-            PullRequestsMode::Viewing => "Esc/q:back to list",
+    let is_wide = width >= STATUS_BAR_WIDTH_THRESHOLD;
+    let help_text = match state.mode {
+        PullRequestsMode::Normal => normal_help(keymap, is_wide),
+        PullRequestsMode::Viewing => {
+            let back_label = if is_wide { "back to list" } else { "back" };
+            keymap.help(
+                KeyContext::PullRequests,
+                &[(&[KeyAction::Exit], back_label)],
+            )
         }
     };
 
@@ -311,6 +314,24 @@ pub fn get_status_bar_content(state: &PullRequestsState, width: u16) -> StatusBa
         mode_text: mode_text.to_string(),
         mode_color,
         extra_info: None,
-        help_text: help_text.to_string(),
+        help_text,
+    }
+}
+
+fn normal_help(keymap: &Keymap, is_wide: bool) -> String {
+    let open_label = if is_wide { "open in browser" } else { "open" };
+    let pull_requests = keymap.help(
+        KeyContext::PullRequests,
+        &[
+            (&[KeyAction::MoveDown, KeyAction::MoveUp], "navigate"),
+            (&[KeyAction::Open], open_label),
+            (&[KeyAction::Refresh], "refresh"),
+        ],
+    );
+
+    if is_wide {
+        join_help([pull_requests, global_help(keymap)])
+    } else {
+        pull_requests
     }
 }
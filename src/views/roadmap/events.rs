@@ -1,6 +1,7 @@
 use super::RoadmapAction as AppRoadmapAction;
 use super::state::{RoadmapMode, RoadmapPriority, RoadmapState};
 use crate::events::{AppAction, EventHandler, EventResult, SettingsAction};
+use crate::types::{KeyAction, KeyContext, Keymap};
 use crossterm::event::{KeyCode, KeyEvent};
 
 enum RoadmapAction {
@@ -11,16 +12,16 @@ enum RoadmapAction {
 }
 
 impl EventHandler for RoadmapState {
-    fn handle_key(&mut self, key: KeyEvent) -> EventResult {
+    fn handle_key(&mut self, key: KeyEvent, keymap: &Keymap) -> EventResult {
         let action = match &self.mode {
-            RoadmapMode::Normal => self.handle_normal_mode(key),
+            RoadmapMode::Normal => self.handle_normal_mode(keymap.action(KeyContext::Roadmap, key)),
             RoadmapMode::AddingItem { .. } => self.handle_adding_item(key),
             RoadmapMode::EditingItem { .. } => self.handle_editing_item(key),
             RoadmapMode::ConfirmDelete { item_index } => {
-                self.handle_confirm_delete(key, *item_index)
+                self.handle_confirm_delete(keymap.action(KeyContext::Dialog, key), *item_index)
             }
             RoadmapMode::ConvertToTask { item_index } => {
-                self.handle_convert_to_task(key, *item_index)
+                self.handle_convert_to_task(keymap.action(KeyContext::Dialog, key), *item_index)
             }
             RoadmapMode::Generating => self.handle_generating_mode(key),
         };
@@ -41,23 +42,23 @@ impl EventHandler for RoadmapState {
 }
 
 impl RoadmapState {
-    fn handle_normal_mode(&mut self, key: KeyEvent) -> RoadmapAction {
-        match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
+    fn handle_normal_mode(&mut self, action: Option<KeyAction>) -> RoadmapAction {
+        match action {
+            Some(KeyAction::MoveDown) => {
                 self.select_next();
                 RoadmapAction::None
             }
-            KeyCode::Char('k') | KeyCode::Up => {
+            Some(KeyAction::MoveUp) => {
                 self.select_previous();
                 RoadmapAction::None
             }
-            KeyCode::Char('a') => {
+            Some(KeyAction::Add) => {
                 self.mode = RoadmapMode::AddingItem {
                     input: String::new(),
                 };
                 RoadmapAction::None
             }
-            KeyCode::Char('e') => {
+            Some(KeyAction::Edit) => {
                 if let Some(index) = self.selected_item
                     && let Some(item) = self.items.get(index)
                 {
@@ -68,19 +69,19 @@ impl RoadmapState {
                 }
                 RoadmapAction::None
             }
-            KeyCode::Char('d') => {
+            Some(KeyAction::Delete) => {
                 if let Some(index) = self.selected_item {
                     self.mode = RoadmapMode::ConfirmDelete { item_index: index };
                 }
                 RoadmapAction::None
             }
-            KeyCode::Char('t') => {
+            Some(KeyAction::ConvertToTask) => {
                 if let Some(index) = self.selected_item {
                     self.mode = RoadmapMode::ConvertToTask { item_index: index };
                 }
                 RoadmapAction::None
             }
-            KeyCode::Char('p') => {
+            Some(KeyAction::CyclePriority) => {
                 if let Some(index) = self.selected_item
                     && let Some(item) = self.items.get(index)
                 {
@@ -94,7 +95,7 @@ impl RoadmapState {
                 }
                 RoadmapAction::None
             }
-            KeyCode::Char('g' | 'G') => RoadmapAction::GenerateRoadmap,
+            Some(KeyAction::Generate) => RoadmapAction::GenerateRoadmap,
             _ => RoadmapAction::None,
         }
     }
@@ -183,14 +184,18 @@ impl RoadmapState {
         }
     }
 
-    fn handle_confirm_delete(&mut self, key: KeyEvent, item_index: usize) -> RoadmapAction {
-        match key.code {
-            KeyCode::Char('y' | 'Y') => {
+    fn handle_confirm_delete(
+        &mut self,
+        action: Option<KeyAction>,
+        item_index: usize,
+    ) -> RoadmapAction {
+        match action {
+            Some(KeyAction::Confirm) => {
                 self.delete_item(item_index);
                 self.mode = RoadmapMode::Normal;
                 RoadmapAction::SaveState
             }
-            KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+            Some(KeyAction::Cancel) => {
                 self.mode = RoadmapMode::Normal;
                 RoadmapAction::None
             }
@@ -198,13 +203,17 @@ impl RoadmapState {
         }
     }
 
-    fn handle_convert_to_task(&mut self, key: KeyEvent, item_index: usize) -> RoadmapAction {
-        match key.code {
-            KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+    fn handle_convert_to_task(
+        &mut self,
+        action: Option<KeyAction>,
+        item_index: usize,
+    ) -> RoadmapAction {
+        match action {
+            Some(KeyAction::Confirm) => {
                 self.mode = RoadmapMode::Normal;
                 RoadmapAction::ConvertToTask(item_index)
             }
-            KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+            Some(KeyAction::Cancel) => {
                 self.mode = RoadmapMode::Normal;
                 RoadmapAction::None
             }
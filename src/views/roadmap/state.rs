@@ -52,6 +52,13 @@ impl RoadmapState {
     pub fn get_selected_item(&self) -> Option<&RoadmapItem> {
         self.selected_item.and_then(|index| self.items.get(index))
     }
+
+    /// Whether a dialog has the keyboard. Generation runs in the background, so the tabs
+    /// can still be switched while it does.
+    #[must_use]
+    pub const fn captures_keys(&self) -> bool {
+        !matches!(self.mode, RoadmapMode::Normal | RoadmapMode::Generating)
+    }
 }
 
 impl Default for RoadmapState {
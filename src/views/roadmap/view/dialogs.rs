@@ -1,4 +1,6 @@
 use super::super::RoadmapState;
+use crate::types::Keymap;
+use crate::views::confirm_help;
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -66,7 +68,13 @@ pub fn render_confirm_dialog(f: &mut Frame, message: &str, area: Rect) {
     f.render_widget(text, inner_area);
 }
 
-pub fn render_convert_dialog(f: &mut Frame, state: &RoadmapState, item_index: usize, area: Rect) {
+pub fn render_convert_dialog(
+    f: &mut Frame,
+    state: &RoadmapState,
+    item_index: usize,
+    keymap: &Keymap,
+    area: Rect,
+) {
     let dialog_width = if area.width < DIALOG_WIDTH_THRESHOLD {
         DIALOG_WIDTH_SMALL
     } else {
@@ -99,7 +107,7 @@ pub fn render_convert_dialog(f: &mut Frame, state: &RoadmapState, item_index: us
             ]),
             Line::from(""),
             Line::from(vec![Span::styled(
-                "Press 'y' to confirm, 'n' to cancel",
+                confirm_help(keymap),
                 Style::default().fg(Color::DarkGray),
             )]),
         ];
@@ -4,7 +4,8 @@ mod items;
 
 use super::{RoadmapMode, RoadmapState};
 use crate::app::App;
-use crate::views::StatusBarContent;
+use crate::types::{KeyAction, KeyContext, Keymap};
+use crate::views::{StatusBarContent, confirm_help, confirm_keys, global_help, join_help};
 use details_panel::render_details_panel;
 use dialogs::{
     render_confirm_dialog, render_convert_dialog, render_input_dialog, render_loading_dialog,
@@ -37,10 +38,11 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
             render_input_dialog(f, "Edit Roadmap Item", input, area);
         }
         RoadmapMode::ConfirmDelete { .. } => {
-            render_confirm_dialog(f, "Delete this roadmap item? (y/n)", area);
+            let message = format!("Delete this roadmap item? {}", confirm_keys(&app.keymap));
+            render_confirm_dialog(f, &message, area);
         }
         RoadmapMode::ConvertToTask { item_index } => {
-            render_convert_dialog(f, state, *item_index, area);
+            render_convert_dialog(f, state, *item_index, &app.keymap, area);
         }
         RoadmapMode::Generating => {
             render_loading_dialog(f, state, area);
@@ -50,7 +52,11 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
 }
 
 #[must_use]
-pub fn get_status_bar_content(state: &RoadmapState, width: u16) -> StatusBarContent {
+pub fn get_status_bar_content(
+    state: &RoadmapState,
+    keymap: &Keymap,
+    width: u16,
+) -> StatusBarContent {
     let mode_color = match &state.mode {
         RoadmapMode::Normal => Color::Cyan,
         RoadmapMode::AddingItem { .. } => Color::Green,
@@ -68,27 +74,38 @@ pub fn get_status_bar_content(state: &RoadmapState, width: u16) -> StatusBarCont
         RoadmapMode::Generating => "GENERATING",
     };
 
-    let help_text = if width < STATUS_BAR_WIDTH_THRESHOLD {
+    let is_wide = width >= STATUS_BAR_WIDTH_THRESHOLD;
+    let help_text = match &state.mode {
+        RoadmapMode::Normal => Some(normal_help(keymap, is_wide)),
+        RoadmapMode::ConfirmDelete { .. } | RoadmapMode::ConvertToTask { .. } => {
+            Some(confirm_help(keymap))
+        }
+        _ => None,
+    };
+    if let Some(help_text) = help_text {
+        return StatusBarContent {
+            mode_text: mode_text.to_string(),
+            mode_color,
+            extra_info: None,
+            help_text,
+        };
+    }
+
+    let help_text = if is_wide {
         match &state.mode {
-            RoadmapMode::Normal => "jk:navigate  a:add  g:generate  e:edit  d:delete  t:convert",
             RoadmapMode::AddingItem { .. } | RoadmapMode::EditingItem { .. } => {
-                "Enter:save  Esc:cancel"
+                "Type to enter text  Enter:save  Esc:cancel"
             }
-            RoadmapMode::ConfirmDelete { .. } | RoadmapMode::ConvertToTask { .. } => "y:yes  n:no",
-            RoadmapMode::Generating => "Esc:cancel",
+            RoadmapMode::Generating => "AI is analyzing your project... Press Esc to cancel",
+            _ => "",
         }
     } else {
         match &state.mode {
-            RoadmapMode::Normal => {
-                "↑↓/jk:navigate  a:add  g:generate-with-ai  e:edit  d:delete  t:convert-to-task  p:priority  q:quit"
-            }
             RoadmapMode::AddingItem { .. } | RoadmapMode::EditingItem { .. } => {
-                "Type to enter text  Enter:save  Esc:cancel"
-            }
-            RoadmapMode::ConfirmDelete { .. } | RoadmapMode::ConvertToTask { .. } => {
-                "y:yes  n:no  Esc:cancel"
+                "Enter:save  Esc:cancel"
             }
-            RoadmapMode::Generating => "AI is analyzing your project... Press Esc to cancel",
+            RoadmapMode::Generating => "Esc:cancel",
+            _ => "",
         }
     };
 
@@ -99,3 +116,33 @@ pub fn get_status_bar_content(state: &RoadmapState, width: u16) -> StatusBarCont
         help_text: help_text.to_string(),
     }
 }
+
+fn normal_help(keymap: &Keymap, is_wide: bool) -> String {
+    if !is_wide {
+        return keymap.help(
+            KeyContext::Roadmap,
+            &[
+                (&[KeyAction::MoveDown, KeyAction::MoveUp], "navigate"),
+                (&[KeyAction::Add], "add"),
+                (&[KeyAction::Generate], "generate"),
+                (&[KeyAction::Edit], "edit"),
+                (&[KeyAction::Delete], "delete"),
+                (&[KeyAction::ConvertToTask], "convert"),
+            ],
+        );
+    }
+
+    let roadmap = keymap.help(
+        KeyContext::Roadmap,
+        &[
+            (&[KeyAction::MoveDown, KeyAction::MoveUp], "navigate"),
+            (&[KeyAction::Add], "add"),
+            (&[KeyAction::Generate], "generate-with-ai"),
+            (&[KeyAction::Edit], "edit"),
+            (&[KeyAction::Delete], "delete"),
+            (&[KeyAction::ConvertToTask], "convert-to-task"),
+            (&[KeyAction::CyclePriority], "priority"),
+        ],
+    );
+    join_help([roadmap, global_help(keymap)])
+}
@@ -1,6 +1,7 @@
 use super::SettingsAction as AppSettingsAction;
-use super::state::{SettingsFocus, SettingsMode, SettingsSection, SettingsState};
+use super::state::{MergeStrategy, SettingsFocus, SettingsMode, SettingsSection, SettingsState};
 use crate::events::{AppAction, EventHandler, EventResult};
+use crate::types::{KeyAction, KeyContext, Keymap};
 use crate::views::tasks::dialogs::{get_option_count, get_selection_result};
 use crossterm::event::{KeyCode, KeyEvent};
 
@@ -15,8 +16,8 @@ pub enum SettingsAction {
 }
 
 impl EventHandler for SettingsState {
-    fn handle_key(&mut self, key: KeyEvent) -> EventResult {
-        let action = handle_key_event(self, key);
+    fn handle_key(&mut self, key: KeyEvent, keymap: &Keymap) -> EventResult {
+        let action = handle_key_event(self, key, keymap);
         match action {
             SettingsAction::None => EventResult::Consumed,
             SettingsAction::SaveSettings => {
@@ -26,57 +27,112 @@ impl EventHandler for SettingsState {
     }
 }
 
-pub fn handle_key_event(state: &mut SettingsState, key: KeyEvent) -> SettingsAction {
+pub fn handle_key_event(
+    state: &mut SettingsState,
+    key: KeyEvent,
+    keymap: &Keymap,
+) -> SettingsAction {
     match state.mode {
-        SettingsMode::Normal => handle_normal_mode(state, key),
-        SettingsMode::EditingShell { .. } | SettingsMode::EditingAutoSave { .. } => {
-            handle_editing_mode(state, key)
-        }
-        SettingsMode::SelectingProvider { .. } => handle_provider_selection_mode(state, key),
-        SettingsMode::SelectingIde { .. } => handle_ide_selection_mode(state, key),
-        SettingsMode::SelectingTerminal { .. } => handle_terminal_selection_mode(state, key),
-        SettingsMode::SelectingVcs { .. } => handle_vcs_selection_mode(state, key),
+        SettingsMode::Normal => handle_normal_mode(state, keymap.action(KeyContext::Settings, key)),
+        SettingsMode::EditingShell { .. }
+        | SettingsMode::EditingAutoSave { .. }
+        | SettingsMode::EditingMaxRunningAgents
+        | SettingsMode::EditingBaseSyncInterval
+        | SettingsMode::EditingProviderConfig { .. } => handle_editing_mode(state, key),
+        SettingsMode::SelectingProvider { .. } => {
+            handle_provider_selection_mode(state, keymap.action(KeyContext::Picker, key))
+        }
+        SettingsMode::SelectingIde { .. } => {
+            handle_ide_selection_mode(state, keymap.action(KeyContext::Picker, key))
+        }
+        SettingsMode::SelectingTerminal { .. } => {
+            handle_terminal_selection_mode(state, keymap.action(KeyContext::Picker, key))
+        }
+        SettingsMode::SelectingVcs { .. } => {
+            handle_vcs_selection_mode(state, keymap.action(KeyContext::Picker, key))
+        }
+        SettingsMode::SelectingMergeStrategy { .. } => {
+            handle_merge_strategy_selection_mode(state, keymap.action(KeyContext::Picker, key))
+        }
         SettingsMode::ConfiguringPermissions { .. } => {
-            handle_permission_configuration_mode(state, key)
+            handle_permission_configuration_mode(state, keymap.action(KeyContext::Picker, key))
         }
     }
 }
 
-fn handle_normal_mode(state: &mut SettingsState, key: KeyEvent) -> SettingsAction {
-    match key.code {
-        KeyCode::Tab | KeyCode::BackTab => {
+/// Moves the selection in the sidebar, the content or an open picker one step, as the mouse
+/// wheel does.
+pub fn move_selection(state: &mut SettingsState, action: KeyAction) {
+    let action = Some(action);
+    match state.mode {
+        SettingsMode::Normal => {
+            handle_normal_mode(state, action);
+        }
+        SettingsMode::SelectingProvider { .. } => {
+            handle_provider_selection_mode(state, action);
+        }
+        SettingsMode::SelectingIde { .. } => {
+            handle_ide_selection_mode(state, action);
+        }
+        SettingsMode::SelectingTerminal { .. } => {
+            handle_terminal_selection_mode(state, action);
+        }
+        SettingsMode::SelectingVcs { .. } => {
+            handle_vcs_selection_mode(state, action);
+        }
+        SettingsMode::SelectingMergeStrategy { .. } => {
+            handle_merge_strategy_selection_mode(state, action);
+        }
+        SettingsMode::ConfiguringPermissions { .. } => {
+            handle_permission_configuration_mode(state, action);
+        }
+        SettingsMode::EditingShell { .. }
+        | SettingsMode::EditingAutoSave { .. }
+        | SettingsMode::EditingMaxRunningAgents
+        | SettingsMode::EditingBaseSyncInterval
+        | SettingsMode::EditingProviderConfig { .. } => {}
+    }
+}
+
+fn handle_normal_mode(state: &mut SettingsState, action: Option<KeyAction>) -> SettingsAction {
+    match action {
+        Some(KeyAction::SwitchPanel) => {
             state.toggle_focus();
             SettingsAction::None
         }
-        KeyCode::Char('j') | KeyCode::Down => {
+        Some(KeyAction::MoveDown) => {
             state.navigate_down();
             SettingsAction::None
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Some(KeyAction::MoveUp) => {
             state.navigate_up();
             SettingsAction::None
         }
-        KeyCode::Char('h') | KeyCode::Left => {
+        Some(KeyAction::MoveLeft) => {
             if state.focus == SettingsFocus::Content {
                 state.focus = SettingsFocus::Sidebar;
             }
             SettingsAction::None
         }
-        KeyCode::Char('l') | KeyCode::Right => {
+        Some(KeyAction::MoveRight) => {
             state.enter_content();
             SettingsAction::None
         }
-        KeyCode::Char('g') => {
+        Some(KeyAction::GoToTop) => {
             state.selected_section = 0;
             state.selected_item_in_section = 0;
             SettingsAction::None
         }
-        KeyCode::Char('G') => {
+        Some(KeyAction::GoToBottom) => {
             state.selected_section = SettingsSection::count() - 1;
             state.selected_item_in_section = 0;
             SettingsAction::None
         }
-        KeyCode::Enter | KeyCode::Char(' ') => {
+        Some(KeyAction::ResetToGlobal) => {
+            state.reset_selected_to_global();
+            SettingsAction::SaveSettings
+        }
+        Some(KeyAction::Open) => {
             if state.focus == SettingsFocus::Sidebar {
                 state.focus = SettingsFocus::Content;
                 SettingsAction::None
@@ -111,44 +167,47 @@ fn handle_editing_mode(state: &mut SettingsState, key: KeyEvent) -> SettingsActi
     }
 }
 
-fn handle_provider_selection_mode(state: &mut SettingsState, key: KeyEvent) -> SettingsAction {
+fn handle_provider_selection_mode(
+    state: &mut SettingsState,
+    action: Option<KeyAction>,
+) -> SettingsAction {
     let SettingsMode::SelectingProvider { selected_index } = state.mode else {
         return SettingsAction::None;
     };
 
     let option_count = get_option_count(&state.detected_providers);
 
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('q') => {
+    match action {
+        Some(KeyAction::Exit) => {
             state.mode = SettingsMode::Normal;
             SettingsAction::None
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        Some(KeyAction::MoveUp) => {
             state.mode = SettingsMode::SelectingProvider {
                 selected_index: selected_index.saturating_sub(1),
             };
             SettingsAction::None
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        Some(KeyAction::MoveDown) => {
             state.mode = SettingsMode::SelectingProvider {
                 selected_index: (selected_index + 1).min(option_count - 1),
             };
             SettingsAction::None
         }
-        KeyCode::Enter => {
+        Some(KeyAction::Open) => {
             let result = get_selection_result(
                 selected_index,
                 &state.detected_providers,
-                state.settings.default_provider,
+                &state.settings.default_provider,
             );
 
             state.mode = SettingsMode::Normal;
 
             if let Some(selection) = result {
-                state.settings.default_provider = selection.provider();
                 if selection.should_remember() {
                     state.settings.skip_provider_selection = true;
                 }
+                state.settings.default_provider = selection.provider();
                 SettingsAction::SaveSettings
             } else {
                 SettingsAction::None
@@ -158,29 +217,32 @@ fn handle_provider_selection_mode(state: &mut SettingsState, key: KeyEvent) -> S
     }
 }
 
-fn handle_ide_selection_mode(state: &mut SettingsState, key: KeyEvent) -> SettingsAction {
+fn handle_ide_selection_mode(
+    state: &mut SettingsState,
+    action: Option<KeyAction>,
+) -> SettingsAction {
     let SettingsMode::SelectingIde { selected_index } = state.mode else {
         return SettingsAction::None;
     };
 
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('q') => {
+    match action {
+        Some(KeyAction::Exit) => {
             state.mode = SettingsMode::Normal;
             SettingsAction::None
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        Some(KeyAction::MoveUp) => {
             state.mode = SettingsMode::SelectingIde {
                 selected_index: selected_index.saturating_sub(1),
             };
             SettingsAction::None
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        Some(KeyAction::MoveDown) => {
             state.mode = SettingsMode::SelectingIde {
                 selected_index: (selected_index + 1).min(IDE_OPTIONS_COUNT - 1),
             };
             SettingsAction::None
         }
-        KeyCode::Enter => {
+        Some(KeyAction::Open) => {
             state.select_ide(selected_index);
             SettingsAction::SaveSettings
         }
@@ -188,29 +250,32 @@ fn handle_ide_selection_mode(state: &mut SettingsState, key: KeyEvent) -> Settin
     }
 }
 
-fn handle_terminal_selection_mode(state: &mut SettingsState, key: KeyEvent) -> SettingsAction {
+fn handle_terminal_selection_mode(
+    state: &mut SettingsState,
+    action: Option<KeyAction>,
+) -> SettingsAction {
     let SettingsMode::SelectingTerminal { selected_index } = state.mode else {
         return SettingsAction::None;
     };
 
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('q') => {
+    match action {
+        Some(KeyAction::Exit) => {
             state.mode = SettingsMode::Normal;
             SettingsAction::None
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        Some(KeyAction::MoveUp) => {
             state.mode = SettingsMode::SelectingTerminal {
                 selected_index: selected_index.saturating_sub(1),
             };
             SettingsAction::None
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        Some(KeyAction::MoveDown) => {
             state.mode = SettingsMode::SelectingTerminal {
                 selected_index: (selected_index + 1).min(TERMINAL_OPTIONS_COUNT - 1),
             };
             SettingsAction::None
         }
-        KeyCode::Enter => {
+        Some(KeyAction::Open) => {
             state.select_terminal(selected_index);
             SettingsAction::SaveSettings
         }
@@ -218,29 +283,32 @@ fn handle_terminal_selection_mode(state: &mut SettingsState, key: KeyEvent) -> S
     }
 }
 
-fn handle_vcs_selection_mode(state: &mut SettingsState, key: KeyEvent) -> SettingsAction {
+fn handle_vcs_selection_mode(
+    state: &mut SettingsState,
+    action: Option<KeyAction>,
+) -> SettingsAction {
     let SettingsMode::SelectingVcs { selected_index } = state.mode else {
         return SettingsAction::None;
     };
 
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('q') => {
+    match action {
+        Some(KeyAction::Exit) => {
             state.mode = SettingsMode::Normal;
             SettingsAction::None
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        Some(KeyAction::MoveUp) => {
             state.mode = SettingsMode::SelectingVcs {
                 selected_index: selected_index.saturating_sub(1),
             };
             SettingsAction::None
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        Some(KeyAction::MoveDown) => {
             state.mode = SettingsMode::SelectingVcs {
                 selected_index: (selected_index + 1).min(VCS_OPTIONS_COUNT - 1),
             };
             SettingsAction::None
         }
-        KeyCode::Enter => {
+        Some(KeyAction::Open) => {
             state.select_vcs(selected_index);
             SettingsAction::SaveSettings
         }
@@ -248,9 +316,42 @@ fn handle_vcs_selection_mode(state: &mut SettingsState, key: KeyEvent) -> Settin
     }
 }
 
+fn handle_merge_strategy_selection_mode(
+    state: &mut SettingsState,
+    action: Option<KeyAction>,
+) -> SettingsAction {
+    let SettingsMode::SelectingMergeStrategy { selected_index } = state.mode else {
+        return SettingsAction::None;
+    };
+
+    match action {
+        Some(KeyAction::Exit) => {
+            state.mode = SettingsMode::Normal;
+            SettingsAction::None
+        }
+        Some(KeyAction::MoveUp) => {
+            state.mode = SettingsMode::SelectingMergeStrategy {
+                selected_index: selected_index.saturating_sub(1),
+            };
+            SettingsAction::None
+        }
+        Some(KeyAction::MoveDown) => {
+            state.mode = SettingsMode::SelectingMergeStrategy {
+                selected_index: (selected_index + 1).min(MergeStrategy::ALL.len() - 1),
+            };
+            SettingsAction::None
+        }
+        Some(KeyAction::Open) => {
+            state.select_merge_strategy(selected_index);
+            SettingsAction::SaveSettings
+        }
+        _ => SettingsAction::None,
+    }
+}
+
 fn handle_permission_configuration_mode(
     state: &mut SettingsState,
-    key: KeyEvent,
+    action: Option<KeyAction>,
 ) -> SettingsAction {
     let SettingsMode::ConfiguringPermissions {
         selected_preset_index,
@@ -259,25 +360,25 @@ fn handle_permission_configuration_mode(
         return SettingsAction::None;
     };
 
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('q') => {
+    match action {
+        Some(KeyAction::Exit) => {
             state.mode = SettingsMode::Normal;
             SettingsAction::None
         }
-        KeyCode::Up | KeyCode::Char('k') => {
+        Some(KeyAction::MoveUp) => {
             state.mode = SettingsMode::ConfiguringPermissions {
                 selected_preset_index: selected_preset_index.saturating_sub(1),
             };
             SettingsAction::None
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        Some(KeyAction::MoveDown) => {
             state.mode = SettingsMode::ConfiguringPermissions {
                 selected_preset_index: (selected_preset_index + 1)
                     .min(PERMISSION_PRESET_OPTIONS_COUNT - 1),
             };
             SettingsAction::None
         }
-        KeyCode::Enter => {
+        Some(KeyAction::Open) => {
             state.select_permission_preset(selected_preset_index);
             SettingsAction::SaveSettings
         }
@@ -4,4 +4,4 @@ pub mod state;
 pub mod view;
 
 pub use action::SettingsAction;
-pub use state::{SettingsState, VcsCommand};
+pub use state::{MergeStrategy, SettingsState, VcsCommand};
@@ -1,7 +1,13 @@
+use crate::helpers::text::{escape_shell_arg, split_shell_words};
+use crate::persistence::config::{SettingSource, SettingsLayers};
+use crate::providers::{self, CustomProvider, ProviderSpec};
 use crate::types::{
-    AgentProvider, DetectedProvider, PermissionConfig, PermissionPreset, ProviderRegistry,
+    AgentProvider, ColumnDefinition, DetectedProvider, KeyBindings, Keymap, PermissionConfig,
+    PermissionPreset, PermissionRule, ProviderConfig, ProviderRegistry,
 };
+use crate::views::worktree::WorktreeHooks;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 const SECTION_COUNT: usize = 4;
@@ -70,11 +76,19 @@ impl SettingsSection {
                 SettingItem::DefaultShell,
                 SettingItem::TerminalCommand,
                 SettingItem::VcsCommand,
+                SettingItem::MergeStrategy,
+                SettingItem::BaseSyncInterval,
             ],
             Self::EditorAndIde => &[SettingItem::IdeCommand],
             Self::Agent => &[
                 SettingItem::DefaultProvider,
                 SettingItem::ProviderPermissions,
+                SettingItem::ProviderCommand,
+                SettingItem::ProviderArguments,
+                SettingItem::ProviderEnvironment,
+                SettingItem::ProviderWorkingDirectoryArgument,
+                SettingItem::ProviderMaxRunningAgents,
+                SettingItem::MaxRunningAgents,
             ],
             Self::Persistence => &[SettingItem::AutoSaveInterval],
         }
@@ -101,6 +115,14 @@ pub struct Settings {
     pub terminal_command: TerminalCommand,
     #[serde(default)]
     pub vcs_command: VcsCommand,
+    /// How "Merge & Complete" lands a task's branch unless another strategy is picked when
+    /// merging.
+    #[serde(default)]
+    pub merge_strategy: MergeStrategy,
+    /// How often task worktrees are synced with the base branch while their agents are idle.
+    /// 0 only syncs them from the review popup.
+    #[serde(default)]
+    pub base_sync_interval_seconds: u64,
     #[serde(default)]
     pub default_provider: AgentProvider,
     #[serde(default)]
@@ -109,6 +131,73 @@ pub struct Settings {
     pub provider_registry: ProviderRegistry,
     #[serde(default)]
     pub permission_configs: HashMap<AgentProvider, PermissionConfig>,
+    #[serde(default)]
+    pub custom_providers: Vec<CustomProvider>,
+    /// Answers given with "always for this tool" in the permission inbox, applied to every
+    /// task.
+    #[serde(default)]
+    pub permission_rules: Vec<PermissionRule>,
+    /// Most agents the auto-run queue lets run at once, across every provider.
+    #[serde(default = "default_max_running_agents")]
+    pub max_running_agents: usize,
+    /// The kanban columns, in order, and what happens to tasks entering each of them.
+    #[serde(default = "ColumnDefinition::defaults")]
+    pub columns: Vec<ColumnDefinition>,
+    /// Chords replacing the default ones, by context and action name.
+    #[serde(default, skip_serializing_if = "KeyBindings::is_empty")]
+    pub keybindings: KeyBindings,
+    /// Files brought into new task worktrees and commands run around their lifetime.
+    #[serde(default, skip_serializing_if = "WorktreeHooks::is_empty")]
+    pub worktree_hooks: WorktreeHooks,
+}
+
+const fn default_max_running_agents() -> usize {
+    2
+}
+
+impl Settings {
+    /// The configured board columns, checked with [`ColumnDefinition::validate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the columns do not describe a usable board.
+    pub fn validated_columns(&self) -> Result<&[ColumnDefinition], String> {
+        ColumnDefinition::validate(&self.columns)
+            .map(|()| self.columns.as_slice())
+            .map_err(|error| format!("Invalid columns in settings: {error}"))
+    }
+
+    /// The default keymap with the configured `keybindings` applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the keybindings name unknown keys or actions, or conflict.
+    pub fn keymap(&self) -> Result<Keymap, String> {
+        Keymap::with_overrides(&self.keybindings)
+            .map_err(|error| format!("Invalid keybindings in settings: {error}"))
+    }
+
+    /// The spec used to launch `provider`, with its `provider_registry` entry applied.
+    #[must_use]
+    pub fn provider_spec(&self, provider: &AgentProvider) -> Cow<'static, ProviderSpec> {
+        let spec = providers::get_spec(provider, &self.custom_providers);
+        match self.provider_registry.configs.get(provider) {
+            Some(config) => Cow::Owned(spec.into_owned().with_config(config)),
+            None => spec,
+        }
+    }
+
+    #[must_use]
+    pub fn provider_config(&self, provider: &AgentProvider) -> ProviderConfig {
+        if let Some(config) = self.provider_registry.configs.get(provider) {
+            return config.clone();
+        }
+
+        self.custom_providers
+            .iter()
+            .find(|custom_provider| custom_provider.provider() == *provider)
+            .map_or_else(|| provider.default_config(), CustomProvider::default_config)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -252,6 +341,69 @@ impl VcsCommand {
     }
 }
 
+/// How a task's branch is brought into the branch it is merged into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MergeStrategy {
+    /// A merge commit, or a fast-forward when the target branch has not moved on
+    #[default]
+    Merge,
+    /// One new commit holding all of the branch's changes
+    Squash,
+    /// The branch's commits replayed onto the target branch, which is then fast-forwarded
+    Rebase,
+    /// Only a fast-forward, refusing when the target branch has moved on
+    FastForwardOnly,
+}
+
+impl MergeStrategy {
+    pub const ALL: [Self; 4] = [
+        Self::Merge,
+        Self::Squash,
+        Self::Rebase,
+        Self::FastForwardOnly,
+    ];
+
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::Merge => "Merge",
+            Self::Squash => "Squash",
+            Self::Rebase => "Rebase",
+            Self::FastForwardOnly => "Fast-forward only",
+        }
+    }
+
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Merge => "Keep the branch's commits, joined by a merge commit",
+            Self::Squash => "Land the branch as a single commit",
+            Self::Rebase => "Replay the branch's commits for a linear history",
+            Self::FastForwardOnly => "Only land branches made on top of the target",
+        }
+    }
+
+    /// The strategy after this one, wrapping around.
+    #[must_use]
+    pub fn next(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|strategy| *strategy == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// The strategy before this one, wrapping around.
+    #[must_use]
+    pub fn previous(self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|strategy| *strategy == self)
+            .unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
 impl TerminalCommand {
     #[must_use]
     pub const fn detect() -> Self {
@@ -310,7 +462,7 @@ impl Default for Settings {
     fn default() -> Self {
         let mut permission_configs = HashMap::new();
         for provider in AgentProvider::all() {
-            permission_configs.insert(*provider, PermissionConfig::default());
+            permission_configs.insert(provider.clone(), PermissionConfig::default());
         }
 
         Self {
@@ -319,10 +471,18 @@ impl Default for Settings {
             ide_command: IdeCommand::detect(),
             terminal_command: TerminalCommand::detect(),
             vcs_command: VcsCommand::detect(),
+            merge_strategy: MergeStrategy::default(),
+            base_sync_interval_seconds: 0,
             default_provider: AgentProvider::default(),
             skip_provider_selection: false,
             provider_registry: ProviderRegistry::new(),
             permission_configs,
+            custom_providers: Vec::new(),
+            permission_rules: Vec::new(),
+            max_running_agents: default_max_running_agents(),
+            columns: ColumnDefinition::defaults(),
+            keybindings: KeyBindings::new(),
+            worktree_hooks: WorktreeHooks::default(),
         }
     }
 }
@@ -337,6 +497,8 @@ pub enum SettingsMode {
     EditingAutoSave {
         initial_value: u64,
     },
+    EditingMaxRunningAgents,
+    EditingBaseSyncInterval,
     SelectingProvider {
         selected_index: usize,
     },
@@ -349,9 +511,111 @@ pub enum SettingsMode {
     SelectingVcs {
         selected_index: usize,
     },
+    SelectingMergeStrategy {
+        selected_index: usize,
+    },
     ConfiguringPermissions {
         selected_preset_index: usize,
     },
+    EditingProviderConfig {
+        field: ProviderConfigField,
+    },
+}
+
+/// The `ProviderRegistry` fields editable from the Agent section. Edits apply to the default
+/// agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderConfigField {
+    Command,
+    Arguments,
+    Environment,
+    WorkingDirectoryArgument,
+    MaxRunningAgents,
+}
+
+impl ProviderConfigField {
+    #[must_use]
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Command => "Edit Agent Command",
+            Self::Arguments => "Edit Agent Arguments (shell quoting)",
+            Self::Environment => "Edit Agent Environment (KEY=value ...)",
+            Self::WorkingDirectoryArgument => "Edit Working Directory Flag",
+            Self::MaxRunningAgents => "Edit Agent Limit (empty for none)",
+        }
+    }
+
+    /// The field's key in a `ProviderConfig` as written to `settings.json`.
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::Command => "command",
+            Self::Arguments => "arguments",
+            Self::Environment => "environment",
+            Self::WorkingDirectoryArgument => "working_directory_argument",
+            Self::MaxRunningAgents => "max_running_agents",
+        }
+    }
+
+    #[must_use]
+    pub fn display_value(self, config: &ProviderConfig) -> String {
+        match self {
+            Self::Command => config.command.to_string_lossy().to_string(),
+            Self::Arguments => config
+                .arguments
+                .iter()
+                .map(|argument| escape_shell_arg(argument))
+                .collect::<Vec<_>>()
+                .join(" "),
+            Self::Environment => {
+                let mut variables: Vec<String> = config
+                    .environment
+                    .iter()
+                    .map(|(key, value)| format!("{key}={}", escape_shell_arg(value)))
+                    .collect();
+                variables.sort();
+                variables.join(" ")
+            }
+            Self::WorkingDirectoryArgument => config
+                .working_directory_argument
+                .clone()
+                .unwrap_or_default(),
+            Self::MaxRunningAgents => config
+                .max_running_agents
+                .map(|limit| limit.to_string())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Parses `input` into `config`. Environment words without an `=` are ignored.
+    pub fn apply(self, config: &mut ProviderConfig, input: &str) {
+        match self {
+            Self::Command => {
+                let command = input.trim();
+                if !command.is_empty() {
+                    config.command = command.into();
+                }
+            }
+            Self::Arguments => config.arguments = split_shell_words(input),
+            Self::Environment => {
+                config.environment = split_shell_words(input)
+                    .into_iter()
+                    .filter_map(|word| {
+                        let (key, value) = word.split_once('=')?;
+                        Some((key.to_string(), value.to_string()))
+                    })
+                    .filter(|(key, _)| !key.is_empty())
+                    .collect();
+            }
+            Self::WorkingDirectoryArgument => {
+                let flag = input.trim();
+                config.working_directory_argument = (!flag.is_empty()).then(|| flag.to_string());
+            }
+            Self::MaxRunningAgents => {
+                config.max_running_agents = input.trim().parse().ok().filter(|limit| *limit > 0);
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -361,8 +625,16 @@ pub enum SettingItem {
     IdeCommand,
     TerminalCommand,
     VcsCommand,
+    MergeStrategy,
+    BaseSyncInterval,
     DefaultProvider,
     ProviderPermissions,
+    ProviderCommand,
+    ProviderArguments,
+    ProviderEnvironment,
+    ProviderWorkingDirectoryArgument,
+    ProviderMaxRunningAgents,
+    MaxRunningAgents,
 }
 
 impl SettingItem {
@@ -374,8 +646,57 @@ impl SettingItem {
             Self::IdeCommand => "IDE Command",
             Self::TerminalCommand => "Terminal",
             Self::VcsCommand => "Version Control",
+            Self::MergeStrategy => "Merge Strategy",
+            Self::BaseSyncInterval => "Base Sync Interval",
             Self::DefaultProvider => "Default Agent",
             Self::ProviderPermissions => "Agent Permissions",
+            Self::ProviderCommand => "Agent Command",
+            Self::ProviderArguments => "Agent Arguments",
+            Self::ProviderEnvironment => "Agent Environment",
+            Self::ProviderWorkingDirectoryArgument => "Working Directory Flag",
+            Self::ProviderMaxRunningAgents => "Agent Queue Limit",
+            Self::MaxRunningAgents => "Queue Limit (all agents)",
+        }
+    }
+
+    #[must_use]
+    pub const fn provider_config_field(self) -> Option<ProviderConfigField> {
+        match self {
+            Self::ProviderCommand => Some(ProviderConfigField::Command),
+            Self::ProviderArguments => Some(ProviderConfigField::Arguments),
+            Self::ProviderEnvironment => Some(ProviderConfigField::Environment),
+            Self::ProviderWorkingDirectoryArgument => {
+                Some(ProviderConfigField::WorkingDirectoryArgument)
+            }
+            Self::ProviderMaxRunningAgents => Some(ProviderConfigField::MaxRunningAgents),
+            _ => None,
+        }
+    }
+
+    /// The keys leading to the item's value in `settings.json`. Agent items point into the
+    /// entry of `provider`.
+    #[must_use]
+    pub fn settings_path(self, provider: &AgentProvider) -> Vec<&str> {
+        match self {
+            Self::DefaultShell => vec!["default_shell"],
+            Self::AutoSaveInterval => vec!["auto_save_interval_seconds"],
+            Self::IdeCommand => vec!["ide_command"],
+            Self::TerminalCommand => vec!["terminal_command"],
+            Self::VcsCommand => vec!["vcs_command"],
+            Self::MergeStrategy => vec!["merge_strategy"],
+            Self::BaseSyncInterval => vec!["base_sync_interval_seconds"],
+            Self::DefaultProvider => vec!["default_provider"],
+            Self::MaxRunningAgents => vec!["max_running_agents"],
+            Self::ProviderPermissions => vec!["permission_configs", provider.key()],
+            Self::ProviderCommand
+            | Self::ProviderArguments
+            | Self::ProviderEnvironment
+            | Self::ProviderWorkingDirectoryArgument
+            | Self::ProviderMaxRunningAgents => {
+                let mut path = vec!["provider_registry", "configs", provider.key()];
+                path.extend(self.provider_config_field().map(ProviderConfigField::key));
+                path
+            }
         }
     }
 }
@@ -395,6 +716,9 @@ pub struct SettingsState {
     pub edit_buffer: String,
     #[serde(skip)]
     pub detected_providers: Vec<DetectedProvider>,
+    /// The global and project files `settings` was merged from.
+    #[serde(skip)]
+    pub layers: SettingsLayers,
 }
 
 impl SettingsState {
@@ -407,12 +731,18 @@ impl SettingsState {
             focus: SettingsFocus::default(),
             mode: SettingsMode::Normal,
             edit_buffer: String::new(),
-            detected_providers: AgentProvider::detect_all_available(),
+            detected_providers: AgentProvider::detect_all_available(&[]),
+            layers: SettingsLayers::default(),
         }
     }
 
     #[must_use]
-    pub fn with_settings(settings: Settings) -> Self {
+    pub fn with_settings(mut settings: Settings, layers: SettingsLayers) -> Self {
+        settings
+            .provider_registry
+            .register_custom_providers(&settings.custom_providers);
+        let detected_providers = AgentProvider::detect_all_available(&settings.custom_providers);
+
         Self {
             settings,
             selected_section: 0,
@@ -420,7 +750,44 @@ impl SettingsState {
             focus: SettingsFocus::default(),
             mode: SettingsMode::Normal,
             edit_buffer: String::new(),
-            detected_providers: AgentProvider::detect_all_available(),
+            detected_providers,
+            layers,
+        }
+    }
+
+    /// Whether a picker or an edit field has the keyboard.
+    #[must_use]
+    pub const fn captures_keys(&self) -> bool {
+        !matches!(self.mode, SettingsMode::Normal)
+    }
+
+    /// Whether `item` is set by the project config, the global config or neither.
+    #[must_use]
+    pub fn setting_source(&self, item: SettingItem) -> SettingSource {
+        self.layers
+            .source(&item.settings_path(&self.settings.default_provider))
+    }
+
+    /// Drops the selected setting from the project config, so it takes the global value or the
+    /// default again.
+    pub fn reset_selected_to_global(&mut self) {
+        let Some(item) = self.selected_item() else {
+            return;
+        };
+        if self.focus != SettingsFocus::Content
+            || self.setting_source(item) != SettingSource::Project
+        {
+            return;
+        }
+
+        let mut layers = self.layers.clone();
+        layers.remove_project_value(&item.settings_path(&self.settings.default_provider));
+        if let Ok(mut settings) = layers.merged() {
+            settings
+                .provider_registry
+                .register_custom_providers(&settings.custom_providers);
+            self.settings = settings;
+            self.layers = layers;
         }
     }
 
@@ -495,6 +862,14 @@ impl SettingsState {
                     initial_value: self.settings.auto_save_interval_seconds,
                 };
             }
+            SettingItem::MaxRunningAgents => {
+                self.edit_buffer = self.settings.max_running_agents.to_string();
+                self.mode = SettingsMode::EditingMaxRunningAgents;
+            }
+            SettingItem::BaseSyncInterval => {
+                self.edit_buffer = self.settings.base_sync_interval_seconds.to_string();
+                self.mode = SettingsMode::EditingBaseSyncInterval;
+            }
             SettingItem::IdeCommand => {
                 let current_index = self.get_current_ide_index();
                 self.mode = SettingsMode::SelectingIde {
@@ -513,10 +888,19 @@ impl SettingsState {
                     selected_index: current_index,
                 };
             }
+            SettingItem::MergeStrategy => {
+                let current_index = MergeStrategy::ALL
+                    .iter()
+                    .position(|strategy| *strategy == self.settings.merge_strategy)
+                    .unwrap_or(0);
+                self.mode = SettingsMode::SelectingMergeStrategy {
+                    selected_index: current_index,
+                };
+            }
             SettingItem::DefaultProvider => {
                 if self.detected_providers.len() <= 1 {
                     if let Some(detected) = self.detected_providers.first() {
-                        self.settings.default_provider = detected.provider;
+                        self.settings.default_provider = detected.provider.clone();
                     }
                 } else {
                     self.mode = SettingsMode::SelectingProvider { selected_index: 0 };
@@ -539,6 +923,20 @@ impl SettingsState {
                     selected_preset_index: preset_index,
                 };
             }
+            SettingItem::ProviderCommand
+            | SettingItem::ProviderArguments
+            | SettingItem::ProviderEnvironment
+            | SettingItem::ProviderWorkingDirectoryArgument
+            | SettingItem::ProviderMaxRunningAgents => {
+                let Some(field) = item.provider_config_field() else {
+                    return;
+                };
+                let config = self
+                    .settings
+                    .provider_config(&self.settings.default_provider);
+                self.edit_buffer = field.display_value(&config);
+                self.mode = SettingsMode::EditingProviderConfig { field };
+            }
         }
     }
 
@@ -594,6 +992,14 @@ impl SettingsState {
         self.mode = SettingsMode::Normal;
     }
 
+    pub fn select_merge_strategy(&mut self, index: usize) {
+        let Some(strategy) = MergeStrategy::ALL.get(index) else {
+            return;
+        };
+        self.settings.merge_strategy = *strategy;
+        self.mode = SettingsMode::Normal;
+    }
+
     pub fn confirm_edit(&mut self) {
         match self.mode {
             SettingsMode::Normal
@@ -601,6 +1007,7 @@ impl SettingsState {
             | SettingsMode::SelectingIde { .. }
             | SettingsMode::SelectingTerminal { .. }
             | SettingsMode::SelectingVcs { .. }
+            | SettingsMode::SelectingMergeStrategy { .. }
             | SettingsMode::ConfiguringPermissions { .. } => {}
             SettingsMode::EditingShell { .. } => {
                 if !self.edit_buffer.is_empty() {
@@ -618,6 +1025,33 @@ impl SettingsState {
                 self.mode = SettingsMode::Normal;
                 self.edit_buffer.clear();
             }
+            SettingsMode::EditingMaxRunningAgents => {
+                if let Ok(value) = self.edit_buffer.parse::<usize>()
+                    && value > 0
+                {
+                    self.settings.max_running_agents = value;
+                }
+                self.mode = SettingsMode::Normal;
+                self.edit_buffer.clear();
+            }
+            SettingsMode::EditingBaseSyncInterval => {
+                if let Ok(value) = self.edit_buffer.parse::<u64>() {
+                    self.settings.base_sync_interval_seconds = value;
+                }
+                self.mode = SettingsMode::Normal;
+                self.edit_buffer.clear();
+            }
+            SettingsMode::EditingProviderConfig { field } => {
+                let provider = self.settings.default_provider.clone();
+                let mut config = self.settings.provider_config(&provider);
+                field.apply(&mut config, &self.edit_buffer);
+                self.settings
+                    .provider_registry
+                    .configs
+                    .insert(provider, config);
+                self.mode = SettingsMode::Normal;
+                self.edit_buffer.clear();
+            }
         }
     }
 
@@ -633,11 +1067,14 @@ impl SettingsState {
             | SettingsMode::SelectingIde { .. }
             | SettingsMode::SelectingTerminal { .. }
             | SettingsMode::SelectingVcs { .. }
+            | SettingsMode::SelectingMergeStrategy { .. }
             | SettingsMode::ConfiguringPermissions { .. } => {}
-            SettingsMode::EditingShell { .. } => {
+            SettingsMode::EditingShell { .. } | SettingsMode::EditingProviderConfig { .. } => {
                 self.edit_buffer.push(character);
             }
-            SettingsMode::EditingAutoSave { .. } => {
+            SettingsMode::EditingAutoSave { .. }
+            | SettingsMode::EditingMaxRunningAgents
+            | SettingsMode::EditingBaseSyncInterval => {
                 if character.is_ascii_digit() {
                     self.edit_buffer.push(character);
                 }
@@ -660,7 +1097,7 @@ impl SettingsState {
         let config = preset.to_config();
         self.settings
             .permission_configs
-            .insert(self.settings.default_provider, config);
+            .insert(self.settings.default_provider.clone(), config);
         self.mode = SettingsMode::Normal;
     }
 }
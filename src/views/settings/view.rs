@@ -1,12 +1,13 @@
 use super::state::{
-    IdeCommand, SettingItem, SettingsFocus, SettingsMode, SettingsSection, SettingsState,
-    TerminalCommand, VcsCommand,
+    IdeCommand, MergeStrategy, SettingItem, SettingsFocus, SettingsMode, SettingsSection,
+    SettingsState, TerminalCommand, VcsCommand,
 };
-use crate::types::PermissionPreset;
-use crate::views::StatusBarContent;
+use crate::persistence::config::SettingSource;
+use crate::types::{KeyAction, KeyContext, Keymap, PermissionPreset};
 use crate::views::tasks::dialogs::{
     ProviderSelectionViewState, centered_rect, render_popup_background, render_provider_selection,
 };
+use crate::views::{StatusBarContent, picker_help};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Margin, Rect},
@@ -195,6 +196,7 @@ fn render_setting_item(
     let label = item.label();
     let value = get_setting_value_text(item, state);
     let item_type = get_item_type_indicator(item);
+    let source = state.setting_source(item);
 
     let label_style = if is_selected {
         Style::default()
@@ -230,6 +232,10 @@ fn render_setting_item(
         Span::styled("   ", Style::default()),
         Span::styled(value, value_style),
         Span::styled(format!(" {item_type}"), indicator_style),
+        Span::styled(
+            format!(" ({})", source.label()),
+            Style::default().fg(get_source_color(source)),
+        ),
     ]);
 
     let content = vec![line, value_line];
@@ -244,25 +250,50 @@ fn render_setting_item(
 
 const fn get_item_type_indicator(item: SettingItem) -> &'static str {
     match item {
-        SettingItem::DefaultShell => "[text]",
-        SettingItem::AutoSaveInterval => "[number]",
+        SettingItem::DefaultShell
+        | SettingItem::ProviderCommand
+        | SettingItem::ProviderArguments
+        | SettingItem::ProviderEnvironment
+        | SettingItem::ProviderWorkingDirectoryArgument => "[text]",
+        SettingItem::AutoSaveInterval
+        | SettingItem::BaseSyncInterval
+        | SettingItem::MaxRunningAgents
+        | SettingItem::ProviderMaxRunningAgents => "[number]",
         SettingItem::IdeCommand
         | SettingItem::TerminalCommand
         | SettingItem::VcsCommand
+        | SettingItem::MergeStrategy
         | SettingItem::DefaultProvider => "[select]",
         SettingItem::ProviderPermissions => "[configure]",
     }
 }
 
+/// Project values override global ones, which override the defaults.
+const fn get_source_color(source: SettingSource) -> Color {
+    match source {
+        SettingSource::Default => Color::DarkGray,
+        SettingSource::Global => Color::Blue,
+        SettingSource::Project => Color::Green,
+    }
+}
+
 fn get_setting_value_text(item: SettingItem, state: &SettingsState) -> String {
     match item {
         SettingItem::DefaultShell => state.settings.default_shell.clone(),
         SettingItem::AutoSaveInterval => {
             format!("{} seconds", state.settings.auto_save_interval_seconds)
         }
+        SettingItem::MaxRunningAgents => {
+            format!("{} running at once", state.settings.max_running_agents)
+        }
         SettingItem::IdeCommand => state.settings.ide_command.display_name().to_string(),
         SettingItem::TerminalCommand => state.settings.terminal_command.display_name().to_string(),
         SettingItem::VcsCommand => state.settings.vcs_command.display_name().to_string(),
+        SettingItem::MergeStrategy => state.settings.merge_strategy.display_name().to_string(),
+        SettingItem::BaseSyncInterval => match state.settings.base_sync_interval_seconds {
+            0 => "Off".to_string(),
+            seconds => format!("every {seconds} seconds"),
+        },
         SettingItem::DefaultProvider => state.settings.default_provider.display_name().to_string(),
         SettingItem::ProviderPermissions => {
             let config = state
@@ -274,6 +305,24 @@ fn get_setting_value_text(item: SettingItem, state: &SettingsState) -> String {
             let preset = PermissionPreset::from_config(&config);
             preset.display_name().to_string()
         }
+        SettingItem::ProviderCommand
+        | SettingItem::ProviderArguments
+        | SettingItem::ProviderEnvironment
+        | SettingItem::ProviderWorkingDirectoryArgument
+        | SettingItem::ProviderMaxRunningAgents => {
+            let Some(field) = item.provider_config_field() else {
+                return String::new();
+            };
+            let config = state
+                .settings
+                .provider_config(&state.settings.default_provider);
+            let value = field.display_value(&config);
+            if value.is_empty() {
+                "(none)".to_string()
+            } else {
+                value
+            }
+        }
     }
 }
 
@@ -282,7 +331,7 @@ fn render_dialogs(frame: &mut Frame, area: Rect, state: &SettingsState) {
         SettingsMode::SelectingProvider { selected_index } => {
             let dialog_state = ProviderSelectionViewState {
                 selected_index,
-                default_provider: state.settings.default_provider,
+                default_provider: &state.settings.default_provider,
                 detected_providers: &state.detected_providers,
             };
             render_provider_selection(frame, &dialog_state, area);
@@ -296,6 +345,9 @@ fn render_dialogs(frame: &mut Frame, area: Rect, state: &SettingsState) {
         SettingsMode::SelectingVcs { selected_index } => {
             render_vcs_selection_dialog(frame, area, state, selected_index);
         }
+        SettingsMode::SelectingMergeStrategy { selected_index } => {
+            render_merge_strategy_selection_dialog(frame, area, state, selected_index);
+        }
         SettingsMode::EditingShell { .. } => {
             render_text_input_dialog(frame, area, "Edit Default Shell", &state.edit_buffer);
         }
@@ -307,11 +359,30 @@ fn render_dialogs(frame: &mut Frame, area: Rect, state: &SettingsState) {
                 &state.edit_buffer,
             );
         }
+        SettingsMode::EditingMaxRunningAgents => {
+            render_text_input_dialog(
+                frame,
+                area,
+                "Edit Queue Limit (agents running at once)",
+                &state.edit_buffer,
+            );
+        }
+        SettingsMode::EditingBaseSyncInterval => {
+            render_text_input_dialog(
+                frame,
+                area,
+                "Edit Base Sync Interval (seconds, 0 for off)",
+                &state.edit_buffer,
+            );
+        }
         SettingsMode::ConfiguringPermissions {
             selected_preset_index,
         } => {
             render_permission_configuration_dialog(frame, area, state, selected_preset_index);
         }
+        SettingsMode::EditingProviderConfig { field } => {
+            render_text_input_dialog(frame, area, field.title(), &state.edit_buffer);
+        }
         SettingsMode::Normal => {}
     }
 }
@@ -469,6 +540,46 @@ fn render_vcs_selection_dialog(
     frame.render_widget(list, inner_area);
 }
 
+fn render_merge_strategy_selection_dialog(
+    frame: &mut Frame,
+    area: Rect,
+    state: &SettingsState,
+    selected_index: usize,
+) {
+    let popup_area = centered_rect(
+        SELECTION_POPUP_WIDTH_PERCENT,
+        SELECTION_POPUP_HEIGHT_PERCENT,
+        area,
+    );
+    render_popup_background(frame, popup_area);
+
+    let block = Block::default()
+        .title(" Select Merge Strategy ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .padding(Padding::uniform(1));
+
+    let inner_area = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let items: Vec<ListItem> = MergeStrategy::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, strategy)| {
+            render_selection_option(
+                strategy.display_name(),
+                strategy.description(),
+                index,
+                selected_index,
+                *strategy == state.settings.merge_strategy,
+            )
+        })
+        .collect();
+
+    let list = List::new(items);
+    frame.render_widget(list, inner_area);
+}
+
 const fn is_current_vcs(command: &VcsCommand, index: usize) -> bool {
     matches!(
         (command, index),
@@ -647,7 +758,11 @@ fn render_text_input_dialog(frame: &mut Frame, area: Rect, title: &str, value: &
 }
 
 #[must_use]
-pub fn get_status_bar_content(state: &SettingsState, width: u16) -> StatusBarContent {
+pub fn get_status_bar_content(
+    state: &SettingsState,
+    keymap: &Keymap,
+    width: u16,
+) -> StatusBarContent {
     let is_in_dialog = !matches!(state.mode, SettingsMode::Normal);
 
     let mode_color = if is_in_dialog {
@@ -664,27 +779,48 @@ pub fn get_status_bar_content(state: &SettingsState, width: u16) -> StatusBarCon
                 "CONTENT"
             }
         }
-        SettingsMode::EditingShell { .. } | SettingsMode::EditingAutoSave { .. } => "EDITING",
+        SettingsMode::EditingShell { .. }
+        | SettingsMode::EditingAutoSave { .. }
+        | SettingsMode::EditingMaxRunningAgents
+        | SettingsMode::EditingBaseSyncInterval
+        | SettingsMode::EditingProviderConfig { .. } => "EDITING",
         SettingsMode::SelectingProvider { .. }
         | SettingsMode::SelectingIde { .. }
         | SettingsMode::SelectingTerminal { .. }
         | SettingsMode::SelectingVcs { .. }
+        | SettingsMode::SelectingMergeStrategy { .. }
         | SettingsMode::ConfiguringPermissions { .. } => "SELECT",
     };
 
-    let help_text = if width < STATUS_BAR_WIDTH_THRESHOLD {
-        match state.mode {
-            SettingsMode::Normal => "jk:nav  Tab:focus  Enter:edit",
-            SettingsMode::SelectingProvider { .. }
+    let is_wide = width >= STATUS_BAR_WIDTH_THRESHOLD;
+    if !is_in_dialog {
+        return StatusBarContent {
+            mode_text: mode_text.to_string(),
+            mode_color,
+            extra_info: None,
+            help_text: normal_help(keymap, is_wide),
+        };
+    }
+
+    let is_picking = matches!(
+        state.mode,
+        SettingsMode::SelectingProvider { .. }
             | SettingsMode::SelectingIde { .. }
             | SettingsMode::SelectingTerminal { .. }
             | SettingsMode::SelectingVcs { .. }
-            | SettingsMode::ConfiguringPermissions { .. } => {
-                "jk:navigate  Enter:select  Esc:cancel"
-            }
-            _ => "Enter:confirm  Esc:cancel",
-        }
-    } else {
+            | SettingsMode::SelectingMergeStrategy { .. }
+            | SettingsMode::ConfiguringPermissions { .. }
+    );
+    if is_picking {
+        return StatusBarContent {
+            mode_text: mode_text.to_string(),
+            mode_color,
+            extra_info: None,
+            help_text: picker_help(keymap, "choose"),
+        };
+    }
+
+    let help_text = if is_wide {
         match state.mode {
             SettingsMode::EditingShell { .. } => {
                 "Enter: confirm  Esc: cancel  Type to edit shell path"
@@ -692,17 +828,19 @@ pub fn get_status_bar_content(state: &SettingsState, width: u16) -> StatusBarCon
             SettingsMode::EditingAutoSave { .. } => {
                 "Enter: confirm  Esc: cancel  Type numbers to set interval"
             }
-            SettingsMode::SelectingProvider { .. }
-            | SettingsMode::SelectingIde { .. }
-            | SettingsMode::SelectingTerminal { .. }
-            | SettingsMode::SelectingVcs { .. }
-            | SettingsMode::ConfiguringPermissions { .. } => {
-                "jk/arrows: navigate  Enter: select  Esc: cancel"
+            SettingsMode::EditingMaxRunningAgents => {
+                "Enter: confirm  Esc: cancel  Queued tasks wait while this many agents run"
+            }
+            SettingsMode::EditingBaseSyncInterval => {
+                "Enter: confirm  Esc: cancel  Idle task worktrees pull in the base branch this often"
             }
-            SettingsMode::Normal => {
-                "jk: navigate  Tab: switch focus  Enter/Space: edit  h/l: focus sidebar/content"
+            SettingsMode::EditingProviderConfig { .. } => {
+                "Enter: confirm  Esc: cancel  Applies to the default agent"
             }
+            _ => "",
         }
+    } else {
+        "Enter:confirm  Esc:cancel"
     };
 
     StatusBarContent {
@@ -712,3 +850,31 @@ pub fn get_status_bar_content(state: &SettingsState, width: u16) -> StatusBarCon
         help_text: help_text.to_string(),
     }
 }
+
+fn normal_help(keymap: &Keymap, is_wide: bool) -> String {
+    if is_wide {
+        keymap.help(
+            KeyContext::Settings,
+            &[
+                (&[KeyAction::MoveDown, KeyAction::MoveUp], "navigate"),
+                (&[KeyAction::SwitchPanel], "switch focus"),
+                (&[KeyAction::Open], "edit"),
+                (&[KeyAction::ResetToGlobal], "reset to global"),
+                (
+                    &[KeyAction::MoveLeft, KeyAction::MoveRight],
+                    "focus sidebar/content",
+                ),
+            ],
+        )
+    } else {
+        keymap.help(
+            KeyContext::Settings,
+            &[
+                (&[KeyAction::MoveDown, KeyAction::MoveUp], "nav"),
+                (&[KeyAction::SwitchPanel], "focus"),
+                (&[KeyAction::Open], "edit"),
+                (&[KeyAction::ResetToGlobal], "reset"),
+            ],
+        )
+    }
+}
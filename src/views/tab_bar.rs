@@ -1,3 +1,4 @@
+use super::mouse::{MouseRegions, MouseTarget};
 use crate::app::{App, Tab};
 use ratatui::{
     Frame,
@@ -7,6 +8,15 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
+const TABS: [Tab; 6] = [
+    Tab::Tasks,
+    Tab::Instances,
+    Tab::Roadmap,
+    Tab::Worktree,
+    Tab::PullRequests,
+    Tab::Settings,
+];
+
 const TAB_COLORS: [Color; 6] = [
     Color::LightBlue, // Tasks
     Color::Yellow,    // Instances
@@ -39,7 +49,7 @@ fn get_tab_name(tab_index: usize, vcs_command: &crate::views::settings::VcsComma
 
 const DIRECTORY_PADDING: u16 = 2;
 
-pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+pub fn render(frame: &mut Frame, app: &App, area: Rect, regions: &mut MouseRegions) {
     let current_directory = std::env::current_dir()
         .ok()
         .and_then(|path| path.to_str().map(String::from))
@@ -59,18 +69,14 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         .constraints([Constraint::Min(0), Constraint::Length(directory_width)])
         .split(inner);
 
-    let selected_index = match app.active_tab {
-        Tab::Tasks => 0,
-        Tab::Instances => 1,
-        Tab::Roadmap => 2,
-        Tab::Worktree => 3,
-        Tab::PullRequests => 4,
-        Tab::Settings => 5,
-    };
+    let selected_index = TABS
+        .iter()
+        .position(|tab| *tab == app.active_tab)
+        .unwrap_or_default();
 
     let vcs_command = &app.settings.settings.vcs_command;
-    let tab_spans: Vec<Span> = (0..6)
-        .flat_map(|index| {
+    let mut tab_spans: Vec<Span> = (0..TABS.len())
+        .map(|index| {
             let is_selected = index == selected_index;
             let tab_number = index + 1;
             let name = get_tab_name(index, vcs_command);
@@ -89,10 +95,43 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
                 Style::default().fg(color)
             };
 
-            vec![Span::styled(format!(" {tab_number}:{name} "), tab_style)]
+            Span::styled(format!(" {tab_number}:{name} "), tab_style)
         })
         .collect();
 
+    let mut tab_x = layout[0].x;
+    for (tab, span) in TABS.into_iter().zip(&tab_spans) {
+        let width = u16::try_from(span.width()).unwrap_or(u16::MAX);
+        let tab_area = Rect {
+            x: tab_x,
+            width,
+            height: 1,
+            ..layout[0]
+        }
+        .intersection(layout[0]);
+        regions.push(tab_area, MouseTarget::Tab(tab));
+        tab_x = tab_x.saturating_add(width);
+    }
+
+    let pending_permissions = app.permissions.pending_count();
+    if pending_permissions > 0 {
+        tab_spans.push(Span::styled(
+            format!("  ! {pending_permissions} permission request(s) "),
+            Style::default()
+                .fg(crate::views::permissions::view::PERMISSION_COLOR)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if app.read_only {
+        tab_spans.push(Span::styled(
+            "  READ-ONLY ",
+            Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
     let tabs_line = Paragraph::new(Line::from(tab_spans));
 
     let directory_display = Paragraph::new(Line::from(vec![
@@ -1,7 +1,8 @@
 use crate::events::AppEvent;
-use crate::providers;
+use crate::providers::ProviderSpec;
 use crate::types::{AgentProvider, Result};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::thread;
 use tokio::sync::mpsc;
 use uuid::Uuid;
@@ -17,10 +18,11 @@ pub fn spawn_classification(
     raw_input: String,
     task_id: Uuid,
     provider: AgentProvider,
+    spec: Cow<'static, ProviderSpec>,
     event_sender: mpsc::UnboundedSender<AppEvent>,
 ) {
     thread::spawn(move || {
-        let result = classify_with_provider(&raw_input, provider);
+        let result = classify_with_provider(&raw_input, &provider, &spec);
         let event_result = result.map_err(|error| error.to_string());
         let _ = event_sender.send(AppEvent::ClassificationCompleted {
             task_id,
@@ -29,7 +31,11 @@ pub fn spawn_classification(
     });
 }
 
-fn classify_with_provider(raw_input: &str, provider: AgentProvider) -> Result<ClassifiedTask> {
+fn classify_with_provider(
+    raw_input: &str,
+    provider: &AgentProvider,
+    spec: &ProviderSpec,
+) -> Result<ClassifiedTask> {
     let prompt = format!(
         r#"Classify this task description and respond with ONLY valid JSON (no markdown, no explanation):
 
@@ -51,7 +57,6 @@ Rules:
 Output JSON only:"#
     );
 
-    let spec = providers::get_spec(provider);
     let command = spec.build_oneshot_command(&prompt);
 
     let mut process_command = std::process::Command::new(&command.program);
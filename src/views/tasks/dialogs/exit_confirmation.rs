@@ -1,4 +1,5 @@
 use super::{centered_rect, render_popup_background};
+use crate::types::{KeyAction, KeyContext, Keymap};
 use ratatui::{
     Frame,
     layout::{Alignment, Rect},
@@ -14,7 +15,7 @@ const DIALOG_PADDING: u16 = 2;
 const DIALOG_VERTICAL_PADDING: u16 = 3;
 const DIALOG_PADDING_DOUBLE: u16 = 4;
 
-pub fn render_exit_confirmation_dialog(frame: &mut Frame, area: Rect) {
+pub fn render_exit_confirmation_dialog(frame: &mut Frame, area: Rect, keymap: &Keymap) {
     let dialog_area = centered_rect(
         EXIT_CONFIRM_DIALOG_WIDTH_PERCENT,
         EXIT_CONFIRM_DIALOG_HEIGHT_PERCENT,
@@ -58,19 +59,14 @@ pub fn render_exit_confirmation_dialog(frame: &mut Frame, area: Rect) {
         Line::from(vec![
             Span::styled("Press ", Style::default().fg(Color::Gray)),
             Span::styled(
-                "Y",
+                first_chord(keymap, KeyAction::Confirm),
                 Style::default()
                     .fg(Color::Green)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(" to confirm or ", Style::default().fg(Color::Gray)),
             Span::styled(
-                "N",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("/", Style::default().fg(Color::Gray)),
-            Span::styled(
-                "Esc",
+                first_chord(keymap, KeyAction::Cancel),
                 Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
             ),
             Span::styled(" to cancel", Style::default().fg(Color::Gray)),
@@ -83,3 +79,11 @@ pub fn render_exit_confirmation_dialog(frame: &mut Frame, area: Rect) {
 
     frame.render_widget(text, inner_area);
 }
+
+fn first_chord(keymap: &Keymap, action: KeyAction) -> String {
+    keymap
+        .chords(KeyContext::Dialog, action)
+        .first()
+        .map(ToString::to_string)
+        .unwrap_or_default()
+}
@@ -1,5 +1,7 @@
 use super::{centered_rect, render_popup_background};
-use crate::views::tasks::state::MergeTarget;
+use crate::types::{KeyAction, KeyContext, Keymap};
+use crate::views::settings::MergeStrategy;
+use crate::views::tasks::state::{MergeField, MergeTarget};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -9,12 +11,36 @@ use ratatui::{
 };
 
 const DIALOG_WIDTH_PERCENT: u16 = 50;
-const DIALOG_HEIGHT_PERCENT: u16 = 30;
+const DIALOG_HEIGHT_PERCENT: u16 = 50;
+
+const SQUASH_SUBJECT_PLACEHOLDER: &str = "task title, then the agent's commit summaries";
+
+pub struct MergeConfirmationViewState<'a> {
+    pub worktree_branch: &'a str,
+    pub selected_target: &'a MergeTarget,
+    pub strategy: MergeStrategy,
+    pub focused_field: MergeField,
+    pub squash_subject: &'a str,
+    pub keymap: &'a Keymap,
+}
+
+/// Help for the merge dialog, shown in it and in the footer.
+#[must_use]
+pub fn merge_help(keymap: &Keymap) -> String {
+    keymap.help(
+        KeyContext::Merge,
+        &[
+            (&[KeyAction::NextPane], "next-field"),
+            (&[KeyAction::MoveDown, KeyAction::MoveUp], "change"),
+            (&[KeyAction::Open], "merge"),
+            (&[KeyAction::Exit], "cancel"),
+        ],
+    )
+}
 
 pub fn render_merge_confirmation(
     frame: &mut Frame,
-    worktree_branch: &str,
-    selected_target: &MergeTarget,
+    state: &MergeConfirmationViewState<'_>,
     area: Rect,
 ) {
     let dialog_area = centered_rect(DIALOG_WIDTH_PERCENT, DIALOG_HEIGHT_PERCENT, area);
@@ -38,6 +64,10 @@ pub fn render_merge_confirmation(
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Min(0),
             Constraint::Length(2),
@@ -47,7 +77,7 @@ pub fn render_merge_confirmation(
     let header = Paragraph::new(Line::from(vec![
         Span::styled("Merge ", Style::default().fg(Color::White)),
         Span::styled(
-            format!("'{worktree_branch}'"),
+            format!("'{}'", state.worktree_branch),
             Style::default()
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
@@ -58,7 +88,24 @@ pub fn render_merge_confirmation(
 
     frame.render_widget(header, chunks[0]);
 
-    let current_branch_name = match selected_target {
+    render_target_options(frame, state, chunks[1]);
+    render_strategy(frame, state, chunks[3]);
+    if state.strategy == MergeStrategy::Squash {
+        render_squash_subject(frame, state, chunks[5]);
+    }
+
+    let footer = Paragraph::new(Line::from(Span::styled(
+        merge_help(state.keymap),
+        Style::default().fg(Color::DarkGray),
+    )))
+    .alignment(Alignment::Center);
+
+    frame.render_widget(footer, chunks[7]);
+}
+
+fn render_target_options(frame: &mut Frame, state: &MergeConfirmationViewState<'_>, area: Rect) {
+    let is_focused = state.focused_field == MergeField::Target;
+    let current_branch_name = match state.selected_target {
         MergeTarget::CurrentBranch(name) => Some(name.as_str()),
         MergeTarget::MainBranch => None,
     };
@@ -68,43 +115,89 @@ pub fn render_merge_confirmation(
     let mut options = Vec::new();
 
     if let Some(label) = &current_option_label {
-        let is_selected = matches!(selected_target, MergeTarget::CurrentBranch(_));
-        options.push(render_option(label, is_selected));
+        let is_selected = matches!(state.selected_target, MergeTarget::CurrentBranch(_));
+        options.push(render_option(label, is_selected, is_focused));
     }
 
-    let main_selected = matches!(selected_target, MergeTarget::MainBranch);
-    options.push(render_option("main", main_selected));
+    let main_selected = matches!(state.selected_target, MergeTarget::MainBranch);
+    options.push(render_option("main", main_selected, is_focused));
 
     let options_area = Layout::default()
         .direction(Direction::Vertical)
         .constraints(vec![Constraint::Length(1); options.len()])
-        .split(chunks[2]);
+        .split(area);
 
     for (index, option) in options.into_iter().enumerate() {
         if index < options_area.len() {
             frame.render_widget(option, options_area[index]);
         }
     }
+}
 
-    let footer = Paragraph::new(Line::from(Span::styled(
-        "j/k: select  Enter: merge  Esc: cancel",
-        Style::default().fg(Color::DarkGray),
-    )))
-    .alignment(Alignment::Center);
+fn render_strategy(frame: &mut Frame, state: &MergeConfirmationViewState<'_>, area: Rect) {
+    let is_focused = state.focused_field == MergeField::Strategy;
+    let value_style = if is_focused {
+        Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
 
-    frame.render_widget(footer, chunks[3]);
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Strategy: ", Style::default().fg(Color::White)),
+            Span::styled(
+                format!("< {} >", state.strategy.display_name()),
+                value_style,
+            ),
+        ]),
+        Line::from(Span::styled(
+            state.strategy.description(),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), area);
 }
 
-fn render_option(label: &str, is_selected: bool) -> Paragraph<'_> {
-    let (prefix, style) = if is_selected {
-        (
+fn render_squash_subject(frame: &mut Frame, state: &MergeConfirmationViewState<'_>, area: Rect) {
+    let is_focused = state.focused_field == MergeField::SquashSubject;
+    let mut spans = vec![Span::styled("Message: ", Style::default().fg(Color::White))];
+
+    if state.squash_subject.is_empty() && !is_focused {
+        spans.push(Span::styled(
+            SQUASH_SUBJECT_PLACEHOLDER,
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else {
+        let style = if is_focused {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        spans.push(Span::styled(state.squash_subject, style));
+        if is_focused {
+            spans.push(Span::styled("▌", Style::default().fg(Color::Green)));
+        }
+    }
+
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).alignment(Alignment::Center),
+        area,
+    );
+}
+
+fn render_option(label: &str, is_selected: bool, is_focused: bool) -> Paragraph<'_> {
+    let (prefix, style) = match (is_selected, is_focused) {
+        (true, true) => (
             "> ",
             Style::default()
                 .fg(Color::Green)
                 .add_modifier(Modifier::BOLD),
-        )
-    } else {
-        ("  ", Style::default().fg(Color::DarkGray))
+        ),
+        (true, false) => ("> ", Style::default().fg(Color::White)),
+        (false, _) => ("  ", Style::default().fg(Color::DarkGray)),
     };
 
     Paragraph::new(Line::from(vec![
@@ -7,14 +7,17 @@ mod worktree_selection;
 
 pub use add_task::{AddTaskDialogState, render_add_task_dialog};
 pub use exit_confirmation::render_exit_confirmation_dialog;
-pub use merge_confirmation::render_merge_confirmation;
+pub use merge_confirmation::{MergeConfirmationViewState, merge_help, render_merge_confirmation};
 pub use provider_selection::{
     ProviderSelectionResult, ProviderSelectionViewState, get_option_count, get_selection_result,
     render_provider_selection,
 };
-pub use review::{ReviewPopupViewState, render_review_popup};
+pub use review::{ReviewPopupViewState, render_review_popup, review_help, review_terminal};
 pub use worktree_selection::{WorktreeSelectionViewState, render_worktree_selection};
 
+use crate::types::Keymap;
+use crate::views::confirm_keys;
+use crate::widgets::dialogs::{ConfirmDialog, DialogStyle};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -47,3 +50,18 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
 pub fn render_popup_background(frame: &mut Frame, area: Rect) {
     frame.render_widget(Clear, area);
 }
+
+/// Asks `question` in a danger dialog, followed by the keys that answer it.
+pub fn render_danger_confirmation(
+    frame: &mut Frame,
+    title: &str,
+    question: &str,
+    keymap: &Keymap,
+    area: Rect,
+) {
+    let message = format!("{question} {}", confirm_keys(keymap));
+    frame.render_widget(
+        ConfirmDialog::new(title, &message).style(DialogStyle::Danger),
+        area,
+    );
+}
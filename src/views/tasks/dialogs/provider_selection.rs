@@ -14,11 +14,11 @@ const POPUP_HEIGHT_PERCENT: u16 = 50;
 
 pub struct ProviderSelectionViewState<'a> {
     pub selected_index: usize,
-    pub default_provider: AgentProvider,
+    pub default_provider: &'a AgentProvider,
     pub detected_providers: &'a [DetectedProvider],
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ProviderSelectionResult {
     Provider(AgentProvider),
     ProviderAndRemember(AgentProvider),
@@ -26,14 +26,14 @@ pub enum ProviderSelectionResult {
 
 impl ProviderSelectionResult {
     #[must_use]
-    pub const fn provider(self) -> AgentProvider {
+    pub fn provider(self) -> AgentProvider {
         match self {
             Self::Provider(provider) | Self::ProviderAndRemember(provider) => provider,
         }
     }
 
     #[must_use]
-    pub const fn should_remember(self) -> bool {
+    pub const fn should_remember(&self) -> bool {
         matches!(self, Self::ProviderAndRemember(_))
     }
 }
@@ -42,16 +42,16 @@ impl ProviderSelectionResult {
 pub fn get_selection_result(
     selected_index: usize,
     detected_providers: &[DetectedProvider],
-    default_provider: AgentProvider,
+    default_provider: &AgentProvider,
 ) -> Option<ProviderSelectionResult> {
     let provider_count = detected_providers.len();
 
     match selected_index.cmp(&provider_count) {
         std::cmp::Ordering::Less => Some(ProviderSelectionResult::Provider(
-            detected_providers[selected_index].provider,
+            detected_providers[selected_index].provider.clone(),
         )),
         std::cmp::Ordering::Equal => Some(ProviderSelectionResult::ProviderAndRemember(
-            default_provider,
+            default_provider.clone(),
         )),
         std::cmp::Ordering::Greater => None,
     }
@@ -117,10 +117,10 @@ fn render_provider_option(
     index: usize,
     detected: &DetectedProvider,
     selected_index: usize,
-    default_provider: AgentProvider,
+    default_provider: &AgentProvider,
 ) -> ListItem<'static> {
     let is_selected = index == selected_index;
-    let is_default = detected.provider == default_provider;
+    let is_default = detected.provider == *default_provider;
 
     let mut name_spans = vec![Span::styled(
         detected.provider.display_name().to_string(),
@@ -158,7 +158,7 @@ fn render_provider_option(
 fn render_remember_option(
     index: usize,
     selected_index: usize,
-    default_provider: AgentProvider,
+    default_provider: &AgentProvider,
 ) -> ListItem<'static> {
     let is_selected = index == selected_index;
 
@@ -1,10 +1,14 @@
 use super::diff;
 use crate::app::App;
+use crate::views::settings::VcsCommand;
+use crate::views::worktree::{
+    ChangedFile, ChangedFileKind, get_changed_files, read_committed_file,
+};
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
 };
-use std::{fs, path::Path, process::Command};
+use std::{fs, path::Path};
 use uuid::Uuid;
 
 const MAX_DIFF_LINES_PER_FILE: usize = 120;
@@ -17,55 +21,6 @@ const ORIGINAL_LABEL_PREFIX: &str = "original/";
 const UPDATED_LABEL_PREFIX: &str = "updated/";
 const SUMMARY_PREFIX: &str = "Changes: +";
 const SUMMARY_SEPARATOR: &str = " -";
-const STATUS_CODE_LENGTH: usize = 2;
-const STATUS_PATH_OFFSET: usize = 3;
-const STATUS_RENAME_SEPARATOR: &str = " -> ";
-const STATUS_UNTRACKED: &str = "??";
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ChangedFileKind {
-    Modified,
-    Added,
-    Deleted,
-    Renamed,
-    Copied,
-    Untracked,
-    Conflict,
-}
-
-impl ChangedFileKind {
-    #[must_use]
-    pub const fn color(self) -> Color {
-        match self {
-            Self::Modified => Color::Yellow,
-            Self::Added | Self::Untracked => Color::Green,
-            Self::Deleted => Color::Red,
-            Self::Renamed => Color::Cyan,
-            Self::Copied => Color::Blue,
-            Self::Conflict => Color::LightRed,
-        }
-    }
-
-    #[must_use]
-    pub const fn label(self) -> &'static str {
-        match self {
-            Self::Modified => "Modified",
-            Self::Added => "Added",
-            Self::Deleted => "Deleted",
-            Self::Renamed => "Renamed",
-            Self::Copied => "Copied",
-            Self::Untracked => "Untracked",
-            Self::Conflict => "Conflict",
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-pub struct ChangedFile {
-    pub path: String,
-    pub original_path: Option<String>,
-    pub kind: ChangedFileKind,
-}
 
 pub struct DiffPanelState {
     pub files: Vec<ChangedFile>,
@@ -79,14 +34,15 @@ pub fn build_diff_panel(app: &App, task_id: Uuid, selected_index: usize) -> Diff
         return empty_diff_panel(TASK_NOT_FOUND_MESSAGE);
     };
 
-    let files = load_changed_files(&worktree_path);
+    let vcs_command = &app.settings.settings.vcs_command;
+    let files = get_changed_files(&worktree_path, vcs_command).unwrap_or_default();
     if files.is_empty() {
         return empty_diff_panel(NO_CHANGES_MESSAGE);
     }
 
     let selected_index = selected_index.min(files.len().saturating_sub(1));
     let selected_file = &files[selected_index];
-    let lines = build_selected_file_diff_lines(&worktree_path, selected_file);
+    let lines = build_selected_file_diff_lines(&worktree_path, selected_file, vcs_command);
 
     DiffPanelState {
         files,
@@ -139,90 +95,12 @@ fn empty_diff_panel(message: &str) -> DiffPanelState {
     }
 }
 
-fn load_changed_files(worktree_path: &Path) -> Vec<ChangedFile> {
-    let output = Command::new("git")
-        .arg("status")
-        .arg("--porcelain")
-        .current_dir(worktree_path)
-        .output();
-
-    let Ok(output) = output else {
-        return Vec::new();
-    };
-
-    if !output.status.success() {
-        return Vec::new();
-    }
-
-    let status_text = String::from_utf8_lossy(&output.stdout);
-    let mut files = Vec::new();
-
-    for line in status_text.lines() {
-        if let Some(file) = parse_status_line(line) {
-            files.push(file);
-        }
-    }
-
-    files
-}
-
-fn parse_status_line(line: &str) -> Option<ChangedFile> {
-    if line.len() < STATUS_PATH_OFFSET {
-        return None;
-    }
-
-    let status_code = &line[..STATUS_CODE_LENGTH];
-    let path_segment = &line[STATUS_PATH_OFFSET..];
-    let kind = parse_status_kind(status_code);
-    let (path, original_path) = split_status_path(path_segment, kind);
-
-    Some(ChangedFile {
-        path,
-        original_path,
-        kind,
-    })
-}
-
-fn parse_status_kind(status_code: &str) -> ChangedFileKind {
-    if status_code == STATUS_UNTRACKED {
-        return ChangedFileKind::Untracked;
-    }
-
-    if status_code.contains('U') {
-        return ChangedFileKind::Conflict;
-    }
-
-    if status_code.contains('R') {
-        return ChangedFileKind::Renamed;
-    }
-
-    if status_code.contains('C') {
-        return ChangedFileKind::Copied;
-    }
-
-    if status_code.contains('D') {
-        return ChangedFileKind::Deleted;
-    }
-
-    if status_code.contains('A') {
-        return ChangedFileKind::Added;
-    }
-
-    ChangedFileKind::Modified
-}
-
-fn split_status_path(path_segment: &str, kind: ChangedFileKind) -> (String, Option<String>) {
-    if matches!(kind, ChangedFileKind::Renamed | ChangedFileKind::Copied)
-        && let Some((original, updated)) = path_segment.split_once(STATUS_RENAME_SEPARATOR)
-    {
-        return (updated.to_string(), Some(original.to_string()));
-    }
-
-    (path_segment.to_string(), None)
-}
-
-fn build_selected_file_diff_lines(worktree_path: &Path, file: &ChangedFile) -> Vec<Line<'static>> {
-    let contents = resolve_diff_contents(worktree_path, file);
+fn build_selected_file_diff_lines(
+    worktree_path: &Path,
+    file: &ChangedFile,
+    vcs_command: &VcsCommand,
+) -> Vec<Line<'static>> {
+    let contents = resolve_diff_contents(worktree_path, file, vcs_command);
     if contents.is_unreadable {
         return vec![message_line(UNREADABLE_FILE_MESSAGE, Color::DarkGray)];
     }
@@ -257,13 +135,17 @@ struct DiffContents {
     is_unreadable: bool,
 }
 
-fn resolve_diff_contents(worktree_path: &Path, file: &ChangedFile) -> DiffContents {
+fn resolve_diff_contents(
+    worktree_path: &Path,
+    file: &ChangedFile,
+    vcs_command: &VcsCommand,
+) -> DiffContents {
     let original_path = file.original_path.as_deref().unwrap_or(&file.path);
     let updated_path = &file.path;
 
     let original_content = match file.kind {
         ChangedFileKind::Added | ChangedFileKind::Untracked => Some(String::new()),
-        _ => read_repository_file(worktree_path, Path::new(original_path)),
+        _ => read_committed_file(worktree_path, Path::new(original_path), vcs_command),
     };
 
     let updated_content = match file.kind {
@@ -363,22 +245,6 @@ fn read_worktree_file(worktree_path: &Path, relative_path: &Path) -> Option<Stri
     String::from_utf8(content).ok()
 }
 
-fn read_repository_file(worktree_path: &Path, relative_path: &Path) -> Option<String> {
-    let file_reference = format!("HEAD:{}", relative_path.to_string_lossy());
-    let output = Command::new("git")
-        .arg("show")
-        .arg(file_reference)
-        .current_dir(worktree_path)
-        .output()
-        .ok()?;
-
-    if !output.status.success() {
-        return None;
-    }
-
-    String::from_utf8(output.stdout).ok()
-}
-
 struct ListWindowRange {
     start: usize,
     length: usize,
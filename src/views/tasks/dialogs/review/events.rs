@@ -1,7 +1,11 @@
+use crate::types::{KeyAction, KeyContext, Keymap};
+use crate::views::settings::{MergeStrategy, VcsCommand};
 use crate::views::tasks::events::TasksAction;
-use crate::views::tasks::state::{MergeTarget, ReviewAction, ReviewPanel, TasksMode, TasksState};
+use crate::views::tasks::state::{
+    MergeField, MergeTarget, ReviewAction, ReviewPanel, TasksMode, TasksState,
+};
 use crate::views::worktree::{find_repository_root, get_current_branch, get_worktree_status};
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use uuid::Uuid;
 
 const SCROLL_STEP_LINES: usize = 1;
@@ -25,36 +29,78 @@ struct ScrollRequest {
 
 pub fn handle_review_popup_mode(
     state: &mut TasksState,
-    key: KeyEvent,
+    action: Option<KeyAction>,
     popup_state: ReviewPopupState,
+    vcs_command: &VcsCommand,
+    merge_strategy: MergeStrategy,
 ) -> TasksAction {
     let mut popup_state = popup_state;
-
-    if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
-        state.mode = TasksMode::Normal;
+    let Some(action) = action else {
         return TasksAction::None;
-    }
-
-    if key.code == KeyCode::Enter {
-        return execute_review_action(state, popup_state.task_id, popup_state.selected_action);
-    }
+    };
 
-    if let Some(panel) = panel_for_key(key.code, popup_state.focused_panel) {
-        popup_state.focused_panel = panel;
-        return apply_review_popup_state(state, popup_state);
+    match action {
+        KeyAction::Exit => {
+            state.mode = TasksMode::Normal;
+            return TasksAction::None;
+        }
+        KeyAction::Open => {
+            return execute_review_action(
+                state,
+                popup_state.task_id,
+                popup_state.selected_action,
+                vcs_command,
+                merge_strategy,
+            );
+        }
+        KeyAction::NextPane => popup_state.focused_panel = next_panel(popup_state.focused_panel),
+        KeyAction::PreviousPane => {
+            popup_state.focused_panel = previous_panel(popup_state.focused_panel);
+        }
+        KeyAction::MoveLeft => {
+            popup_state.selected_action = previous_action(popup_state.selected_action);
+        }
+        KeyAction::MoveRight => {
+            popup_state.selected_action = next_action(popup_state.selected_action);
+        }
+        action => {
+            let Some(scroll_request) = scroll_request_for_action(action) else {
+                return TasksAction::None;
+            };
+            popup_state.apply_scroll_request(scroll_request);
+        }
     }
 
-    if let Some(scroll_request) = scroll_request_for_key(key.code) {
-        popup_state.apply_scroll_request(scroll_request);
-        return apply_review_popup_state(state, popup_state);
-    }
+    apply_review_popup_state(state, popup_state)
+}
 
-    if let Some(action) = action_for_key(key.code, popup_state.selected_action) {
-        popup_state.selected_action = action;
-        return apply_review_popup_state(state, popup_state);
-    }
+/// Scrolls the focused panel of the review popup one step, as the mouse wheel does.
+pub fn scroll_review_popup(state: &mut TasksState, action: KeyAction) {
+    let TasksMode::ReviewPopup {
+        task_id,
+        diff_scroll_offset,
+        output_scroll_offset,
+        selected_file_index,
+        focused_panel,
+        selected_action,
+    } = state.mode
+    else {
+        return;
+    };
+    let Some(scroll_request) = scroll_request_for_action(action) else {
+        return;
+    };
 
-    TasksAction::None
+    let mut popup_state = ReviewPopupState {
+        task_id,
+        diff_scroll_offset,
+        output_scroll_offset,
+        selected_file_index,
+        focused_panel,
+        selected_action,
+    };
+    popup_state.apply_scroll_request(scroll_request);
+    apply_review_popup_state(state, popup_state);
 }
 
 impl ReviewPopupState {
@@ -88,29 +134,21 @@ fn apply_review_popup_state(state: &mut TasksState, popup_state: ReviewPopupStat
     TasksAction::None
 }
 
-const fn panel_for_key(key: KeyCode, focused_panel: ReviewPanel) -> Option<ReviewPanel> {
-    match key {
-        KeyCode::Tab => Some(next_panel(focused_panel)),
-        KeyCode::BackTab => Some(previous_panel(focused_panel)),
-        _ => None,
-    }
-}
-
-const fn scroll_request_for_key(key: KeyCode) -> Option<ScrollRequest> {
-    match key {
-        KeyCode::Char('j') | KeyCode::Down => Some(ScrollRequest {
+const fn scroll_request_for_action(action: KeyAction) -> Option<ScrollRequest> {
+    match action {
+        KeyAction::MoveDown => Some(ScrollRequest {
             step: SCROLL_STEP_LINES,
             is_forward: true,
         }),
-        KeyCode::Char('k') | KeyCode::Up => Some(ScrollRequest {
+        KeyAction::MoveUp => Some(ScrollRequest {
             step: SCROLL_STEP_LINES,
             is_forward: false,
         }),
-        KeyCode::PageDown => Some(ScrollRequest {
+        KeyAction::PageDown => Some(ScrollRequest {
             step: PAGE_SCROLL_LINES,
             is_forward: true,
         }),
-        KeyCode::PageUp => Some(ScrollRequest {
+        KeyAction::PageUp => Some(ScrollRequest {
             step: PAGE_SCROLL_LINES,
             is_forward: false,
         }),
@@ -118,14 +156,6 @@ const fn scroll_request_for_key(key: KeyCode) -> Option<ScrollRequest> {
     }
 }
 
-fn action_for_key(key: KeyCode, selected_action: ReviewAction) -> Option<ReviewAction> {
-    match key {
-        KeyCode::Left | KeyCode::Char('h') => Some(previous_action(selected_action)),
-        KeyCode::Right | KeyCode::Char('l') => Some(next_action(selected_action)),
-        _ => None,
-    }
-}
-
 const fn update_offset(offset: usize, step: usize, is_forward: bool) -> usize {
     if is_forward {
         offset.saturating_add(step)
@@ -178,12 +208,14 @@ fn execute_review_action(
     state: &mut TasksState,
     task_id: Uuid,
     action: ReviewAction,
+    vcs_command: &VcsCommand,
+    merge_strategy: MergeStrategy,
 ) -> TasksAction {
     let task = state.find_task_by_id(task_id);
     let worktree_info = task.and_then(|task| task.worktree_info.clone());
     let is_clean = worktree_info
         .as_ref()
-        .and_then(|info| get_worktree_status(&info.worktree_path).ok())
+        .and_then(|info| get_worktree_status(&info.worktree_path, vcs_command).ok())
         .is_none_or(|status| status.is_clean);
 
     match action {
@@ -193,9 +225,15 @@ fn execute_review_action(
         }
         ReviewAction::RequestChanges => begin_request_changes(state, task_id),
         ReviewAction::CommitChanges => commit_review_changes(state, task_id, is_clean),
-        ReviewAction::MergeAndComplete => {
-            begin_merge_confirmation(state, task_id, is_clean, worktree_info)
-        }
+        ReviewAction::SyncWithBase => sync_review_with_base(state, task_id, is_clean),
+        ReviewAction::MergeAndComplete => begin_merge_confirmation(
+            state,
+            task_id,
+            is_clean,
+            worktree_info,
+            vcs_command,
+            merge_strategy,
+        ),
     }
 }
 
@@ -220,11 +258,21 @@ fn commit_review_changes(state: &mut TasksState, task_id: Uuid, is_clean: bool)
     finalize_review_action(state, TasksAction::CommitChanges(task_id))
 }
 
+fn sync_review_with_base(state: &mut TasksState, task_id: Uuid, is_clean: bool) -> TasksAction {
+    if !is_clean {
+        return TasksAction::None;
+    }
+
+    finalize_review_action(state, TasksAction::SyncWithBase(task_id))
+}
+
 fn begin_merge_confirmation(
     state: &mut TasksState,
     task_id: Uuid,
     is_clean: bool,
     worktree_info: Option<crate::views::worktree::WorktreeInfo>,
+    vcs_command: &VcsCommand,
+    strategy: MergeStrategy,
 ) -> TasksAction {
     if !is_clean {
         return TasksAction::None;
@@ -233,18 +281,21 @@ fn begin_merge_confirmation(
     let worktree_branch = worktree_info
         .map(|info| info.branch_name)
         .unwrap_or_default();
-    let selected_target = resolve_merge_target();
+    let selected_target = resolve_merge_target(vcs_command);
 
     state.mode = TasksMode::MergeConfirmation {
         task_id,
         worktree_branch,
         selected_target,
+        strategy,
+        focused_field: MergeField::Target,
+        squash_subject: String::new(),
     };
     TasksAction::None
 }
 
-fn resolve_merge_target() -> MergeTarget {
-    let current_branch = resolve_current_branch();
+fn resolve_merge_target(vcs_command: &VcsCommand) -> MergeTarget {
+    let current_branch = resolve_current_branch(vcs_command);
 
     if current_branch == "main" || current_branch == "master" {
         MergeTarget::MainBranch
@@ -253,11 +304,11 @@ fn resolve_merge_target() -> MergeTarget {
     }
 }
 
-fn resolve_current_branch() -> String {
+fn resolve_current_branch(vcs_command: &VcsCommand) -> String {
     std::env::current_dir()
         .ok()
         .and_then(|dir| find_repository_root(&dir).ok())
-        .and_then(|root| get_current_branch(&root).ok())
+        .and_then(|root| get_current_branch(&root, vcs_command).ok())
         .unwrap_or_else(|| "main".to_string())
 }
 
@@ -299,50 +350,127 @@ pub fn handle_review_request_changes_mode(
     }
 }
 
+/// Picks the target branch, the strategy and, when squashing, the commit's subject. Tab moves
+/// between them, and the arrows change the target and strategy.
 pub fn handle_merge_confirmation_mode(
     state: &mut TasksState,
     key: KeyEvent,
-    task_id: Uuid,
-    worktree_branch: String,
-    selected_target: MergeTarget,
+    keymap: &Keymap,
+    vcs_command: &VcsCommand,
 ) -> TasksAction {
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('q') => {
-            state.mode = TasksMode::Normal;
-            TasksAction::None
+    let TasksMode::MergeConfirmation {
+        task_id,
+        selected_target,
+        strategy,
+        focused_field,
+        squash_subject,
+        ..
+    } = &mut state.mode
+    else {
+        return TasksAction::None;
+    };
+
+    if *focused_field == MergeField::SquashSubject {
+        match key.code {
+            KeyCode::Backspace => {
+                squash_subject.pop();
+                return TasksAction::None;
+            }
+            KeyCode::Char(character) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                squash_subject.push(character);
+                return TasksAction::None;
+            }
+            _ => {}
         }
-        KeyCode::Up | KeyCode::Down | KeyCode::Char('k' | 'j') => {
-            let current_branch = std::env::current_dir()
-                .ok()
-                .and_then(|dir| find_repository_root(&dir).ok())
-                .and_then(|root| get_current_branch(&root).ok())
-                .unwrap_or_else(|| "main".to_string());
-
-            let new_target = match &selected_target {
-                MergeTarget::CurrentBranch(_) => MergeTarget::MainBranch,
-                MergeTarget::MainBranch => {
-                    if current_branch == "main" || current_branch == "master" {
-                        MergeTarget::MainBranch
-                    } else {
-                        MergeTarget::CurrentBranch(current_branch)
-                    }
-                }
-            };
+    }
 
-            state.mode = TasksMode::MergeConfirmation {
-                task_id,
-                worktree_branch,
-                selected_target: new_target,
+    match keymap.action(KeyContext::Merge, key) {
+        Some(KeyAction::Exit) => state.mode = TasksMode::Normal,
+        Some(KeyAction::Open) => {
+            let subject = squash_subject.trim();
+            let action = TasksAction::MergeBranch {
+                task_id: *task_id,
+                target: selected_target.clone(),
+                strategy: *strategy,
+                squash_subject: (!subject.is_empty()).then(|| subject.to_string()),
             };
-            TasksAction::None
-        }
-        KeyCode::Enter => {
             state.mode = TasksMode::Normal;
-            TasksAction::MergeBranch {
-                task_id,
-                target: selected_target,
+            return action;
+        }
+        Some(KeyAction::NextPane) => *focused_field = focused_field.next(*strategy),
+        Some(KeyAction::PreviousPane) => *focused_field = focused_field.previous(*strategy),
+        Some(action @ (KeyAction::MoveUp | KeyAction::MoveDown)) => match focused_field {
+            MergeField::Target => {
+                *selected_target = match selected_target {
+                    MergeTarget::CurrentBranch(_) => MergeTarget::MainBranch,
+                    MergeTarget::MainBranch => resolve_merge_target(vcs_command),
+                };
+            }
+            MergeField::Strategy => {
+                *strategy = if action == KeyAction::MoveUp {
+                    strategy.previous()
+                } else {
+                    strategy.next()
+                };
             }
+            MergeField::SquashSubject => {}
+        },
+        _ => {}
+    }
+    TasksAction::None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merge_dialog(focused_field: MergeField) -> TasksState {
+        let mut state = TasksState::new();
+        state.mode = TasksMode::MergeConfirmation {
+            task_id: Uuid::new_v4(),
+            worktree_branch: "chloe/task".to_string(),
+            selected_target: MergeTarget::MainBranch,
+            strategy: MergeStrategy::Squash,
+            focused_field,
+            squash_subject: String::new(),
+        };
+        state
+    }
+
+    fn press(state: &mut TasksState, keymap: &Keymap, code: KeyCode) {
+        let key = KeyEvent::new(code, KeyModifiers::NONE);
+        handle_merge_confirmation_mode(state, key, keymap, &VcsCommand::default());
+    }
+
+    #[test]
+    fn test_merge_dialog_keys_come_from_the_keymap() {
+        let keymap = Keymap::default();
+        let mut state = merge_dialog(MergeField::Strategy);
+
+        press(&mut state, &keymap, KeyCode::Char('j'));
+        assert!(matches!(
+            state.mode,
+            TasksMode::MergeConfirmation { strategy, .. } if strategy == MergeStrategy::Squash.next()
+        ));
+
+        press(&mut state, &keymap, KeyCode::Char('q'));
+        assert_eq!(state.mode, TasksMode::Normal);
+    }
+
+    #[test]
+    fn test_squash_subject_takes_typed_keys() {
+        let keymap = Keymap::default();
+        let mut state = merge_dialog(MergeField::SquashSubject);
+
+        for character in "jq".chars() {
+            press(&mut state, &keymap, KeyCode::Char(character));
         }
-        _ => TasksAction::None,
+        assert!(matches!(
+            &state.mode,
+            TasksMode::MergeConfirmation { squash_subject, .. } if squash_subject == "jq"
+        ));
+
+        press(&mut state, &keymap, KeyCode::Esc);
+        assert_eq!(state.mode, TasksMode::Normal);
     }
 }
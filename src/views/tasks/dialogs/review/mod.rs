@@ -6,6 +6,6 @@ pub mod status;
 
 pub use events::{
     ReviewPopupState, handle_merge_confirmation_mode, handle_review_popup_mode,
-    handle_review_request_changes_mode,
+    handle_review_request_changes_mode, scroll_review_popup,
 };
-pub use popup::{ReviewPopupViewState, render_review_popup};
+pub use popup::{ReviewPopupViewState, render_review_popup, review_help, review_terminal};
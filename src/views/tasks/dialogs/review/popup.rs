@@ -1,9 +1,13 @@
 use super::super::{centered_rect, render_popup_background};
 use super::{details, status};
 use crate::app::App;
+use crate::types::{KeyAction, KeyContext, Keymap};
 use crate::views::instances::InstancePane;
-use crate::views::tasks::state::{ReviewAction, ReviewPanel};
-use crate::views::worktree::{WorktreeStatus, get_commits_ahead_of_base, get_worktree_status};
+use crate::views::mouse::{MouseRegions, MouseTarget};
+use crate::views::tasks::state::{ReviewAction, ReviewPanel, TasksMode};
+use crate::views::worktree::{
+    BaseComparison, WorktreeStatus, compare_with_base, get_worktree_status,
+};
 use crate::widgets::terminal::{AlacrittyScreen, Cursor, PseudoTerminal};
 use ratatui::{
     Frame,
@@ -12,13 +16,14 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
 };
+use std::rc::Rc;
 use uuid::Uuid;
 
 const REVIEW_POPUP_WIDTH_PERCENT: u16 = 90;
 const REVIEW_POPUP_HEIGHT_PERCENT: u16 = 90;
 
-const BUTTON_COUNT: usize = 5;
-const BUTTON_WIDTH_PERCENT: u16 = 20;
+const BUTTON_COUNT: usize = 6;
+const BUTTON_WIDTH_PERCENT: u16 = 16;
 
 const STATUS_HEADER_HEIGHT: u16 = 9;
 const BUTTON_ROW_HEIGHT: u16 = 3;
@@ -43,8 +48,7 @@ const BORDER_HEIGHT_OFFSET: u16 = 2;
 
 pub struct ReviewInfo {
     pub branch_name: Option<String>,
-    pub base_branch_name: Option<String>,
-    pub commits_ahead_count: Option<usize>,
+    pub base_comparison: Option<BaseComparison>,
     pub worktree_status: WorktreeStatus,
     pub task_title: String,
 }
@@ -63,12 +67,9 @@ pub fn render_review_popup(
     app: &App,
     popup_state: &ReviewPopupViewState,
     area: Rect,
+    regions: &mut MouseRegions,
 ) {
-    let dialog_area = centered_rect(
-        REVIEW_POPUP_WIDTH_PERCENT,
-        REVIEW_POPUP_HEIGHT_PERCENT,
-        area,
-    );
+    let dialog_area = review_dialog_area(area);
 
     render_popup_background(frame, dialog_area);
 
@@ -77,18 +78,67 @@ pub fn render_review_popup(
     let review_info = get_review_info(app, task_id);
     let is_clean = review_info.worktree_status.is_clean;
 
-    let chunks = Layout::default()
+    let chunks = split_review_dialog(dialog_area);
+
+    render_status_header(frame, &review_info, chunks[0]);
+    render_review_sections(frame, app, popup_state, chunks[1], regions);
+    render_action_buttons(frame, popup_state.selected_action, is_clean, chunks[2]);
+}
+
+/// Sizes the reviewed task's agent terminal to the output section of the popup drawn over
+/// `area`, so its screen shows there as the agent drew it.
+/// Footer help for the review popup.
+#[must_use]
+pub fn review_help(keymap: &Keymap, is_wide: bool) -> String {
+    let labels = if is_wide {
+        ["select-button", "execute-action"]
+    } else {
+        ["button", "action"]
+    };
+
+    keymap.help(
+        KeyContext::Review,
+        &[
+            (&[KeyAction::NextPane], "panel"),
+            (&[KeyAction::MoveDown, KeyAction::MoveUp], "move/scroll"),
+            (&[KeyAction::MoveLeft, KeyAction::MoveRight], labels[0]),
+            (&[KeyAction::Open], labels[1]),
+            (&[KeyAction::Exit], "close"),
+        ],
+    )
+}
+
+/// The reviewed task's agent pane and the area the popup draws its output in.
+#[must_use]
+pub fn review_terminal(app: &App, area: Rect) -> Option<(Uuid, Rect)> {
+    let TasksMode::ReviewPopup { task_id, .. } = app.tasks.mode else {
+        return None;
+    };
+    let instance_id = app.tasks.find_task_by_id(task_id)?.instance_id?;
+
+    let dialog_chunks = split_review_dialog(review_dialog_area(area));
+    let output_area = split_review_sections(dialog_chunks[1])[1];
+    let inner_area = Block::default().borders(Borders::ALL).inner(output_area);
+    Some((instance_id, inner_area))
+}
+
+fn review_dialog_area(area: Rect) -> Rect {
+    centered_rect(
+        REVIEW_POPUP_WIDTH_PERCENT,
+        REVIEW_POPUP_HEIGHT_PERCENT,
+        area,
+    )
+}
+
+fn split_review_dialog(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(STATUS_HEADER_HEIGHT),
             Constraint::Min(0),
             Constraint::Length(BUTTON_ROW_HEIGHT),
         ])
-        .split(dialog_area);
-
-    render_status_header(frame, &review_info, chunks[0]);
-    render_review_sections(frame, app, popup_state, chunks[1]);
-    render_action_buttons(frame, popup_state.selected_action, is_clean, chunks[2]);
+        .split(area)
 }
 
 fn get_review_info(app: &App, task_id: Uuid) -> ReviewInfo {
@@ -96,8 +146,7 @@ fn get_review_info(app: &App, task_id: Uuid) -> ReviewInfo {
 
     let default_info = ReviewInfo {
         branch_name: None,
-        base_branch_name: None,
-        commits_ahead_count: None,
+        base_comparison: None,
         worktree_status: WorktreeStatus::default(),
         task_title: "Unknown Task".to_string(),
     };
@@ -111,8 +160,7 @@ fn get_review_info(app: &App, task_id: Uuid) -> ReviewInfo {
     let Some(worktree_info) = &task.worktree_info else {
         return ReviewInfo {
             branch_name: None,
-            base_branch_name: None,
-            commits_ahead_count: None,
+            base_comparison: None,
             worktree_status: WorktreeStatus {
                 is_clean: true,
                 ..WorktreeStatus::default()
@@ -121,19 +169,19 @@ fn get_review_info(app: &App, task_id: Uuid) -> ReviewInfo {
         };
     };
 
-    let worktree_status = get_worktree_status(&worktree_info.worktree_path).unwrap_or_default();
-    let (base_branch_name, commits_ahead_count) =
-        match get_commits_ahead_of_base(&worktree_info.worktree_path, &worktree_info.branch_name) {
-            Ok((base_branch_name, commits_ahead_count)) => {
-                (Some(base_branch_name), Some(commits_ahead_count))
-            }
-            Err(_) => (None, None),
-        };
+    let vcs_command = &app.settings.settings.vcs_command;
+    let worktree_status =
+        get_worktree_status(&worktree_info.worktree_path, vcs_command).unwrap_or_default();
+    let base_comparison = compare_with_base(
+        &worktree_info.worktree_path,
+        &worktree_info.branch_name,
+        vcs_command,
+    )
+    .ok();
 
     ReviewInfo {
         branch_name: Some(worktree_info.branch_name.clone()),
-        base_branch_name,
-        commits_ahead_count,
+        base_comparison,
         worktree_status,
         task_title,
     }
@@ -155,8 +203,7 @@ fn render_status_header(frame: &mut Frame, info: &ReviewInfo, area: Rect) {
 
     let lines = status::build_status_lines(
         info.branch_name.as_deref(),
-        info.base_branch_name.as_deref(),
-        info.commits_ahead_count,
+        info.base_comparison.as_ref(),
         &info.worktree_status,
     );
     let text = Paragraph::new(lines);
@@ -168,14 +215,20 @@ fn render_review_sections(
     app: &App,
     popup_state: &ReviewPopupViewState,
     area: Rect,
+    regions: &mut MouseRegions,
 ) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage(DIFF_SECTION_PERCENT),
-            Constraint::Percentage(OUTPUT_SECTION_PERCENT),
-        ])
-        .split(area);
+    let chunks = split_review_sections(area);
+
+    let diff_chunks = split_diff_section(chunks[0]);
+    regions.push(
+        diff_chunks[0],
+        MouseTarget::ReviewPanel(ReviewPanel::FileList),
+    );
+    regions.push(
+        diff_chunks[1],
+        MouseTarget::ReviewPanel(ReviewPanel::DiffContent),
+    );
+    regions.push(chunks[1], MouseTarget::ReviewPanel(ReviewPanel::Output));
 
     render_diff_section(
         frame,
@@ -196,6 +249,26 @@ fn render_review_sections(
     );
 }
 
+fn split_review_sections(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(DIFF_SECTION_PERCENT),
+            Constraint::Percentage(OUTPUT_SECTION_PERCENT),
+        ])
+        .split(area)
+}
+
+fn split_diff_section(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(FILE_LIST_SECTION_PERCENT),
+            Constraint::Percentage(DIFF_CONTENT_SECTION_PERCENT),
+        ])
+        .split(area)
+}
+
 fn render_diff_section(
     frame: &mut Frame,
     app: &App,
@@ -206,13 +279,7 @@ fn render_diff_section(
     area: Rect,
 ) {
     let diff_panel = details::build_diff_panel(app, task_id, selected_file_index);
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(FILE_LIST_SECTION_PERCENT),
-            Constraint::Percentage(DIFF_CONTENT_SECTION_PERCENT),
-        ])
-        .split(area);
+    let chunks = split_diff_section(area);
 
     render_file_list_section(
         frame,
@@ -1,4 +1,4 @@
-use crate::views::worktree::WorktreeStatus;
+use crate::views::worktree::{BaseComparison, WorktreeStatus};
 use ratatui::{
     style::{Color, Style},
     text::{Line, Span},
@@ -18,19 +18,20 @@ const MORE_FILES_SUFFIX: &str = " more";
 const AHEAD_STATUS_PREFIX: &str = "Ahead of ";
 const AHEAD_STATUS_SEPARATOR: &str = ": ";
 const AHEAD_STATUS_SUFFIX: &str = " commit";
+const BEHIND_STATUS_SEPARATOR: &str = ", ";
+const BEHIND_STATUS_SUFFIX: &str = " behind";
 
 #[must_use]
 pub fn build_status_lines(
     branch_name: Option<&str>,
-    base_branch_name: Option<&str>,
-    commits_ahead_count: Option<usize>,
+    base_comparison: Option<&BaseComparison>,
     status: &WorktreeStatus,
 ) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
 
     lines.push(build_branch_line(branch_name));
-    if let Some(line) = build_ahead_line(base_branch_name, commits_ahead_count) {
-        lines.push(line);
+    if let Some(comparison) = base_comparison {
+        lines.push(build_base_line(comparison));
     }
     lines.push(build_status_line(status));
     append_changed_files_lines(status, &mut lines);
@@ -55,21 +56,30 @@ fn build_branch_line(branch_name: Option<&str>) -> Line<'static> {
     ])
 }
 
-fn build_ahead_line(
-    base_branch_name: Option<&str>,
-    commits_ahead_count: Option<usize>,
-) -> Option<Line<'static>> {
-    let base_branch_name = base_branch_name?;
-    let commits_ahead_count = commits_ahead_count?;
-
-    let suffix = if commits_ahead_count == 1 { "" } else { "s" };
-    let label = format!("{AHEAD_STATUS_PREFIX}{base_branch_name}{AHEAD_STATUS_SEPARATOR}");
-    let value = format!("{commits_ahead_count}{AHEAD_STATUS_SUFFIX}{suffix}");
-
-    Some(Line::from(vec![
+fn build_base_line(comparison: &BaseComparison) -> Line<'static> {
+    let suffix = if comparison.ahead == 1 { "" } else { "s" };
+    let label = format!(
+        "{AHEAD_STATUS_PREFIX}{}{AHEAD_STATUS_SEPARATOR}",
+        comparison.base_branch_name
+    );
+    let value = format!("{}{AHEAD_STATUS_SUFFIX}{suffix}", comparison.ahead);
+    let mut spans = vec![
         Span::styled(label, Style::default().fg(Color::DarkGray)),
         Span::styled(value, Style::default().fg(Color::White)),
-    ]))
+    ];
+
+    if comparison.behind > 0 {
+        spans.push(Span::styled(
+            BEHIND_STATUS_SEPARATOR.to_string(),
+            Style::default().fg(Color::DarkGray),
+        ));
+        spans.push(Span::styled(
+            format!("{}{BEHIND_STATUS_SUFFIX}", comparison.behind),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    Line::from(spans)
 }
 
 fn build_status_line(status: &WorktreeStatus) -> Line<'static> {
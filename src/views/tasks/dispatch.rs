@@ -1,4 +1,4 @@
-use crate::app::{App, Tab};
+use crate::app::App;
 use crate::views;
 use crate::views::tasks::state::WorktreeSelectionOption;
 use crate::views::tasks::{FocusPanel, TasksAction, get_active_tasks, get_done_tasks};
@@ -7,7 +7,7 @@ use uuid::Uuid;
 
 pub fn process_event(app: &mut App, key: KeyEvent) {
     let selected_instance_id = get_selected_instance_id(app);
-    let default_provider = app.settings.settings.default_provider;
+    let default_provider = &app.settings.settings.default_provider;
     let vcs_command = &app.settings.settings.vcs_command;
     let action = views::tasks::handle_key_event(
         &mut app.tasks,
@@ -15,6 +15,8 @@ pub fn process_event(app: &mut App, key: KeyEvent) {
         selected_instance_id,
         default_provider,
         vcs_command,
+        app.settings.settings.merge_strategy,
+        &app.keymap,
     );
 
     handle_action(app, action);
@@ -44,11 +46,6 @@ fn get_selected_instance_id(app: &App) -> Option<Uuid> {
 fn handle_action(app: &mut App, action: TasksAction) {
     match action {
         TasksAction::None => {}
-        TasksAction::JumpToInstance(instance_id) => {
-            app.active_tab = Tab::Instances;
-            app.instances.select_pane_by_id(instance_id);
-            app.instances.mode = views::instances::InstanceMode::Focused;
-        }
         TasksAction::SendToTerminal(instance_id, data) => {
             if !data.is_empty() {
                 app.instances.send_raw_input_to_instance(instance_id, &data);
@@ -70,9 +67,10 @@ fn handle_action(app: &mut App, action: TasksAction) {
         }
         TasksAction::CreateTask { title } => {
             if let Some(event_sender) = app.event_sender() {
-                let provider = app.settings.settings.default_provider;
+                let provider = app.settings.settings.default_provider.clone();
+                let spec = app.settings.settings.provider_spec(&provider);
                 app.tasks
-                    .start_classification(title, provider, event_sender);
+                    .start_classification(title, provider, spec, event_sender);
                 let _ = app.save();
             }
         }
@@ -98,7 +96,13 @@ fn handle_action(app: &mut App, action: TasksAction) {
             }
         }
         TasksAction::CommitChanges(task_id) => app.commit_task_changes(task_id),
-        TasksAction::MergeBranch { task_id, target } => app.merge_task_branch(task_id, &target),
+        TasksAction::SyncWithBase(task_id) => app.sync_task_with_base(task_id),
+        TasksAction::MergeBranch {
+            task_id,
+            target,
+            strategy,
+            squash_subject,
+        } => app.merge_task_branch(task_id, &target, strategy, squash_subject.as_deref()),
         TasksAction::WorktreeSelected {
             task_id,
             worktree_option,
@@ -135,9 +139,9 @@ fn handle_worktree_selected(
 
     if should_skip {
         let provider = if detected_providers.len() == 1 {
-            detected_providers[0].provider
+            detected_providers[0].provider.clone()
         } else {
-            app.settings.settings.default_provider
+            app.settings.settings.default_provider.clone()
         };
         app.tasks.set_task_provider(task_id, provider);
         let vcs_command = &app.settings.settings.vcs_command;
@@ -161,7 +165,7 @@ fn handle_provider_selected(
     worktree_option: WorktreeSelectionOption,
     remember: bool,
 ) {
-    app.tasks.set_task_provider(task_id, provider);
+    app.tasks.set_task_provider(task_id, provider.clone());
     if remember {
         app.settings.settings.default_provider = provider;
         app.settings.settings.skip_provider_selection = true;
@@ -178,16 +182,7 @@ fn process_pending_actions(app: &mut App) {
         app.instances.close_pane_by_id(instance_id);
     }
 
-    if let Some(worktree_info) = app.tasks.pending_worktree_deletion.take()
-        && let Ok(repo_root) = views::worktree::find_repository_root(
-            std::env::current_dir()
-                .unwrap_or_else(|_| std::path::PathBuf::from("."))
-                .as_path(),
-        )
-    {
-        let vcs_command = &app.settings.settings.vcs_command;
-        let _ = views::worktree::delete_worktree(&repo_root, &worktree_info, vcs_command);
-    }
+    app.delete_pending_worktrees();
 
     if let Some(task_id) = app.tasks.pending_ide_open.take() {
         app.open_task_in_ide(task_id);
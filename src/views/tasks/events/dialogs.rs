@@ -1,19 +1,19 @@
 use super::TasksAction;
+use crate::types::KeyAction;
 use crate::views::tasks::state::{TasksMode, TasksState};
-use crossterm::event::{KeyCode, KeyEvent};
 use uuid::Uuid;
 
 pub fn handle_confirm_delete_mode(
     state: &mut TasksState,
-    key: KeyEvent,
+    action: Option<KeyAction>,
     task_id: Uuid,
 ) -> TasksAction {
-    match key.code {
-        KeyCode::Char('y' | 'Y') => {
+    match action {
+        Some(KeyAction::Confirm) => {
             state.mode = TasksMode::Normal;
             TasksAction::DeleteTask(task_id)
         }
-        KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+        Some(KeyAction::Cancel) => {
             state.mode = TasksMode::Normal;
             TasksAction::None
         }
@@ -23,16 +23,16 @@ pub fn handle_confirm_delete_mode(
 
 pub fn handle_confirm_move_back_mode(
     state: &mut TasksState,
-    key: KeyEvent,
+    action: Option<KeyAction>,
     _task_id: Uuid,
 ) -> TasksAction {
-    match key.code {
-        KeyCode::Char('y' | 'Y') => {
+    match action {
+        Some(KeyAction::Confirm) => {
             state.execute_move_task_previous();
             state.mode = TasksMode::Normal;
             TasksAction::None
         }
-        KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+        Some(KeyAction::Cancel) => {
             state.mode = TasksMode::Normal;
             TasksAction::None
         }
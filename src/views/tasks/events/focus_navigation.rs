@@ -1,16 +1,16 @@
 use super::TasksAction;
+use crate::types::KeyAction;
 use crate::views::settings::VcsCommand;
 use crate::views::tasks::operations::{
     get_active_task_count, get_active_tasks, get_done_task_count, get_done_tasks,
 };
 use crate::views::tasks::state::{FocusPanel, ReviewAction, ReviewPanel, TasksMode, TasksState};
-use crossterm::event::{KeyCode, KeyEvent};
 use uuid::Uuid;
 
 #[allow(clippy::too_many_lines)]
 pub fn handle_focus_normal_mode(
     state: &mut TasksState,
-    key: KeyEvent,
+    action: Option<KeyAction>,
     selected_instance_id: Option<Uuid>,
     vcs_command: &VcsCommand,
 ) -> TasksAction {
@@ -28,27 +28,31 @@ pub fn handle_focus_normal_mode(
         }
     };
 
-    match key.code {
-        KeyCode::Char('j') | KeyCode::Down => {
+    let Some(action) = action else {
+        return TasksAction::None;
+    };
+
+    match action {
+        KeyAction::MoveDown => {
             state.focus_select_next();
             TasksAction::None
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        KeyAction::MoveUp => {
             state.focus_select_previous();
             TasksAction::None
         }
-        KeyCode::Tab => {
+        KeyAction::SwitchPanel => {
             match state.focus_panel {
                 FocusPanel::ActiveTasks => state.focus_switch_to_done_panel(),
                 FocusPanel::DoneTasks => state.focus_switch_to_active_panel(),
             }
             TasksAction::None
         }
-        KeyCode::Char('a') => {
+        KeyAction::Add => {
             state.begin_add_task();
             TasksAction::None
         }
-        KeyCode::Char('e') => {
+        KeyAction::Edit => {
             if let Some(task_ref) = selected_task {
                 state.mode = TasksMode::EditingTask {
                     task_id: task_ref.task.id,
@@ -57,7 +61,7 @@ pub fn handle_focus_normal_mode(
             }
             TasksAction::None
         }
-        KeyCode::Char('d') => {
+        KeyAction::Delete => {
             if let Some(task_ref) = selected_task {
                 state.mode = TasksMode::ConfirmDelete {
                     task_id: task_ref.task.id,
@@ -65,11 +69,11 @@ pub fn handle_focus_normal_mode(
             }
             TasksAction::None
         }
-        KeyCode::Enter => {
+        KeyAction::Open => {
             if let Some(task_ref) = selected_task {
                 let is_planning = task_ref.column_index == 0;
-                let is_in_progress = task_ref.column_index == 1;
-                let is_review = task_ref.column_index == 2;
+                let is_in_progress = state.is_agent_column(task_ref.column_index);
+                let is_review = state.review_column_index() == Some(task_ref.column_index);
 
                 if is_planning {
                     let is_task_classifying = task_ref.task.is_classifying;
@@ -100,22 +104,21 @@ pub fn handle_focus_normal_mode(
                 TasksAction::None
             }
         }
-        KeyCode::Char('t') => {
-            selected_instance_id.map_or(TasksAction::None, TasksAction::JumpToInstance)
-        }
-        KeyCode::Char('s') => {
+        KeyAction::Start => {
             if let Some(task_ref) = selected_task {
                 let is_planning = task_ref.column_index == 0;
                 if is_planning {
                     let is_task_classifying = task_ref.task.is_classifying;
-                    if !is_task_classifying {
+                    if let Some(message) = state.blocked_message(task_ref.task) {
+                        state.error_message = Some(message);
+                    } else if !is_task_classifying {
                         state.begin_worktree_selection_for_task(task_ref.task.id, vcs_command);
                     }
                 }
             }
             TasksAction::None
         }
-        KeyCode::Char('g') => {
+        KeyAction::GoToTop => {
             match state.focus_panel {
                 FocusPanel::ActiveTasks => {
                     if active_count > 0 {
@@ -130,7 +133,7 @@ pub fn handle_focus_normal_mode(
             }
             TasksAction::None
         }
-        KeyCode::Char('G') => {
+        KeyAction::GoToBottom => {
             match state.focus_panel {
                 FocusPanel::ActiveTasks => {
                     if active_count > 0 {
@@ -145,7 +148,7 @@ pub fn handle_focus_normal_mode(
             }
             TasksAction::None
         }
-        KeyCode::Backspace => {
+        KeyAction::MoveBack => {
             if let Some(task_ref) = selected_task {
                 let task_index_in_column = state.columns[task_ref.column_index]
                     .tasks
@@ -156,8 +159,9 @@ pub fn handle_focus_normal_mode(
                     state.kanban_selected_column = task_ref.column_index;
                     state.kanban_selected_task = Some(task_index);
 
-                    let is_review_column = task_ref.column_index == 2;
-                    let is_in_progress_column = task_ref.column_index == 1;
+                    let is_review_column =
+                        state.review_column_index() == Some(task_ref.column_index);
+                    let is_in_progress_column = state.is_agent_column(task_ref.column_index);
                     let needs_confirmation = is_review_column || is_in_progress_column;
 
                     if needs_confirmation {
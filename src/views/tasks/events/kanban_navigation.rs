@@ -1,17 +1,26 @@
+use crate::types::{KeyAction, KeyContext, Keymap};
 use crate::views::settings::VcsCommand;
-use crate::views::tasks::state::{ReviewAction, ReviewPanel, TasksMode, TasksState};
-use crossterm::event::{KeyCode, KeyEvent};
-
-pub fn handle_kanban_normal_mode(state: &mut TasksState, key: KeyEvent, vcs_command: &VcsCommand) {
-    match key.code {
-        KeyCode::Left | KeyCode::Char('h') => state.previous_column(),
-        KeyCode::Right | KeyCode::Char('l') => state.next_column(),
-        KeyCode::Up | KeyCode::Char('k') => state.previous_task(),
-        KeyCode::Down | KeyCode::Char('j') => state.next_task(),
-        KeyCode::Char('a') => {
+use crate::views::tasks::state::{QueuePriority, ReviewAction, ReviewPanel, TasksMode, TasksState};
+use crossterm::event::KeyEvent;
+use uuid::Uuid;
+
+pub fn handle_kanban_normal_mode(
+    state: &mut TasksState,
+    action: Option<KeyAction>,
+    vcs_command: &VcsCommand,
+) {
+    let Some(action) = action else {
+        return;
+    };
+    if move_selection(state, action) {
+        return;
+    }
+
+    match action {
+        KeyAction::Add => {
             state.begin_add_task();
         }
-        KeyCode::Char('e') => {
+        KeyAction::Edit => {
             if let Some(task) = state.get_kanban_selected_task() {
                 let task_id = task.id;
                 let title = task.title.clone();
@@ -21,14 +30,37 @@ pub fn handle_kanban_normal_mode(state: &mut TasksState, key: KeyEvent, vcs_comm
                 };
             }
         }
-        KeyCode::Char('d') => {
+        KeyAction::Delete => {
             if let Some(task) = state.get_kanban_selected_task() {
                 state.mode = TasksMode::ConfirmDelete { task_id: task.id };
             }
         }
-        KeyCode::Enter => {
-            let is_review_column = state.kanban_selected_column == 2;
-            let is_in_progress_column = state.kanban_selected_column == 1;
+        KeyAction::ToggleQueue => {
+            if let Some(task) = state.get_kanban_selected_task() {
+                let priority = match task.queue_priority {
+                    Some(_) => None,
+                    None => Some(QueuePriority::Normal),
+                };
+                set_queue_priority(state, task.id, priority);
+            }
+        }
+        KeyAction::CyclePriority => {
+            if let Some(task) = state.get_kanban_selected_task() {
+                let priority = task
+                    .queue_priority
+                    .map_or(QueuePriority::High, QueuePriority::next);
+                set_queue_priority(state, task.id, Some(priority));
+            }
+        }
+        KeyAction::SelectBlockers => {
+            if let Some(task) = state.get_kanban_selected_task() {
+                state.mode = TasksMode::SelectBlockers { task_id: task.id };
+            }
+        }
+        KeyAction::Open => {
+            let is_review_column =
+                state.review_column_index() == Some(state.kanban_selected_column);
+            let is_in_progress_column = state.is_agent_column(state.kanban_selected_column);
 
             if is_review_column {
                 if let Some(task) = state.get_kanban_selected_task() {
@@ -45,9 +77,10 @@ pub fn handle_kanban_normal_mode(state: &mut TasksState, key: KeyEvent, vcs_comm
                 state.move_task_next(vcs_command);
             }
         }
-        KeyCode::Backspace => {
-            let is_review_column = state.kanban_selected_column == 2;
-            let is_in_progress_column = state.kanban_selected_column == 1;
+        KeyAction::MoveBack => {
+            let is_review_column =
+                state.review_column_index() == Some(state.kanban_selected_column);
+            let is_in_progress_column = state.is_agent_column(state.kanban_selected_column);
             let needs_confirmation = is_review_column || is_in_progress_column;
 
             if needs_confirmation {
@@ -61,3 +94,49 @@ pub fn handle_kanban_normal_mode(state: &mut TasksState, key: KeyEvent, vcs_comm
         _ => {}
     }
 }
+
+/// Moves the kanban selection for the movement actions, returning whether `action` was one.
+fn move_selection(state: &mut TasksState, action: KeyAction) -> bool {
+    match action {
+        KeyAction::MoveLeft => state.previous_column(),
+        KeyAction::MoveRight => state.next_column(),
+        KeyAction::MoveUp => state.previous_task(),
+        KeyAction::MoveDown => state.next_task(),
+        _ => return false,
+    }
+    true
+}
+
+fn set_queue_priority(state: &mut TasksState, task_id: Uuid, priority: Option<QueuePriority>) {
+    if let Err(error) = state.set_task_queue_priority(task_id, priority) {
+        state.error_message = Some(error);
+    }
+}
+
+pub fn handle_select_blockers_mode(
+    state: &mut TasksState,
+    key: KeyEvent,
+    keymap: &Keymap,
+    task_id: Uuid,
+) {
+    let action = keymap.action(KeyContext::Kanban, key);
+    if action.is_some_and(|action| move_selection(state, action)) {
+        return;
+    }
+    if action == Some(KeyAction::SelectBlockers) {
+        state.mode = TasksMode::Normal;
+        return;
+    }
+
+    match keymap.action(KeyContext::Picker, key) {
+        Some(KeyAction::Open) => {
+            if let Some(blocker) = state.get_kanban_selected_task()
+                && let Err(error) = state.toggle_task_dependency(task_id, blocker.id)
+            {
+                state.error_message = Some(error);
+            }
+        }
+        Some(KeyAction::Exit) => state.mode = TasksMode::Normal,
+        _ => {}
+    }
+}
@@ -8,14 +8,13 @@ mod worktree_selection;
 
 use super::dialogs::review;
 use super::state::{MergeTarget, TasksMode, TasksState, TasksViewMode, WorktreeSelectionOption};
-use crate::types::AgentProvider;
-use crate::views::settings::VcsCommand;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::types::{AgentProvider, KeyAction, KeyContext, Keymap};
+use crate::views::settings::{MergeStrategy, VcsCommand};
+use crossterm::event::KeyEvent;
 use uuid::Uuid;
 
 pub enum TasksAction {
     None,
-    JumpToInstance(Uuid),
     SendToTerminal(Uuid, Vec<u8>),
     ScrollTerminal {
         instance_id: Uuid,
@@ -38,9 +37,13 @@ pub enum TasksAction {
         message: String,
     },
     CommitChanges(Uuid),
+    SyncWithBase(Uuid),
     MergeBranch {
         task_id: Uuid,
         target: MergeTarget,
+        strategy: MergeStrategy,
+        /// The first line of a squash commit, if the user wrote one.
+        squash_subject: Option<String>,
     },
     WorktreeSelected {
         task_id: Uuid,
@@ -58,15 +61,19 @@ pub fn handle_key_event(
     state: &mut TasksState,
     key: KeyEvent,
     selected_instance_id: Option<Uuid>,
-    default_provider: AgentProvider,
+    default_provider: &AgentProvider,
     vcs_command: &VcsCommand,
+    merge_strategy: MergeStrategy,
+    keymap: &Keymap,
 ) -> TasksAction {
     if state.error_message.is_some() {
         state.error_message = None;
         return TasksAction::None;
     }
 
-    if state.mode == TasksMode::Normal && key.code == KeyCode::Char('/') {
+    if state.mode == TasksMode::Normal
+        && keymap.action(KeyContext::Tasks, key) == Some(KeyAction::ToggleView)
+    {
         state.toggle_view_mode();
         return TasksAction::None;
     }
@@ -75,35 +82,49 @@ pub fn handle_key_event(
         TasksMode::Normal => match state.view_mode {
             TasksViewMode::Focus => focus_navigation::handle_focus_normal_mode(
                 state,
-                key,
+                keymap.action(KeyContext::Focus, key),
                 selected_instance_id,
                 vcs_command,
             ),
             TasksViewMode::Kanban => {
-                kanban_navigation::handle_kanban_normal_mode(state, key, vcs_command);
+                kanban_navigation::handle_kanban_normal_mode(
+                    state,
+                    keymap.action(KeyContext::Kanban, key),
+                    vcs_command,
+                );
                 TasksAction::None
             }
         },
         TasksMode::TerminalFocused => {
-            terminal::handle_terminal_focused_mode(state, key, selected_instance_id)
+            terminal::handle_terminal_focused_mode(state, key, keymap, selected_instance_id)
         }
         TasksMode::TerminalScroll => {
-            terminal::handle_terminal_scroll_mode(state, key, selected_instance_id)
+            terminal::handle_terminal_scroll_mode(state, key, keymap, selected_instance_id)
         }
         TasksMode::AddingTask { .. } => text_input::handle_adding_task_mode(state, key),
-        TasksMode::SelectWorktree { .. } => {
-            worktree_selection::handle_worktree_selection_mode(state, key)
-        }
-        TasksMode::SelectProvider { .. } => {
-            provider_selection::handle_provider_selection_mode(state, key, default_provider)
-        }
+        TasksMode::SelectWorktree { .. } => worktree_selection::handle_worktree_selection_mode(
+            state,
+            keymap.action(KeyContext::Picker, key),
+        ),
+        TasksMode::SelectProvider { .. } => provider_selection::handle_provider_selection_mode(
+            state,
+            keymap.action(KeyContext::Picker, key),
+            default_provider,
+        ),
         TasksMode::EditingTask { .. } => text_input::handle_editing_task_mode(state, key),
-        TasksMode::ConfirmDelete { task_id } => {
-            dialogs::handle_confirm_delete_mode(state, key, *task_id)
-        }
-
-        TasksMode::ConfirmMoveBack { task_id } => {
-            dialogs::handle_confirm_move_back_mode(state, key, *task_id)
+        TasksMode::ConfirmDelete { task_id } => dialogs::handle_confirm_delete_mode(
+            state,
+            keymap.action(KeyContext::Dialog, key),
+            *task_id,
+        ),
+        TasksMode::ConfirmMoveBack { task_id } => dialogs::handle_confirm_move_back_mode(
+            state,
+            keymap.action(KeyContext::Dialog, key),
+            *task_id,
+        ),
+        TasksMode::SelectBlockers { task_id } => {
+            kanban_navigation::handle_select_blockers_mode(state, key, keymap, *task_id);
+            TasksAction::None
         }
         TasksMode::ReviewPopup {
             task_id,
@@ -121,21 +142,53 @@ pub fn handle_key_event(
                 focused_panel: *focused_panel,
                 selected_action: *selected_action,
             };
-            review::handle_review_popup_mode(state, key, popup_state)
+            review::handle_review_popup_mode(
+                state,
+                keymap.action(KeyContext::Review, key),
+                popup_state,
+                vcs_command,
+                merge_strategy,
+            )
         }
         TasksMode::ReviewRequestChanges { task_id, .. } => {
             review::handle_review_request_changes_mode(state, key, *task_id)
         }
-        TasksMode::MergeConfirmation {
-            task_id,
-            worktree_branch,
-            selected_target,
-        } => review::handle_merge_confirmation_mode(
-            state,
-            key,
-            *task_id,
-            worktree_branch.clone(),
-            selected_target.clone(),
-        ),
+        TasksMode::MergeConfirmation { .. } => {
+            review::handle_merge_confirmation_mode(state, key, keymap, vcs_command)
+        }
+    }
+}
+
+/// Moves the selection on the board, in an open picker or in the review popup one step, as the
+/// mouse wheel does.
+pub fn move_selection(state: &mut TasksState, action: KeyAction, default_provider: &AgentProvider) {
+    let is_down = action == KeyAction::MoveDown;
+    match &state.mode {
+        TasksMode::Normal if state.view_mode == TasksViewMode::Focus => {
+            if is_down {
+                state.focus_select_next();
+            } else {
+                state.focus_select_previous();
+            }
+        }
+        TasksMode::Normal | TasksMode::SelectBlockers { .. } => {
+            if is_down {
+                state.next_task();
+            } else {
+                state.previous_task();
+            }
+        }
+        TasksMode::SelectWorktree { .. } => {
+            worktree_selection::handle_worktree_selection_mode(state, Some(action));
+        }
+        TasksMode::SelectProvider { .. } => {
+            provider_selection::handle_provider_selection_mode(
+                state,
+                Some(action),
+                default_provider,
+            );
+        }
+        TasksMode::ReviewPopup { .. } => review::scroll_review_popup(state, action),
+        _ => {}
     }
 }
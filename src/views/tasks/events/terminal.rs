@@ -1,4 +1,5 @@
 use super::TasksAction;
+use crate::types::{KeyAction, KeyContext, Keymap};
 use crate::views::tasks::state::TasksState;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use uuid::Uuid;
@@ -9,25 +10,25 @@ const SCROLL_HALF_PAGE: isize = 15;
 pub fn handle_terminal_focused_mode(
     state: &mut TasksState,
     key: KeyEvent,
+    keymap: &Keymap,
     selected_instance_id: Option<Uuid>,
 ) -> TasksAction {
-    let is_shift_escape = key.code == KeyCode::Esc && key.modifiers.contains(KeyModifiers::SHIFT);
-    if is_shift_escape {
-        let Some(instance_id) = selected_instance_id else {
+    match keymap.action(KeyContext::Terminal, key) {
+        Some(KeyAction::SendEscape) => {
+            let Some(instance_id) = selected_instance_id else {
+                return TasksAction::None;
+            };
+            return TasksAction::SendToTerminal(instance_id, b"\x1b".to_vec());
+        }
+        Some(KeyAction::ExitTerminal) => {
+            state.exit_terminal_mode();
             return TasksAction::None;
-        };
-        return TasksAction::SendToTerminal(instance_id, b"\x1b".to_vec());
-    }
-
-    if key.code == KeyCode::Esc {
-        state.exit_terminal_mode();
-        return TasksAction::None;
-    }
-
-    let is_ctrl_s = key.code == KeyCode::Char('s') && key.modifiers.contains(KeyModifiers::CONTROL);
-    if is_ctrl_s {
-        state.enter_terminal_scroll_mode();
-        return TasksAction::None;
+        }
+        Some(KeyAction::ScrollMode) => {
+            state.enter_terminal_scroll_mode();
+            return TasksAction::None;
+        }
+        _ => {}
     }
 
     let Some(instance_id) = selected_instance_id else {
@@ -46,6 +47,7 @@ pub fn handle_terminal_focused_mode(
 pub fn handle_terminal_scroll_mode(
     state: &mut TasksState,
     key: KeyEvent,
+    keymap: &Keymap,
     selected_instance_id: Option<Uuid>,
 ) -> TasksAction {
     let Some(instance_id) = selected_instance_id else {
@@ -53,33 +55,29 @@ pub fn handle_terminal_scroll_mode(
         return TasksAction::None;
     };
 
-    match key.code {
-        KeyCode::Esc | KeyCode::Char('q') => {
+    match keymap.action(KeyContext::Scroll, key) {
+        Some(KeyAction::Exit) => {
             state.exit_terminal_scroll_mode();
             TasksAction::ScrollTerminalToBottom(instance_id)
         }
-        KeyCode::Char('j') | KeyCode::Down => TasksAction::ScrollTerminal {
+        Some(KeyAction::MoveDown) => TasksAction::ScrollTerminal {
             instance_id,
             delta: -SCROLL_LINES,
         },
-        KeyCode::Char('k') | KeyCode::Up => TasksAction::ScrollTerminal {
+        Some(KeyAction::MoveUp) => TasksAction::ScrollTerminal {
             instance_id,
             delta: SCROLL_LINES,
         },
-        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            TasksAction::ScrollTerminal {
-                instance_id,
-                delta: -SCROLL_HALF_PAGE,
-            }
-        }
-        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            TasksAction::ScrollTerminal {
-                instance_id,
-                delta: SCROLL_HALF_PAGE,
-            }
-        }
-        KeyCode::Char('g') => TasksAction::ScrollTerminalToTop(instance_id),
-        KeyCode::Char('G') => TasksAction::ScrollTerminalToBottom(instance_id),
+        Some(KeyAction::HalfPageDown) => TasksAction::ScrollTerminal {
+            instance_id,
+            delta: -SCROLL_HALF_PAGE,
+        },
+        Some(KeyAction::HalfPageUp) => TasksAction::ScrollTerminal {
+            instance_id,
+            delta: SCROLL_HALF_PAGE,
+        },
+        Some(KeyAction::GoToTop) => TasksAction::ScrollTerminalToTop(instance_id),
+        Some(KeyAction::GoToBottom) => TasksAction::ScrollTerminalToBottom(instance_id),
         _ => TasksAction::None,
     }
 }
@@ -1,9 +1,12 @@
+use crate::types::KeyAction;
 use crate::views::tasks::state::{TasksMode, TasksState, WorktreeSelectionOption};
-use crossterm::event::{KeyCode, KeyEvent};
 
 use super::TasksAction;
 
-pub fn handle_worktree_selection_mode(state: &mut TasksState, key: KeyEvent) -> TasksAction {
+pub fn handle_worktree_selection_mode(
+    state: &mut TasksState,
+    action: Option<KeyAction>,
+) -> TasksAction {
     let (task_id, task_title, selected_index, options) = match &state.mode {
         TasksMode::SelectWorktree {
             task_id,
@@ -21,8 +24,8 @@ pub fn handle_worktree_selection_mode(state: &mut TasksState, key: KeyEvent) ->
 
     let last_index = options.len().saturating_sub(1);
 
-    match key.code {
-        KeyCode::Char('j') | KeyCode::Down => {
+    match action {
+        Some(KeyAction::MoveDown) => {
             let next_index = (selected_index + 1).min(last_index);
             state.mode = TasksMode::SelectWorktree {
                 task_id,
@@ -32,7 +35,7 @@ pub fn handle_worktree_selection_mode(state: &mut TasksState, key: KeyEvent) ->
             };
             TasksAction::None
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Some(KeyAction::MoveUp) => {
             let previous_index = selected_index.saturating_sub(1);
             state.mode = TasksMode::SelectWorktree {
                 task_id,
@@ -42,7 +45,7 @@ pub fn handle_worktree_selection_mode(state: &mut TasksState, key: KeyEvent) ->
             };
             TasksAction::None
         }
-        KeyCode::Enter => {
+        Some(KeyAction::Open) => {
             state.mode = TasksMode::Normal;
             let selected_option = options
                 .get(selected_index)
@@ -53,7 +56,7 @@ pub fn handle_worktree_selection_mode(state: &mut TasksState, key: KeyEvent) ->
                 worktree_option: selected_option,
             }
         }
-        KeyCode::Esc => {
+        Some(KeyAction::Exit) => {
             state.mode = TasksMode::Normal;
             TasksAction::None
         }
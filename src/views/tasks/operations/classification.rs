@@ -1,17 +1,18 @@
 use crate::events::AppEvent;
-use crate::types::AgentProvider;
+use crate::providers::ProviderSpec;
+use crate::types::{AgentProvider, PLANNING_COLUMN_INDEX};
 use crate::views::tasks::ai_classifier::{ClassifiedTask, spawn_classification};
 use crate::views::tasks::state::{Task, TaskType, TasksState};
+use std::borrow::Cow;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
-const PLANNING_COLUMN_INDEX: usize = 0;
-
 impl TasksState {
     pub fn start_classification(
         &mut self,
         raw_input: String,
         provider: AgentProvider,
+        spec: Cow<'static, ProviderSpec>,
         event_sender: mpsc::UnboundedSender<AppEvent>,
     ) -> Uuid {
         let task = Task::new_classifying(raw_input.clone());
@@ -22,7 +23,7 @@ impl TasksState {
         self.kanban_selected_task = Some(self.columns[PLANNING_COLUMN_INDEX].tasks.len() - 1);
 
         self.pending_classifications.insert(task_id);
-        spawn_classification(raw_input, task_id, provider, event_sender);
+        spawn_classification(raw_input, task_id, provider, spec, event_sender);
 
         task_id
     }
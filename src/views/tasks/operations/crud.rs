@@ -18,6 +18,7 @@ impl TasksState {
         for column in &mut self.columns {
             if let Some(position) = column.tasks.iter().position(|task| task.id == task_id) {
                 let task = column.tasks.remove(position);
+                self.remove_dependency_everywhere(task_id);
                 return task.instance_id;
             }
         }
@@ -35,4 +36,28 @@ impl TasksState {
         }
         false
     }
+
+    pub fn update_task_description_by_id(&mut self, task_id: Uuid, description: String) -> bool {
+        for column in &mut self.columns {
+            for task in &mut column.tasks {
+                if task.id == task_id {
+                    task.description = description;
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    pub fn update_task_kind_by_id(&mut self, task_id: Uuid, kind: TaskType) -> bool {
+        for column in &mut self.columns {
+            for task in &mut column.tasks {
+                if task.id == task_id {
+                    task.kind = kind;
+                    return true;
+                }
+            }
+        }
+        false
+    }
 }
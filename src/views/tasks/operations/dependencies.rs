@@ -0,0 +1,224 @@
+use crate::types::PLANNING_COLUMN_INDEX;
+use crate::views::tasks::state::{QueuePriority, Task, TasksState};
+use uuid::Uuid;
+
+impl TasksState {
+    /// The tasks `task` depends on that are not in a terminal column yet. Dependencies that
+    /// were deleted are ignored.
+    #[must_use]
+    pub fn blockers_of(&self, task: &Task) -> Vec<&Task> {
+        task.dependencies
+            .iter()
+            .filter_map(|dependency_id| {
+                let (column_index, task_index) = self.find_task_location(*dependency_id)?;
+                let column = &self.columns[column_index];
+                (!column.workflow.is_terminal).then(|| &column.tasks[task_index])
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn is_task_blocked(&self, task: &Task) -> bool {
+        !self.blockers_of(task).is_empty()
+    }
+
+    /// Explains why `task` cannot start yet, or `None` when nothing blocks it.
+    #[must_use]
+    pub fn blocked_message(&self, task: &Task) -> Option<String> {
+        let blockers = self.blockers_of(task);
+        if blockers.is_empty() {
+            return None;
+        }
+        let titles: Vec<&str> = blockers
+            .iter()
+            .map(|blocker| blocker.title.as_str())
+            .collect();
+        Some(format!(
+            "\"{}\" is blocked by: {}",
+            task.title,
+            titles.join(", ")
+        ))
+    }
+
+    /// Replaces the tasks `task_id` depends on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a dependency does not exist, is the task itself, or already depends
+    /// on the task directly or through other tasks.
+    pub fn set_task_dependencies(
+        &mut self,
+        task_id: Uuid,
+        dependencies: Vec<Uuid>,
+    ) -> Result<(), String> {
+        for dependency_id in &dependencies {
+            if *dependency_id == task_id {
+                return Err("A task cannot depend on itself".to_string());
+            }
+            if self.find_task_by_id(*dependency_id).is_none() {
+                return Err(format!("No task with ID {dependency_id}"));
+            }
+            if self.depends_on(*dependency_id, task_id) {
+                return Err("That dependency would create a cycle".to_string());
+            }
+        }
+
+        let task = self
+            .columns
+            .iter_mut()
+            .flat_map(|column| &mut column.tasks)
+            .find(|task| task.id == task_id)
+            .ok_or_else(|| format!("No task with ID {task_id}"))?;
+        let mut dependencies = dependencies;
+        dependencies.sort_unstable();
+        dependencies.dedup();
+        task.dependencies = dependencies;
+        Ok(())
+    }
+
+    /// Adds `dependency_id` to `task_id`'s dependencies, or removes it if already there.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`Self::set_task_dependencies`].
+    pub fn toggle_task_dependency(
+        &mut self,
+        task_id: Uuid,
+        dependency_id: Uuid,
+    ) -> Result<(), String> {
+        let mut dependencies = self
+            .find_task_by_id(task_id)
+            .map(|task| task.dependencies.clone())
+            .unwrap_or_default();
+
+        if let Some(position) = dependencies.iter().position(|id| *id == dependency_id) {
+            dependencies.remove(position);
+        } else {
+            dependencies.push(dependency_id);
+        }
+        self.set_task_dependencies(task_id, dependencies)
+    }
+
+    /// Whether `task_id` depends on `dependency_id`, directly or through other tasks.
+    fn depends_on(&self, task_id: Uuid, dependency_id: Uuid) -> bool {
+        let mut pending = vec![task_id];
+        let mut visited = Vec::new();
+
+        while let Some(current_id) = pending.pop() {
+            if visited.contains(&current_id) {
+                continue;
+            }
+            visited.push(current_id);
+
+            let Some(task) = self.find_task_by_id(current_id) else {
+                continue;
+            };
+            if task.dependencies.contains(&dependency_id) {
+                return true;
+            }
+            pending.extend(task.dependencies.iter().copied());
+        }
+        false
+    }
+
+    /// Forgets `task_id` as a dependency of every other task, for when it is deleted.
+    pub(super) fn remove_dependency_everywhere(&mut self, task_id: Uuid) {
+        for task in self.columns.iter_mut().flat_map(|column| &mut column.tasks) {
+            task.dependencies
+                .retain(|dependency_id| *dependency_id != task_id);
+        }
+    }
+
+    /// Puts the planned tasks that `done_task_id` was the last blocker of in the auto-run
+    /// queue, so they start within the same agent limits as every other queued task. Tasks
+    /// that were already queued keep their priority.
+    pub(super) fn queue_unblocked_dependents(&mut self, done_task_id: Uuid) {
+        let Some(planning) = self.columns.get(PLANNING_COLUMN_INDEX) else {
+            return;
+        };
+
+        let unblocked: Vec<Uuid> = planning
+            .tasks
+            .iter()
+            .filter(|task| task.dependencies.contains(&done_task_id))
+            .filter(|task| !self.is_task_blocked(task))
+            .map(|task| task.id)
+            .collect();
+        for task in &mut self.columns[PLANNING_COLUMN_INDEX].tasks {
+            if unblocked.contains(&task.id) {
+                task.queue_priority.get_or_insert(QueuePriority::Normal);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::AgentProvider;
+    use crate::views::tasks::state::{TaskType, TasksState};
+    use std::collections::HashMap;
+
+    fn board_with_tasks(titles: &[&str]) -> (TasksState, Vec<uuid::Uuid>) {
+        let mut tasks = TasksState::new();
+        for title in titles {
+            tasks.add_task_to_planning((*title).to_string(), String::new(), TaskType::Task);
+        }
+        let ids = tasks.columns[0].tasks.iter().map(|task| task.id).collect();
+        (tasks, ids)
+    }
+
+    #[test]
+    fn test_dependencies_reject_cycles() {
+        let (mut tasks, ids) = board_with_tasks(&["Schema", "API", "UI"]);
+
+        assert!(tasks.set_task_dependencies(ids[1], vec![ids[0]]).is_ok());
+        assert!(tasks.set_task_dependencies(ids[2], vec![ids[1]]).is_ok());
+        assert!(tasks.set_task_dependencies(ids[0], vec![ids[2]]).is_err());
+        assert!(tasks.toggle_task_dependency(ids[0], ids[0]).is_err());
+    }
+
+    #[test]
+    fn test_finishing_last_blocker_queues_dependent() {
+        let (mut tasks, ids) = board_with_tasks(&["Schema", "Docs", "API"]);
+        tasks
+            .set_task_dependencies(ids[2], vec![ids[0], ids[1]])
+            .unwrap();
+
+        let api = tasks.find_task_by_id(ids[2]).unwrap();
+        assert_eq!(tasks.blockers_of(api).len(), 2);
+
+        let schema = tasks.columns[0].tasks.remove(0);
+        tasks.columns[3].tasks.push(schema);
+        tasks.queue_unblocked_dependents(ids[0]);
+        assert_eq!(tasks.queued_task_count(), 0);
+
+        let docs = tasks.columns[0].tasks.remove(0);
+        tasks.columns[3].tasks.push(docs);
+        tasks.queue_unblocked_dependents(ids[1]);
+        let queued: Vec<_> = tasks
+            .startable_queued_tasks()
+            .iter()
+            .map(|task| task.id)
+            .collect();
+        assert_eq!(queued, vec![ids[2]]);
+    }
+
+    #[test]
+    fn test_unblocked_dependents_start_within_the_agent_limit() {
+        let (mut tasks, ids) = board_with_tasks(&["Schema", "API", "UI", "CLI"]);
+        for dependent_id in &ids[1..] {
+            tasks
+                .set_task_dependencies(*dependent_id, vec![ids[0]])
+                .unwrap();
+        }
+
+        let schema = tasks.columns[0].tasks.remove(0);
+        tasks.columns[3].tasks.push(schema);
+        tasks.queue_unblocked_dependents(ids[0]);
+        assert_eq!(tasks.queued_task_count(), 3);
+
+        let claude = AgentProvider::ClaudeCode;
+        let started = tasks.select_queued_tasks_to_start(HashMap::new(), 2, &claude, |_| None);
+        assert_eq!(started, vec![ids[1], ids[2]]);
+    }
+}
@@ -1,16 +1,23 @@
 mod classification;
 mod crud;
+mod dependencies;
 mod movement;
 mod navigation;
 mod queries;
+mod queue;
+mod workflow;
 mod worktree;
 
 use super::state::Task;
+use serde::Serialize;
 
 pub use queries::{get_active_task_count, get_active_tasks, get_done_task_count, get_done_tasks};
 
+#[derive(Serialize)]
 pub struct TaskReference<'a> {
+    #[serde(flatten)]
     pub task: &'a Task,
+    #[serde(rename = "column")]
     pub column_name: &'a str,
     pub column_index: usize,
 }
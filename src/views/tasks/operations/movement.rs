@@ -1,14 +1,135 @@
+use crate::types::PLANNING_COLUMN_INDEX;
 use crate::views::settings::VcsCommand;
-use crate::views::tasks::state::TasksState;
+use crate::views::tasks::state::{
+    QueuePriority, TasksMode, TasksState, WorktreeDeletion, WorktreeDeletionPurpose,
+};
 use uuid::Uuid;
 
 use crate::views::tasks::state::WorktreeSelectionOption;
 use crate::views::worktree::WorktreeInfo;
 
 impl TasksState {
+    /// Steps the task one column at a time through the same movement operations the kanban
+    /// board uses, so entering an agent column still creates a worktree and moving back still
+    /// cleans up auto-created ones. `process_pending` runs after every step to handle the
+    /// pending instance and worktree actions that step queued.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task does not exist or a step could not move it.
+    pub fn move_task_to_column<F>(
+        &mut self,
+        task_id: Uuid,
+        target_column_index: usize,
+        vcs_command: &VcsCommand,
+        mut process_pending: F,
+    ) -> Result<(), String>
+    where
+        F: FnMut(&mut Self),
+    {
+        loop {
+            let (column_index, task_index) = self
+                .find_task_location(task_id)
+                .ok_or_else(|| format!("No task with ID {task_id}"))?;
+
+            if column_index == target_column_index {
+                return Ok(());
+            }
+
+            self.kanban_selected_column = column_index;
+            self.kanban_selected_task = Some(task_index);
+
+            if column_index < target_column_index {
+                self.move_task_next(vcs_command);
+
+                let needs_worktree = matches!(self.mode, TasksMode::SelectWorktree { .. });
+                if needs_worktree {
+                    self.mode = TasksMode::Normal;
+                    self.move_task_to_in_progress_with_worktree(
+                        task_id,
+                        WorktreeSelectionOption::AutoCreate,
+                        vcs_command,
+                    );
+                }
+            } else {
+                self.execute_move_task_previous();
+            }
+
+            if let Some(error_message) = self.error_message.take() {
+                return Err(error_message);
+            }
+
+            process_pending(self);
+
+            if let Some(error_message) = self.error_message.take() {
+                return Err(error_message);
+            }
+
+            let has_moved = self
+                .find_task_location(task_id)
+                .is_some_and(|(new_column_index, _)| new_column_index != column_index);
+            if !has_moved {
+                return Err(format!(
+                    "Task cannot leave the {} column right now",
+                    self.columns[column_index].name
+                ));
+            }
+        }
+    }
+
+    /// Moves each task still in Planning into the first agent column, the same way the board
+    /// would, leaving the board's selection and mode as they were. `process_pending` runs after
+    /// every step as in [`Self::move_task_to_column`]. Returns the tasks that were started.
+    pub fn start_planned_tasks<F>(
+        &mut self,
+        task_ids: &[Uuid],
+        vcs_command: &VcsCommand,
+        mut process_pending: F,
+    ) -> Vec<Uuid>
+    where
+        F: FnMut(&mut Self),
+    {
+        let Some(agent_column_index) = self.agent_column_index() else {
+            return Vec::new();
+        };
+
+        let selected_column = self.kanban_selected_column;
+        let selected_task = self.kanban_selected_task;
+        let mode = std::mem::replace(&mut self.mode, TasksMode::Normal);
+        let mut started = Vec::new();
+
+        for task_id in task_ids {
+            let is_planned = self
+                .find_task_location(*task_id)
+                .is_some_and(|(column_index, _)| column_index == PLANNING_COLUMN_INDEX);
+            if !is_planned {
+                continue;
+            }
+
+            match self.move_task_to_column(
+                *task_id,
+                agent_column_index,
+                vcs_command,
+                &mut process_pending,
+            ) {
+                Ok(()) => started.push(*task_id),
+                Err(error) => self.error_message = Some(error),
+            }
+        }
+
+        let column_length = self.columns[selected_column].tasks.len();
+        self.mode = mode;
+        self.kanban_selected_column = selected_column;
+        self.kanban_selected_task = selected_task
+            .filter(|_| column_length > 0)
+            .map(|task_index| task_index.min(column_length - 1));
+        started
+    }
+
     pub fn move_task_next(&mut self, vcs_command: &VcsCommand) {
-        let can_move_next = self.kanban_selected_column < self.columns.len() - 1;
-        if !can_move_next {
+        let source_column_index = self.kanban_selected_column;
+        let target_column_index = source_column_index + 1;
+        if target_column_index >= self.columns.len() {
             return;
         }
 
@@ -16,56 +137,37 @@ impl TasksState {
             return;
         };
 
-        let is_entering_in_progress =
-            self.kanban_selected_column == 0 && self.kanban_selected_column + 1 == 1;
+        let Some(task) = self.columns[source_column_index].tasks.get(task_index) else {
+            return;
+        };
 
-        if is_entering_in_progress {
-            let task = self.columns[self.kanban_selected_column]
-                .tasks
-                .get(task_index);
+        let is_entering_agent_column =
+            self.is_agent_column(target_column_index) && !self.is_agent_column(source_column_index);
 
-            let is_task_classifying = task.is_some_and(|task| task.is_classifying);
-            if is_task_classifying {
+        if is_entering_agent_column {
+            if task.is_classifying {
                 return;
             }
 
-            let has_worktree = task.is_some_and(|task| task.worktree_info.is_some());
-            if !has_worktree {
-                if let Some(task) = task {
-                    self.begin_worktree_selection_for_task(task.id, vcs_command);
-                }
+            if let Some(message) = self.blocked_message(task) {
+                self.error_message = Some(message);
                 return;
             }
-        }
-
-        let done_column_index = 3;
-        let is_entering_done = self.kanban_selected_column + 1 == done_column_index;
 
-        let Some(mut task) = self.columns[self.kanban_selected_column]
-            .tasks
-            .get(task_index)
-            .cloned()
-        else {
-            return;
-        };
-
-        if is_entering_in_progress {
-            self.pending_instance_creation = Some(task.id);
+            if task.worktree_info.is_none() {
+                self.begin_worktree_selection_for_task(task.id, vcs_command);
+                return;
+            }
         }
 
-        if is_entering_done && let Some(instance_id) = task.instance_id.take() {
-            self.pending_instance_termination = Some(instance_id);
+        if let Some(message) = self.wip_limit_message(target_column_index) {
+            self.error_message = Some(message);
+            return;
         }
 
-        self.columns[self.kanban_selected_column]
-            .tasks
-            .remove(task_index);
-        self.columns[self.kanban_selected_column + 1]
-            .tasks
-            .push(task);
-
-        self.kanban_selected_column += 1;
-        self.kanban_selected_task = Some(self.columns[self.kanban_selected_column].tasks.len() - 1);
+        self.transfer_task(source_column_index, task_index, target_column_index);
+        self.kanban_selected_column = target_column_index;
+        self.kanban_selected_task = Some(self.columns[target_column_index].tasks.len() - 1);
     }
 
     pub fn move_task_previous(&mut self) {
@@ -99,6 +201,11 @@ impl TasksState {
             return;
         };
 
+        if let Some(message) = self.wip_limit_message(self.kanban_selected_column - 1) {
+            self.error_message = Some(message);
+            return;
+        }
+
         if let Some(instance_id) = task.instance_id {
             self.pending_instance_termination = Some(instance_id);
         }
@@ -106,7 +213,13 @@ impl TasksState {
         if let Some(worktree_info) = task.worktree_info.clone() {
             let should_delete_worktree = worktree_info.auto_created;
             if should_delete_worktree {
-                self.pending_worktree_deletion = Some(worktree_info);
+                self.pending_worktree_deletions.push(WorktreeDeletion {
+                    task_id: task.id,
+                    worktree_info,
+                    purpose: WorktreeDeletionPurpose::Moved {
+                        source_column_index: self.kanban_selected_column,
+                    },
+                });
             }
         }
 
@@ -126,77 +239,99 @@ impl TasksState {
         self.kanban_selected_task = Some(self.columns[self.kanban_selected_column].tasks.len() - 1);
     }
 
+    /// Moves the task whose agent just finished from its agent column to the next column.
     pub fn move_task_to_review_by_instance(&mut self, instance_id: Uuid) -> bool {
-        let in_progress_column_index = 1;
-        let review_column_index = 2;
-
-        if self.columns.len() <= review_column_index {
-            return false;
-        }
-
-        let in_progress_column = &mut self.columns[in_progress_column_index];
-        let task_index = in_progress_column
-            .tasks
+        let task_location = self
+            .columns
             .iter()
-            .position(|task| task.instance_id == Some(instance_id));
+            .enumerate()
+            .filter(|(_, column)| column.workflow.starts_agent)
+            .find_map(|(column_index, column)| {
+                column
+                    .tasks
+                    .iter()
+                    .position(|task| task.instance_id == Some(instance_id))
+                    .map(|task_index| (column_index, task_index))
+            });
 
-        let Some(task_index) = task_index else {
+        let Some((column_index, task_index)) = task_location else {
             return false;
         };
 
-        let task = in_progress_column.tasks.remove(task_index);
-        self.columns[review_column_index].tasks.push(task);
+        let next_column_index = column_index + 1;
+        if next_column_index >= self.columns.len() {
+            return false;
+        }
 
+        self.transfer_task(column_index, task_index, next_column_index);
         true
     }
 
-    pub fn move_task_to_done_by_id(&mut self, task_id: Uuid, vcs_command: &VcsCommand) -> bool {
-        let review_column_index = 2;
-        let done_column_index = 3;
+    /// Moves a task in Review to Done. Its branch has already landed and its worktree is gone.
+    pub fn move_task_to_done_by_id(&mut self, task_id: Uuid) -> bool {
+        let (Some(review_column_index), Some(done_column_index)) =
+            (self.review_column_index(), self.done_column_index())
+        else {
+            return false;
+        };
 
-        let Some(task_index) = self
-            .columns
-            .get(review_column_index)
-            .and_then(|column| column.tasks.iter().position(|task| task.id == task_id))
+        let Some(task_index) = self.columns[review_column_index]
+            .tasks
+            .iter()
+            .position(|task| task.id == task_id)
         else {
             return false;
         };
 
-        if self.columns.len() <= done_column_index {
+        if let Some(message) = self.wip_limit_message(done_column_index) {
+            self.error_message = Some(message);
             return false;
         }
 
-        let task = &self.columns[review_column_index].tasks[task_index];
+        self.columns[review_column_index].tasks[task_index].worktree_info = None;
+        self.transfer_task(review_column_index, task_index, done_column_index);
 
-        let has_worktree_to_merge = task.worktree_info.is_some();
-        if has_worktree_to_merge {
-            let merge_result = Self::try_merge_worktree(task);
+        true
+    }
 
-            match merge_result {
-                Some(crate::views::worktree::MergeResult::Success) | None => {}
-                Some(crate::views::worktree::MergeResult::Conflicts { conflicted_files }) => {
-                    let conflict_message = format!(
-                        "Merge conflicts detected in the following files:\n{}\n\nPlease resolve these conflicts and commit the changes.",
-                        conflicted_files.join("\n")
-                    );
-                    self.pending_change_request = Some((task_id, conflict_message));
-                    return false;
-                }
-            }
+    /// Gives the task back the worktree a move let go of and returns it to the column it left,
+    /// for when deleting the worktree failed. A task that got another worktree since stays put.
+    pub fn restore_worktree(
+        &mut self,
+        task_id: Uuid,
+        worktree_info: WorktreeInfo,
+        column_index: usize,
+    ) {
+        let Some((current_column_index, task_index)) = self.find_task_location(task_id) else {
+            return;
+        };
+        if self.columns[current_column_index].tasks[task_index]
+            .worktree_info
+            .is_some()
+        {
+            return;
         }
 
-        let task = &self.columns[review_column_index].tasks[task_index];
-        Self::try_cleanup_worktree(task, vcs_command);
-
-        let mut task = self.columns[review_column_index].tasks.remove(task_index);
-
-        if let Some(instance_id) = task.instance_id.take() {
-            self.pending_instance_termination = Some(instance_id);
-        }
+        let mut task = self.columns[current_column_index].tasks.remove(task_index);
+        task.worktree_info = Some(worktree_info);
+        let column_index = column_index.min(self.columns.len() - 1);
+        self.columns[column_index].tasks.push(task);
+    }
 
-        self.columns[done_column_index].tasks.push(task);
+    /// Puts a task that was just started back in Planning with its worktree and queue
+    /// priority, for when no agent could be started for it.
+    pub fn return_task_to_planning(
+        &mut self,
+        task_id: Uuid,
+        queue_priority: Option<QueuePriority>,
+    ) {
+        let Some((column_index, task_index)) = self.find_task_location(task_id) else {
+            return;
+        };
 
-        true
+        let mut task = self.columns[column_index].tasks.remove(task_index);
+        task.queue_priority = queue_priority;
+        self.columns[PLANNING_COLUMN_INDEX].tasks.push(task);
     }
 
     pub fn move_task_to_in_progress(
@@ -204,35 +339,28 @@ impl TasksState {
         task_index: usize,
         vcs_command: &VcsCommand,
     ) -> Option<Uuid> {
-        let review_column_index = 2;
-        let in_progress_column_index = 1;
+        let review_column_index = self.review_column_index()?;
+        let agent_column_index = self.agent_column_index()?;
 
-        if self.columns.len() <= review_column_index {
-            return None;
-        }
+        let task = self.columns[review_column_index].tasks.get(task_index)?;
 
-        if task_index >= self.columns[review_column_index].tasks.len() {
+        if task.is_classifying {
             return None;
         }
 
-        let task = self.columns[review_column_index].tasks.get(task_index);
-
-        let is_task_classifying = task.is_some_and(|task| task.is_classifying);
-        if is_task_classifying {
+        if task.worktree_info.is_none() {
+            self.begin_worktree_selection_for_task(task.id, vcs_command);
             return None;
         }
 
-        let has_worktree = task.is_some_and(|task| task.worktree_info.is_some());
-        if !has_worktree {
-            if let Some(task) = task {
-                self.begin_worktree_selection_for_task(task.id, vcs_command);
-            }
+        if let Some(message) = self.wip_limit_message(agent_column_index) {
+            self.error_message = Some(message);
             return None;
         }
 
         let task = self.columns[review_column_index].tasks.remove(task_index);
         let instance_id = task.instance_id;
-        self.columns[in_progress_column_index].tasks.push(task);
+        self.columns[agent_column_index].tasks.push(task);
 
         let review_tasks_remaining = self.columns[review_column_index].tasks.len();
         if review_tasks_remaining == 0 {
@@ -249,93 +377,66 @@ impl TasksState {
         task_id: Uuid,
         vcs_command: &VcsCommand,
     ) -> Option<Uuid> {
-        let in_progress_column_index = 1;
-
-        let task_location = self
-            .columns
-            .iter()
-            .enumerate()
-            .find_map(|(column_index, column)| {
-                column
-                    .tasks
-                    .iter()
-                    .position(|task| task.id == task_id)
-                    .map(|task_index| (column_index, task_index))
-            });
-
-        let (source_column_index, task_index) = task_location?;
+        let (source_column_index, task_index) = self.find_task_location(task_id)?;
+        let task = &self.columns[source_column_index].tasks[task_index];
 
-        let is_already_in_progress = source_column_index == in_progress_column_index;
+        let is_already_in_progress = self.is_agent_column(source_column_index);
         if is_already_in_progress {
-            let task = self.columns[in_progress_column_index]
-                .tasks
-                .iter()
-                .find(|task| task.id == task_id);
-
-            let has_worktree = task.is_some_and(|task| task.worktree_info.is_some());
-            if !has_worktree {
-                if let Some(task) = task {
-                    self.begin_worktree_selection_for_task(task.id, vcs_command);
-                }
+            if task.worktree_info.is_none() {
+                self.begin_worktree_selection_for_task(task_id, vcs_command);
                 return None;
             }
 
-            let instance_id = task.and_then(|task| task.instance_id);
-            return instance_id;
+            return task.instance_id;
         }
 
-        let task = self.columns[source_column_index].tasks.get(task_index);
+        if task.is_classifying {
+            return None;
+        }
 
-        let is_task_classifying = task.is_some_and(|task| task.is_classifying);
-        if is_task_classifying {
+        if task.worktree_info.is_none() {
+            self.begin_worktree_selection_for_task(task_id, vcs_command);
             return None;
         }
 
-        let has_worktree = task.is_some_and(|task| task.worktree_info.is_some());
-        if !has_worktree {
-            if let Some(task) = task {
-                self.begin_worktree_selection_for_task(task.id, vcs_command);
-            }
+        let agent_column_index = self.agent_column_index()?;
+        if let Some(message) = self.wip_limit_message(agent_column_index) {
+            self.error_message = Some(message);
             return None;
         }
 
         let task = self.columns[source_column_index].tasks.remove(task_index);
         let instance_id = task.instance_id;
         self.pending_instance_creation = Some(task.id);
-        self.columns[in_progress_column_index].tasks.push(task);
+        self.columns[agent_column_index].tasks.push(task);
 
         instance_id
     }
 
+    /// Gives the task a worktree and moves it into the agent column it was about to enter:
+    /// the next one from where it is, or the first one when none comes after it.
     pub fn move_task_to_in_progress_with_worktree(
         &mut self,
         task_id: Uuid,
         worktree_option: WorktreeSelectionOption,
         vcs_command: &VcsCommand,
     ) -> Option<Uuid> {
-        let in_progress_column_index = 1;
+        let (source_column_index, task_index) = self.find_task_location(task_id)?;
+        let agent_column_index = self.agent_column_for(source_column_index)?;
 
-        let task_location = self
-            .columns
-            .iter()
-            .enumerate()
-            .find_map(|(column_index, column)| {
-                column
-                    .tasks
-                    .iter()
-                    .position(|task| task.id == task_id)
-                    .map(|task_index| (column_index, task_index))
-            });
-
-        let (source_column_index, task_index) = task_location?;
+        let task = &self.columns[source_column_index].tasks[task_index];
+        if task.is_classifying {
+            return None;
+        }
 
-        let task = self.columns[source_column_index].tasks.get(task_index);
-        let is_task_classifying = task.is_some_and(|task| task.is_classifying);
-        if is_task_classifying {
+        if source_column_index != agent_column_index
+            && let Some(message) = self.wip_limit_message(agent_column_index)
+        {
+            self.error_message = Some(message);
             return None;
         }
 
-        let task_title = task.map(|task| task.title.clone())?;
+        let task_title = task.title.clone();
         let worktree_info = match worktree_option {
             WorktreeSelectionOption::AutoCreate => {
                 match Self::create_worktree_for_new_task(&task_title, &task_id, vcs_command) {
@@ -354,19 +455,20 @@ impl TasksState {
 
         let mut task = self.columns[source_column_index].tasks.remove(task_index);
         task.worktree_info = Some(worktree_info);
+        task.queue_priority = None;
 
-        if source_column_index == in_progress_column_index {
+        if source_column_index == agent_column_index {
             let instance_id = task.instance_id;
-            self.columns[in_progress_column_index].tasks.push(task);
+            self.columns[agent_column_index].tasks.push(task);
             return instance_id;
         }
 
         let instance_id = task.instance_id;
         self.pending_instance_creation = Some(task.id);
-        self.columns[in_progress_column_index].tasks.push(task);
+        self.columns[agent_column_index].tasks.push(task);
 
-        if source_column_index == 2 {
-            let review_tasks_remaining = self.columns[2].tasks.len();
+        if Some(source_column_index) == self.review_column_index() {
+            let review_tasks_remaining = self.columns[source_column_index].tasks.len();
             if review_tasks_remaining == 0 {
                 self.kanban_selected_task = None;
             } else if task_index >= review_tasks_remaining {
@@ -376,4 +478,164 @@ impl TasksState {
 
         instance_id
     }
+
+    fn agent_column_for(&self, source_column_index: usize) -> Option<usize> {
+        self.columns
+            .iter()
+            .skip(source_column_index)
+            .position(|column| column.workflow.starts_agent)
+            .map(|offset| source_column_index + offset)
+            .or_else(|| self.agent_column_index())
+    }
+
+    /// Moves the task at `task_index` of one column to the end of another and applies the
+    /// rules of the column it enters.
+    fn transfer_task(
+        &mut self,
+        source_column_index: usize,
+        task_index: usize,
+        target_column_index: usize,
+    ) {
+        let mut task = self.columns[source_column_index].tasks.remove(task_index);
+        let workflow = self.columns[target_column_index].workflow;
+
+        if self.is_agent_column(target_column_index) && !self.is_agent_column(source_column_index) {
+            self.pending_instance_creation = Some(task.id);
+        }
+
+        if (workflow.is_terminal || workflow.removes_worktree)
+            && let Some(instance_id) = task.instance_id.take()
+        {
+            self.pending_instance_termination = Some(instance_id);
+        }
+
+        // Worktrees the user picked outlive the task, so it keeps pointing at them
+        if workflow.removes_worktree
+            && task
+                .worktree_info
+                .as_ref()
+                .is_some_and(|worktree_info| worktree_info.auto_created)
+            && let Some(worktree_info) = task.worktree_info.take()
+        {
+            self.pending_worktree_deletions.push(WorktreeDeletion {
+                task_id: task.id,
+                worktree_info,
+                purpose: WorktreeDeletionPurpose::Moved {
+                    source_column_index,
+                },
+            });
+        }
+
+        let task_id = task.id;
+        self.columns[target_column_index].tasks.push(task);
+
+        if workflow.is_terminal {
+            self.queue_unblocked_dependents(task_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::tasks::state::TaskType;
+
+    #[test]
+    fn test_move_task_to_column_back_to_planning() {
+        let mut tasks = TasksState::new();
+        tasks.add_task_to_planning("First".to_string(), String::new(), TaskType::Task);
+        let task = tasks.columns[0].tasks.remove(0);
+        let task_id = task.id;
+        tasks.columns[2].tasks.push(task);
+
+        let mut steps = 0;
+        let result = tasks.move_task_to_column(task_id, 0, &VcsCommand::Git, |_| steps += 1);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(steps, 2);
+        assert_eq!(tasks.find_task_location(task_id), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_removes_worktree_column_keeps_user_selected_worktrees() {
+        let mut tasks = TasksState::new();
+        tasks.columns[2].workflow.removes_worktree = true;
+        for (title, worktree_info) in [
+            (
+                "Picked",
+                WorktreeInfo::new_existing("feature".to_string(), "/tmp/feature".into()),
+            ),
+            (
+                "Created",
+                WorktreeInfo::new("chloe/created".to_string(), "/tmp/created".into()),
+            ),
+        ] {
+            tasks.add_task_to_planning(title.to_string(), String::new(), TaskType::Task);
+            let mut task = tasks.columns[0].tasks.remove(0);
+            task.worktree_info = Some(worktree_info);
+            tasks.columns[1].tasks.push(task);
+        }
+
+        tasks.transfer_task(1, 0, 2);
+        assert!(tasks.pending_worktree_deletions.is_empty());
+        assert!(tasks.columns[2].tasks[0].worktree_info.is_some());
+
+        tasks.transfer_task(1, 0, 2);
+        assert_eq!(
+            tasks
+                .pending_worktree_deletions
+                .iter()
+                .map(|deletion| deletion.worktree_info.branch_name.as_str())
+                .collect::<Vec<_>>(),
+            ["chloe/created"]
+        );
+        assert!(tasks.columns[2].tasks[1].worktree_info.is_none());
+    }
+
+    #[test]
+    fn test_restore_worktree_returns_the_task_with_its_worktree() {
+        let mut tasks = TasksState::new();
+        tasks.columns[2].workflow.removes_worktree = true;
+        tasks.add_task_to_planning("Created".to_string(), String::new(), TaskType::Task);
+        let mut task = tasks.columns[0].tasks.remove(0);
+        let task_id = task.id;
+        task.worktree_info = Some(WorktreeInfo::new(
+            "chloe/created".to_string(),
+            "/tmp/created".into(),
+        ));
+        tasks.columns[1].tasks.push(task);
+
+        tasks.transfer_task(1, 0, 2);
+        let deletion = tasks.pending_worktree_deletions.remove(0);
+        assert_eq!(
+            deletion.purpose,
+            WorktreeDeletionPurpose::Moved {
+                source_column_index: 1
+            }
+        );
+
+        tasks.restore_worktree(task_id, deletion.worktree_info, 1);
+        assert_eq!(tasks.find_task_location(task_id), Some((1, 0)));
+        assert!(tasks.columns[1].tasks[0].worktree_info.is_some());
+    }
+
+    #[test]
+    fn test_return_task_to_planning_keeps_its_worktree_and_queue_priority() {
+        let mut tasks = TasksState::new();
+        tasks.add_task_to_planning("Started".to_string(), String::new(), TaskType::Task);
+        let mut task = tasks.columns[0].tasks.remove(0);
+        let task_id = task.id;
+        task.worktree_info = Some(WorktreeInfo::new(
+            "chloe/started".to_string(),
+            "/tmp/started".into(),
+        ));
+        tasks.columns[1].tasks.push(task);
+
+        tasks.return_task_to_planning(task_id, Some(QueuePriority::High));
+
+        assert_eq!(tasks.find_task_location(task_id), Some((0, 0)));
+        let task = &tasks.columns[0].tasks[0];
+        assert!(task.worktree_info.is_some());
+        assert_eq!(task.queue_priority, Some(QueuePriority::High));
+    }
 }
@@ -1,5 +1,9 @@
-use super::queries::{get_active_task_count, get_done_task_count};
+use super::TaskReference;
+use super::queries::{
+    get_active_task_count, get_active_tasks, get_done_task_count, get_done_tasks,
+};
 use crate::views::tasks::state::{FocusPanel, TasksMode, TasksState};
+use uuid::Uuid;
 
 impl TasksState {
     pub fn next_column(&mut self) {
@@ -26,6 +30,16 @@ impl TasksState {
         }
     }
 
+    /// Selects the `task`th task of `column` on the board, or nothing in it when it has fewer.
+    pub fn select_kanban_task(&mut self, column: usize, task: usize) {
+        let Some(task_count) = self.columns.get(column).map(|column| column.tasks.len()) else {
+            return;
+        };
+
+        self.kanban_selected_column = column;
+        self.kanban_selected_task = (task < task_count).then_some(task);
+    }
+
     pub fn next_task(&mut self) {
         let column = &self.columns[self.kanban_selected_column];
         if column.tasks.is_empty() {
@@ -54,6 +68,48 @@ impl TasksState {
         });
     }
 
+    /// Selects the task in both views, switching the focus view to the panel listing it.
+    pub fn select_task_by_id(&mut self, task_id: Uuid) -> bool {
+        let Some((column_index, task_index)) =
+            self.columns
+                .iter()
+                .enumerate()
+                .find_map(|(column_index, column)| {
+                    let task_index = column.tasks.iter().position(|task| task.id == task_id)?;
+                    Some((column_index, task_index))
+                })
+        else {
+            return false;
+        };
+
+        self.kanban_selected_column = column_index;
+        self.kanban_selected_task = Some(task_index);
+
+        let position = |tasks: Vec<_>| {
+            tasks
+                .iter()
+                .position(|reference: &TaskReference| reference.task.id == task_id)
+        };
+        if let Some(index) = position(get_active_tasks(&self.columns)) {
+            self.focus_panel = FocusPanel::ActiveTasks;
+            self.focus_active_index = index;
+        } else if let Some(index) = position(get_done_tasks(&self.columns)) {
+            self.focus_panel = FocusPanel::DoneTasks;
+            self.focus_done_index = index;
+        }
+        self.focus_details_scroll = 0;
+        true
+    }
+
+    pub const fn select_focus_task(&mut self, panel: FocusPanel, index: usize) {
+        self.focus_panel = panel;
+        match panel {
+            FocusPanel::ActiveTasks => self.focus_active_index = index,
+            FocusPanel::DoneTasks => self.focus_done_index = index,
+        }
+        self.focus_details_scroll = 0;
+    }
+
     pub fn focus_select_next(&mut self) {
         let active_count = get_active_task_count(&self.columns);
         let done_count = get_done_task_count(&self.columns);
@@ -1,68 +1,217 @@
 use super::TaskReference;
 use crate::views::tasks::state::{Column, TasksState};
+use uuid::Uuid;
 
 impl TasksState {
     #[must_use]
     pub fn find_task_index_by_id(&self, task_id: uuid::Uuid) -> Option<usize> {
-        let review_column_index = 2;
         self.columns
-            .get(review_column_index)?
+            .get(self.review_column_index()?)?
             .tasks
             .iter()
             .position(|task| task.id == task_id)
     }
+
+    #[must_use]
+    pub fn find_task_location(&self, task_id: Uuid) -> Option<(usize, usize)> {
+        self.columns
+            .iter()
+            .enumerate()
+            .find_map(|(column_index, column)| {
+                column
+                    .tasks
+                    .iter()
+                    .position(|task| task.id == task_id)
+                    .map(|task_index| (column_index, task_index))
+            })
+    }
+
+    #[must_use]
+    pub fn find_task_reference(&self, task_id: Uuid) -> Option<TaskReference<'_>> {
+        let (column_index, task_index) = self.find_task_location(task_id)?;
+        let column = &self.columns[column_index];
+        Some(TaskReference {
+            task: &column.tasks[task_index],
+            column_name: &column.name,
+            column_index,
+        })
+    }
+
+    #[must_use]
+    pub fn list_task_references(&self, column_filter: Option<usize>) -> Vec<TaskReference<'_>> {
+        self.columns
+            .iter()
+            .enumerate()
+            .filter(|(column_index, _)| column_filter.is_none_or(|filter| filter == *column_index))
+            .flat_map(|(column_index, column)| {
+                column.tasks.iter().map(move |task| TaskReference {
+                    task,
+                    column_name: &column.name,
+                    column_index,
+                })
+            })
+            .collect()
+    }
+
+    /// Resolves a full task ID or a unique prefix of one, so callers can reuse the short IDs
+    /// shown by `chloe task list`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no task matches or the prefix is ambiguous.
+    pub fn resolve_task_reference(&self, reference: &str) -> Result<Uuid, String> {
+        if let Ok(task_id) = Uuid::parse_str(reference) {
+            return self
+                .find_task_by_id(task_id)
+                .map(|task| task.id)
+                .ok_or_else(|| format!("No task with ID {task_id}"));
+        }
+
+        let prefix = reference.to_lowercase();
+        let matches: Vec<Uuid> = self
+            .columns
+            .iter()
+            .flat_map(|column| &column.tasks)
+            .filter(|task| task.id.to_string().starts_with(&prefix))
+            .map(|task| task.id)
+            .collect();
+
+        match matches.as_slice() {
+            [] => Err(format!("No task matches '{reference}'")),
+            [task_id] => Ok(*task_id),
+            _ => Err(format!(
+                "'{reference}' matches {} tasks, use a longer prefix",
+                matches.len()
+            )),
+        }
+    }
+
+    /// Resolves a column index or a column name, ignoring case and punctuation so
+    /// "in-progress" matches "In Progress".
+    ///
+    /// # Errors
+    ///
+    /// Returns an error listing the available columns if nothing matches.
+    pub fn resolve_column_reference(&self, reference: &str) -> Result<usize, String> {
+        if let Ok(index) = reference.parse::<usize>() {
+            if index < self.columns.len() {
+                return Ok(index);
+            }
+            return Err(format!("Column index {index} is out of range"));
+        }
+
+        let normalized_reference = normalize_column_name(reference);
+        self.columns
+            .iter()
+            .position(|column| normalize_column_name(&column.name) == normalized_reference)
+            .ok_or_else(|| {
+                let available: Vec<&str> = self
+                    .columns
+                    .iter()
+                    .map(|column| column.name.as_str())
+                    .collect();
+                format!(
+                    "Unknown column '{reference}'. Available columns: {}",
+                    available.join(", ")
+                )
+            })
+    }
+}
+
+pub(super) fn normalize_column_name(name: &str) -> String {
+    name.chars()
+        .filter(|character| character.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
 }
 
 #[must_use]
 pub fn get_active_task_count(columns: &[Column]) -> usize {
     columns
         .iter()
-        .take(3)
+        .filter(|column| !column.workflow.is_terminal)
         .map(|column| column.tasks.len())
         .sum()
 }
 
 #[must_use]
 pub fn get_done_task_count(columns: &[Column]) -> usize {
-    columns.get(3).map_or(0, |column| column.tasks.len())
+    columns
+        .iter()
+        .filter(|column| column.workflow.is_terminal)
+        .map(|column| column.tasks.len())
+        .sum()
 }
 
 #[must_use]
 pub fn get_active_tasks(columns: &[Column]) -> Vec<TaskReference<'_>> {
-    let mut tasks = Vec::new();
-    let active_column_indices = [0, 1, 2];
-
-    for &column_index in &active_column_indices {
-        let Some(column) = columns.get(column_index) else {
-            continue;
-        };
-        for task in &column.tasks {
-            tasks.push(TaskReference {
+    collect_task_references(columns, |column| !column.workflow.is_terminal)
+}
+
+#[must_use]
+pub fn get_done_tasks(columns: &[Column]) -> Vec<TaskReference<'_>> {
+    collect_task_references(columns, |column| column.workflow.is_terminal)
+}
+
+fn collect_task_references(
+    columns: &[Column],
+    include_column: impl Fn(&Column) -> bool,
+) -> Vec<TaskReference<'_>> {
+    columns
+        .iter()
+        .enumerate()
+        .filter(|(_, column)| include_column(column))
+        .flat_map(|(column_index, column)| {
+            column.tasks.iter().map(move |task| TaskReference {
                 task,
                 column_name: &column.name,
                 column_index,
-            });
-        }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::tasks::state::TaskType;
+
+    #[test]
+    fn test_resolve_task_reference_by_prefix() {
+        let mut tasks = TasksState::new();
+        tasks.add_task_to_planning("First".to_string(), String::new(), TaskType::Task);
+        let task_id = tasks.columns[0].tasks[0].id;
+        let prefix = &task_id.to_string()[..8];
+
+        assert_eq!(tasks.resolve_task_reference(prefix), Ok(task_id));
+        assert_eq!(
+            tasks.resolve_task_reference(&task_id.to_string()),
+            Ok(task_id)
+        );
     }
 
-    tasks
-}
+    #[test]
+    fn test_resolve_task_reference_unknown() {
+        let mut tasks = TasksState::new();
+        tasks.add_task_to_planning("First".to_string(), String::new(), TaskType::Task);
 
-#[must_use]
-pub fn get_done_tasks(columns: &[Column]) -> Vec<TaskReference<'_>> {
-    let mut tasks = Vec::new();
-    let done_column_index = 3;
-
-    let Some(column) = columns.get(done_column_index) else {
-        return tasks;
-    };
-    for task in &column.tasks {
-        tasks.push(TaskReference {
-            task,
-            column_name: &column.name,
-            column_index: done_column_index,
-        });
+        assert!(tasks.resolve_task_reference("zzzz").is_err());
+        assert!(
+            tasks
+                .resolve_task_reference(&Uuid::new_v4().to_string())
+                .is_err()
+        );
     }
 
-    tasks
+    #[test]
+    fn test_resolve_column_reference_by_name_and_index() {
+        let tasks = TasksState::new();
+
+        assert_eq!(tasks.resolve_column_reference("planning"), Ok(0));
+        assert_eq!(tasks.resolve_column_reference("in-progress"), Ok(1));
+        assert_eq!(tasks.resolve_column_reference("In Progress"), Ok(1));
+        assert_eq!(tasks.resolve_column_reference("3"), Ok(3));
+        assert!(tasks.resolve_column_reference("9").is_err());
+        assert!(tasks.resolve_column_reference("qa").is_err());
+    }
 }
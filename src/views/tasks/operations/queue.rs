@@ -0,0 +1,141 @@
+use crate::types::{AgentProvider, PLANNING_COLUMN_INDEX};
+use crate::views::tasks::state::{QueuePriority, Task, TasksState};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+impl TasksState {
+    /// Puts a planned task in the auto-run queue with `priority`, or takes it out with `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task does not exist or is not in the first column.
+    pub fn set_task_queue_priority(
+        &mut self,
+        task_id: Uuid,
+        priority: Option<QueuePriority>,
+    ) -> Result<(), String> {
+        let (column_index, task_index) = self
+            .find_task_location(task_id)
+            .ok_or_else(|| format!("No task with ID {task_id}"))?;
+        if column_index != PLANNING_COLUMN_INDEX && priority.is_some() {
+            return Err(format!(
+                "Only tasks in {} can be queued",
+                self.columns[PLANNING_COLUMN_INDEX].name
+            ));
+        }
+
+        self.columns[column_index].tasks[task_index].queue_priority = priority;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn queued_task_count(&self) -> usize {
+        self.columns
+            .get(PLANNING_COLUMN_INDEX)
+            .map_or(0, |planning| {
+                planning
+                    .tasks
+                    .iter()
+                    .filter(|task| task.queue_priority.is_some())
+                    .count()
+            })
+    }
+
+    /// Queued tasks that could start now, in the order the queue starts them: by priority,
+    /// then by their place in the column.
+    #[must_use]
+    pub fn startable_queued_tasks(&self) -> Vec<&Task> {
+        let Some(planning) = self.columns.get(PLANNING_COLUMN_INDEX) else {
+            return Vec::new();
+        };
+
+        let mut tasks: Vec<&Task> = planning
+            .tasks
+            .iter()
+            .filter(|task| task.queue_priority.is_some() && !task.is_classifying)
+            .filter(|task| !self.is_task_blocked(task))
+            .collect();
+        tasks.sort_by_key(|task| task.queue_priority);
+        tasks
+    }
+
+    /// Picks the queued tasks to start so that no more than `max_running` agents run in total,
+    /// no more than `provider_limit` of any one provider, and the agent column stays within its
+    /// WIP limit. `running` counts the agents already running per provider.
+    #[must_use]
+    pub fn select_queued_tasks_to_start(
+        &self,
+        mut running: HashMap<AgentProvider, usize>,
+        max_running: usize,
+        default_provider: &AgentProvider,
+        provider_limit: impl Fn(&AgentProvider) -> Option<usize>,
+    ) -> Vec<Uuid> {
+        let mut total_running: usize = running.values().sum();
+        let mut agent_column_capacity = self
+            .agent_column_index()
+            .and_then(|column_index| self.wip_capacity(column_index));
+        let mut selected = Vec::new();
+
+        for task in self.startable_queued_tasks() {
+            if total_running >= max_running || agent_column_capacity == Some(0) {
+                break;
+            }
+
+            let provider = task.provider.as_ref().unwrap_or(default_provider);
+            let provider_running = running.entry(provider.clone()).or_default();
+            if provider_limit(provider).is_some_and(|limit| *provider_running >= limit) {
+                continue;
+            }
+
+            *provider_running += 1;
+            total_running += 1;
+            agent_column_capacity = agent_column_capacity.map(|capacity| capacity - 1);
+            selected.push(task.id);
+        }
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::tasks::state::TaskType;
+
+    #[test]
+    fn test_queue_respects_priority_and_limits() {
+        let mut tasks = TasksState::new();
+        for title in ["Low", "Gemini", "High", "Normal", "Unqueued"] {
+            tasks.add_task_to_planning(title.to_string(), String::new(), TaskType::Task);
+        }
+        let ids: Vec<Uuid> = tasks.columns[0].tasks.iter().map(|task| task.id).collect();
+        let priorities = [
+            Some(QueuePriority::Low),
+            Some(QueuePriority::High),
+            Some(QueuePriority::High),
+            Some(QueuePriority::Normal),
+            None,
+        ];
+        for (task_id, priority) in ids.iter().zip(priorities) {
+            tasks.set_task_queue_priority(*task_id, priority).unwrap();
+        }
+        tasks.columns[0].tasks[1].provider = Some(AgentProvider::Gemini);
+
+        let claude = AgentProvider::ClaudeCode;
+        let gemini_limit =
+            |provider: &AgentProvider| (*provider == AgentProvider::Gemini).then_some(1);
+
+        let started = tasks.select_queued_tasks_to_start(HashMap::new(), 3, &claude, gemini_limit);
+        assert_eq!(started, vec![ids[1], ids[2], ids[3]]);
+
+        let busy_gemini = HashMap::from([(AgentProvider::Gemini, 1)]);
+        let started = tasks.select_queued_tasks_to_start(busy_gemini, 3, &claude, gemini_limit);
+        assert_eq!(started, vec![ids[2], ids[3]]);
+
+        let busy = HashMap::from([(claude.clone(), 3)]);
+        assert!(
+            tasks
+                .select_queued_tasks_to_start(busy, 3, &claude, gemini_limit)
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use super::queries::normalize_column_name;
+use crate::types::{ColumnDefinition, ColumnWorkflow};
+use crate::views::tasks::state::{Column, TasksState};
+
+impl TasksState {
+    /// Lays the board out as `definitions` describe, keeping each task in the column with the
+    /// same name. Tasks in columns that no longer exist go back to the first column.
+    pub fn apply_column_definitions(&mut self, definitions: &[ColumnDefinition]) {
+        if definitions.is_empty() {
+            return;
+        }
+
+        let mut columns: Vec<Column> = definitions.iter().cloned().map(Column::from).collect();
+        for old_column in std::mem::take(&mut self.columns) {
+            let normalized_name = normalize_column_name(&old_column.name);
+            let target = columns
+                .iter_mut()
+                .find(|column| normalize_column_name(&column.name) == normalized_name);
+            match target {
+                Some(column) => column.tasks.extend(old_column.tasks),
+                None => columns[0].tasks.extend(old_column.tasks),
+            }
+        }
+        self.columns = columns;
+
+        self.kanban_selected_column = self.kanban_selected_column.min(self.columns.len() - 1);
+        let column_length = self.columns[self.kanban_selected_column].tasks.len();
+        self.kanban_selected_task = self
+            .kanban_selected_task
+            .filter(|_| column_length > 0)
+            .map(|task_index| task_index.min(column_length - 1));
+    }
+
+    /// Keeps the board's columns and tasks as they are, giving each column named like a
+    /// default column that column's workflow if it has none. A saved board has no workflows,
+    /// so this is what it runs with while the configured columns are invalid.
+    pub fn apply_default_workflows(&mut self) {
+        let defaults = ColumnDefinition::defaults();
+        for column in &mut self.columns {
+            if column.workflow != ColumnWorkflow::default() {
+                continue;
+            }
+            let normalized_name = normalize_column_name(&column.name);
+            if let Some(definition) = defaults
+                .iter()
+                .find(|definition| normalize_column_name(&definition.name) == normalized_name)
+            {
+                column.workflow = definition.workflow;
+            }
+        }
+    }
+
+    /// The first column that starts agents, where tasks go when they are started.
+    #[must_use]
+    pub fn agent_column_index(&self) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|column| column.workflow.starts_agent)
+    }
+
+    /// The column right after the first agent column, where tasks wait for review once their
+    /// agent finishes. Boards whose agents hand their tasks straight to a terminal column have
+    /// no review column.
+    #[must_use]
+    pub fn review_column_index(&self) -> Option<usize> {
+        let review_column_index = self.agent_column_index()? + 1;
+        let column = self.columns.get(review_column_index)?;
+        let is_review_column = !column.workflow.starts_agent && !column.workflow.is_terminal;
+        is_review_column.then_some(review_column_index)
+    }
+
+    /// The first terminal column, where merged tasks go.
+    #[must_use]
+    pub fn done_column_index(&self) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|column| column.workflow.is_terminal)
+    }
+
+    #[must_use]
+    pub fn is_agent_column(&self, column_index: usize) -> bool {
+        self.columns
+            .get(column_index)
+            .is_some_and(|column| column.workflow.starts_agent)
+    }
+
+    /// Explains why no task can move into `column_index` right now, or `None` when the
+    /// column has room.
+    #[must_use]
+    pub fn wip_limit_message(&self, column_index: usize) -> Option<String> {
+        let column = self.columns.get(column_index)?;
+        let wip_limit = column.workflow.wip_limit?;
+        (column.tasks.len() >= wip_limit).then(|| {
+            format!(
+                "{} is at its WIP limit of {wip_limit} task{}",
+                column.name,
+                if wip_limit == 1 { "" } else { "s" }
+            )
+        })
+    }
+
+    /// How many more tasks `column_index` takes before reaching its WIP limit, or `None` when
+    /// it has no limit.
+    #[must_use]
+    pub fn wip_capacity(&self, column_index: usize) -> Option<usize> {
+        let column = self.columns.get(column_index)?;
+        let wip_limit = column.workflow.wip_limit?;
+        Some(wip_limit.saturating_sub(column.tasks.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::ColumnDefinition;
+    use crate::views::tasks::state::{TaskType, TasksState};
+
+    #[test]
+    fn test_apply_column_definitions_keeps_tasks_by_name() {
+        let mut tasks = TasksState::new();
+        tasks.add_task_to_planning("Planned".to_string(), String::new(), TaskType::Task);
+        tasks.add_task_to_planning("Reviewed".to_string(), String::new(), TaskType::Task);
+        let reviewed = tasks.columns[0].tasks.remove(1);
+        tasks.columns[2].tasks.push(reviewed);
+
+        let mut definitions = vec![
+            ColumnDefinition::new("Planning"),
+            ColumnDefinition::new("Doing").starts_agent(),
+            ColumnDefinition::new("QA"),
+            ColumnDefinition::new("Done").terminal(),
+        ];
+        definitions[2].workflow.wip_limit = Some(1);
+        tasks.apply_column_definitions(&definitions);
+
+        assert_eq!(tasks.columns.len(), 4);
+        assert_eq!(tasks.columns[0].tasks.len(), 2);
+        assert_eq!(tasks.agent_column_index(), Some(1));
+        assert_eq!(tasks.review_column_index(), Some(2));
+        assert_eq!(tasks.done_column_index(), Some(3));
+        assert_eq!(tasks.wip_capacity(2), Some(1));
+        assert!(tasks.wip_limit_message(2).is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::views::settings::VcsCommand;
-use crate::views::tasks::state::{Task, TasksMode, TasksState, WorktreeSelectionOption};
+use crate::views::tasks::state::{TasksMode, TasksState, WorktreeSelectionOption};
 use crate::views::worktree::WorktreeInfo;
 use crate::views::worktree::operations::list_worktrees;
 use uuid::Uuid;
@@ -99,43 +99,4 @@ impl TasksState {
 
         crate::views::worktree::create_worktree(&repository_root, task_title, task_id, vcs_command)
     }
-
-    #[must_use]
-    pub fn try_merge_worktree(task: &Task) -> Option<crate::views::worktree::MergeResult> {
-        let worktree_info = task.worktree_info.as_ref()?;
-
-        let was_auto_created = worktree_info.auto_created;
-        if !was_auto_created {
-            return None;
-        }
-
-        let current_directory = std::env::current_dir().ok()?;
-        let repository_root =
-            crate::views::worktree::find_repository_root(&current_directory).ok()?;
-
-        crate::views::worktree::merge_worktree_to_main(&repository_root, worktree_info).ok()
-    }
-
-    pub fn try_cleanup_worktree(task: &Task, vcs_command: &VcsCommand) {
-        let Some(worktree_info) = &task.worktree_info else {
-            return;
-        };
-
-        let was_auto_created = worktree_info.auto_created;
-        if !was_auto_created {
-            return;
-        }
-
-        let Ok(current_directory) = std::env::current_dir() else {
-            return;
-        };
-
-        let Ok(repository_root) = crate::views::worktree::find_repository_root(&current_directory)
-        else {
-            return;
-        };
-
-        let _ =
-            crate::views::worktree::delete_worktree(&repository_root, worktree_info, vcs_command);
-    }
 }
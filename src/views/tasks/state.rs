@@ -1,11 +1,16 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, Utc};
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::PathBuf;
+use std::time::Instant;
 use uuid::Uuid;
 
-use crate::types::{AgentProvider, DetectedProvider};
+use crate::providers::UsageReport;
+use crate::types::{
+    AgentProvider, ColumnDefinition, ColumnWorkflow, DetectedProvider, PermissionRule, TaskUsage,
+};
+use crate::views::settings::MergeStrategy;
 use crate::views::worktree::WorktreeInfo;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -41,7 +46,10 @@ pub struct TasksState {
     #[serde(skip)]
     pub pending_instance_termination: Option<Uuid>,
     #[serde(skip)]
-    pub pending_worktree_deletion: Option<WorktreeInfo>,
+    pub pending_worktree_deletions: Vec<WorktreeDeletion>,
+    /// Tasks whose worktree is being deleted in the background.
+    #[serde(skip)]
+    pub removing_worktrees: HashSet<Uuid>,
     #[serde(skip)]
     pub pending_instance_creation: Option<Uuid>,
     #[serde(skip)]
@@ -50,6 +58,10 @@ pub struct TasksState {
     pub pending_terminal_switch: Option<Uuid>,
     #[serde(skip)]
     pub pending_change_request: Option<(Uuid, String)>,
+    /// Tasks the auto-run queue started recently, holding their slot until the agent reports
+    /// that it is running.
+    #[serde(skip)]
+    pub queue_launches: Vec<(Uuid, Instant)>,
     #[serde(skip)]
     pub error_message: Option<String>,
     #[serde(skip)]
@@ -82,24 +94,10 @@ impl TasksState {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            columns: vec![
-                Column {
-                    name: "Planning".to_string(),
-                    tasks: Vec::new(),
-                },
-                Column {
-                    name: "In Progress".to_string(),
-                    tasks: Vec::new(),
-                },
-                Column {
-                    name: "Review".to_string(),
-                    tasks: Vec::new(),
-                },
-                Column {
-                    name: "Done".to_string(),
-                    tasks: Vec::new(),
-                },
-            ],
+            columns: ColumnDefinition::defaults()
+                .into_iter()
+                .map(Column::from)
+                .collect(),
             mode: TasksMode::Normal,
             view_mode: TasksViewMode::default(),
             kanban_selected_column: 0,
@@ -110,11 +108,13 @@ impl TasksState {
             focus_details_scroll: 0,
             pending_classifications: HashSet::new(),
             pending_instance_termination: None,
-            pending_worktree_deletion: None,
+            pending_worktree_deletions: Vec::new(),
+            removing_worktrees: HashSet::new(),
             pending_instance_creation: None,
             pending_ide_open: None,
             pending_terminal_switch: None,
             pending_change_request: None,
+            queue_launches: Vec::new(),
             error_message: None,
             spinner_frame: 0,
         }
@@ -133,11 +133,15 @@ impl TasksState {
             .and_then(|index| self.columns[self.kanban_selected_column].tasks.get(index))
     }
 
+    /// Records the pane started for the task, which has run its worktree's pending setup.
     pub fn link_task_to_instance(&mut self, task_id: Uuid, instance_id: Uuid) {
         for column in &mut self.columns {
             for task in &mut column.tasks {
                 if task.id == task_id {
                     task.instance_id = Some(instance_id);
+                    if let Some(worktree_info) = &mut task.worktree_info {
+                        worktree_info.pending_setup = None;
+                    }
                     return;
                 }
             }
@@ -156,10 +160,20 @@ impl TasksState {
         None
     }
 
+    fn find_task_by_id_mut(&mut self, task_id: Uuid) -> Option<&mut Task> {
+        self.columns
+            .iter_mut()
+            .flat_map(|column| &mut column.tasks)
+            .find(|task| task.id == task_id)
+    }
+
     pub fn set_task_provider(&mut self, task_id: Uuid, provider: AgentProvider) {
         for column in &mut self.columns {
             for task in &mut column.tasks {
                 if task.id == task_id {
+                    if task.provider.as_ref() != Some(&provider) {
+                        task.session_id = None;
+                    }
                     task.provider = Some(provider);
                     return;
                 }
@@ -167,6 +181,75 @@ impl TasksState {
         }
     }
 
+    /// Adds a permission rule that only applies to `task_id`.
+    pub fn add_task_permission_rule(&mut self, task_id: Uuid, rule: PermissionRule) {
+        if let Some(task) = self
+            .columns
+            .iter_mut()
+            .flat_map(|column| &mut column.tasks)
+            .find(|task| task.id == task_id)
+        {
+            PermissionRule::upsert(&mut task.permission_rules, rule);
+        }
+    }
+
+    /// Records usage reported by `task_id`'s agent. Returns whether it changed.
+    pub fn record_task_usage(
+        &mut self,
+        task_id: Uuid,
+        session_id: &str,
+        report: UsageReport,
+    ) -> bool {
+        let Some(task) = self.find_task_by_id_mut(task_id) else {
+            return false;
+        };
+
+        match report {
+            UsageReport::Transcript(by_day) => task.usage.replace_session(session_id, by_day),
+            UsageReport::SessionTotal(total) => {
+                task.usage
+                    .record_session_total(session_id, total, Local::now().date_naive())
+            }
+        }
+    }
+
+    /// Pauses `task_id` if its usage has reached its budget. Returns whether it was paused
+    /// just now.
+    pub fn pause_task_over_budget(&mut self, task_id: Uuid) -> bool {
+        match self.find_task_by_id_mut(task_id) {
+            Some(task) if !task.is_paused && task.is_over_budget() => {
+                task.is_paused = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Sets or removes `task_id`'s budget, resuming the task if it is no longer over it.
+    pub fn set_task_budget(&mut self, task_id: Uuid, budget_usd: Option<f64>) {
+        if let Some(task) = self.find_task_by_id_mut(task_id) {
+            task.budget_usd = budget_usd;
+            task.is_paused = task.is_over_budget();
+        }
+    }
+
+    /// Records the session id reported for `task_id`. Returns whether it changed.
+    pub fn set_task_session_id(&mut self, task_id: Uuid, session_id: &str) -> bool {
+        let task = self
+            .columns
+            .iter_mut()
+            .flat_map(|column| &mut column.tasks)
+            .find(|task| task.id == task_id);
+
+        match task {
+            Some(task) if task.session_id.as_deref() != Some(session_id) => {
+                task.session_id = Some(session_id.to_string());
+                true
+            }
+            _ => false,
+        }
+    }
+
     #[must_use]
     pub const fn is_normal_mode(&self) -> bool {
         matches!(self.mode, TasksMode::Normal)
@@ -187,6 +270,20 @@ impl TasksState {
             TasksMode::AddingTask { .. }
                 | TasksMode::EditingTask { .. }
                 | TasksMode::ReviewRequestChanges { .. }
+                | TasksMode::MergeConfirmation {
+                    focused_field: MergeField::SquashSubject,
+                    ..
+                }
+        )
+    }
+
+    /// Dialogs and pickers, which have the keyboard to themselves, so global
+    /// shortcuts must not see their keys while they are open.
+    #[must_use]
+    pub const fn captures_keys(&self) -> bool {
+        !matches!(
+            self.mode,
+            TasksMode::Normal | TasksMode::TerminalFocused | TasksMode::TerminalScroll
         )
     }
 
@@ -209,9 +306,22 @@ impl Default for TasksState {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Column {
     pub name: String,
+    /// Comes from the project settings, see [`TasksState::apply_column_definitions`].
+    #[serde(skip)]
+    pub workflow: ColumnWorkflow,
     pub tasks: Vec<Task>,
 }
 
+impl From<ColumnDefinition> for Column {
+    fn from(definition: ColumnDefinition) -> Self {
+        Self {
+            name: definition.name,
+            workflow: definition.workflow,
+            tasks: Vec::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub enum TaskType {
     Feature,
@@ -243,6 +353,37 @@ impl TaskType {
     }
 }
 
+/// How soon a queued task is started relative to the rest of the auto-run queue.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum QueuePriority {
+    High,
+    #[default]
+    Normal,
+    Low,
+}
+
+impl QueuePriority {
+    #[must_use]
+    pub const fn badge_text(self) -> &'static str {
+        match self {
+            Self::High => "QUEUED↑",
+            Self::Normal => "QUEUED",
+            Self::Low => "QUEUED↓",
+        }
+    }
+
+    /// The next priority when cycling through them from the board.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::High => Self::Normal,
+            Self::Normal => Self::Low,
+            Self::Low => Self::High,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
     pub id: Uuid,
@@ -259,6 +400,25 @@ pub struct Task {
     pub is_paused: bool,
     #[serde(default)]
     pub worktree_info: Option<WorktreeInfo>,
+    /// The provider's conversation id, reported by its hooks, used to resume the agent after
+    /// a restart.
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Answers given with "always for this task" in the permission inbox.
+    #[serde(default)]
+    pub permission_rules: Vec<PermissionRule>,
+    /// Tokens and estimated cost of the task's agent sessions.
+    #[serde(default)]
+    pub usage: TaskUsage,
+    /// Spending limit in dollars. The agent is paused once its usage reaches it.
+    #[serde(default)]
+    pub budget_usd: Option<f64>,
+    /// Tasks that must reach Done before this one can start.
+    #[serde(default)]
+    pub dependencies: Vec<Uuid>,
+    /// Set while the task waits in Planning for the auto-run queue to start it.
+    #[serde(default)]
+    pub queue_priority: Option<QueuePriority>,
     #[serde(skip)]
     pub is_classifying: bool,
 }
@@ -276,10 +436,23 @@ impl Task {
             instance_id: None,
             is_paused: false,
             worktree_info: None,
+            session_id: None,
+            permission_rules: Vec::new(),
+            usage: TaskUsage::default(),
+            budget_usd: None,
+            dependencies: Vec::new(),
+            queue_priority: None,
             is_classifying: false,
         }
     }
 
+    /// Whether the task's usage has reached its budget.
+    #[must_use]
+    pub fn is_over_budget(&self) -> bool {
+        self.budget_usd
+            .is_some_and(|budget_usd| self.usage.total().cost_usd >= budget_usd)
+    }
+
     #[must_use]
     pub fn new_classifying(raw_input: String) -> Self {
         Self {
@@ -292,6 +465,12 @@ impl Task {
             instance_id: None,
             is_paused: false,
             worktree_info: None,
+            session_id: None,
+            permission_rules: Vec::new(),
+            usage: TaskUsage::default(),
+            budget_usd: None,
+            dependencies: Vec::new(),
+            queue_priority: None,
             is_classifying: true,
         }
     }
@@ -303,6 +482,7 @@ pub enum ReviewAction {
     ReviewInTerminal,
     RequestChanges,
     CommitChanges,
+    SyncWithBase,
     MergeAndComplete,
 }
 
@@ -316,12 +496,13 @@ pub enum ReviewPanel {
 
 impl ReviewAction {
     #[must_use]
-    pub const fn all() -> [Self; 5] {
+    pub const fn all() -> [Self; 6] {
         [
             Self::ReviewInIDE,
             Self::ReviewInTerminal,
             Self::RequestChanges,
             Self::CommitChanges,
+            Self::SyncWithBase,
             Self::MergeAndComplete,
         ]
     }
@@ -333,6 +514,7 @@ impl ReviewAction {
             Self::ReviewInTerminal => "Review in Terminal".to_string(),
             Self::RequestChanges => "Request Changes".to_string(),
             Self::CommitChanges => "Commit".to_string(),
+            Self::SyncWithBase => "Sync with Base".to_string(),
             Self::MergeAndComplete => "Merge & Complete".to_string(),
         }
     }
@@ -342,7 +524,7 @@ impl ReviewAction {
         match self {
             Self::ReviewInIDE | Self::ReviewInTerminal | Self::RequestChanges => true,
             Self::CommitChanges => !is_clean,
-            Self::MergeAndComplete => is_clean,
+            Self::SyncWithBase | Self::MergeAndComplete => is_clean,
         }
     }
 }
@@ -363,6 +545,55 @@ impl MergeTarget {
     }
 }
 
+/// The part of the merge dialog that the keys change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum MergeField {
+    #[default]
+    Target,
+    Strategy,
+    SquashSubject,
+}
+
+impl MergeField {
+    /// The field after this one, wrapping around. The squash subject is only shown, and so
+    /// only reachable, when squashing.
+    #[must_use]
+    pub fn next(self, strategy: MergeStrategy) -> Self {
+        match self {
+            Self::Target => Self::Strategy,
+            Self::Strategy if strategy == MergeStrategy::Squash => Self::SquashSubject,
+            Self::Strategy | Self::SquashSubject => Self::Target,
+        }
+    }
+
+    /// The field before this one, wrapping around.
+    #[must_use]
+    pub fn previous(self, strategy: MergeStrategy) -> Self {
+        match self {
+            Self::Target if strategy == MergeStrategy::Squash => Self::SquashSubject,
+            Self::Target | Self::SquashSubject => Self::Strategy,
+            Self::Strategy => Self::Target,
+        }
+    }
+}
+
+/// An auto-created worktree to delete, and what becomes of its task afterwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeDeletion {
+    pub task_id: Uuid,
+    pub worktree_info: WorktreeInfo,
+    pub purpose: WorktreeDeletionPurpose,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorktreeDeletionPurpose {
+    /// The task left this column for one that does not keep worktrees, and goes back to it
+    /// with its worktree if the deletion fails.
+    Moved { source_column_index: usize },
+    /// The task's branch has landed, and it moves to Done once its worktree is gone.
+    Complete,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WorktreeSelectionOption {
     AutoCreate,
@@ -423,5 +654,16 @@ pub enum TasksMode {
         task_id: Uuid,
         worktree_branch: String,
         selected_target: MergeTarget,
+        #[serde(default)]
+        strategy: MergeStrategy,
+        #[serde(default)]
+        focused_field: MergeField,
+        /// The first line of a squash commit, where empty means the task's title.
+        #[serde(default)]
+        squash_subject: String,
+    },
+    /// Picking the tasks that must finish before `task_id` can start.
+    SelectBlockers {
+        task_id: Uuid,
     },
 }
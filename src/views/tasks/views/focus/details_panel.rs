@@ -1,4 +1,6 @@
+use crate::helpers::text;
 use crate::views::tasks::operations::TaskReference;
+use crate::views::tasks::state::Task;
 use ratatui::{
     Frame,
     layout::Rect,
@@ -7,7 +9,12 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
-pub fn render(frame: &mut Frame, selected_task: Option<&TaskReference<'_>>, area: Rect) {
+pub fn render(
+    frame: &mut Frame,
+    selected_task: Option<&TaskReference<'_>>,
+    blockers: &[&Task],
+    area: Rect,
+) {
     let block = Block::default()
         .title("Details")
         .title_style(
@@ -22,13 +29,18 @@ pub fn render(frame: &mut Frame, selected_task: Option<&TaskReference<'_>>, area
     frame.render_widget(block, area);
 
     if let Some(task_ref) = selected_task {
-        render_task_details(frame, task_ref, inner_area);
+        render_task_details(frame, task_ref, blockers, inner_area);
     } else {
         render_no_selection(frame, inner_area);
     }
 }
 
-fn render_task_details(frame: &mut Frame, task_ref: &TaskReference<'_>, area: Rect) {
+fn render_task_details(
+    frame: &mut Frame,
+    task_ref: &TaskReference<'_>,
+    blockers: &[&Task],
+    area: Rect,
+) {
     let task = task_ref.task;
 
     let mut lines = vec![
@@ -82,6 +94,25 @@ fn render_task_details(frame: &mut Frame, task_ref: &TaskReference<'_>, area: Re
         lines.push(Line::from(""));
     }
 
+    if !blockers.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Blocked by:",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )));
+        for blocker in blockers {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", blocker.title),
+                Style::default().fg(Color::White),
+            )));
+        }
+        lines.push(Line::from(""));
+    }
+
+    if !task.usage.is_empty() || task.budget_usd.is_some() {
+        lines.extend(usage_lines(task));
+        lines.push(Line::from(""));
+    }
+
     lines.push(Line::from(vec![
         Span::styled("Created: ", Style::default().fg(Color::DarkGray)),
         Span::styled(
@@ -105,6 +136,43 @@ fn render_task_details(frame: &mut Frame, task_ref: &TaskReference<'_>, area: Re
     frame.render_widget(paragraph, area);
 }
 
+fn usage_lines(task: &Task) -> Vec<Line<'static>> {
+    let usage = task.usage.total();
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(Color::DarkGray));
+    let value = |text: String| Span::styled(text, Style::default().fg(Color::Gray));
+
+    let tokens = Line::from(vec![
+        label("Tokens: "),
+        value(format!(
+            "{} in · {} out · {} cached",
+            text::format_token_count(usage.input_tokens + usage.cache_write_tokens),
+            text::format_token_count(usage.output_tokens),
+            text::format_token_count(usage.cache_read_tokens),
+        )),
+    ]);
+
+    let mut cost = vec![label("Cost: "), value(format!("${:.2}", usage.cost_usd))];
+    if let Some(budget_usd) = task.budget_usd {
+        let budget_color = if task.is_over_budget() {
+            Color::Red
+        } else {
+            Color::Gray
+        };
+        cost.push(Span::styled(
+            format!(" of ${budget_usd:.2} budget"),
+            Style::default().fg(budget_color),
+        ));
+    }
+    if task.is_paused {
+        cost.push(Span::styled(
+            "  PAUSED",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    vec![tokens, Line::from(cost)]
+}
+
 fn render_no_selection(frame: &mut Frame, area: Rect) {
     let text = Paragraph::new(vec![
         Line::from(""),
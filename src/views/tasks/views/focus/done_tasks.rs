@@ -1,4 +1,6 @@
 use crate::app::App;
+use crate::views::mouse::{MouseRegions, MouseTarget};
+use crate::views::tasks::operations::{get_done_task_count, get_done_tasks};
 use crate::views::tasks::state::FocusPanel;
 use crate::widgets::task::TaskItem;
 use ratatui::{
@@ -9,13 +11,12 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem},
 };
 
-pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+pub fn render(frame: &mut Frame, app: &App, area: Rect, regions: &mut MouseRegions) {
     let state = &app.tasks;
     let columns = &state.columns;
     let is_focused = state.focus_panel == FocusPanel::DoneTasks;
 
-    let done_column = columns.get(3);
-    let done_count = done_column.map_or(0, |column| column.tasks.len());
+    let done_count = get_done_task_count(columns);
 
     let border_color = if is_focused {
         Color::Green
@@ -61,6 +62,16 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
 
     let title_max_length = calculate_title_max_length(inner_area.width);
     let items = build_done_task_items(app, is_focused, title_max_length);
+    regions.push_rows(
+        inner_area,
+        items.iter().enumerate().map(|(index, item)| {
+            let target = MouseTarget::FocusTask {
+                panel: FocusPanel::DoneTasks,
+                index,
+            };
+            (item.height(), Some(target))
+        }),
+    );
     let list = List::new(items);
     frame.render_widget(list, inner_area);
 }
@@ -97,11 +108,8 @@ fn build_done_task_items(
     let columns = &app.tasks.columns;
     let selected_index = app.tasks.focus_done_index;
 
-    let Some(done_column) = columns.get(3) else {
-        return items;
-    };
-
-    for (index, task) in done_column.tasks.iter().enumerate() {
+    for (index, task_ref) in get_done_tasks(columns).into_iter().enumerate() {
+        let task = task_ref.task;
         let is_selected = is_panel_focused && index == selected_index;
         items.push(create_task_item(
             &task.title,
@@ -1,4 +1,6 @@
 use crate::app::App;
+use crate::views::mouse::{MouseRegions, MouseTarget};
+use crate::views::tasks::TasksState;
 use crate::views::tasks::state::FocusPanel;
 use crate::widgets::task::TaskItem;
 use ratatui::{
@@ -9,7 +11,7 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem},
 };
 
-pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+pub fn render(frame: &mut Frame, app: &App, area: Rect, regions: &mut MouseRegions) {
     let state = &app.tasks;
     let columns = &state.columns;
     let is_focused = state.focus_panel == FocusPanel::ActiveTasks;
@@ -67,8 +69,18 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     let title_max_length = calculate_title_max_length(inner_area.width);
-    let items = build_task_list_items(app, is_focused, title_max_length);
-    let list = List::new(items);
+    let rows = build_task_list_items(app, is_focused, title_max_length);
+    regions.push_rows(
+        inner_area,
+        rows.iter().map(|(index, item)| {
+            let target = index.map(|index| MouseTarget::FocusTask {
+                panel: FocusPanel::ActiveTasks,
+                index,
+            });
+            (item.height(), target)
+        }),
+    );
+    let list = List::new(rows.into_iter().map(|(_, item)| item));
     frame.render_widget(list, inner_area);
 }
 
@@ -108,29 +120,31 @@ fn build_task_list_items(
     app: &App,
     is_panel_focused: bool,
     title_max_length: usize,
-) -> Vec<ListItem<'static>> {
+) -> Vec<(Option<usize>, ListItem<'static>)> {
     let mut items = Vec::new();
     let mut current_index = 0;
     let columns = &app.tasks.columns;
     let selected_index = app.tasks.focus_active_index;
 
-    for (column_index, column) in columns.iter().take(3).enumerate() {
-        if !column.tasks.is_empty() {
-            items.push(create_column_header(&column.name, column_index));
+    for (column_index, column) in columns.iter().enumerate() {
+        if !column.workflow.is_terminal && !column.tasks.is_empty() {
+            items.push((None, create_column_header(&app.tasks, column_index)));
 
             for task in &column.tasks {
                 let is_selected = is_panel_focused && current_index == selected_index;
                 let instance_id = task.instance_id;
                 let claude_state = instance_id.and_then(|id| app.get_instance_claude_state(id));
 
-                items.push(create_task_item(
-                    &task.title,
-                    task.kind,
-                    is_selected,
-                    task.is_classifying,
-                    claude_state,
-                    title_max_length,
-                    app.tasks.spinner_frame,
+                items.push((
+                    Some(current_index),
+                    create_task_item(
+                        task,
+                        is_selected,
+                        app.tasks.is_task_blocked(task),
+                        claude_state,
+                        title_max_length,
+                        app.tasks.spinner_frame,
+                    ),
                 ));
                 current_index += 1;
             }
@@ -140,17 +154,20 @@ fn build_task_list_items(
     items
 }
 
-fn create_column_header(name: &str, column_index: usize) -> ListItem<'static> {
-    let header_color = match column_index {
-        0 => Color::Yellow,
-        1 => Color::Cyan,
-        2 => Color::Magenta,
-        3 => Color::Green,
-        _ => Color::Gray,
+fn create_column_header(state: &TasksState, column_index: usize) -> ListItem<'static> {
+    let column = &state.columns[column_index];
+    let header_color = if column_index == 0 {
+        Color::Yellow
+    } else if column.workflow.starts_agent {
+        Color::Cyan
+    } else if state.review_column_index() == Some(column_index) {
+        Color::Magenta
+    } else {
+        Color::Gray
     };
 
     ListItem::new(Line::from(vec![Span::styled(
-        format!(" {}", name.to_uppercase()),
+        format!(" {}", column.name.to_uppercase()),
         Style::default()
             .fg(header_color)
             .add_modifier(Modifier::BOLD),
@@ -158,17 +175,18 @@ fn create_column_header(name: &str, column_index: usize) -> ListItem<'static> {
 }
 
 fn create_task_item(
-    title: &str,
-    task_type: crate::views::tasks::TaskType,
+    task: &crate::views::tasks::Task,
     is_selected: bool,
-    is_classifying: bool,
+    is_blocked: bool,
     claude_state: Option<crate::views::instances::ClaudeState>,
     title_max_length: usize,
     spinner_frame: usize,
 ) -> ListItem<'static> {
-    TaskItem::new(title, task_type)
+    TaskItem::new(&task.title, task.kind)
         .selected(is_selected)
-        .classifying(is_classifying)
+        .classifying(task.is_classifying)
+        .blocked(is_blocked)
+        .queued(task.queue_priority)
         .claude_state(claude_state)
         .title_max_length(title_max_length)
         .spinner_frame(spinner_frame)
@@ -11,7 +11,7 @@ use ratatui::{
 
 pub fn render(
     frame: &mut Frame,
-    pane: Option<&mut InstancePane>,
+    pane: Option<&InstancePane>,
     is_focused: bool,
     is_scroll_mode: bool,
     area: Rect,
@@ -42,7 +42,7 @@ pub fn render(
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color));
 
-    if let Some(pane) = &pane {
+    if let Some(pane) = pane {
         let (indicator, color) = claude_indicator::label(pane.claude_state);
         block = block.title_bottom(
             Line::from(vec![Span::styled(
@@ -62,23 +62,14 @@ pub fn render(
     }
 }
 
-fn render_pane_content(frame: &mut Frame, pane: &mut InstancePane, is_focused: bool, area: Rect) {
-    let Some(session) = &mut pane.pty_session else {
+fn render_pane_content(frame: &mut Frame, pane: &InstancePane, is_focused: bool, area: Rect) {
+    let Some(session) = &pane.pty_session else {
         let message =
             Paragraph::new("PTY session not available").style(Style::default().fg(Color::Red));
         frame.render_widget(message, area);
         return;
     };
 
-    let desired_rows = area.height;
-    let desired_columns = area.width;
-
-    if pane.rows != desired_rows || pane.columns != desired_columns {
-        session.resize(desired_rows, desired_columns);
-        pane.rows = desired_rows;
-        pane.columns = desired_columns;
-    }
-
     let term_mutex = session.term();
     let Ok(term) = term_mutex.lock() else {
         return;
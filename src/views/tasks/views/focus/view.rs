@@ -1,15 +1,24 @@
 use super::{details_panel, done_tasks, task_list, terminal_panel};
 use crate::app::App;
-use crate::views::StatusBarContent;
+use crate::types::{KeyAction, KeyContext, Keymap};
+use crate::views::mouse::{MouseRegions, MouseTarget};
 use crate::views::tasks::dialogs;
-use crate::views::tasks::operations::{TaskReference, get_active_tasks, get_done_tasks};
+use crate::views::tasks::operations::{
+    TaskReference, get_active_task_count, get_active_tasks, get_done_task_count, get_done_tasks,
+};
 use crate::views::tasks::state::{FocusPanel, TasksMode, TasksViewMode};
-use crate::widgets::dialogs::{ConfirmDialog, DialogStyle, ErrorDialog, InputDialog};
+use crate::views::{
+    StatusBarContent, confirm_help, join_help, picker_help, scroll_help, terminal_help,
+};
+use crate::widgets::dialogs::{ErrorDialog, InputDialog};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::Color,
+    widgets::{Block, Borders},
 };
+use std::rc::Rc;
+use uuid::Uuid;
 
 const LEFT_PANEL_PERCENT: u16 = 35;
 const RIGHT_PANEL_PERCENT: u16 = 65;
@@ -19,7 +28,54 @@ const DETAILS_PANEL_PERCENT: u16 = 30;
 const TERMINAL_PANEL_PERCENT: u16 = 70;
 const STATUS_BAR_WIDTH_THRESHOLD: u16 = 80;
 
-pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+pub fn render(frame: &mut Frame, app: &App, area: Rect, regions: &mut MouseRegions) {
+    let (left_chunks, right_chunks) = split_panels(area);
+
+    task_list::render(frame, app, left_chunks[0], regions);
+    done_tasks::render(frame, app, left_chunks[1], regions);
+
+    let selected_task = get_selected_task(app);
+    let blockers = selected_task
+        .as_ref()
+        .map(|task_ref| app.tasks.blockers_of(task_ref.task))
+        .unwrap_or_default();
+    details_panel::render(frame, selected_task.as_ref(), &blockers, right_chunks[0]);
+
+    let terminal_area = Block::default()
+        .borders(Borders::ALL)
+        .inner(right_chunks[1]);
+    let instance_id = selected_task.and_then(|task_ref| task_ref.task.instance_id);
+    let instance_pane = instance_id.and_then(|id| app.instances.find_pane(id));
+
+    let is_terminal_focused = matches!(
+        app.tasks.mode,
+        TasksMode::TerminalFocused | TasksMode::TerminalScroll
+    );
+    let is_scroll_mode = matches!(app.tasks.mode, TasksMode::TerminalScroll);
+    terminal_panel::render(
+        frame,
+        instance_pane,
+        is_terminal_focused,
+        is_scroll_mode,
+        right_chunks[1],
+    );
+    regions.push(terminal_area, MouseTarget::TaskTerminal);
+
+    render_dialogs(frame, app, &app.tasks.mode, area, regions);
+}
+
+/// The selected task's agent pane and the area the view draws its terminal in.
+#[must_use]
+pub fn shown_terminal(app: &App, area: Rect) -> Option<(Uuid, Rect)> {
+    let instance_id = get_selected_task(app)?.task.instance_id?;
+    let terminal_area = Block::default()
+        .borders(Borders::ALL)
+        .inner(split_panels(area).1[1]);
+    Some((instance_id, terminal_area))
+}
+
+/// Splits the view into the task lists on the left and the details and terminal on the right.
+fn split_panels(area: Rect) -> (Rc<[Rect]>, Rc<[Rect]>) {
     let horizontal_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -36,9 +92,6 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         ])
         .split(horizontal_chunks[0]);
 
-    task_list::render(frame, app, left_chunks[0]);
-    done_tasks::render(frame, app, left_chunks[1]);
-
     let right_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -47,26 +100,7 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         ])
         .split(horizontal_chunks[1]);
 
-    let selected_task = get_selected_task(app);
-    details_panel::render(frame, selected_task.as_ref(), right_chunks[0]);
-
-    let instance_id = selected_task.and_then(|task_ref| task_ref.task.instance_id);
-    let instance_pane = instance_id.and_then(|id| app.instances.find_pane_mut(id));
-
-    let is_terminal_focused = matches!(
-        app.tasks.mode,
-        TasksMode::TerminalFocused | TasksMode::TerminalScroll
-    );
-    let is_scroll_mode = matches!(app.tasks.mode, TasksMode::TerminalScroll);
-    terminal_panel::render(
-        frame,
-        instance_pane,
-        is_terminal_focused,
-        is_scroll_mode,
-        right_chunks[1],
-    );
-
-    render_dialogs(frame, app, &app.tasks.mode, area);
+    (left_chunks, right_chunks)
 }
 
 fn get_selected_task(app: &App) -> Option<TaskReference<'_>> {
@@ -82,7 +116,13 @@ fn get_selected_task(app: &App) -> Option<TaskReference<'_>> {
     }
 }
 
-fn render_dialogs(frame: &mut Frame, app: &App, mode: &TasksMode, area: Rect) {
+fn render_dialogs(
+    frame: &mut Frame,
+    app: &App,
+    mode: &TasksMode,
+    area: Rect,
+    regions: &mut MouseRegions,
+) {
     match mode {
         TasksMode::AddingTask { input, prompt } => {
             let dialog_state = dialogs::AddTaskDialogState { input, prompt };
@@ -105,18 +145,20 @@ fn render_dialogs(frame: &mut Frame, app: &App, mode: &TasksMode, area: Rect) {
             frame.render_widget(InputDialog::new("Edit Task", input), area);
         }
         TasksMode::ConfirmDelete { .. } => {
-            frame.render_widget(
-                ConfirmDialog::new("Delete Task", "Are you sure? (y/n)").style(DialogStyle::Danger),
+            dialogs::render_danger_confirmation(
+                frame,
+                "Delete Task",
+                "Are you sure?",
+                &app.keymap,
                 area,
             );
         }
         TasksMode::ConfirmMoveBack { .. } => {
-            frame.render_widget(
-                ConfirmDialog::new(
-                    "Move Back",
-                    "Move back to previous column? This will terminate the Claude Code instance and clean up resources. (y/n)",
-                )
-                .style(DialogStyle::Danger),
+            dialogs::render_danger_confirmation(
+                frame,
+                "Move Back",
+                "Move back to previous column? This will terminate the Claude Code instance and clean up resources.",
+                &app.keymap,
                 area,
             );
         }
@@ -136,7 +178,7 @@ fn render_dialogs(frame: &mut Frame, app: &App, mode: &TasksMode, area: Rect) {
                 focused_panel: *focused_panel,
                 selected_action: *selected_action,
             };
-            dialogs::render_review_popup(frame, app, &popup_state, area);
+            dialogs::render_review_popup(frame, app, &popup_state, area, regions);
         }
         TasksMode::ReviewRequestChanges { input, .. } => {
             frame.render_widget(InputDialog::new("Request Changes", input), area);
@@ -144,9 +186,20 @@ fn render_dialogs(frame: &mut Frame, app: &App, mode: &TasksMode, area: Rect) {
         TasksMode::MergeConfirmation {
             worktree_branch,
             selected_target,
+            strategy,
+            focused_field,
+            squash_subject,
             ..
         } => {
-            dialogs::render_merge_confirmation(frame, worktree_branch, selected_target, area);
+            let dialog_state = dialogs::MergeConfirmationViewState {
+                worktree_branch,
+                selected_target,
+                strategy: *strategy,
+                focused_field: *focused_field,
+                squash_subject,
+                keymap: &app.keymap,
+            };
+            dialogs::render_merge_confirmation(frame, &dialog_state, area);
         }
         TasksMode::SelectProvider {
             selected_index,
@@ -155,12 +208,15 @@ fn render_dialogs(frame: &mut Frame, app: &App, mode: &TasksMode, area: Rect) {
         } => {
             let dialog_state = dialogs::ProviderSelectionViewState {
                 selected_index: *selected_index,
-                default_provider: app.settings.settings.default_provider,
+                default_provider: &app.settings.settings.default_provider,
                 detected_providers,
             };
             dialogs::render_provider_selection(frame, &dialog_state, area);
         }
-        TasksMode::Normal | TasksMode::TerminalFocused | TasksMode::TerminalScroll => {}
+        TasksMode::Normal
+        | TasksMode::TerminalFocused
+        | TasksMode::TerminalScroll
+        | TasksMode::SelectBlockers { .. } => {}
     }
 
     if let Some(error) = &app.tasks.error_message {
@@ -188,77 +244,101 @@ pub fn get_status_bar_content(app: &App, width: u16) -> StatusBarContent {
         TasksMode::ReviewRequestChanges { .. } => ("REQUEST CHANGES", Color::Yellow),
         TasksMode::MergeConfirmation { .. } => ("MERGE", Color::Green),
         TasksMode::SelectProvider { .. } => ("SELECT PROVIDER", Color::Yellow),
+        TasksMode::SelectBlockers { .. } => ("SELECT BLOCKERS", Color::LightRed),
     };
 
-    let active_count: usize = state
-        .columns
-        .iter()
-        .take(3)
-        .map(|column| column.tasks.len())
-        .sum();
-    let done_count = state.columns.get(3).map_or(0, |column| column.tasks.len());
+    let active_count = get_active_task_count(&state.columns);
+    let done_count = get_done_task_count(&state.columns);
 
     let view_indicator = match state.view_mode {
         TasksViewMode::Focus => "[Focus]",
         TasksViewMode::Kanban => "[Kanban]",
     };
 
-    let help_text = if width < STATUS_BAR_WIDTH_THRESHOLD {
-        match &state.mode {
-            TasksMode::Normal => {
-                "jk:nav  Tab:panel  a:add  e:edit  d:del  s:start  Bksp:back  /:view"
-            }
-            TasksMode::TerminalFocused => "Ctrl+s:scroll  Esc:back",
-            TasksMode::TerminalScroll => "jk:line  Ctrl+d/u:page  g/G:top/bottom  q:exit",
-            TasksMode::AddingTask { .. } | TasksMode::EditingTask { .. } => {
-                "Enter:save  Esc:cancel"
-            }
-            TasksMode::SelectWorktree { .. } | TasksMode::SelectProvider { .. } => {
-                "jk:select  Enter:choose  Esc:cancel"
-            }
-            TasksMode::ConfirmDelete { .. } | TasksMode::ConfirmMoveBack { .. } => {
-                "y:confirm  n:cancel"
-            }
-            TasksMode::ReviewPopup { .. } => {
-                "Tab:panel  jk:move/scroll  hl:buttons  Enter:select  Esc:close"
-            }
-            TasksMode::ReviewRequestChanges { .. } => "Enter:send  Esc:cancel",
-            TasksMode::MergeConfirmation { .. } => "jk:select  Enter:merge  Esc:cancel",
-        }
+    let is_wide = width >= STATUS_BAR_WIDTH_THRESHOLD;
+    let help_text = match &state.mode {
+        TasksMode::Normal => normal_help(&app.keymap, is_wide),
+        TasksMode::TerminalFocused => terminal_help(&app.keymap, is_wide),
+        TasksMode::TerminalScroll => scroll_help(&app.keymap, is_wide),
+        mode => dialog_help(mode, &app.keymap, is_wide),
+    };
+
+    StatusBarContent {
+        mode_text: mode_text.to_string(),
+        mode_color,
+        extra_info: Some(format!(
+            "{view_indicator} Active: {active_count}  Queued: {}  Done: {done_count}  ",
+            state.queued_task_count()
+        )),
+        help_text,
+    }
+}
+
+fn normal_help(keymap: &Keymap, is_wide: bool) -> String {
+    let board = if is_wide {
+        keymap.help(
+            KeyContext::Focus,
+            &[
+                (&[KeyAction::MoveDown, KeyAction::MoveUp], "navigate"),
+                (&[KeyAction::SwitchPanel], "switch-panel"),
+                (&[KeyAction::Add], "add"),
+                (&[KeyAction::Edit], "edit"),
+                (&[KeyAction::Delete], "delete"),
+                (&[KeyAction::Start], "start"),
+                (&[KeyAction::MoveBack], "move-back"),
+            ],
+        )
     } else {
-        match &state.mode {
-            TasksMode::Normal => {
-                "↑↓/jk:navigate  Tab:switch-panel  a:add  e:edit  d:delete  s:start  Backspace:move-back  /:switch-view"
-            }
-            TasksMode::TerminalFocused => "Ctrl+s:scroll-mode  Esc:back-to-navigation",
-            TasksMode::TerminalScroll => {
-                "j/k:scroll-line  Ctrl+d/u:half-page  g/G:top/bottom  q/Esc:exit-scroll"
-            }
+        keymap.help(
+            KeyContext::Focus,
+            &[
+                (&[KeyAction::MoveDown, KeyAction::MoveUp], "nav"),
+                (&[KeyAction::SwitchPanel], "panel"),
+                (&[KeyAction::Add], "add"),
+                (&[KeyAction::Edit], "edit"),
+                (&[KeyAction::Delete], "del"),
+                (&[KeyAction::Start], "start"),
+                (&[KeyAction::MoveBack], "back"),
+            ],
+        )
+    };
+
+    let view_label = if is_wide { "switch-view" } else { "view" };
+    let view = keymap.help(KeyContext::Tasks, &[(&[KeyAction::ToggleView], view_label)]);
+    join_help([board, view])
+}
+
+fn dialog_help(mode: &TasksMode, keymap: &Keymap, is_wide: bool) -> String {
+    match mode {
+        TasksMode::SelectWorktree { .. } | TasksMode::SelectProvider { .. } => {
+            picker_help(keymap, "choose")
+        }
+        TasksMode::ConfirmDelete { .. } | TasksMode::ConfirmMoveBack { .. } => confirm_help(keymap),
+        TasksMode::ReviewPopup { .. } => dialogs::review_help(keymap, is_wide),
+        TasksMode::MergeConfirmation { .. } => dialogs::merge_help(keymap),
+        mode => text_help(mode, is_wide).to_string(),
+    }
+}
+
+/// Help for the text fields, whose keys are fixed rather than taken from the keymap.
+const fn text_help(mode: &TasksMode, is_wide: bool) -> &'static str {
+    if is_wide {
+        match mode {
             TasksMode::AddingTask { .. } | TasksMode::EditingTask { .. } => {
                 "Type task title  Enter:save  Esc:cancel"
             }
-            TasksMode::SelectWorktree { .. } | TasksMode::SelectProvider { .. } => {
-                "↑↓/jk:select  Enter:choose  Esc:cancel"
-            }
-            TasksMode::ConfirmDelete { .. } | TasksMode::ConfirmMoveBack { .. } => {
-                "Press y to confirm, n or Esc to cancel"
-            }
-            TasksMode::ReviewPopup { .. } => {
-                "Tab:panel  h/l:switch-buttons  j/k:move/scroll  Enter:select-action  Esc/q:close"
-            }
             TasksMode::ReviewRequestChanges { .. } => {
                 "Type your change request  Enter:send  Esc:cancel"
             }
-            TasksMode::MergeConfirmation { .. } => "↑↓/jk:select-branch  Enter:merge  Esc/q:cancel",
+            _ => "",
+        }
+    } else {
+        match mode {
+            TasksMode::AddingTask { .. } | TasksMode::EditingTask { .. } => {
+                "Enter:save  Esc:cancel"
+            }
+            TasksMode::ReviewRequestChanges { .. } => "Enter:send  Esc:cancel",
+            _ => "",
         }
-    };
-
-    StatusBarContent {
-        mode_text: mode_text.to_string(),
-        mode_color,
-        extra_info: Some(format!(
-            "{view_indicator} Active: {active_count}  Done: {done_count}  "
-        )),
-        help_text: help_text.to_string(),
     }
 }
@@ -1,9 +1,10 @@
 use super::helpers::{
-    COLUMN_COLORS, COLUMN_COLORS_SELECTED, get_claude_state_indicator_for_card, truncate_string,
-    wrap_text,
+    column_color, get_claude_state_indicator_for_card, truncate_string, wrap_text,
 };
 use crate::app::App;
-use crate::views::tasks::Task;
+use crate::views::mouse::{MouseRegions, MouseTarget};
+use crate::views::tasks::state::{Column, QueuePriority};
+use crate::views::tasks::{Task, TasksMode};
 use crate::widgets::spinner;
 use ratatui::{
     Frame,
@@ -17,8 +18,29 @@ const COLUMN_WIDTH_THRESHOLD: u16 = 120;
 const COLUMN_WIDTH_PERCENT: u16 = 25;
 const TASK_CARD_HEIGHT: u16 = 7;
 const MAX_DESCRIPTION_LINES: usize = 3;
+const BLOCKED_BADGE: &str = "BLOCKED";
+
+fn column_title(column: &Column, is_selected: bool) -> Line<'static> {
+    let indicator = if is_selected { "→ " } else { "" };
+    let wip_text = column
+        .workflow
+        .wip_limit
+        .map(|wip_limit| format!(" {}/{wip_limit}", column.tasks.len()))
+        .unwrap_or_default();
+
+    Line::from(vec![
+        Span::raw(" "),
+        Span::styled(
+            format!("{indicator}{}{wip_text}", column.name),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+    ])
+}
 
-pub fn render_columns(frame: &mut Frame, app: &App, area: Rect) {
+pub fn render_columns(frame: &mut Frame, app: &App, area: Rect, regions: &mut MouseRegions) {
     let state = &app.tasks;
     let column_count = state.columns.len();
 
@@ -28,7 +50,9 @@ pub fn render_columns(frame: &mut Frame, app: &App, area: Rect) {
     let constraints = if area.width < COLUMN_WIDTH_THRESHOLD {
         vec![Constraint::Ratio(1, column_count_u32); column_count]
     } else {
-        vec![Constraint::Percentage(COLUMN_WIDTH_PERCENT); column_count]
+        #[allow(clippy::cast_possible_truncation)]
+        let column_percent = COLUMN_WIDTH_PERCENT.min((100 / column_count.max(1)) as u16);
+        vec![Constraint::Percentage(column_percent); column_count]
     };
 
     let column_chunks = Layout::default()
@@ -40,11 +64,7 @@ pub fn render_columns(frame: &mut Frame, app: &App, area: Rect) {
         state.columns.iter().zip(column_chunks.iter()).enumerate()
     {
         let is_selected = column_index == state.kanban_selected_column;
-        let border_color = if is_selected {
-            COLUMN_COLORS_SELECTED[column_index]
-        } else {
-            COLUMN_COLORS[column_index]
-        };
+        let border_color = column_color(state, column_index, is_selected);
 
         let border_style = Style::default()
             .fg(border_color)
@@ -54,19 +74,7 @@ pub fn render_columns(frame: &mut Frame, app: &App, area: Rect) {
                 Modifier::empty()
             });
 
-        let indicator = if is_selected { "→ " } else { "" };
-        let name_with_indicator = format!("{}{}", indicator, column.name);
-
-        let title_text = Line::from(vec![
-            Span::raw(" "),
-            Span::styled(
-                name_with_indicator,
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(" "),
-        ]);
+        let title_text = column_title(column, is_selected);
 
         let mut column_block = Block::default()
             .borders(Borders::ALL)
@@ -91,6 +99,7 @@ pub fn render_columns(frame: &mut Frame, app: &App, area: Rect) {
 
         let inner_area = column_block.inner(*chunk);
         frame.render_widget(column_block, *chunk);
+        regions.push(*chunk, MouseTarget::KanbanColumn(column_index));
 
         if column.tasks.is_empty() {
             continue;
@@ -132,6 +141,13 @@ pub fn render_columns(frame: &mut Frame, app: &App, area: Rect) {
             let is_selected_task = is_selected && state.kanban_selected_task == Some(task_index);
 
             render_task_card(frame, app, task, card_area, is_selected_task);
+            regions.push(
+                card_area,
+                MouseTarget::KanbanTask {
+                    column: column_index,
+                    task: task_index,
+                },
+            );
 
             y_offset += card_height;
         }
@@ -143,15 +159,26 @@ fn render_task_card(frame: &mut Frame, app: &App, task: &Task, area: Rect, is_se
         .instance_id
         .and_then(|instance_id| app.get_instance_claude_state(instance_id));
 
+    // While picking blockers, outline the tasks already chosen for the task being edited.
+    let is_chosen_blocker = match &app.tasks.mode {
+        TasksMode::SelectBlockers { task_id } => app
+            .tasks
+            .find_task_by_id(*task_id)
+            .is_some_and(|dependent| dependent.dependencies.contains(&task.id)),
+        _ => false,
+    };
+
     let border_color = if task.is_classifying {
         Color::Yellow
     } else if is_selected {
         Color::White
+    } else if is_chosen_blocker {
+        Color::Red
     } else {
         Color::DarkGray
     };
 
-    let border_style = if is_selected || task.is_classifying {
+    let border_style = if is_selected || task.is_classifying || is_chosen_blocker {
         Style::default()
             .fg(border_color)
             .add_modifier(Modifier::BOLD)
@@ -159,10 +186,18 @@ fn render_task_card(frame: &mut Frame, app: &App, task: &Task, area: Rect, is_se
         Style::default().fg(border_color)
     };
 
+    let blocker_titles: Vec<&str> = app
+        .tasks
+        .blockers_of(task)
+        .iter()
+        .map(|blocker| blocker.title.as_str())
+        .collect();
+
     let title_max_width = area.width.saturating_sub(4) as usize;
     let title_line = build_task_card_title(
         task,
         claude_indicator,
+        !blocker_titles.is_empty(),
         title_max_width,
         app.tasks.spinner_frame,
     );
@@ -176,7 +211,7 @@ fn render_task_card(frame: &mut Frame, app: &App, task: &Task, area: Rect, is_se
     frame.render_widget(block, area);
 
     let max_width = inner.width.saturating_sub(2) as usize;
-    let lines = build_task_card_content(task, max_width);
+    let lines = build_task_card_content(task, &blocker_titles, max_width);
 
     frame.render_widget(Paragraph::new(lines), inner);
 }
@@ -184,6 +219,7 @@ fn render_task_card(frame: &mut Frame, app: &App, task: &Task, area: Rect, is_se
 fn build_task_card_title(
     task: &Task,
     claude_indicator: Option<crate::views::instances::ClaudeState>,
+    is_blocked: bool,
     title_max_width: usize,
     spinner_frame: usize,
 ) -> Line<'_> {
@@ -196,8 +232,16 @@ fn build_task_card_title(
     let has_indicator = claude_indicator.is_some()
         && claude_indicator != Some(crate::views::instances::ClaudeState::Idle);
     let indicator_width = if has_indicator { 2 } else { 0 };
+    let blocked_width = if is_blocked {
+        BLOCKED_BADGE.len() + 1
+    } else {
+        0
+    };
+    let queued_badge = task.queue_priority.map(QueuePriority::badge_text);
+    let queued_width = queued_badge.map_or(0, |badge| badge.chars().count() + 1);
 
-    let available_title_width = title_max_width.saturating_sub(8 + indicator_width);
+    let available_title_width =
+        title_max_width.saturating_sub(8 + indicator_width + blocked_width + queued_width);
 
     let mut title_spans = vec![Span::raw(" ")];
 
@@ -214,6 +258,22 @@ fn build_task_card_title(
     ));
     title_spans.push(Span::raw(" "));
 
+    if is_blocked {
+        title_spans.push(Span::styled(
+            format!("{BLOCKED_BADGE} "),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if let Some(badge) = queued_badge {
+        title_spans.push(Span::styled(
+            format!("{badge} "),
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
     if let Some(state) = claude_indicator
         && state != crate::views::instances::ClaudeState::Idle
     {
@@ -235,7 +295,11 @@ fn build_task_card_title(
     Line::from(title_spans)
 }
 
-fn build_task_card_content(task: &Task, max_width: usize) -> Vec<Line<'static>> {
+fn build_task_card_content(
+    task: &Task,
+    blocker_titles: &[&str],
+    max_width: usize,
+) -> Vec<Line<'static>> {
     let mut lines = vec![];
 
     if task.is_classifying {
@@ -265,11 +329,22 @@ fn build_task_card_content(task: &Task, max_width: usize) -> Vec<Line<'static>>
         lines.push(Line::from(""));
     }
 
-    let created = task.created_at.format("%Y/%m/%d-%H:%M:%S").to_string();
-    lines.push(Line::from(Span::styled(
-        format!("Created: {created}"),
-        Style::default().fg(Color::DarkGray),
-    )));
+    // The card has room for one footer line, and what blocks a task matters more than its age.
+    if blocker_titles.is_empty() {
+        let created = task.created_at.format("%Y/%m/%d-%H:%M:%S").to_string();
+        lines.push(Line::from(Span::styled(
+            format!("Created: {created}"),
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else {
+        lines.push(Line::from(Span::styled(
+            truncate_string(
+                &format!("Blocked by: {}", blocker_titles.join(", ")),
+                max_width,
+            ),
+            Style::default().fg(Color::Red),
+        )));
+    }
 
     lines
 }
@@ -1,18 +1,25 @@
+use crate::views::tasks::TasksState;
 use ratatui::style::Color;
 
 pub use crate::helpers::text::{truncate as truncate_string, wrap as wrap_text};
 pub use crate::widgets::claude_indicator::dot as get_claude_state_indicator_for_card;
 
-pub const COLUMN_COLORS: [Color; 4] = [
-    Color::Cyan,    // Planning
-    Color::Yellow,  // In Progress
-    Color::Magenta, // Review
-    Color::Green,   // Done
-];
+/// The border color of a column, picked by the role it plays on the board so that added
+/// columns fit in.
+#[must_use]
+pub fn column_color(state: &TasksState, column_index: usize, is_selected: bool) -> Color {
+    let workflow = state.columns[column_index].workflow;
+    let (color, selected_color) = if column_index == 0 {
+        (Color::Cyan, Color::LightCyan)
+    } else if workflow.starts_agent {
+        (Color::Yellow, Color::LightYellow)
+    } else if workflow.is_terminal {
+        (Color::Green, Color::LightGreen)
+    } else if state.review_column_index() == Some(column_index) {
+        (Color::Magenta, Color::LightMagenta)
+    } else {
+        (Color::Blue, Color::LightBlue)
+    };
 
-pub const COLUMN_COLORS_SELECTED: [Color; 4] = [
-    Color::LightCyan,    // Planning
-    Color::LightYellow,  // In Progress
-    Color::LightMagenta, // Review
-    Color::LightGreen,   // Done
-];
+    if is_selected { selected_color } else { color }
+}
@@ -1,17 +1,21 @@
 use super::columns::render_columns;
 use crate::app::App;
-use crate::views::StatusBarContent;
+use crate::types::{KeyAction, KeyContext, Keymap};
+use crate::views::mouse::MouseRegions;
 use crate::views::tasks::dialogs;
 use crate::views::tasks::state::{TasksMode, TasksViewMode};
-use crate::widgets::dialogs::{ConfirmDialog, DialogStyle, ErrorDialog, InputDialog};
+use crate::views::{
+    StatusBarContent, confirm_help, join_help, picker_help, scroll_help, terminal_help,
+};
+use crate::widgets::dialogs::{ErrorDialog, InputDialog};
 use ratatui::{Frame, layout::Rect, style::Color};
 
 const STATUS_BAR_WIDTH_THRESHOLD: u16 = 100;
 
-pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+pub fn render(frame: &mut Frame, app: &App, area: Rect, regions: &mut MouseRegions) {
     let state = &app.tasks;
 
-    render_columns(frame, app, area);
+    render_columns(frame, app, area, regions);
 
     match &state.mode {
         TasksMode::AddingTask { input, prompt } => {
@@ -35,19 +39,20 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             frame.render_widget(InputDialog::new("Edit Task", input), area);
         }
         TasksMode::ConfirmDelete { .. } => {
-            frame.render_widget(
-                ConfirmDialog::new("Delete Task", "Delete this task? (y/n)")
-                    .style(DialogStyle::Danger),
+            dialogs::render_danger_confirmation(
+                frame,
+                "Delete Task",
+                "Delete this task?",
+                &app.keymap,
                 area,
             );
         }
         TasksMode::ConfirmMoveBack { .. } => {
-            frame.render_widget(
-                ConfirmDialog::new(
-                    "Move Back",
-                    "Move back to previous column? This will terminate the Claude Code instance and clean up resources. (y/n)",
-                )
-                .style(DialogStyle::Danger),
+            dialogs::render_danger_confirmation(
+                frame,
+                "Move Back",
+                "Move back to previous column? This will terminate the Claude Code instance and clean up resources.",
+                &app.keymap,
                 area,
             );
         }
@@ -67,7 +72,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
                 focused_panel: *focused_panel,
                 selected_action: *selected_action,
             };
-            dialogs::render_review_popup(frame, app, &popup_state, area);
+            dialogs::render_review_popup(frame, app, &popup_state, area, regions);
         }
         TasksMode::ReviewRequestChanges { input, .. } => {
             frame.render_widget(InputDialog::new("Request Changes", input), area);
@@ -75,9 +80,20 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         TasksMode::MergeConfirmation {
             worktree_branch,
             selected_target,
+            strategy,
+            focused_field,
+            squash_subject,
             ..
         } => {
-            dialogs::render_merge_confirmation(frame, worktree_branch, selected_target, area);
+            let dialog_state = dialogs::MergeConfirmationViewState {
+                worktree_branch,
+                selected_target,
+                strategy: *strategy,
+                focused_field: *focused_field,
+                squash_subject,
+                keymap: &app.keymap,
+            };
+            dialogs::render_merge_confirmation(frame, &dialog_state, area);
         }
         TasksMode::SelectProvider {
             selected_index,
@@ -86,12 +102,15 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         } => {
             let dialog_state = dialogs::ProviderSelectionViewState {
                 selected_index: *selected_index,
-                default_provider: app.settings.settings.default_provider,
+                default_provider: &app.settings.settings.default_provider,
                 detected_providers,
             };
             dialogs::render_provider_selection(frame, &dialog_state, area);
         }
-        TasksMode::Normal | TasksMode::TerminalFocused | TasksMode::TerminalScroll => {}
+        TasksMode::Normal
+        | TasksMode::TerminalFocused
+        | TasksMode::TerminalScroll
+        | TasksMode::SelectBlockers { .. } => {}
     }
 
     if let Some(error) = &state.error_message {
@@ -114,7 +133,7 @@ pub fn get_status_bar_content(app: &App, width: u16) -> StatusBarContent {
         | TasksMode::ReviewRequestChanges { .. }
         | TasksMode::SelectProvider { .. } => Color::Yellow,
         TasksMode::ConfirmDelete { .. } => Color::Red,
-        TasksMode::ConfirmMoveBack { .. } => Color::LightRed,
+        TasksMode::ConfirmMoveBack { .. } | TasksMode::SelectBlockers { .. } => Color::LightRed,
     };
 
     let mode_text = match &state.mode {
@@ -130,6 +149,7 @@ pub fn get_status_bar_content(app: &App, width: u16) -> StatusBarContent {
         TasksMode::ReviewPopup { .. } => "REVIEW OUTPUT",
         TasksMode::ReviewRequestChanges { .. } => "REQUEST CHANGES",
         TasksMode::MergeConfirmation { .. } => "MERGE",
+        TasksMode::SelectBlockers { .. } => "SELECT BLOCKERS",
     };
 
     let view_indicator = match state.view_mode {
@@ -137,52 +157,141 @@ pub fn get_status_bar_content(app: &App, width: u16) -> StatusBarContent {
         TasksViewMode::Kanban => "[Kanban]",
     };
 
-    let help_text = if width < STATUS_BAR_WIDTH_THRESHOLD {
-        match &state.mode {
-            TasksMode::Normal => "hjkl/arrows:navigate  a:add  e:edit  d:delete  /:view",
-            TasksMode::AddingTask { .. }
-            | TasksMode::EditingTask { .. }
-            | TasksMode::ReviewRequestChanges { .. } => "Enter:save  Esc:cancel",
-            TasksMode::SelectWorktree { .. } | TasksMode::SelectProvider { .. } => {
-                "jk:select  Enter:choose  Esc:cancel"
-            }
-            TasksMode::ConfirmDelete { .. } | TasksMode::ConfirmMoveBack { .. } => "y:yes  n:no",
-            TasksMode::ReviewPopup { .. } => "Tab:panel  jk:move/scroll  hl:button  Enter:action",
-            TasksMode::TerminalFocused => "Ctrl+s:scroll  Esc:back",
-            TasksMode::TerminalScroll => "jk:line  Ctrl+d/u:page  g/G:top/bottom  q:exit",
-            TasksMode::MergeConfirmation { .. } => "jk:select  Enter:merge  Esc:cancel",
-        }
+    let is_wide = width >= STATUS_BAR_WIDTH_THRESHOLD;
+    let help_text = match &state.mode {
+        TasksMode::Normal => normal_help(&app.keymap, is_wide),
+        TasksMode::TerminalFocused => terminal_help(&app.keymap, is_wide),
+        TasksMode::TerminalScroll => scroll_help(&app.keymap, is_wide),
+        TasksMode::SelectBlockers { .. } => blockers_help(&app.keymap, is_wide),
+        mode => dialog_help(mode, &app.keymap, is_wide),
+    };
+
+    StatusBarContent {
+        mode_text: mode_text.to_string(),
+        mode_color,
+        extra_info: Some(queue_info(state).map_or_else(
+            || format!("{view_indicator} "),
+            |queue_info| format!("{view_indicator} {queue_info}  "),
+        )),
+        help_text,
+    }
+}
+
+fn normal_help(keymap: &Keymap, is_wide: bool) -> String {
+    let board = if is_wide {
+        keymap.help(
+            KeyContext::Kanban,
+            &[
+                (&[KeyAction::MoveDown, KeyAction::MoveUp], "task"),
+                (&[KeyAction::MoveLeft, KeyAction::MoveRight], "column"),
+                (&[KeyAction::Add], "add-to-planning"),
+                (&[KeyAction::Edit], "edit"),
+                (&[KeyAction::Delete], "delete"),
+                (&[KeyAction::SelectBlockers], "blockers"),
+                (
+                    &[KeyAction::ToggleQueue, KeyAction::CyclePriority],
+                    "queue/priority",
+                ),
+                (&[KeyAction::Open], "move→"),
+                (&[KeyAction::MoveBack], "move←"),
+            ],
+        )
     } else {
-        match &state.mode {
-            TasksMode::Normal => {
-                "↑↓/jk:task  ←→/hl:column  a:add-to-planning  e:edit  d:delete  Enter:move→  Backspace:move←  /:switch-view"
-            }
+        keymap.help(
+            KeyContext::Kanban,
+            &[
+                (
+                    &[
+                        KeyAction::MoveLeft,
+                        KeyAction::MoveDown,
+                        KeyAction::MoveUp,
+                        KeyAction::MoveRight,
+                    ],
+                    "navigate",
+                ),
+                (&[KeyAction::Add], "add"),
+                (&[KeyAction::Edit], "edit"),
+                (&[KeyAction::Delete], "delete"),
+                (&[KeyAction::SelectBlockers], "blockers"),
+                (&[KeyAction::ToggleQueue], "queue"),
+            ],
+        )
+    };
+
+    let view_label = if is_wide { "switch-view" } else { "view" };
+    let view = keymap.help(KeyContext::Tasks, &[(&[KeyAction::ToggleView], view_label)]);
+    join_help([board, view])
+}
+
+fn blockers_help(keymap: &Keymap, is_wide: bool) -> String {
+    let navigation = if is_wide {
+        keymap.help(
+            KeyContext::Kanban,
+            &[
+                (&[KeyAction::MoveDown, KeyAction::MoveUp], "task"),
+                (&[KeyAction::MoveLeft, KeyAction::MoveRight], "column"),
+            ],
+        )
+    } else {
+        keymap.help(
+            KeyContext::Kanban,
+            &[(
+                &[
+                    KeyAction::MoveLeft,
+                    KeyAction::MoveDown,
+                    KeyAction::MoveUp,
+                    KeyAction::MoveRight,
+                ],
+                "navigate",
+            )],
+        )
+    };
+
+    let toggle_label = if is_wide { "toggle-blocker" } else { "toggle" };
+    let picker = keymap.help(
+        KeyContext::Picker,
+        &[
+            (&[KeyAction::Open], toggle_label),
+            (&[KeyAction::Exit], "done"),
+        ],
+    );
+    join_help([navigation, picker])
+}
+
+fn dialog_help(mode: &TasksMode, keymap: &Keymap, is_wide: bool) -> String {
+    match mode {
+        TasksMode::SelectWorktree { .. } | TasksMode::SelectProvider { .. } => {
+            picker_help(keymap, "choose")
+        }
+        TasksMode::ConfirmDelete { .. } | TasksMode::ConfirmMoveBack { .. } => confirm_help(keymap),
+        TasksMode::ReviewPopup { .. } => dialogs::review_help(keymap, is_wide),
+        TasksMode::MergeConfirmation { .. } => dialogs::merge_help(keymap),
+        mode => text_help(mode, is_wide).to_string(),
+    }
+}
+
+/// Help for the text fields, whose keys are fixed rather than taken from the keymap.
+const fn text_help(mode: &TasksMode, is_wide: bool) -> &'static str {
+    if is_wide {
+        match mode {
             TasksMode::AddingTask { .. } => "Type task title  Enter:save  Esc:cancel",
-            TasksMode::SelectWorktree { .. } | TasksMode::SelectProvider { .. } => {
-                "↑↓/jk:select  Enter:choose  Esc:cancel"
-            }
             TasksMode::EditingTask { .. } => "Type to enter text  Enter:save  Esc:cancel",
-            TasksMode::ConfirmDelete { .. } | TasksMode::ConfirmMoveBack { .. } => {
-                "y:yes  n:no  Esc:cancel"
-            }
-            TasksMode::ReviewPopup { .. } => {
-                "Tab:panel  jk:move/scroll  ←→/hl:select-button  Enter:execute-action  q/Esc:close"
-            }
             TasksMode::ReviewRequestChanges { .. } => {
                 "Type your change request  Enter:save  Esc:cancel"
             }
-            TasksMode::TerminalFocused => "Ctrl+s:scroll-mode  Esc:back-to-navigation",
-            TasksMode::TerminalScroll => {
-                "j/k:scroll-line  Ctrl+d/u:half-page  g/G:top/bottom  q/Esc:exit-scroll"
-            }
-            TasksMode::MergeConfirmation { .. } => "↑↓/jk:select-branch  Enter:merge  Esc/q:cancel",
+            _ => "",
+        }
+    } else {
+        match mode {
+            TasksMode::AddingTask { .. }
+            | TasksMode::EditingTask { .. }
+            | TasksMode::ReviewRequestChanges { .. } => "Enter:save  Esc:cancel",
+            _ => "",
         }
-    };
-
-    StatusBarContent {
-        mode_text: mode_text.to_string(),
-        mode_color,
-        extra_info: Some(format!("{view_indicator} ")),
-        help_text: help_text.to_string(),
     }
 }
+
+fn queue_info(state: &crate::views::tasks::TasksState) -> Option<String> {
+    let queued_count = state.queued_task_count();
+    (queued_count > 0).then(|| format!("Queued: {queued_count}"))
+}
@@ -0,0 +1,158 @@
+use super::git::GitBackend;
+use super::jujutsu::JujutsuBackend;
+use super::operations::{BaseComparison, MergeResult};
+use super::state::{Worktree, WorktreeInfo};
+use crate::views::settings::{MergeStrategy, VcsCommand};
+use anyhow::Result;
+use ratatui::style::Color;
+use std::path::Path;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangedFileKind {
+    Modified,
+    Added,
+    Deleted,
+    Renamed,
+    Copied,
+    Untracked,
+    Conflict,
+}
+
+impl ChangedFileKind {
+    #[must_use]
+    pub const fn color(self) -> Color {
+        match self {
+            Self::Modified => Color::Yellow,
+            Self::Added | Self::Untracked => Color::Green,
+            Self::Deleted => Color::Red,
+            Self::Renamed => Color::Cyan,
+            Self::Copied => Color::Blue,
+            Self::Conflict => Color::LightRed,
+        }
+    }
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Modified => "Modified",
+            Self::Added => "Added",
+            Self::Deleted => "Deleted",
+            Self::Renamed => "Renamed",
+            Self::Copied => "Copied",
+            Self::Untracked => "Untracked",
+            Self::Conflict => "Conflict",
+        }
+    }
+}
+
+/// A file with uncommitted changes in a worktree
+#[derive(Debug, Clone)]
+pub struct ChangedFile {
+    pub path: String,
+    pub original_path: Option<String>,
+    pub kind: ChangedFileKind,
+}
+
+/// Everything the task, review and merge flows ask of a version control system.
+///
+/// For jj the "branch" of a `WorktreeInfo` is its workspace name, the work in it is the
+/// workspace's working-copy commit and its ancestors, and merge targets are bookmarks.
+pub trait VcsBackend {
+    /// # Errors
+    ///
+    /// Returns an error if the repository cannot be read.
+    fn list_worktrees(&self, repository_path: &Path) -> Result<Vec<Worktree>>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the worktree/workspace cannot be created.
+    fn create_worktree(
+        &self,
+        repository_path: &Path,
+        task_title: &str,
+        task_id: &Uuid,
+    ) -> Result<WorktreeInfo>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the worktree/workspace or its branch cannot be removed.
+    fn delete_worktree(&self, repository_path: &Path, worktree_info: &WorktreeInfo) -> Result<()>;
+
+    /// Files changed since the last commit in the worktree
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the status cannot be read.
+    fn changed_files(&self, worktree_path: &Path) -> Result<Vec<ChangedFile>>;
+
+    /// Contents of `relative_path` as of the last commit in the worktree
+    fn read_committed_file(&self, worktree_path: &Path, relative_path: &Path) -> Option<String>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the changes cannot be committed.
+    fn commit_changes(&self, worktree_path: &Path, message: &str) -> Result<()>;
+
+    /// # Errors
+    ///
+    /// Returns an error if no branch is checked out.
+    fn current_branch(&self, repository_path: &Path) -> Result<String>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the repository cannot be read.
+    fn default_branch(&self, repository_path: &Path) -> Result<String>;
+
+    /// # Errors
+    ///
+    /// Returns an error if either branch cannot be resolved.
+    fn compare_with_base(
+        &self,
+        repository_path: &Path,
+        branch_name: &str,
+    ) -> Result<BaseComparison>;
+
+    /// Brings the default branch into the worktree, leaving conflicts there
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sync fails for a reason other than conflicts.
+    fn sync_with_base(
+        &self,
+        worktree_info: &WorktreeInfo,
+        strategy: MergeStrategy,
+    ) -> Result<MergeResult>;
+
+    /// Paths that would conflict when merging the worktree into the default branch
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the merge cannot be tried.
+    fn check_merge_conflicts(
+        &self,
+        repository_path: &Path,
+        worktree_info: &WorktreeInfo,
+    ) -> Result<Option<Vec<String>>>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the merge cannot be recorded or `strategy` cannot be honoured.
+    fn merge_worktree(
+        &self,
+        repository_path: &Path,
+        worktree_info: &WorktreeInfo,
+        target_branch: &str,
+        strategy: MergeStrategy,
+        squash_subject: Option<&str>,
+    ) -> Result<MergeResult>;
+}
+
+/// The backend that runs `vcs_command`
+#[must_use]
+pub fn backend(vcs_command: &VcsCommand) -> &'static dyn VcsBackend {
+    match vcs_command {
+        VcsCommand::Git => &GitBackend,
+        VcsCommand::Jujutsu => &JujutsuBackend,
+    }
+}
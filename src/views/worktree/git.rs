@@ -0,0 +1,1225 @@
+use super::backend::{ChangedFile, ChangedFileKind, VcsBackend};
+use super::operations::{BaseComparison, MergeResult, UncommittedChanges, generate_branch_name};
+use super::state::{Worktree, WorktreeInfo};
+use crate::views::settings::MergeStrategy;
+use anyhow::{Context, Result, anyhow};
+use git2::build::CheckoutBuilder;
+use git2::{BranchType, Repository};
+use std::collections::HashSet;
+use std::path::Path;
+use uuid::Uuid;
+
+const STATUS_CODE_LENGTH: usize = 2;
+const STATUS_PATH_OFFSET: usize = 3;
+const STATUS_RENAME_SEPARATOR: &str = " -> ";
+const STATUS_UNTRACKED: &str = "??";
+
+/// Git worktrees, with merges made through libgit2
+pub struct GitBackend;
+
+impl VcsBackend for GitBackend {
+    fn list_worktrees(&self, repository_path: &Path) -> Result<Vec<Worktree>> {
+        list_git_worktrees(repository_path)
+    }
+
+    fn create_worktree(
+        &self,
+        repository_path: &Path,
+        task_title: &str,
+        task_id: &Uuid,
+    ) -> Result<WorktreeInfo> {
+        create_git_worktree(repository_path, task_title, task_id)
+    }
+
+    fn delete_worktree(&self, repository_path: &Path, worktree_info: &WorktreeInfo) -> Result<()> {
+        delete_git_worktree(repository_path, worktree_info)
+    }
+
+    fn changed_files(&self, worktree_path: &Path) -> Result<Vec<ChangedFile>> {
+        get_changed_files(worktree_path)
+    }
+
+    fn read_committed_file(&self, worktree_path: &Path, relative_path: &Path) -> Option<String> {
+        read_head_file(worktree_path, relative_path)
+    }
+
+    fn commit_changes(&self, worktree_path: &Path, message: &str) -> Result<()> {
+        commit_worktree_changes(worktree_path, message)
+    }
+
+    fn current_branch(&self, repository_path: &Path) -> Result<String> {
+        get_current_branch(repository_path)
+    }
+
+    fn default_branch(&self, repository_path: &Path) -> Result<String> {
+        get_default_branch(repository_path)
+    }
+
+    fn compare_with_base(
+        &self,
+        repository_path: &Path,
+        branch_name: &str,
+    ) -> Result<BaseComparison> {
+        compare_with_base(repository_path, branch_name)
+    }
+
+    fn sync_with_base(
+        &self,
+        worktree_info: &WorktreeInfo,
+        strategy: MergeStrategy,
+    ) -> Result<MergeResult> {
+        sync_with_base(worktree_info, strategy)
+    }
+
+    fn check_merge_conflicts(
+        &self,
+        repository_path: &Path,
+        worktree_info: &WorktreeInfo,
+    ) -> Result<Option<Vec<String>>> {
+        check_merge_conflicts(repository_path, worktree_info)
+    }
+
+    fn merge_worktree(
+        &self,
+        repository_path: &Path,
+        worktree_info: &WorktreeInfo,
+        target_branch: &str,
+        strategy: MergeStrategy,
+        squash_subject: Option<&str>,
+    ) -> Result<MergeResult> {
+        merge_worktree(
+            repository_path,
+            worktree_info,
+            target_branch,
+            strategy,
+            squash_subject,
+        )
+    }
+}
+
+fn get_changed_files(worktree_path: &Path) -> Result<Vec<ChangedFile>> {
+    let output = std::process::Command::new("git")
+        .arg("status")
+        .arg("--porcelain")
+        .current_dir(worktree_path)
+        .output()
+        .context("Failed to get git status")?;
+
+    if !output.status.success() {
+        let error_message = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Git status failed: {error_message}"));
+    }
+
+    let status_text = String::from_utf8_lossy(&output.stdout);
+    Ok(status_text.lines().filter_map(parse_status_line).collect())
+}
+
+fn parse_status_line(line: &str) -> Option<ChangedFile> {
+    if line.len() < STATUS_PATH_OFFSET {
+        return None;
+    }
+
+    let status_code = &line[..STATUS_CODE_LENGTH];
+    let path_segment = &line[STATUS_PATH_OFFSET..];
+    let kind = parse_status_kind(status_code);
+    let (path, original_path) = split_status_path(path_segment, kind);
+
+    Some(ChangedFile {
+        path,
+        original_path,
+        kind,
+    })
+}
+
+fn parse_status_kind(status_code: &str) -> ChangedFileKind {
+    if status_code == STATUS_UNTRACKED {
+        return ChangedFileKind::Untracked;
+    }
+
+    if status_code.contains('U') || status_code == "AA" || status_code == "DD" {
+        return ChangedFileKind::Conflict;
+    }
+
+    if status_code.contains('R') {
+        return ChangedFileKind::Renamed;
+    }
+
+    if status_code.contains('C') {
+        return ChangedFileKind::Copied;
+    }
+
+    if status_code.contains('D') {
+        return ChangedFileKind::Deleted;
+    }
+
+    if status_code.contains('A') {
+        return ChangedFileKind::Added;
+    }
+
+    ChangedFileKind::Modified
+}
+
+fn split_status_path(path_segment: &str, kind: ChangedFileKind) -> (String, Option<String>) {
+    if matches!(kind, ChangedFileKind::Renamed | ChangedFileKind::Copied)
+        && let Some((original, updated)) = path_segment.split_once(STATUS_RENAME_SEPARATOR)
+    {
+        return (updated.to_string(), Some(original.to_string()));
+    }
+
+    (path_segment.to_string(), None)
+}
+
+fn read_head_file(worktree_path: &Path, relative_path: &Path) -> Option<String> {
+    let file_reference = format!("HEAD:{}", relative_path.to_string_lossy());
+    let output = std::process::Command::new("git")
+        .arg("show")
+        .arg(file_reference)
+        .current_dir(worktree_path)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout).ok()
+}
+
+/// Get the current branch name for a repository
+///
+/// # Errors
+///
+/// Returns an error if git command fails or HEAD is detached.
+fn get_current_branch(repository_path: &Path) -> Result<String> {
+    let output = std::process::Command::new("git")
+        .arg("rev-parse")
+        .arg("--abbrev-ref")
+        .arg("HEAD")
+        .current_dir(repository_path)
+        .output()
+        .context("Failed to get current branch")?;
+
+    if !output.status.success() {
+        let error_message = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Failed to get current branch: {error_message}"));
+    }
+
+    let branch_name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    if branch_name == "HEAD" {
+        return Err(anyhow!("HEAD is detached, no branch checked out"));
+    }
+
+    Ok(branch_name)
+}
+
+/// Commit all changes in a worktree
+///
+/// # Errors
+///
+/// Returns an error if git add or commit fails.
+fn commit_worktree_changes(worktree_path: &Path, message: &str) -> Result<()> {
+    let add_output = std::process::Command::new("git")
+        .arg("add")
+        .arg("-A")
+        .current_dir(worktree_path)
+        .output()
+        .context("Failed to stage changes")?;
+
+    if !add_output.status.success() {
+        let error_message = String::from_utf8_lossy(&add_output.stderr);
+        return Err(anyhow!("Git add failed: {error_message}"));
+    }
+
+    let commit_output = std::process::Command::new("git")
+        .arg("commit")
+        .arg("-m")
+        .arg(message)
+        .current_dir(worktree_path)
+        .output()
+        .context("Failed to commit changes")?;
+
+    if !commit_output.status.success() {
+        let error_message = String::from_utf8_lossy(&commit_output.stderr);
+        return Err(anyhow!("Git commit failed: {error_message}"));
+    }
+
+    Ok(())
+}
+
+/// Get the default branch name (main or master) for the repository
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be opened or HEAD cannot be read.
+fn get_default_branch(repository_path: &Path) -> Result<String> {
+    let repository = Repository::open(repository_path).context("Failed to open git repository")?;
+
+    if repository.find_branch("main", BranchType::Local).is_ok() {
+        return Ok("main".to_string());
+    }
+
+    if repository.find_branch("master", BranchType::Local).is_ok() {
+        return Ok("master".to_string());
+    }
+
+    let head = repository.head().context("Failed to get HEAD reference")?;
+    if let Some(name) = head.shorthand() {
+        return Ok(name.to_string());
+    }
+
+    Ok("main".to_string())
+}
+
+/// Count the commits a branch is ahead of and behind the default branch.
+///
+/// # Errors
+///
+/// Returns an error if the repository or branches cannot be resolved.
+fn compare_with_base(repository_path: &Path, branch_name: &str) -> Result<BaseComparison> {
+    let repository = Repository::open(repository_path).context("Failed to open git repository")?;
+    let base_branch_name = get_default_branch(repository_path)?;
+
+    let base_branch = repository
+        .find_branch(&base_branch_name, BranchType::Local)
+        .context("Failed to find base branch")?;
+    let review_branch = repository
+        .find_branch(branch_name, BranchType::Local)
+        .context("Failed to find review branch")?;
+
+    let base_commit = base_branch
+        .get()
+        .peel_to_commit()
+        .context("Failed to resolve base commit")?;
+    let review_commit = review_branch
+        .get()
+        .peel_to_commit()
+        .context("Failed to resolve review commit")?;
+
+    let (ahead, behind) = repository
+        .graph_ahead_behind(review_commit.id(), base_commit.id())
+        .context("Failed to compare branches")?;
+
+    Ok(BaseComparison {
+        base_branch_name,
+        ahead,
+        behind,
+    })
+}
+
+/// Merge or rebase the default branch into a worktree's branch, in the worktree itself, so
+/// that conflicts are left there for the agent to resolve
+fn sync_with_base(worktree_info: &WorktreeInfo, strategy: MergeStrategy) -> Result<MergeResult> {
+    let worktree_path = &worktree_info.worktree_path;
+    let comparison = compare_with_base(worktree_path, &worktree_info.branch_name)?;
+    if comparison.behind == 0 {
+        return Ok(MergeResult::Success);
+    }
+
+    let has_uncommitted_changes = get_changed_files(worktree_path)?
+        .iter()
+        .any(|file| file.kind != ChangedFileKind::Untracked);
+    if has_uncommitted_changes {
+        return Err(UncommittedChanges {
+            worktree_path: worktree_path.clone(),
+        }
+        .into());
+    }
+
+    let mut command = std::process::Command::new("git");
+    let subcommand = if strategy == MergeStrategy::Rebase {
+        command.arg("rebase");
+        "rebase"
+    } else {
+        command.arg("merge").arg("--no-edit");
+        "merge"
+    };
+    let output = command
+        .arg(&comparison.base_branch_name)
+        .current_dir(worktree_path)
+        .output()
+        .with_context(|| format!("Failed to execute git {subcommand}"))?;
+
+    if output.status.success() {
+        return Ok(MergeResult::Success);
+    }
+
+    let conflicted_files: Vec<String> = get_changed_files(worktree_path)?
+        .into_iter()
+        .filter(|file| file.kind == ChangedFileKind::Conflict)
+        .map(|file| file.path)
+        .collect();
+    if conflicted_files.is_empty() {
+        let error_message = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Git {subcommand} failed: {error_message}"));
+    }
+
+    Ok(MergeResult::Conflicts { conflicted_files })
+}
+
+/// Check if merging a branch into the default branch would cause conflicts
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be opened or merge analysis fails.
+fn check_merge_conflicts(
+    repository_path: &Path,
+    worktree_info: &WorktreeInfo,
+) -> Result<Option<Vec<String>>> {
+    let repository = Repository::open(repository_path).context("Failed to open git repository")?;
+    let default_branch = get_default_branch(repository_path)?;
+
+    let our_branch = repository
+        .find_branch(&default_branch, BranchType::Local)
+        .context("Failed to find default branch")?;
+    let their_branch = repository
+        .find_branch(&worktree_info.branch_name, BranchType::Local)
+        .context("Failed to find worktree branch")?;
+
+    let our_commit = our_branch
+        .get()
+        .peel_to_commit()
+        .context("Failed to get default branch commit")?;
+    let their_commit = their_branch
+        .get()
+        .peel_to_commit()
+        .context("Failed to get worktree branch commit")?;
+
+    let ancestor = repository
+        .find_commit(
+            repository
+                .merge_base(our_commit.id(), their_commit.id())
+                .context("Failed to find merge base")?,
+        )
+        .context("Failed to find ancestor commit")?;
+
+    let merge_options = git2::MergeOptions::new();
+    let index = repository
+        .merge_commits(&ancestor, &their_commit, Some(&merge_options))
+        .context("Failed to perform merge analysis")?;
+
+    if index.has_conflicts() {
+        return Ok(Some(get_index_conflicts(&index)?));
+    }
+
+    Ok(None)
+}
+
+fn list_git_worktrees(repository_path: &Path) -> Result<Vec<Worktree>> {
+    let repository = Repository::open(repository_path).context("Failed to open git repository")?;
+
+    let worktree_list = repository.worktrees().context("Failed to list worktrees")?;
+
+    let mut worktrees = Vec::new();
+
+    for worktree_name in worktree_list.iter().flatten() {
+        let Ok(worktree) = repository.find_worktree(worktree_name) else {
+            continue;
+        };
+
+        let path = worktree.path().to_path_buf();
+
+        let branch_name = extract_branch_name_from_worktree(&repository, &path)
+            .unwrap_or_else(|| "(detached)".to_string());
+
+        worktrees.push(Worktree {
+            path,
+            branch_name: branch_name.clone(),
+            is_bare: false,
+            is_detached: branch_name == "(detached)",
+        });
+    }
+
+    Ok(worktrees)
+}
+
+fn create_git_worktree(
+    repository_path: &Path,
+    task_title: &str,
+    task_id: &Uuid,
+) -> Result<WorktreeInfo> {
+    let repository = Repository::open(repository_path).context("Failed to open git repository")?;
+
+    let branch_name = generate_branch_name(task_title);
+
+    let branch_exists = repository
+        .find_branch(&branch_name, BranchType::Local)
+        .is_ok();
+
+    let final_branch_name = if branch_exists {
+        let short_id = &task_id.to_string()[..8];
+        format!("{branch_name}-{short_id}")
+    } else {
+        branch_name
+    };
+
+    let worktree_dir_name = final_branch_name.replace('/', "-");
+    let worktree_path = repository_path.join(format!(".chloe/worktrees/{worktree_dir_name}"));
+
+    let output = std::process::Command::new("git")
+        .arg("worktree")
+        .arg("add")
+        .arg(&worktree_path)
+        .arg("-b")
+        .arg(&final_branch_name)
+        .current_dir(repository_path)
+        .output()
+        .context("Failed to execute git worktree add")?;
+
+    if !output.status.success() {
+        let error_message = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Git worktree add failed: {error_message}"));
+    }
+
+    Ok(WorktreeInfo::new(final_branch_name, worktree_path))
+}
+
+/// Merge a worktree branch into a target branch using `strategy`
+/// Returns `MergeResult` indicating success or conflicts
+///
+/// The merge is made in memory and only moves the target branch, so the main checkout keeps
+/// its branch and uncommitted work. A checkout that has the target branch checked out is
+/// brought along the way `git merge` would, refusing rather than overwriting local changes.
+///
+/// A squash commit starts with `squash_subject`, or the branch name when there is none,
+/// followed by the summaries of the commits it squashes.
+///
+/// # Errors
+///
+/// Returns an error if the branches cannot be read, the merge cannot be recorded, a
+/// fast-forward is impossible, or local changes in a checkout of the target branch are in
+/// the way.
+fn merge_worktree(
+    repository_path: &Path,
+    worktree_info: &WorktreeInfo,
+    target_branch: &str,
+    strategy: MergeStrategy,
+    squash_subject: Option<&str>,
+) -> Result<MergeResult> {
+    let repository = Repository::open(repository_path).context("Failed to open git repository")?;
+    let branch_name = &worktree_info.branch_name;
+
+    let mut target_reference = repository
+        .find_branch(target_branch, BranchType::Local)
+        .with_context(|| format!("Failed to find branch {target_branch}"))?
+        .into_reference();
+    let our_commit = target_reference
+        .peel_to_commit()
+        .context("Failed to get target branch commit")?;
+    let their_commit = repository
+        .find_branch(branch_name, BranchType::Local)
+        .context("Failed to find worktree branch")?
+        .get()
+        .peel_to_commit()
+        .context("Failed to get worktree branch commit")?;
+
+    let is_up_to_date = our_commit.id() == their_commit.id()
+        || repository.graph_descendant_of(our_commit.id(), their_commit.id())?;
+    if is_up_to_date {
+        return Ok(MergeResult::Success);
+    }
+
+    let can_fast_forward = repository.graph_descendant_of(their_commit.id(), our_commit.id())?;
+    let landing = match strategy {
+        MergeStrategy::Merge | MergeStrategy::Rebase | MergeStrategy::FastForwardOnly
+            if can_fast_forward =>
+        {
+            Landing::Commit(their_commit.id())
+        }
+        MergeStrategy::FastForwardOnly => {
+            return Err(anyhow!(
+                "{target_branch} has moved on since {branch_name} branched off it, so it cannot be fast-forwarded; rebase or merge instead"
+            ));
+        }
+        MergeStrategy::Merge => {
+            let message = format!("Merge branch '{branch_name}' into {target_branch}");
+            commit_merged_tree(
+                &repository,
+                &our_commit,
+                &their_commit,
+                &message,
+                &[&our_commit, &their_commit],
+            )?
+        }
+        MergeStrategy::Squash => {
+            let subject = squash_subject.unwrap_or(branch_name);
+            let message = squash_message(&repository, &our_commit, &their_commit, subject)?;
+            commit_merged_tree(
+                &repository,
+                &our_commit,
+                &their_commit,
+                &message,
+                &[&our_commit],
+            )?
+        }
+        MergeStrategy::Rebase => rebase_commits(&repository, &our_commit, &their_commit)?,
+    };
+
+    let merged_commit_id = match landing {
+        Landing::Commit(commit_id) => commit_id,
+        Landing::Conflicts(conflicted_files) => {
+            return Ok(MergeResult::Conflicts { conflicted_files });
+        }
+    };
+    advance_branch(
+        &repository,
+        &mut target_reference,
+        merged_commit_id,
+        &format!("merge {branch_name}"),
+    )?;
+
+    Ok(MergeResult::Success)
+}
+
+/// The commit a merge strategy moves the target branch to, or the paths that kept it from
+/// making one
+enum Landing {
+    Commit(git2::Oid),
+    Conflicts(Vec<String>),
+}
+
+/// Commits the merge of `their_commit` into `our_commit` on top of `parents`
+fn commit_merged_tree(
+    repository: &Repository,
+    our_commit: &git2::Commit,
+    their_commit: &git2::Commit,
+    message: &str,
+    parents: &[&git2::Commit],
+) -> Result<Landing> {
+    let mut index = repository
+        .merge_commits(our_commit, their_commit, None)
+        .context("Failed to merge branches")?;
+    if index.has_conflicts() {
+        return Ok(Landing::Conflicts(get_index_conflicts(&index)?));
+    }
+
+    let tree_id = index
+        .write_tree_to(repository)
+        .context("Failed to write merged tree")?;
+    let tree = repository.find_tree(tree_id)?;
+    let signature = repository
+        .signature()
+        .context("Failed to read user.name and user.email from git config")?;
+    let commit_id = repository
+        .commit(None, &signature, &signature, message, &tree, parents)
+        .context("Failed to create merge commit")?;
+
+    Ok(Landing::Commit(commit_id))
+}
+
+/// `subject` followed by the summaries of the commits on `their_commit` that `our_commit`
+/// lacks, oldest first
+fn squash_message(
+    repository: &Repository,
+    our_commit: &git2::Commit,
+    their_commit: &git2::Commit,
+    subject: &str,
+) -> Result<String> {
+    let mut revision_walk = repository.revwalk()?;
+    revision_walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+    revision_walk.push(their_commit.id())?;
+    revision_walk.hide(our_commit.id())?;
+
+    let mut summaries = Vec::new();
+    for commit_id in revision_walk {
+        let commit = repository.find_commit(commit_id?)?;
+        if commit.parent_count() == 1
+            && let Some(summary) = commit.summary()
+        {
+            summaries.push(format!("* {summary}"));
+        }
+    }
+
+    Ok(if summaries.is_empty() {
+        format!("{subject}\n")
+    } else {
+        format!("{subject}\n\n{}\n", summaries.join("\n"))
+    })
+}
+
+/// Replays the commits on `their_commit` that `our_commit` lacks onto it, in memory, leaving
+/// the branches where they are
+fn rebase_commits(
+    repository: &Repository,
+    our_commit: &git2::Commit,
+    their_commit: &git2::Commit,
+) -> Result<Landing> {
+    let branch = repository.find_annotated_commit(their_commit.id())?;
+    let upstream = repository.find_annotated_commit(our_commit.id())?;
+    let mut rebase_options = git2::RebaseOptions::new();
+    rebase_options.inmemory(true);
+    let mut rebase = repository
+        .rebase(
+            Some(&branch),
+            Some(&upstream),
+            None,
+            Some(&mut rebase_options),
+        )
+        .context("Failed to start rebase")?;
+
+    let committer = repository
+        .signature()
+        .context("Failed to read user.name and user.email from git config")?;
+    let mut rebased_commit_id = our_commit.id();
+    while let Some(operation) = rebase.next() {
+        operation.context("Failed to replay commit")?;
+
+        let index = rebase.inmemory_index()?;
+        if index.has_conflicts() {
+            let conflicted_files = get_index_conflicts(&index)?;
+            rebase.abort()?;
+            return Ok(Landing::Conflicts(conflicted_files));
+        }
+
+        match rebase.commit(None, &committer, None) {
+            Ok(commit_id) => rebased_commit_id = commit_id,
+            // The change is already on the target branch
+            Err(error) if error.code() == git2::ErrorCode::Applied => {}
+            Err(error) => return Err(error).context("Failed to commit replayed change"),
+        }
+    }
+    rebase.finish(Some(&committer))?;
+
+    Ok(Landing::Commit(rebased_commit_id))
+}
+
+/// Moves `target_reference` to `commit_id`, first updating any checkout that has it checked
+/// out the way `git merge` would
+fn advance_branch(
+    repository: &Repository,
+    target_reference: &mut git2::Reference,
+    commit_id: git2::Oid,
+    log_message: &str,
+) -> Result<()> {
+    // Every checkout is checked first, so local changes in any of them stop the merge before
+    // the files in the others change
+    let checkouts = find_checkouts_of_branch(repository, target_reference.name())?;
+    for checkout in &checkouts {
+        check_for_local_changes(checkout, commit_id)?;
+    }
+    for checkout in &checkouts {
+        check_out_commit(checkout, commit_id)?;
+    }
+
+    target_reference
+        .set_target(commit_id, log_message)
+        .context("Failed to update target branch")?;
+
+    Ok(())
+}
+
+/// Fails when `checkout` has local changes to a path that moving it to `commit_id` would
+/// write, the same check a safe checkout makes before it writes anything
+fn check_for_local_changes(checkout: &Repository, commit_id: git2::Oid) -> Result<()> {
+    let head_tree = checkout.head()?.peel_to_tree()?;
+    let target_tree = checkout.find_commit(commit_id)?.tree()?;
+    let diff = checkout.diff_tree_to_tree(Some(&head_tree), Some(&target_tree), None)?;
+    let merged_paths: HashSet<&Path> = diff
+        .deltas()
+        .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+        .flatten()
+        .collect();
+
+    let mut status_options = git2::StatusOptions::new();
+    status_options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true);
+    let statuses = checkout.statuses(Some(&mut status_options))?;
+    let overwritten = statuses.iter().any(|entry| {
+        entry
+            .path()
+            .is_some_and(|path| merged_paths.contains(Path::new(path)))
+    });
+    if overwritten {
+        return Err(local_changes_error(checkout));
+    }
+    Ok(())
+}
+
+/// Updates the files in `checkout` to `commit_id` without touching local changes
+fn check_out_commit(checkout: &Repository, commit_id: git2::Oid) -> Result<()> {
+    let commit = checkout.find_commit(commit_id)?;
+    let mut checkout_options = CheckoutBuilder::new();
+    checkout_options.safe();
+    checkout
+        .checkout_tree(commit.as_object(), Some(&mut checkout_options))
+        .map_err(|error| {
+            if error.code() == git2::ErrorCode::Conflict {
+                local_changes_error(checkout)
+            } else {
+                let checkout_path = checkout.workdir().unwrap_or_else(|| checkout.path());
+                anyhow!("Failed to update {}: {error}", checkout_path.display())
+            }
+        })
+}
+
+fn local_changes_error(checkout: &Repository) -> anyhow::Error {
+    let checkout_path = checkout.workdir().unwrap_or_else(|| checkout.path());
+    anyhow!(
+        "Local changes in {} would be overwritten by the merge; commit or stash them first",
+        checkout_path.display()
+    )
+}
+
+/// The main checkout and linked worktrees that have the branch `reference_name` checked out
+fn find_checkouts_of_branch(
+    repository: &Repository,
+    reference_name: Option<&str>,
+) -> Result<Vec<Repository>> {
+    let mut checkouts = Vec::new();
+    if repository.is_bare() {
+        return Ok(checkouts);
+    }
+
+    checkouts.push(Repository::open(repository.path()).context("Failed to open git repository")?);
+    for worktree_name in repository.worktrees()?.iter().flatten() {
+        let Ok(worktree) = repository.find_worktree(worktree_name) else {
+            continue;
+        };
+        if let Ok(checkout) = Repository::open_from_worktree(&worktree) {
+            checkouts.push(checkout);
+        }
+    }
+
+    checkouts.retain(|checkout| {
+        checkout
+            .head()
+            .is_ok_and(|head| head.name().is_some() && head.name() == reference_name)
+    });
+    Ok(checkouts)
+}
+
+/// Paths with conflicts in an in-memory merge index
+fn get_index_conflicts(index: &git2::Index) -> Result<Vec<String>> {
+    let conflicted_files = index
+        .conflicts()
+        .context("Failed to get conflicts")?
+        .filter_map(Result::ok)
+        .filter_map(|conflict| {
+            conflict
+                .our
+                .or(conflict.their)
+                .or(conflict.ancestor)
+                .and_then(|entry| String::from_utf8(entry.path).ok())
+        })
+        .collect();
+
+    Ok(conflicted_files)
+}
+
+fn delete_git_worktree(repository_path: &Path, worktree_info: &WorktreeInfo) -> Result<()> {
+    let output = std::process::Command::new("git")
+        .arg("worktree")
+        .arg("remove")
+        .arg(&worktree_info.worktree_path)
+        .arg("--force")
+        .current_dir(repository_path)
+        .output()
+        .context("Failed to execute git worktree remove")?;
+
+    if !output.status.success() {
+        let error_message = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("Git worktree remove failed: {error_message}"));
+    }
+
+    let repository = Repository::open(repository_path).context("Failed to open git repository")?;
+
+    let mut branch = repository
+        .find_branch(&worktree_info.branch_name, BranchType::Local)
+        .context("Failed to find branch")?;
+
+    branch.delete().context("Failed to delete branch")?;
+
+    Ok(())
+}
+
+fn extract_branch_name_from_worktree(
+    _repository: &Repository,
+    worktree_path: &Path,
+) -> Option<String> {
+    let worktree_repository = Repository::open(worktree_path).ok()?;
+    let head = worktree_repository.head().ok()?;
+
+    if head.is_branch() {
+        head.shorthand().map(String::from)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// A repository on `main` with `shared.txt` committed, removed again when dropped
+    struct ScratchRepository {
+        path: PathBuf,
+        repository: Repository,
+    }
+
+    impl ScratchRepository {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(format!("chloe-merge-{}", Uuid::new_v4()));
+            let repository = Repository::init(&path).unwrap();
+            let mut config = repository.config().unwrap();
+            config.set_str("user.name", "Chloe").unwrap();
+            config.set_str("user.email", "chloe@example.com").unwrap();
+            repository.set_head("refs/heads/main").unwrap();
+
+            let scratch = Self { path, repository };
+            scratch.commit("HEAD", &[], "shared.txt", "one\n");
+            scratch
+        }
+
+        /// Commits `file_name` with `contents` on top of `parents`, moving `reference_name`
+        fn commit(
+            &self,
+            reference_name: &str,
+            parents: &[&git2::Commit],
+            file_name: &str,
+            contents: &str,
+        ) -> git2::Oid {
+            let blob = self.repository.blob(contents.as_bytes()).unwrap();
+            let base_tree = parents.first().map(|parent| parent.tree().unwrap());
+            let mut builder = self.repository.treebuilder(base_tree.as_ref()).unwrap();
+            builder.insert(file_name, blob, 0o100_644).unwrap();
+            let tree = self.repository.find_tree(builder.write().unwrap()).unwrap();
+            let signature = Signature::now("Chloe", "chloe@example.com").unwrap();
+            let commit_id = self
+                .repository
+                .commit(
+                    Some(reference_name),
+                    &signature,
+                    &signature,
+                    file_name,
+                    &tree,
+                    parents,
+                )
+                .unwrap();
+            if reference_name == "HEAD" {
+                let mut options = CheckoutBuilder::new();
+                options.force();
+                self.repository.checkout_head(Some(&mut options)).unwrap();
+            }
+            commit_id
+        }
+
+        fn branch_commit(&self, branch_name: &str) -> git2::Commit<'_> {
+            self.repository
+                .find_branch(branch_name, BranchType::Local)
+                .unwrap()
+                .get()
+                .peel_to_commit()
+                .unwrap()
+        }
+
+        fn check_out(&self, branch_name: &str) {
+            self.repository
+                .set_head(&format!("refs/heads/{branch_name}"))
+                .unwrap();
+            let mut options = CheckoutBuilder::new();
+            options.force();
+            self.repository.checkout_head(Some(&mut options)).unwrap();
+        }
+
+        fn task_branch(&self, file_name: &str, contents: &str) -> WorktreeInfo {
+            let base = self.branch_commit("main");
+            self.commit("refs/heads/chloe/task", &[&base], file_name, contents);
+            WorktreeInfo::new("chloe/task".to_string(), self.path.clone())
+        }
+    }
+
+    impl Drop for ScratchRepository {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    #[test]
+    fn test_parse_status_line_reads_renames_and_conflicts() {
+        let renamed = parse_status_line("R  old.rs -> new.rs").unwrap();
+        assert_eq!(renamed.kind, ChangedFileKind::Renamed);
+        assert_eq!(renamed.path, "new.rs");
+        assert_eq!(renamed.original_path.as_deref(), Some("old.rs"));
+
+        assert_eq!(
+            parse_status_line("?? notes.txt").unwrap().kind,
+            ChangedFileKind::Untracked
+        );
+        for status_code in ["UU", "AA", "DD"] {
+            let line = format!("{status_code} shared.txt");
+            assert_eq!(
+                parse_status_line(&line).unwrap().kind,
+                ChangedFileKind::Conflict
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge_leaves_the_main_checkout_and_its_changes_in_place() {
+        let scratch = ScratchRepository::new();
+        let worktree_info = scratch.task_branch("feature.txt", "feature\n");
+        let main_commit = scratch.branch_commit("main");
+        scratch.commit("HEAD", &[&main_commit], "other.txt", "other\n");
+        fs::write(scratch.path.join("shared.txt"), "work in progress\n").unwrap();
+        fs::write(scratch.path.join("notes.txt"), "untracked\n").unwrap();
+
+        let result = merge_worktree(
+            &scratch.path,
+            &worktree_info,
+            "main",
+            MergeStrategy::Merge,
+            None,
+        )
+        .unwrap();
+
+        assert!(matches!(result, MergeResult::Success));
+        let head = scratch.repository.head().unwrap();
+        assert_eq!(head.shorthand(), Some("main"));
+        let merged = head.peel_to_commit().unwrap();
+        assert_eq!(merged.parent_count(), 2);
+        assert!(merged.tree().unwrap().get_name("feature.txt").is_some());
+        assert_eq!(
+            fs::read_to_string(scratch.path.join("feature.txt")).unwrap(),
+            "feature\n"
+        );
+        assert_eq!(
+            fs::read_to_string(scratch.path.join("shared.txt")).unwrap(),
+            "work in progress\n"
+        );
+        assert!(scratch.path.join("notes.txt").exists());
+        let statuses = scratch.repository.statuses(None).unwrap();
+        let mut changed_paths: Vec<_> = statuses
+            .iter()
+            .filter_map(|entry| entry.path().map(String::from))
+            .collect();
+        changed_paths.sort();
+        assert_eq!(changed_paths, ["notes.txt", "shared.txt"]);
+        assert!(scratch.repository.find_reference("refs/stash").is_err());
+    }
+
+    #[test]
+    fn test_squash_lands_the_branch_as_one_commit() {
+        let scratch = ScratchRepository::new();
+        let worktree_info = scratch.task_branch("feature.txt", "feature\n");
+        let main_commit = scratch.branch_commit("main");
+        let main_id = scratch.commit("HEAD", &[&main_commit], "other.txt", "other\n");
+
+        let result = merge_worktree(
+            &scratch.path,
+            &worktree_info,
+            "main",
+            MergeStrategy::Squash,
+            Some("Add the feature"),
+        )
+        .unwrap();
+
+        assert!(matches!(result, MergeResult::Success));
+        let squashed = scratch.branch_commit("main");
+        assert_eq!(squashed.parent_ids().collect::<Vec<_>>(), [main_id]);
+        assert_eq!(
+            squashed.message(),
+            Some("Add the feature\n\n* feature.txt\n")
+        );
+        assert!(squashed.tree().unwrap().get_name("feature.txt").is_some());
+    }
+
+    #[test]
+    fn test_rebase_replays_the_branch_onto_the_target() {
+        let scratch = ScratchRepository::new();
+        let worktree_info = scratch.task_branch("feature.txt", "feature\n");
+        let main_commit = scratch.branch_commit("main");
+        let main_id = scratch.commit("HEAD", &[&main_commit], "other.txt", "other\n");
+
+        let result = merge_worktree(
+            &scratch.path,
+            &worktree_info,
+            "main",
+            MergeStrategy::Rebase,
+            None,
+        )
+        .unwrap();
+
+        assert!(matches!(result, MergeResult::Success));
+        let rebased = scratch.branch_commit("main");
+        assert_eq!(rebased.parent_ids().collect::<Vec<_>>(), [main_id]);
+        assert_eq!(rebased.summary(), Some("feature.txt"));
+        assert!(rebased.tree().unwrap().get_name("other.txt").is_some());
+    }
+
+    #[test]
+    fn test_fast_forward_only_refuses_diverged_branches() {
+        let scratch = ScratchRepository::new();
+        let worktree_info = scratch.task_branch("feature.txt", "feature\n");
+        let main_commit = scratch.branch_commit("main");
+        let main_id = scratch.commit("HEAD", &[&main_commit], "other.txt", "other\n");
+
+        let diverged = merge_worktree(
+            &scratch.path,
+            &worktree_info,
+            "main",
+            MergeStrategy::FastForwardOnly,
+            None,
+        );
+        assert!(diverged.is_err());
+        assert_eq!(scratch.branch_commit("main").id(), main_id);
+
+        let main_commit = scratch.branch_commit("main");
+        scratch
+            .repository
+            .branch("chloe/task", &main_commit, true)
+            .unwrap();
+        let worktree_info = scratch.task_branch("feature.txt", "feature\n");
+        merge_worktree(
+            &scratch.path,
+            &worktree_info,
+            "main",
+            MergeStrategy::FastForwardOnly,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            scratch.branch_commit("main").id(),
+            scratch.branch_commit("chloe/task").id()
+        );
+    }
+
+    #[test]
+    fn test_merge_reports_conflicts_without_moving_the_branch() {
+        let scratch = ScratchRepository::new();
+        let worktree_info = scratch.task_branch("shared.txt", "theirs\n");
+        let main_commit = scratch.branch_commit("main");
+        let main_id = scratch.commit("HEAD", &[&main_commit], "shared.txt", "ours\n");
+
+        let result = merge_worktree(
+            &scratch.path,
+            &worktree_info,
+            "main",
+            MergeStrategy::Merge,
+            None,
+        )
+        .unwrap();
+
+        let MergeResult::Conflicts { conflicted_files } = result else {
+            panic!("expected conflicts");
+        };
+        assert_eq!(conflicted_files, ["shared.txt"]);
+        assert_eq!(scratch.branch_commit("main").id(), main_id);
+    }
+
+    #[test]
+    fn test_merge_refuses_to_overwrite_local_changes() {
+        let scratch = ScratchRepository::new();
+        let worktree_info = scratch.task_branch("shared.txt", "theirs\n");
+        let main_id = scratch.branch_commit("main").id();
+        fs::write(scratch.path.join("shared.txt"), "work in progress\n").unwrap();
+
+        let error = merge_worktree(
+            &scratch.path,
+            &worktree_info,
+            "main",
+            MergeStrategy::Merge,
+            None,
+        )
+        .unwrap_err();
+        assert!(error.to_string().starts_with("Local changes in"), "{error}");
+        assert_eq!(scratch.branch_commit("main").id(), main_id);
+        assert_eq!(
+            fs::read_to_string(scratch.path.join("shared.txt")).unwrap(),
+            "work in progress\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_leaves_every_checkout_alone_when_one_has_local_changes() {
+        let scratch = ScratchRepository::new();
+        let worktree_info = scratch.task_branch("shared.txt", "theirs\n");
+        let main_id = scratch.branch_commit("main").id();
+        let checkout_path = scratch.path.with_extension("checkout");
+        let worktree = scratch
+            .repository
+            .worktree("checkout", &checkout_path, None)
+            .unwrap();
+        let checkout = Repository::open_from_worktree(&worktree).unwrap();
+        // libgit2 will not check out a branch twice, but git does with --force
+        fs::write(checkout.path().join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        let mut options = CheckoutBuilder::new();
+        options.force();
+        checkout.checkout_head(Some(&mut options)).unwrap();
+        fs::write(checkout_path.join("shared.txt"), "work in progress\n").unwrap();
+
+        let result = merge_worktree(
+            &scratch.path,
+            &worktree_info,
+            "main",
+            MergeStrategy::Merge,
+            None,
+        );
+        let main_contents = fs::read_to_string(scratch.path.join("shared.txt")).unwrap();
+        let _ = fs::remove_dir_all(&checkout_path);
+
+        let error = result.unwrap_err();
+        assert!(error.to_string().starts_with("Local changes in"), "{error}");
+        assert_eq!(scratch.branch_commit("main").id(), main_id);
+        assert_eq!(main_contents, "one\n");
+    }
+
+    #[test]
+    fn test_sync_merges_the_base_branch_into_the_worktree() {
+        let scratch = ScratchRepository::new();
+        let worktree_info = scratch.task_branch("feature.txt", "feature\n");
+        let main_commit = scratch.branch_commit("main");
+        scratch.commit("refs/heads/main", &[&main_commit], "other.txt", "other\n");
+        scratch.check_out("chloe/task");
+        assert_eq!(
+            compare_with_base(&scratch.path, "chloe/task").unwrap(),
+            BaseComparison {
+                base_branch_name: "main".to_string(),
+                ahead: 1,
+                behind: 1,
+            }
+        );
+
+        let result = sync_with_base(&worktree_info, MergeStrategy::Merge).unwrap();
+
+        assert!(matches!(result, MergeResult::Success));
+        let comparison = compare_with_base(&scratch.path, "chloe/task").unwrap();
+        assert_eq!((comparison.ahead, comparison.behind), (2, 0));
+        assert!(scratch.path.join("other.txt").exists());
+    }
+
+    #[test]
+    fn test_sync_leaves_rebase_conflicts_in_the_worktree() {
+        let scratch = ScratchRepository::new();
+        let worktree_info = scratch.task_branch("shared.txt", "task\n");
+        let main_commit = scratch.branch_commit("main");
+        scratch.commit("refs/heads/main", &[&main_commit], "shared.txt", "main\n");
+        scratch.check_out("chloe/task");
+
+        let result = sync_with_base(&worktree_info, MergeStrategy::Rebase).unwrap();
+
+        let MergeResult::Conflicts { conflicted_files } = result else {
+            panic!("expected conflicts");
+        };
+        assert_eq!(conflicted_files, ["shared.txt"]);
+        assert!(scratch.repository.path().join("rebase-merge").exists());
+    }
+
+    #[test]
+    fn test_sync_reports_uncommitted_changes_as_their_own_error() {
+        let scratch = ScratchRepository::new();
+        let worktree_info = scratch.task_branch("feature.txt", "feature\n");
+        let main_commit = scratch.branch_commit("main");
+        scratch.commit("refs/heads/main", &[&main_commit], "other.txt", "other\n");
+        scratch.check_out("chloe/task");
+        fs::write(scratch.path.join("feature.txt"), "work in progress\n").unwrap();
+
+        let error = sync_with_base(&worktree_info, MergeStrategy::Merge).unwrap_err();
+
+        assert!(error.is::<UncommittedChanges>(), "{error}");
+        assert!(!scratch.path.join("other.txt").exists());
+    }
+}
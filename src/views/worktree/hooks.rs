@@ -0,0 +1,247 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+/// Steps that prepare a new task worktree and clean up before it is deleted, set per project
+/// under `worktree_hooks` in `settings.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorktreeHooks {
+    /// Untracked files or directories copied from the main checkout, relative to its root.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copy: Vec<PathBuf>,
+    /// Untracked files or directories linked to the main checkout's copy instead.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub symlink: Vec<PathBuf>,
+    /// Shell command run in the task's pane, before its agent starts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub setup: Option<String>,
+    /// Shell command run in the worktree before it is deleted. Deletion stops if it fails.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub teardown: Option<String>,
+}
+
+impl WorktreeHooks {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.copy.is_empty()
+            && self.symlink.is_empty()
+            && self.setup.is_none()
+            && self.teardown.is_none()
+    }
+
+    /// The hooks in the project's settings, read when a worktree is created or deleted so
+    /// that edits apply to the next one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a settings file cannot be read.
+    pub fn configured() -> Result<Self> {
+        let settings = crate::persistence::storage::load_settings()
+            .context("Failed to read the worktree hooks")?;
+        Ok(settings.worktree_hooks)
+    }
+
+    /// Copies and links the listed files from `repository_path` into `worktree_path`. Files
+    /// missing from the main checkout, or already in the worktree, are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a path leaves the repository or a file cannot be copied or linked.
+    pub fn prepare(&self, repository_path: &Path, worktree_path: &Path) -> Result<()> {
+        for relative_path in &self.copy {
+            if let Some((source, destination)) =
+                resolve_paths(repository_path, worktree_path, relative_path)?
+            {
+                copy_recursively(&source, &destination)
+                    .with_context(|| format!("Failed to copy {}", relative_path.display()))?;
+            }
+        }
+
+        for relative_path in &self.symlink {
+            if let Some((source, destination)) =
+                resolve_paths(repository_path, worktree_path, relative_path)?
+            {
+                std::os::unix::fs::symlink(&source, &destination)
+                    .with_context(|| format!("Failed to link {}", relative_path.display()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs the teardown command, if any, in `worktree_path`. It can take a while, so Chloe
+    /// runs it away from the UI.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with what the command printed if it cannot be started or exits
+    /// unsuccessfully.
+    pub fn tear_down(&self, worktree_path: &Path) -> Result<()> {
+        let Some(teardown) = &self.teardown else {
+            return Ok(());
+        };
+        if !worktree_path.exists() {
+            return Ok(());
+        }
+
+        let output = Command::new("bash")
+            .args(["-c", teardown])
+            .current_dir(worktree_path)
+            .output()
+            .context("Failed to run the teardown command")?;
+
+        if !output.status.success() {
+            let printed = [&output.stdout, &output.stderr]
+                .iter()
+                .map(|stream| String::from_utf8_lossy(stream).trim().to_string())
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("; ");
+            bail!("Teardown command failed ({}): {printed}", output.status);
+        }
+
+        Ok(())
+    }
+}
+
+/// Where `relative_path` is in the main checkout and where it goes in the worktree, or `None`
+/// when there is nothing to bring over.
+fn resolve_paths(
+    repository_path: &Path,
+    worktree_path: &Path,
+    relative_path: &Path,
+) -> Result<Option<(PathBuf, PathBuf)>> {
+    let stays_inside = relative_path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if !stays_inside {
+        bail!(
+            "Worktree hook paths must be relative to the repository root: {}",
+            relative_path.display()
+        );
+    }
+
+    let source = repository_path.join(relative_path);
+    let destination = worktree_path.join(relative_path);
+    if !source.exists() || destination.symlink_metadata().is_ok() {
+        return Ok(None);
+    }
+
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    Ok(Some((source, destination)))
+}
+
+fn copy_recursively(source: &Path, destination: &Path) -> Result<()> {
+    if !source.is_dir() {
+        fs::copy(source, destination)?;
+        return Ok(());
+    }
+
+    fs::create_dir_all(destination)?;
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        copy_recursively(&entry.path(), &destination.join(entry.file_name()))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WorktreeHooks;
+    use std::fs;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    /// An empty directory, removed again when dropped
+    struct ScratchDirectory(PathBuf);
+
+    impl ScratchDirectory {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(format!("chloe-hooks-{}", Uuid::new_v4()));
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for ScratchDirectory {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_prepare_copies_and_links_untracked_files() {
+        let repository = ScratchDirectory::new();
+        let worktree = ScratchDirectory::new();
+        fs::write(repository.0.join(".env"), "SECRET=1").unwrap();
+        fs::create_dir_all(repository.0.join("config/local")).unwrap();
+        fs::write(repository.0.join("config/local/db.toml"), "port = 1").unwrap();
+        fs::create_dir_all(repository.0.join("node_modules")).unwrap();
+        fs::write(repository.0.join("README.md"), "main checkout").unwrap();
+        fs::write(worktree.0.join("README.md"), "tracked").unwrap();
+
+        let hooks = WorktreeHooks {
+            copy: vec![
+                PathBuf::from(".env"),
+                PathBuf::from("config/local"),
+                PathBuf::from("README.md"),
+                PathBuf::from("missing.txt"),
+            ],
+            symlink: vec![PathBuf::from("node_modules")],
+            ..WorktreeHooks::default()
+        };
+        hooks.prepare(&repository.0, &worktree.0).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(worktree.0.join(".env")).unwrap(),
+            "SECRET=1"
+        );
+        assert_eq!(
+            fs::read_to_string(worktree.0.join("config/local/db.toml")).unwrap(),
+            "port = 1"
+        );
+        assert_eq!(
+            fs::read_to_string(worktree.0.join("README.md")).unwrap(),
+            "tracked"
+        );
+        assert!(!worktree.0.join("missing.txt").exists());
+        assert_eq!(
+            fs::read_link(worktree.0.join("node_modules")).unwrap(),
+            repository.0.join("node_modules")
+        );
+    }
+
+    #[test]
+    fn test_prepare_rejects_paths_outside_the_repository() {
+        let repository = ScratchDirectory::new();
+        let worktree = ScratchDirectory::new();
+        let hooks = WorktreeHooks {
+            copy: vec![PathBuf::from("../secrets")],
+            ..WorktreeHooks::default()
+        };
+
+        assert!(hooks.prepare(&repository.0, &worktree.0).is_err());
+    }
+
+    #[test]
+    fn test_tear_down_reports_failing_commands() {
+        let worktree = ScratchDirectory::new();
+        let hooks = WorktreeHooks {
+            teardown: Some(
+                "touch torn-down && echo stopping && echo nope >&2 && false".to_string(),
+            ),
+            ..WorktreeHooks::default()
+        };
+
+        let error = hooks.tear_down(&worktree.0).unwrap_err();
+
+        assert!(worktree.0.join("torn-down").exists());
+        assert!(error.to_string().contains("stopping; nope"));
+    }
+}
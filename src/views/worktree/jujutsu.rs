@@ -0,0 +1,777 @@
+use super::backend::{ChangedFile, ChangedFileKind, VcsBackend};
+use super::operations::{BaseComparison, MergeResult, generate_workspace_name};
+use super::state::{Worktree, WorktreeInfo};
+use crate::views::settings::MergeStrategy;
+use anyhow::{Context, Result, anyhow};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use uuid::Uuid;
+
+const DIFF_SUMMARY_PATH_OFFSET: usize = 2;
+const RENAME_SEPARATOR: &str = " => ";
+
+/// Jujutsu workspaces, driven through the `jj` command line
+///
+/// Revisions are passed around as change ids, which keep naming the same change when jj
+/// rewrites it.
+pub struct JujutsuBackend;
+
+impl VcsBackend for JujutsuBackend {
+    fn list_worktrees(&self, repository_path: &Path) -> Result<Vec<Worktree>> {
+        list_jj_workspaces(repository_path)
+    }
+
+    fn create_worktree(
+        &self,
+        repository_path: &Path,
+        task_title: &str,
+        task_id: &Uuid,
+    ) -> Result<WorktreeInfo> {
+        create_jj_workspace(repository_path, task_title, task_id)
+    }
+
+    fn delete_worktree(&self, repository_path: &Path, worktree_info: &WorktreeInfo) -> Result<()> {
+        delete_jj_workspace(repository_path, worktree_info)
+    }
+
+    fn changed_files(&self, worktree_path: &Path) -> Result<Vec<ChangedFile>> {
+        get_changed_files(worktree_path)
+    }
+
+    fn read_committed_file(&self, worktree_path: &Path, relative_path: &Path) -> Option<String> {
+        let output = Command::new("jj")
+            .arg("file")
+            .arg("show")
+            .arg("-r")
+            .arg("@-")
+            .arg(format!("file:{}", quote(&relative_path.to_string_lossy())))
+            .current_dir(worktree_path)
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        String::from_utf8(output.stdout).ok()
+    }
+
+    fn commit_changes(&self, worktree_path: &Path, message: &str) -> Result<()> {
+        run_jj(worktree_path, &["commit", "-m", message]).map(|_| ())
+    }
+
+    fn current_branch(&self, repository_path: &Path) -> Result<String> {
+        log_lines(
+            repository_path,
+            "latest(::@ & bookmarks())",
+            "local_bookmarks.map(|bookmark| bookmark.name()).join(\"\\n\")",
+        )?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("No bookmark on the working copy or its ancestors"))
+    }
+
+    fn default_branch(&self, repository_path: &Path) -> Result<String> {
+        for name in ["main", "master"] {
+            if !log_lines(repository_path, &bookmark(name), "change_id")?.is_empty() {
+                return Ok(name.to_string());
+            }
+        }
+
+        Ok(self
+            .current_branch(repository_path)
+            .unwrap_or_else(|_| "main".to_string()))
+    }
+
+    fn compare_with_base(
+        &self,
+        repository_path: &Path,
+        branch_name: &str,
+    ) -> Result<BaseComparison> {
+        let base_branch_name = self.default_branch(repository_path)?;
+        let base = bookmark(&base_branch_name);
+        let workspace = format!("{}@", quote(branch_name));
+        let ahead_revset = format!("({base})..({workspace}) ~ empty()");
+        let behind_revset = format!("({workspace})..({base})");
+
+        Ok(BaseComparison {
+            ahead: log_lines(repository_path, &ahead_revset, "change_id")?.len(),
+            behind: log_lines(repository_path, &behind_revset, "change_id")?.len(),
+            base_branch_name,
+        })
+    }
+
+    fn sync_with_base(
+        &self,
+        worktree_info: &WorktreeInfo,
+        strategy: MergeStrategy,
+    ) -> Result<MergeResult> {
+        let worktree_path = &worktree_info.worktree_path;
+        let base_bookmark = self.default_branch(worktree_path)?;
+        let base = bookmark(&base_bookmark);
+        if log_lines(worktree_path, &format!("@..({base})"), "change_id")?.is_empty() {
+            return Ok(MergeResult::Success);
+        }
+
+        let conflicted_files = if strategy == MergeStrategy::Rebase {
+            run_jj(worktree_path, &["rebase", "-b", "@", "-d", &base])?;
+            collect_conflicted_files(worktree_path, &format!("({base})..@"))?
+        } else {
+            let head = resolve_task_head(worktree_path)?;
+            let message = format!(
+                "Merge {base_bookmark} into workspace '{}'",
+                worktree_info.branch_name
+            );
+            run_jj(worktree_path, &["new", &head, &base, "-m", &message])?;
+            run_jj(worktree_path, &["new"])?;
+            get_conflicted_files(worktree_path, "@-")?
+        };
+
+        if conflicted_files.is_empty() {
+            Ok(MergeResult::Success)
+        } else {
+            Ok(MergeResult::Conflicts { conflicted_files })
+        }
+    }
+
+    fn check_merge_conflicts(
+        &self,
+        repository_path: &Path,
+        worktree_info: &WorktreeInfo,
+    ) -> Result<Option<Vec<String>>> {
+        let base_bookmark = self.default_branch(repository_path)?;
+        let base = resolve_change(repository_path, &bookmark(&base_bookmark))?;
+        let head = resolve_task_head(&worktree_info.worktree_path)?;
+        if is_ancestor(repository_path, &head, &base)? {
+            return Ok(None);
+        }
+
+        let merge = create_commit(repository_path, &[&base, &head])?;
+        let conflicted_files = get_conflicted_files(repository_path, &merge);
+        run_jj(repository_path, &["abandon", &merge])?;
+        let conflicted_files = conflicted_files?;
+
+        Ok((!conflicted_files.is_empty()).then_some(conflicted_files))
+    }
+
+    fn merge_worktree(
+        &self,
+        repository_path: &Path,
+        worktree_info: &WorktreeInfo,
+        target_branch: &str,
+        strategy: MergeStrategy,
+        squash_subject: Option<&str>,
+    ) -> Result<MergeResult> {
+        merge_workspace(
+            repository_path,
+            worktree_info,
+            target_branch,
+            strategy,
+            squash_subject,
+        )
+    }
+}
+
+/// Land a workspace's work on the bookmark `target_bookmark` using `strategy`
+///
+/// Nothing but the bookmark moves, except for a rebase, which rewrites the workspace's
+/// commits onto the target the way `jj rebase` does and leaves any conflicts it runs into in
+/// them for the agent to resolve.
+fn merge_workspace(
+    repository_path: &Path,
+    worktree_info: &WorktreeInfo,
+    target_bookmark: &str,
+    strategy: MergeStrategy,
+    squash_subject: Option<&str>,
+) -> Result<MergeResult> {
+    let workspace_name = &worktree_info.branch_name;
+    let target = resolve_change(repository_path, &bookmark(target_bookmark))
+        .with_context(|| format!("Failed to find bookmark {target_bookmark}"))?;
+    let head = resolve_task_head(&worktree_info.worktree_path)?;
+
+    if is_ancestor(repository_path, &head, &target)? {
+        return Ok(MergeResult::Success);
+    }
+
+    let can_fast_forward = is_ancestor(repository_path, &target, &head)?;
+    let landed = match strategy {
+        MergeStrategy::Merge | MergeStrategy::Rebase | MergeStrategy::FastForwardOnly
+            if can_fast_forward =>
+        {
+            head
+        }
+        MergeStrategy::FastForwardOnly => {
+            return Err(anyhow!(
+                "{target_bookmark} has moved on since {workspace_name} branched off it, so it cannot be fast-forwarded; rebase or merge instead"
+            ));
+        }
+        MergeStrategy::Merge => {
+            let merge = create_commit(repository_path, &[&target, &head])?;
+            let conflicted_files = get_conflicted_files(repository_path, &merge)?;
+            if !conflicted_files.is_empty() {
+                run_jj(repository_path, &["abandon", &merge])?;
+                return Ok(MergeResult::Conflicts { conflicted_files });
+            }
+
+            let message = format!("Merge workspace '{workspace_name}' into {target_bookmark}");
+            run_jj(repository_path, &["describe", &merge, "-m", &message])?;
+            merge
+        }
+        MergeStrategy::Squash => {
+            let subject = squash_subject.unwrap_or(workspace_name);
+            let message = squash_message(repository_path, &target, &head, subject)?;
+            let squashed = create_commit(repository_path, &[&target])?;
+            let merge = create_commit(repository_path, &[&target, &head])?;
+            let conflicted_files = get_conflicted_files(repository_path, &merge)?;
+            if conflicted_files.is_empty() {
+                run_jj(
+                    repository_path,
+                    &["restore", "--from", &merge, "--into", &squashed],
+                )?;
+                run_jj(repository_path, &["describe", &squashed, "-m", &message])?;
+            }
+            run_jj(repository_path, &["abandon", &merge])?;
+            if !conflicted_files.is_empty() {
+                run_jj(repository_path, &["abandon", &squashed])?;
+                return Ok(MergeResult::Conflicts { conflicted_files });
+            }
+            squashed
+        }
+        MergeStrategy::Rebase => {
+            let roots = format!("roots({target}..{head})");
+            run_jj(repository_path, &["rebase", "-s", &roots, "-d", &target])?;
+            run_jj(&worktree_info.worktree_path, &["workspace", "update-stale"])?;
+
+            let conflicted_files =
+                collect_conflicted_files(repository_path, &format!("{target}..{head}"))?;
+            if !conflicted_files.is_empty() {
+                return Ok(MergeResult::Conflicts { conflicted_files });
+            }
+            head
+        }
+    };
+
+    run_jj(
+        repository_path,
+        &["bookmark", "set", target_bookmark, "-r", &landed],
+    )?;
+
+    Ok(MergeResult::Success)
+}
+
+/// `subject` followed by the summaries of the changes in `head` that `target` lacks, oldest
+/// first
+fn squash_message(
+    repository_path: &Path,
+    target: &str,
+    head: &str,
+    subject: &str,
+) -> Result<String> {
+    let revset = format!("({target}..{head}) ~ merges()");
+    let output = run_jj(
+        repository_path,
+        &[
+            "log",
+            "--no-graph",
+            "--reversed",
+            "-r",
+            &revset,
+            "-T",
+            "description.first_line() ++ \"\\n\"",
+        ],
+    )?;
+    let summaries: Vec<String> = output
+        .lines()
+        .filter(|summary| !summary.trim().is_empty())
+        .map(|summary| format!("* {summary}"))
+        .collect();
+
+    Ok(if summaries.is_empty() {
+        format!("{subject}\n")
+    } else {
+        format!("{subject}\n\n{}\n", summaries.join("\n"))
+    })
+}
+
+/// The workspace's latest non-empty change: its working-copy commit when that has edits,
+/// otherwise the commit below it
+fn resolve_task_head(worktree_path: &Path) -> Result<String> {
+    let heads = log_lines(worktree_path, "heads((@ | @-) ~ empty())", "change_id")?;
+    heads
+        .into_iter()
+        .next()
+        .map_or_else(|| resolve_change(worktree_path, "latest(@-)"), Ok)
+}
+
+/// Creates an empty commit on `parents` without moving any working copy, returning its
+/// change id
+fn create_commit(repository_path: &Path, parents: &[&str]) -> Result<String> {
+    let marker = format!("chloe-{}", Uuid::new_v4());
+    let mut arguments = vec!["new", "--no-edit", "-m", &marker];
+    arguments.extend_from_slice(parents);
+    run_jj(repository_path, &arguments)?;
+
+    resolve_change(
+        repository_path,
+        &format!("description(substring:{})", quote(&marker)),
+    )
+}
+
+fn is_ancestor(repository_path: &Path, ancestor: &str, descendant: &str) -> Result<bool> {
+    let revset = format!("{ancestor} & ::{descendant}");
+    Ok(!log_lines(repository_path, &revset, "change_id")?.is_empty())
+}
+
+/// Paths left with conflicts in the commit `change`
+fn get_conflicted_files(directory: &Path, change: &str) -> Result<Vec<String>> {
+    let has_conflicts = log_lines(directory, change, "conflict")?
+        .first()
+        .is_some_and(|conflict| conflict == "true");
+    if !has_conflicts {
+        return Ok(Vec::new());
+    }
+
+    let output = run_jj(directory, &["resolve", "--list", "-r", change])?;
+    Ok(parse_resolve_list(&output))
+}
+
+/// Paths left with conflicts in any change in `revset`
+fn collect_conflicted_files(directory: &Path, revset: &str) -> Result<Vec<String>> {
+    let conflicted_revset = format!("conflicts() & ({revset})");
+    let mut conflicted_files = Vec::new();
+    for change in log_lines(directory, &conflicted_revset, "change_id")? {
+        for file in get_conflicted_files(directory, &change)? {
+            if !conflicted_files.contains(&file) {
+                conflicted_files.push(file);
+            }
+        }
+    }
+
+    Ok(conflicted_files)
+}
+
+fn resolve_change(directory: &Path, revset: &str) -> Result<String> {
+    let mut changes = log_lines(directory, revset, "change_id")?;
+    if changes.len() != 1 {
+        return Err(anyhow!("{revset} does not name a single revision"));
+    }
+
+    Ok(changes.remove(0))
+}
+
+/// One line per revision in `revset`, rendered with `template`
+fn log_lines(directory: &Path, revset: &str, template: &str) -> Result<Vec<String>> {
+    let template = format!("{template} ++ \"\\n\"");
+    let output = run_jj(
+        directory,
+        &["log", "--no-graph", "-r", revset, "-T", &template],
+    )?;
+
+    Ok(output
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+fn run_jj(directory: &Path, arguments: &[&str]) -> Result<String> {
+    let subcommand = arguments.first().copied().unwrap_or_default();
+    let output = Command::new("jj")
+        .args(arguments)
+        .current_dir(directory)
+        .output()
+        .with_context(|| format!("Failed to execute jj {subcommand}"))?;
+
+    if !output.status.success() {
+        let error_message = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("jj {subcommand} failed: {error_message}"));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// A revset string literal naming `symbol`
+fn quote(symbol: &str) -> String {
+    let escaped = symbol
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+fn bookmark(name: &str) -> String {
+    format!("bookmarks(exact:{})", quote(name))
+}
+
+fn get_changed_files(worktree_path: &Path) -> Result<Vec<ChangedFile>> {
+    let summary = run_jj(worktree_path, &["diff", "--summary"])?;
+    let mut files: Vec<ChangedFile> = summary.lines().filter_map(parse_summary_line).collect();
+
+    for conflicted_path in get_conflicted_files(worktree_path, "@")? {
+        match files.iter_mut().find(|file| file.path == conflicted_path) {
+            Some(file) => file.kind = ChangedFileKind::Conflict,
+            None => files.push(ChangedFile {
+                path: conflicted_path,
+                original_path: None,
+                kind: ChangedFileKind::Conflict,
+            }),
+        }
+    }
+
+    Ok(files)
+}
+
+/// Parses a `jj diff --summary` line such as `M src/main.rs` or `R src/{old.rs => new.rs}`
+fn parse_summary_line(line: &str) -> Option<ChangedFile> {
+    let status = line.chars().next()?;
+    let path_segment = line.get(DIFF_SUMMARY_PATH_OFFSET..)?;
+    let kind = match status {
+        'A' => ChangedFileKind::Added,
+        'D' => ChangedFileKind::Deleted,
+        'R' => ChangedFileKind::Renamed,
+        'C' => ChangedFileKind::Copied,
+        _ => ChangedFileKind::Modified,
+    };
+
+    let (path, original_path) = match kind {
+        ChangedFileKind::Renamed | ChangedFileKind::Copied => split_renamed_path(path_segment),
+        _ => (path_segment.to_string(), None),
+    };
+
+    Some(ChangedFile {
+        path,
+        original_path,
+        kind,
+    })
+}
+
+fn split_renamed_path(path_segment: &str) -> (String, Option<String>) {
+    if let (Some(open), Some(close)) = (path_segment.find('{'), path_segment.rfind('}'))
+        && open < close
+        && let Some((original, updated)) =
+            path_segment[open + 1..close].split_once(RENAME_SEPARATOR)
+    {
+        let prefix = &path_segment[..open];
+        let suffix = &path_segment[close + 1..];
+        let join = |middle: &str| format!("{prefix}{middle}{suffix}").replace("//", "/");
+        return (join(updated), Some(join(original)));
+    }
+
+    match path_segment.split_once(RENAME_SEPARATOR) {
+        Some((original, updated)) => (updated.to_string(), Some(original.to_string())),
+        None => (path_segment.to_string(), None),
+    }
+}
+
+/// Paths in `jj resolve --list` output, which pads each path before describing its conflict
+fn parse_resolve_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split("  ").next())
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+fn list_jj_workspaces(repository_path: &Path) -> Result<Vec<Worktree>> {
+    let output = std::process::Command::new("jj")
+        .arg("workspace")
+        .arg("list")
+        .current_dir(repository_path)
+        .output()
+        .context("Failed to execute jj workspace list")?;
+
+    if !output.status.success() {
+        let error_message = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("jj workspace list failed: {error_message}"));
+    }
+
+    let output_text = String::from_utf8_lossy(&output.stdout);
+    let mut workspaces = Vec::new();
+
+    for line in output_text.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let parts: Vec<&str> = line.split(':').collect();
+        if parts.len() < 2 {
+            continue;
+        }
+
+        let workspace_name = parts[0].trim();
+        let workspace_path_str = parts[1].trim();
+        let workspace_path = PathBuf::from(workspace_path_str);
+
+        workspaces.push(Worktree {
+            path: workspace_path,
+            branch_name: workspace_name.to_string(),
+            is_bare: false,
+            is_detached: false,
+        });
+    }
+
+    Ok(workspaces)
+}
+
+fn create_jj_workspace(
+    repository_path: &Path,
+    task_title: &str,
+    task_id: &Uuid,
+) -> Result<WorktreeInfo> {
+    let workspace_name = generate_workspace_name(task_title, task_id);
+    let workspace_path = repository_path.join(format!(".chloe/workspaces/{workspace_name}"));
+
+    let workspaces_parent = repository_path.join(".chloe/workspaces");
+    fs::create_dir_all(&workspaces_parent)
+        .context("Failed to create .chloe/workspaces directory")?;
+
+    let output = std::process::Command::new("jj")
+        .arg("workspace")
+        .arg("add")
+        .arg("--name")
+        .arg(&workspace_name)
+        .arg(&workspace_path)
+        .current_dir(repository_path)
+        .output()
+        .context("Failed to execute jj workspace add")?;
+
+    if !output.status.success() {
+        let error_message = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("jj workspace add failed: {error_message}"));
+    }
+
+    Ok(WorktreeInfo::new(workspace_name, workspace_path))
+}
+
+fn delete_jj_workspace(repository_path: &Path, worktree_info: &WorktreeInfo) -> Result<()> {
+    const DEFAULT_WORKSPACE_NAME: &str = "default";
+
+    let is_default_workspace = worktree_info.branch_name == DEFAULT_WORKSPACE_NAME;
+    if is_default_workspace {
+        return Err(anyhow!(
+            "Cannot delete the default workspace. The default workspace is the main workspace and must be preserved."
+        ));
+    }
+
+    let output = std::process::Command::new("jj")
+        .arg("workspace")
+        .arg("forget")
+        .arg(&worktree_info.branch_name)
+        .current_dir(repository_path)
+        .output()
+        .context("Failed to execute jj workspace forget")?;
+
+    if !output.status.success() {
+        let error_message = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("jj workspace forget failed: {error_message}"));
+    }
+
+    if worktree_info.worktree_path.exists() {
+        fs::remove_dir_all(&worktree_info.worktree_path)
+            .context("Failed to remove workspace directory")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A jj repository in `root/repository` whose `main` bookmark holds `shared.txt`, with
+    /// room next to it for task workspaces
+    struct ScratchRepository {
+        root: PathBuf,
+        path: PathBuf,
+    }
+
+    impl ScratchRepository {
+        /// `None` when `jj` is not installed, so the tests using it pass without checking
+        /// anything
+        fn new() -> Option<Self> {
+            Command::new("jj").arg("--version").output().ok()?;
+
+            let root = std::env::temp_dir().join(format!("chloe-jj-{}", Uuid::new_v4()));
+            let path = root.join("repository");
+            fs::create_dir_all(&path).unwrap();
+            let scratch = Self { root, path };
+            run_jj(&scratch.path, &["git", "init"]).unwrap();
+            scratch.commit_on_main("shared.txt", "one\n");
+            Some(scratch)
+        }
+
+        /// Commits `file_name` with `contents` on top of `main` and moves `main` to it
+        fn commit_on_main(&self, file_name: &str, contents: &str) {
+            if !log_lines(&self.path, &bookmark("main"), "change_id")
+                .unwrap()
+                .is_empty()
+            {
+                run_jj(&self.path, &["new", "main"]).unwrap();
+            }
+            fs::write(self.path.join(file_name), contents).unwrap();
+            run_jj(&self.path, &["commit", "-m", file_name]).unwrap();
+            run_jj(&self.path, &["bookmark", "set", "main", "-r", "@-"]).unwrap();
+        }
+
+        /// Adds a workspace named `task` on `main` and commits `file_name` with `contents`
+        /// in it
+        fn task_workspace(&self, file_name: &str, contents: &str) -> WorktreeInfo {
+            let workspace_path = self.root.join("task");
+            let workspace_argument = workspace_path.to_string_lossy();
+            run_jj(
+                &self.path,
+                &[
+                    "workspace",
+                    "add",
+                    "--name",
+                    "task",
+                    "-r",
+                    "main",
+                    &workspace_argument,
+                ],
+            )
+            .unwrap();
+            fs::write(workspace_path.join(file_name), contents).unwrap();
+            run_jj(&workspace_path, &["commit", "-m", file_name]).unwrap();
+            WorktreeInfo::new("task".to_string(), workspace_path)
+        }
+
+        fn main_change(&self) -> String {
+            resolve_change(&self.path, &bookmark("main")).unwrap()
+        }
+    }
+
+    impl Drop for ScratchRepository {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[test]
+    fn test_merge_lands_the_workspace_on_the_target_bookmark() {
+        let Some(scratch) = ScratchRepository::new() else {
+            return;
+        };
+        let worktree_info = scratch.task_workspace("feature.txt", "feature\n");
+        scratch.commit_on_main("other.txt", "other\n");
+        let head = resolve_task_head(&worktree_info.worktree_path).unwrap();
+
+        let result = merge_workspace(
+            &scratch.path,
+            &worktree_info,
+            "main",
+            MergeStrategy::Merge,
+            None,
+        )
+        .unwrap();
+
+        assert!(matches!(result, MergeResult::Success));
+        let main = scratch.main_change();
+        assert!(is_ancestor(&scratch.path, &head, &main).unwrap());
+        let parents = log_lines(&scratch.path, &format!("parents({main})"), "change_id");
+        assert_eq!(parents.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_merge_reports_conflicts_without_moving_the_bookmark() {
+        let Some(scratch) = ScratchRepository::new() else {
+            return;
+        };
+        let worktree_info = scratch.task_workspace("shared.txt", "theirs\n");
+        scratch.commit_on_main("shared.txt", "ours\n");
+        let main_before = scratch.main_change();
+
+        let result = merge_workspace(
+            &scratch.path,
+            &worktree_info,
+            "main",
+            MergeStrategy::Merge,
+            None,
+        )
+        .unwrap();
+
+        let MergeResult::Conflicts { conflicted_files } = result else {
+            panic!("expected conflicts");
+        };
+        assert_eq!(conflicted_files, ["shared.txt"]);
+        assert_eq!(scratch.main_change(), main_before);
+        let conflicts = JujutsuBackend
+            .check_merge_conflicts(&scratch.path, &worktree_info)
+            .unwrap();
+        assert_eq!(conflicts, Some(vec!["shared.txt".to_string()]));
+    }
+
+    #[test]
+    fn test_sync_merges_the_base_bookmark_into_the_workspace() {
+        let Some(scratch) = ScratchRepository::new() else {
+            return;
+        };
+        let worktree_info = scratch.task_workspace("feature.txt", "feature\n");
+        scratch.commit_on_main("other.txt", "other\n");
+        let comparison = JujutsuBackend
+            .compare_with_base(&scratch.path, "task")
+            .unwrap();
+        assert_eq!((comparison.ahead, comparison.behind), (1, 1));
+
+        let result = JujutsuBackend
+            .sync_with_base(&worktree_info, MergeStrategy::Merge)
+            .unwrap();
+
+        assert!(matches!(result, MergeResult::Success));
+        let comparison = JujutsuBackend
+            .compare_with_base(&scratch.path, "task")
+            .unwrap();
+        assert_eq!(comparison.behind, 0);
+        assert!(worktree_info.worktree_path.join("other.txt").exists());
+    }
+
+    #[test]
+    fn test_parse_summary_line_reads_each_kind() {
+        let modified = parse_summary_line("M src/main.rs").unwrap();
+        assert_eq!(modified.kind, ChangedFileKind::Modified);
+        assert_eq!(modified.path, "src/main.rs");
+
+        assert_eq!(
+            parse_summary_line("A notes.txt").unwrap().kind,
+            ChangedFileKind::Added
+        );
+        assert_eq!(
+            parse_summary_line("D old.txt").unwrap().kind,
+            ChangedFileKind::Deleted
+        );
+        assert!(parse_summary_line("").is_none());
+    }
+
+    #[test]
+    fn test_parse_summary_line_expands_renames() {
+        let renamed = parse_summary_line("R src/{old.rs => new.rs}").unwrap();
+        assert_eq!(renamed.kind, ChangedFileKind::Renamed);
+        assert_eq!(renamed.path, "src/new.rs");
+        assert_eq!(renamed.original_path.as_deref(), Some("src/old.rs"));
+
+        let moved = parse_summary_line("R {src => lib}/mod.rs").unwrap();
+        assert_eq!(moved.path, "lib/mod.rs");
+        assert_eq!(moved.original_path.as_deref(), Some("src/mod.rs"));
+
+        let flattened = parse_summary_line("R {nested/ => }file.txt").unwrap();
+        assert_eq!(flattened.path, "file.txt");
+        assert_eq!(flattened.original_path.as_deref(), Some("nested/file.txt"));
+    }
+
+    #[test]
+    fn test_parse_resolve_list_keeps_only_paths() {
+        let output =
+            "src/main.rs    2-sided conflict\nREADME.md    2-sided conflict including 1 deletion\n";
+        assert_eq!(parse_resolve_list(output), ["src/main.rs", "README.md"]);
+    }
+
+    #[test]
+    fn test_quote_escapes_revset_strings() {
+        assert_eq!(quote("chloe/task"), "\"chloe/task\"");
+        assert_eq!(quote("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(bookmark("main"), "bookmarks(exact:\"main\")");
+    }
+}
@@ -1,4 +1,8 @@
 mod action;
+pub mod backend;
+mod git;
+mod hooks;
+mod jujutsu;
 pub mod operations;
 pub mod state;
 pub mod tab_events;
@@ -6,10 +10,13 @@ pub mod tab_state;
 pub mod view;
 
 pub use action::WorktreeAction;
+pub use backend::{ChangedFile, ChangedFileKind};
+pub use hooks::WorktreeHooks;
 pub use operations::{
-    MergeResult, WorktreeStatus, check_merge_conflicts, create_worktree, delete_worktree,
-    find_repository_root, get_commits_ahead_of_base, get_current_branch, get_default_branch,
-    get_worktree_status, merge_worktree, merge_worktree_to_main,
+    BaseComparison, MergeResult, UncommittedChanges, WorktreeStatus, check_merge_conflicts,
+    compare_with_base, create_worktree, delete_worktree, find_repository_root, get_changed_files,
+    get_current_branch, get_default_branch, get_worktree_status, merge_worktree,
+    read_committed_file, sync_with_base,
 };
 pub use state::WorktreeInfo;
 pub use tab_state::WorktreeTabState;
@@ -1,7 +1,12 @@
+use super::backend::{ChangedFile, ChangedFileKind, backend};
+use super::hooks::WorktreeHooks;
 use super::state::{Worktree, WorktreeInfo};
-use crate::views::settings::VcsCommand;
+use crate::providers::{
+    PERMISSION_HOOK_TIMEOUT_SECONDS, notify_command, permission_decision_command,
+};
+use crate::views::settings::{MergeStrategy, VcsCommand};
 use anyhow::{Context, Result, anyhow};
-use git2::{BranchType, Repository};
+use git2::Repository;
 use std::fs;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
@@ -10,6 +15,7 @@ const MAX_SLUG_LENGTH: usize = 50;
 
 /// Generate `.claude/settings.local.json` in the worktree to pre-configure permissions
 fn generate_claude_settings(worktree_path: &Path, task_id: &Uuid) -> Result<()> {
+    let socket_path = crate::events::get_socket_path();
     let settings = serde_json::json!({
         "permissions": {
             "allow": [
@@ -33,39 +39,40 @@ fn generate_claude_settings(worktree_path: &Path, task_id: &Uuid) -> Result<()>
             "SessionStart": [{
                 "hooks": [{
                     "type": "command",
-                    "command": format!("chloe notify start --worktree-id {}", task_id)
+                    "command": notify_command("start", *task_id, &socket_path)
                 }]
             }],
             "SessionEnd": [{
                 "hooks": [{
                     "type": "command",
-                    "command": format!("chloe notify end --worktree-id {}", task_id)
+                    "command": notify_command("end", *task_id, &socket_path)
                 }]
             }],
             "PermissionRequest": [{
                 "matcher": "*",
                 "hooks": [{
                     "type": "command",
-                    "command": format!("chloe notify permission --worktree-id {}", task_id)
+                    "command": permission_decision_command(*task_id, &socket_path),
+                    "timeout": PERMISSION_HOOK_TIMEOUT_SECONDS
                 }]
             }],
             "PostToolUse": [{
                 "matcher": "*",
                 "hooks": [{
                     "type": "command",
-                    "command": format!("chloe notify start --worktree-id {}", task_id)
+                    "command": notify_command("start", *task_id, &socket_path)
                 }]
             }],
             "Stop": [{
                 "hooks": [{
                     "type": "command",
-                    "command": format!("chloe notify end --worktree-id {}", task_id)
+                    "command": notify_command("end", *task_id, &socket_path)
                 }]
             }],
             "UserPromptSubmit": [{
                 "hooks": [{
                     "type": "command",
-                    "command": format!("chloe notify start --worktree-id {}", task_id)
+                    "command": notify_command("start", *task_id, &socket_path)
                 }]
             }]
         }
@@ -93,6 +100,35 @@ pub enum MergeResult {
     Conflicts { conflicted_files: Vec<String> },
 }
 
+/// Why a worktree could not be synced with the default branch: it has uncommitted changes.
+/// A later sync can succeed once they are committed, so callers may treat this as a skip.
+#[derive(Debug)]
+pub struct UncommittedChanges {
+    pub worktree_path: PathBuf,
+}
+
+impl std::fmt::Display for UncommittedChanges {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "{} has uncommitted changes; commit them before syncing",
+            self.worktree_path.display()
+        )
+    }
+}
+
+impl std::error::Error for UncommittedChanges {}
+
+/// How a worktree's branch compares with the default branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseComparison {
+    pub base_branch_name: String,
+    /// Commits on the branch that the default branch lacks
+    pub ahead: usize,
+    /// Commits on the default branch that the branch lacks
+    pub behind: usize,
+}
+
 /// Status of a worktree's working directory
 #[derive(Debug, Clone, Default)]
 pub struct WorktreeStatus {
@@ -118,179 +154,109 @@ impl WorktreeStatus {
 ///
 /// # Errors
 ///
-/// Returns an error if git status command fails.
-pub fn get_worktree_status(worktree_path: &Path) -> Result<WorktreeStatus> {
-    let output = std::process::Command::new("git")
-        .arg("status")
-        .arg("--porcelain")
-        .current_dir(worktree_path)
-        .output()
-        .context("Failed to get git status")?;
-
-    if !output.status.success() {
-        let error_message = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("Git status failed: {error_message}"));
-    }
-
-    let status_text = String::from_utf8_lossy(&output.stdout);
-    let mut modified_files = Vec::new();
-    let mut untracked_files = Vec::new();
-    let mut has_conflicts = false;
-
-    for line in status_text.lines() {
-        if line.len() < 3 {
-            continue;
-        }
-
-        let status_code = &line[..2];
-        let file_path = line[3..].to_string();
-
-        match status_code {
-            "UU" | "AA" | "DD" | "AU" | "UA" | "DU" | "UD" => {
-                has_conflicts = true;
-                modified_files.push(file_path);
-            }
-            "??" => {
-                untracked_files.push(file_path);
-            }
-            _ => {
-                if !status_code.trim().is_empty() {
-                    modified_files.push(file_path);
-                }
+/// Returns an error if the status command fails.
+pub fn get_worktree_status(
+    worktree_path: &Path,
+    vcs_command: &VcsCommand,
+) -> Result<WorktreeStatus> {
+    let mut status = WorktreeStatus::default();
+
+    for file in get_changed_files(worktree_path, vcs_command)? {
+        match file.kind {
+            ChangedFileKind::Untracked => status.untracked_files.push(file.path),
+            ChangedFileKind::Conflict => {
+                status.has_conflicts = true;
+                status.modified_files.push(file.path);
             }
+            _ => status.modified_files.push(file.path),
         }
     }
+    status.is_clean = status.modified_files.is_empty() && status.untracked_files.is_empty();
 
-    let is_clean = modified_files.is_empty() && untracked_files.is_empty();
-
-    Ok(WorktreeStatus {
-        is_clean,
-        modified_files,
-        untracked_files,
-        has_conflicts,
-    })
+    Ok(status)
 }
 
-/// Get the current branch name for a repository
+/// Get the files changed since the last commit in a worktree
 ///
 /// # Errors
 ///
-/// Returns an error if git command fails or HEAD is detached.
-pub fn get_current_branch(repository_path: &Path) -> Result<String> {
-    let output = std::process::Command::new("git")
-        .arg("rev-parse")
-        .arg("--abbrev-ref")
-        .arg("HEAD")
-        .current_dir(repository_path)
-        .output()
-        .context("Failed to get current branch")?;
-
-    if !output.status.success() {
-        let error_message = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("Failed to get current branch: {error_message}"));
-    }
-
-    let branch_name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+/// Returns an error if the status command fails.
+pub fn get_changed_files(
+    worktree_path: &Path,
+    vcs_command: &VcsCommand,
+) -> Result<Vec<ChangedFile>> {
+    backend(vcs_command).changed_files(worktree_path)
+}
 
-    if branch_name == "HEAD" {
-        return Err(anyhow!("HEAD is detached, no branch checked out"));
-    }
+/// Read a file as of the last commit in a worktree
+#[must_use]
+pub fn read_committed_file(
+    worktree_path: &Path,
+    relative_path: &Path,
+    vcs_command: &VcsCommand,
+) -> Option<String> {
+    backend(vcs_command).read_committed_file(worktree_path, relative_path)
+}
 
-    Ok(branch_name)
+/// Get the current branch name for a repository (for jj, the closest bookmark below the
+/// working copy)
+///
+/// # Errors
+///
+/// Returns an error if the command fails or no branch is checked out.
+pub fn get_current_branch(repository_path: &Path, vcs_command: &VcsCommand) -> Result<String> {
+    backend(vcs_command).current_branch(repository_path)
 }
 
 /// Commit all changes in a worktree
 ///
 /// # Errors
 ///
-/// Returns an error if git add or commit fails.
+/// Returns an error if the changes cannot be committed.
 #[allow(dead_code)]
-pub fn commit_worktree_changes(worktree_path: &Path, message: &str) -> Result<()> {
-    let add_output = std::process::Command::new("git")
-        .arg("add")
-        .arg("-A")
-        .current_dir(worktree_path)
-        .output()
-        .context("Failed to stage changes")?;
-
-    if !add_output.status.success() {
-        let error_message = String::from_utf8_lossy(&add_output.stderr);
-        return Err(anyhow!("Git add failed: {error_message}"));
-    }
-
-    let commit_output = std::process::Command::new("git")
-        .arg("commit")
-        .arg("-m")
-        .arg(message)
-        .current_dir(worktree_path)
-        .output()
-        .context("Failed to commit changes")?;
-
-    if !commit_output.status.success() {
-        let error_message = String::from_utf8_lossy(&commit_output.stderr);
-        return Err(anyhow!("Git commit failed: {error_message}"));
-    }
-
-    Ok(())
+pub fn commit_worktree_changes(
+    worktree_path: &Path,
+    message: &str,
+    vcs_command: &VcsCommand,
+) -> Result<()> {
+    backend(vcs_command).commit_changes(worktree_path, message)
 }
 
 /// Get the default branch name (main or master) for the repository
 ///
 /// # Errors
 ///
-/// Returns an error if the repository cannot be opened or HEAD cannot be read.
-pub fn get_default_branch(repository_path: &Path) -> Result<String> {
-    let repository = Repository::open(repository_path).context("Failed to open git repository")?;
-
-    if repository.find_branch("main", BranchType::Local).is_ok() {
-        return Ok("main".to_string());
-    }
-
-    if repository.find_branch("master", BranchType::Local).is_ok() {
-        return Ok("master".to_string());
-    }
-
-    let head = repository.head().context("Failed to get HEAD reference")?;
-    if let Some(name) = head.shorthand() {
-        return Ok(name.to_string());
-    }
-
-    Ok("main".to_string())
+/// Returns an error if the repository cannot be read.
+pub fn get_default_branch(repository_path: &Path, vcs_command: &VcsCommand) -> Result<String> {
+    backend(vcs_command).default_branch(repository_path)
 }
 
-/// Get the number of commits a branch is ahead of the default branch.
+/// Count the commits a branch is ahead of and behind the default branch.
 ///
 /// # Errors
 ///
 /// Returns an error if the repository or branches cannot be resolved.
-pub fn get_commits_ahead_of_base(
+pub fn compare_with_base(
     repository_path: &Path,
     branch_name: &str,
-) -> Result<(String, usize)> {
-    let repository = Repository::open(repository_path).context("Failed to open git repository")?;
-    let base_branch_name = get_default_branch(repository_path)?;
-
-    let base_branch = repository
-        .find_branch(&base_branch_name, BranchType::Local)
-        .context("Failed to find base branch")?;
-    let review_branch = repository
-        .find_branch(branch_name, BranchType::Local)
-        .context("Failed to find review branch")?;
-
-    let base_commit = base_branch
-        .get()
-        .peel_to_commit()
-        .context("Failed to resolve base commit")?;
-    let review_commit = review_branch
-        .get()
-        .peel_to_commit()
-        .context("Failed to resolve review commit")?;
-
-    let (ahead, _) = repository
-        .graph_ahead_behind(review_commit.id(), base_commit.id())
-        .context("Failed to compare branches")?;
-
-    Ok((base_branch_name, ahead))
+    vcs_command: &VcsCommand,
+) -> Result<BaseComparison> {
+    backend(vcs_command).compare_with_base(repository_path, branch_name)
+}
+
+/// Bring the default branch into a worktree: rebasing onto it for `MergeStrategy::Rebase`,
+/// merging it for every other strategy. Conflicts are left in the worktree for its agent.
+///
+/// # Errors
+///
+/// Returns an [`UncommittedChanges`] error if the worktree has uncommitted changes, or an
+/// error if the sync fails for a reason other than conflicts.
+pub fn sync_with_base(
+    worktree_info: &WorktreeInfo,
+    strategy: MergeStrategy,
+    vcs_command: &VcsCommand,
+) -> Result<MergeResult> {
+    backend(vcs_command).sync_with_base(worktree_info, strategy)
 }
 
 /// Check if merging a branch into the default branch would cause conflicts
@@ -301,65 +267,24 @@ pub fn get_commits_ahead_of_base(
 pub fn check_merge_conflicts(
     repository_path: &Path,
     worktree_info: &WorktreeInfo,
+    vcs_command: &VcsCommand,
 ) -> Result<Option<Vec<String>>> {
-    let repository = Repository::open(repository_path).context("Failed to open git repository")?;
-    let default_branch = get_default_branch(repository_path)?;
-
-    let our_branch = repository
-        .find_branch(&default_branch, BranchType::Local)
-        .context("Failed to find default branch")?;
-    let their_branch = repository
-        .find_branch(&worktree_info.branch_name, BranchType::Local)
-        .context("Failed to find worktree branch")?;
-
-    let our_commit = our_branch
-        .get()
-        .peel_to_commit()
-        .context("Failed to get default branch commit")?;
-    let their_commit = their_branch
-        .get()
-        .peel_to_commit()
-        .context("Failed to get worktree branch commit")?;
-
-    let ancestor = repository
-        .find_commit(
-            repository
-                .merge_base(our_commit.id(), their_commit.id())
-                .context("Failed to find merge base")?,
-        )
-        .context("Failed to find ancestor commit")?;
-
-    let merge_options = git2::MergeOptions::new();
-    let index = repository
-        .merge_commits(&ancestor, &their_commit, Some(&merge_options))
-        .context("Failed to perform merge analysis")?;
-
-    if index.has_conflicts() {
-        let conflicts: Vec<String> = index
-            .conflicts()
-            .context("Failed to get conflicts")?
-            .filter_map(Result::ok)
-            .filter_map(|conflict| {
-                conflict
-                    .our
-                    .or(conflict.their)
-                    .or(conflict.ancestor)
-                    .and_then(|entry| String::from_utf8(entry.path).ok())
-            })
-            .collect();
-        return Ok(Some(conflicts));
-    }
-
-    Ok(None)
+    backend(vcs_command).check_merge_conflicts(repository_path, worktree_info)
 }
 
 /// Get the repository root for a given path
 ///
 /// # Errors
 ///
-/// Returns an error if the path is not within a git repository.
+/// Returns an error if the path is not within a git or jj repository.
 pub fn find_repository_root(path: &Path) -> Result<PathBuf> {
-    let repository = Repository::discover(path).context("Not a git repository")?;
+    let Ok(repository) = Repository::discover(path) else {
+        return path
+            .ancestors()
+            .find(|directory| directory.join(".jj").is_dir())
+            .map(Path::to_path_buf)
+            .ok_or_else(|| anyhow!("Not a git or jj repository"));
+    };
 
     let workdir = repository
         .workdir()
@@ -374,79 +299,7 @@ pub fn find_repository_root(path: &Path) -> Result<PathBuf> {
 ///
 /// Returns an error if the repository cannot be opened or worktrees cannot be listed.
 pub fn list_worktrees(repository_path: &Path, vcs_command: &VcsCommand) -> Result<Vec<Worktree>> {
-    match vcs_command {
-        VcsCommand::Git => list_git_worktrees(repository_path),
-        VcsCommand::Jujutsu => list_jj_workspaces(repository_path),
-    }
-}
-
-fn list_git_worktrees(repository_path: &Path) -> Result<Vec<Worktree>> {
-    let repository = Repository::open(repository_path).context("Failed to open git repository")?;
-
-    let worktree_list = repository.worktrees().context("Failed to list worktrees")?;
-
-    let mut worktrees = Vec::new();
-
-    for worktree_name in worktree_list.iter().flatten() {
-        let Ok(worktree) = repository.find_worktree(worktree_name) else {
-            continue;
-        };
-
-        let path = worktree.path().to_path_buf();
-
-        let branch_name = extract_branch_name_from_worktree(&repository, &path)
-            .unwrap_or_else(|| "(detached)".to_string());
-
-        worktrees.push(Worktree {
-            path,
-            branch_name: branch_name.clone(),
-            is_bare: false,
-            is_detached: branch_name == "(detached)",
-        });
-    }
-
-    Ok(worktrees)
-}
-
-fn list_jj_workspaces(repository_path: &Path) -> Result<Vec<Worktree>> {
-    let output = std::process::Command::new("jj")
-        .arg("workspace")
-        .arg("list")
-        .current_dir(repository_path)
-        .output()
-        .context("Failed to execute jj workspace list")?;
-
-    if !output.status.success() {
-        let error_message = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("jj workspace list failed: {error_message}"));
-    }
-
-    let output_text = String::from_utf8_lossy(&output.stdout);
-    let mut workspaces = Vec::new();
-
-    for line in output_text.lines() {
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        let parts: Vec<&str> = line.split(':').collect();
-        if parts.len() < 2 {
-            continue;
-        }
-
-        let workspace_name = parts[0].trim();
-        let workspace_path_str = parts[1].trim();
-        let workspace_path = PathBuf::from(workspace_path_str);
-
-        workspaces.push(Worktree {
-            path: workspace_path,
-            branch_name: workspace_name.to_string(),
-            is_bare: false,
-            is_detached: false,
-        });
-    }
-
-    Ok(workspaces)
+    backend(vcs_command).list_worktrees(repository_path)
 }
 
 /// Generate a valid git branch name from a task title
@@ -483,101 +336,37 @@ pub fn generate_branch_name(task_title: &str) -> String {
 /// Create a new worktree/workspace for a task
 /// Returns `WorktreeInfo` with the branch/workspace name and path
 ///
+/// The project's worktree hooks copy and link their files into it, and its setup command is
+/// left pending for the task's pane to run.
+///
 /// # Errors
 ///
-/// Returns an error if the repository cannot be opened or worktree/workspace creation fails.
+/// Returns an error if the settings or the repository cannot be read, worktree/workspace
+/// creation fails, or the hook files cannot be brought over.
 pub fn create_worktree(
     repository_path: &Path,
     task_title: &str,
     task_id: &Uuid,
     vcs_command: &VcsCommand,
 ) -> Result<WorktreeInfo> {
-    match vcs_command {
-        VcsCommand::Git => create_git_worktree(repository_path, task_title, task_id),
-        VcsCommand::Jujutsu => create_jj_workspace(repository_path, task_title, task_id),
-    }
-}
+    let hooks = WorktreeHooks::configured()?;
+    let mut worktree_info =
+        backend(vcs_command).create_worktree(repository_path, task_title, task_id)?;
 
-fn create_git_worktree(
-    repository_path: &Path,
-    task_title: &str,
-    task_id: &Uuid,
-) -> Result<WorktreeInfo> {
-    let repository = Repository::open(repository_path).context("Failed to open git repository")?;
-
-    let branch_name = generate_branch_name(task_title);
-
-    let branch_exists = repository
-        .find_branch(&branch_name, BranchType::Local)
-        .is_ok();
-
-    let final_branch_name = if branch_exists {
-        let short_id = &task_id.to_string()[..8];
-        format!("{branch_name}-{short_id}")
-    } else {
-        branch_name
-    };
-
-    let worktree_dir_name = final_branch_name.replace('/', "-");
-    let worktree_path = repository_path.join(format!(".chloe/worktrees/{worktree_dir_name}"));
-
-    let output = std::process::Command::new("git")
-        .arg("worktree")
-        .arg("add")
-        .arg(&worktree_path)
-        .arg("-b")
-        .arg(&final_branch_name)
-        .current_dir(repository_path)
-        .output()
-        .context("Failed to execute git worktree add")?;
-
-    if !output.status.success() {
-        let error_message = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("Git worktree add failed: {error_message}"));
+    let prepared = generate_claude_settings(&worktree_info.worktree_path, task_id)
+        .and_then(|()| hooks.prepare(repository_path, &worktree_info.worktree_path));
+    if let Err(error) = prepared {
+        let _ = backend(vcs_command).delete_worktree(repository_path, &worktree_info);
+        return Err(error);
     }
-
-    let worktree_info = WorktreeInfo::new(final_branch_name, worktree_path.clone());
-
-    generate_claude_settings(&worktree_path, task_id)?;
+    worktree_info.pending_setup = hooks.setup;
 
     Ok(worktree_info)
 }
 
-fn create_jj_workspace(
-    repository_path: &Path,
-    task_title: &str,
-    task_id: &Uuid,
-) -> Result<WorktreeInfo> {
-    let workspace_name = generate_workspace_name(task_title, task_id);
-    let workspace_path = repository_path.join(format!(".chloe/workspaces/{workspace_name}"));
-
-    let workspaces_parent = repository_path.join(".chloe/workspaces");
-    fs::create_dir_all(&workspaces_parent)
-        .context("Failed to create .chloe/workspaces directory")?;
-
-    let output = std::process::Command::new("jj")
-        .arg("workspace")
-        .arg("add")
-        .arg("--name")
-        .arg(&workspace_name)
-        .arg(&workspace_path)
-        .current_dir(repository_path)
-        .output()
-        .context("Failed to execute jj workspace add")?;
-
-    if !output.status.success() {
-        let error_message = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("jj workspace add failed: {error_message}"));
-    }
-
-    let worktree_info = WorktreeInfo::new(workspace_name, workspace_path.clone());
-
-    generate_claude_settings(&workspace_path, task_id)?;
-
-    Ok(worktree_info)
-}
-
-fn generate_workspace_name(task_title: &str, task_id: &Uuid) -> String {
+/// Generate a jj workspace name from a task title and id
+/// Example: "Implement Worktree Support" -> "chloe-implement-worktree-support-1a2b3c4d"
+pub(super) fn generate_workspace_name(task_title: &str, task_id: &Uuid) -> String {
     let slug = task_title
         .to_lowercase()
         .chars()
@@ -606,191 +395,48 @@ fn generate_workspace_name(task_title: &str, task_id: &Uuid) -> String {
     format!("chloe-{truncated_slug}-{short_id}")
 }
 
-/// Merge a worktree branch into main (legacy function, calls `merge_worktree` with "main")
+/// Merge a worktree branch into a target branch using `strategy`
 /// Returns `MergeResult` indicating success or conflicts
 ///
-/// # Errors
-///
-/// Returns an error if checkout, stash, or merge operations fail.
-pub fn merge_worktree_to_main(
-    repository_path: &Path,
-    worktree_info: &WorktreeInfo,
-) -> Result<MergeResult> {
-    merge_worktree(repository_path, worktree_info, "main")
-}
-
-/// Merge a worktree branch into a target branch
-/// Returns `MergeResult` indicating success or conflicts
+/// Only the target branch moves, so the main checkout keeps its branch and uncommitted
+/// work. A squash commit starts with `squash_subject`, or the branch name when there is
+/// none, followed by the summaries of the commits it squashes.
 ///
 /// # Errors
 ///
-/// Returns an error if checkout, stash, or merge operations fail.
+/// Returns an error if the branches cannot be read, the merge cannot be recorded, a
+/// fast-forward is impossible, or local changes in a checkout of the target branch are in
+/// the way.
 pub fn merge_worktree(
     repository_path: &Path,
     worktree_info: &WorktreeInfo,
     target_branch: &str,
+    strategy: MergeStrategy,
+    squash_subject: Option<&str>,
+    vcs_command: &VcsCommand,
 ) -> Result<MergeResult> {
-    let branch_name = &worktree_info.branch_name;
-
-    let stash_output = std::process::Command::new("git")
-        .arg("stash")
-        .arg("--include-untracked")
-        .current_dir(repository_path)
-        .output()
-        .context("Failed to stash changes")?;
-
-    if !stash_output.status.success() {
-        let error_message = String::from_utf8_lossy(&stash_output.stderr);
-        return Err(anyhow!("Git stash failed: {error_message}"));
-    }
-
-    let checkout_output = std::process::Command::new("git")
-        .arg("checkout")
-        .arg(target_branch)
-        .current_dir(repository_path)
-        .output()
-        .context("Failed to checkout target branch")?;
-
-    if !checkout_output.status.success() {
-        let error_message = String::from_utf8_lossy(&checkout_output.stderr);
-        return Err(anyhow!(
-            "Git checkout {target_branch} failed: {error_message}"
-        ));
-    }
-
-    let merge_output = std::process::Command::new("git")
-        .arg("merge")
-        .arg(branch_name)
-        .arg("--no-edit")
-        .current_dir(repository_path)
-        .output()
-        .context("Failed to execute git merge")?;
-
-    if !merge_output.status.success() {
-        let stderr = String::from_utf8_lossy(&merge_output.stderr);
-
-        if stderr.contains("CONFLICT") || stderr.contains("conflict") {
-            let conflicted_files = get_conflicted_files(repository_path)?;
-            return Ok(MergeResult::Conflicts { conflicted_files });
-        }
-
-        let error_message = String::from_utf8_lossy(&merge_output.stderr);
-        return Err(anyhow!("Git merge failed: {error_message}"));
-    }
-
-    Ok(MergeResult::Success)
+    backend(vcs_command).merge_worktree(
+        repository_path,
+        worktree_info,
+        target_branch,
+        strategy,
+        squash_subject,
+    )
 }
 
-/// Get list of conflicted files from git status
-fn get_conflicted_files(repository_path: &Path) -> Result<Vec<String>> {
-    let status_output = std::process::Command::new("git")
-        .arg("status")
-        .arg("--porcelain")
-        .current_dir(repository_path)
-        .output()
-        .context("Failed to get git status")?;
-
-    if !status_output.status.success() {
-        return Err(anyhow!("Git status failed"));
-    }
-
-    let status_text = String::from_utf8_lossy(&status_output.stdout);
-    let conflicted_files: Vec<String> = status_text
-        .lines()
-        .filter(|line| {
-            line.starts_with("UU ") || line.starts_with("AA ") || line.starts_with("DD ")
-        })
-        .map(|line| line[3..].to_string())
-        .collect();
-
-    Ok(conflicted_files)
-}
-
-/// Delete a worktree/workspace (cleanup)
+/// Delete a worktree/workspace (cleanup), after running the project's teardown command in it
 ///
 /// # Errors
 ///
-/// Returns an error if the worktree/workspace or branch cannot be deleted.
+/// Returns an error if the settings cannot be read or the teardown command fails, leaving the
+/// worktree in place, or if the worktree/workspace or branch cannot be deleted.
 pub fn delete_worktree(
     repository_path: &Path,
     worktree_info: &WorktreeInfo,
     vcs_command: &VcsCommand,
 ) -> Result<()> {
-    match vcs_command {
-        VcsCommand::Git => delete_git_worktree(repository_path, worktree_info),
-        VcsCommand::Jujutsu => delete_jj_workspace(repository_path, worktree_info),
-    }
-}
-
-fn delete_git_worktree(repository_path: &Path, worktree_info: &WorktreeInfo) -> Result<()> {
-    let output = std::process::Command::new("git")
-        .arg("worktree")
-        .arg("remove")
-        .arg(&worktree_info.worktree_path)
-        .arg("--force")
-        .current_dir(repository_path)
-        .output()
-        .context("Failed to execute git worktree remove")?;
-
-    if !output.status.success() {
-        let error_message = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("Git worktree remove failed: {error_message}"));
-    }
-
-    let repository = Repository::open(repository_path).context("Failed to open git repository")?;
-
-    let mut branch = repository
-        .find_branch(&worktree_info.branch_name, BranchType::Local)
-        .context("Failed to find branch")?;
-
-    branch.delete().context("Failed to delete branch")?;
-
-    Ok(())
-}
-
-fn delete_jj_workspace(repository_path: &Path, worktree_info: &WorktreeInfo) -> Result<()> {
-    const DEFAULT_WORKSPACE_NAME: &str = "default";
-
-    let is_default_workspace = worktree_info.branch_name == DEFAULT_WORKSPACE_NAME;
-    if is_default_workspace {
-        return Err(anyhow!(
-            "Cannot delete the default workspace. The default workspace is the main workspace and must be preserved."
-        ));
-    }
-
-    let output = std::process::Command::new("jj")
-        .arg("workspace")
-        .arg("forget")
-        .arg(&worktree_info.branch_name)
-        .current_dir(repository_path)
-        .output()
-        .context("Failed to execute jj workspace forget")?;
-
-    if !output.status.success() {
-        let error_message = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("jj workspace forget failed: {error_message}"));
-    }
-
-    if worktree_info.worktree_path.exists() {
-        fs::remove_dir_all(&worktree_info.worktree_path)
-            .context("Failed to remove workspace directory")?;
-    }
-
-    Ok(())
-}
-
-fn extract_branch_name_from_worktree(
-    _repository: &Repository,
-    worktree_path: &Path,
-) -> Option<String> {
-    let worktree_repository = Repository::open(worktree_path).ok()?;
-    let head = worktree_repository.head().ok()?;
-
-    if head.is_branch() {
-        head.shorthand().map(String::from)
-    } else {
-        None
-    }
+    WorktreeHooks::configured()?.tear_down(&worktree_info.worktree_path)?;
+    backend(vcs_command).delete_worktree(repository_path, worktree_info)
 }
 
 #[cfg(test)]
@@ -10,6 +10,9 @@ pub struct WorktreeInfo {
     pub worktree_path: PathBuf,
     /// Whether this worktree was auto-created by Chloe
     pub auto_created: bool,
+    /// The project's setup command, until the task's pane has been started with it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_setup: Option<String>,
 }
 
 impl WorktreeInfo {
@@ -19,6 +22,7 @@ impl WorktreeInfo {
             branch_name,
             worktree_path,
             auto_created: true,
+            pending_setup: None,
         }
     }
 
@@ -29,6 +33,7 @@ impl WorktreeInfo {
             branch_name,
             worktree_path,
             auto_created: false,
+            pending_setup: None,
         }
     }
 }
@@ -1,33 +1,37 @@
 use super::WorktreeAction;
 use super::tab_state::{WorktreeMode, WorktreeTabState};
 use crate::events::{AppAction, EventHandler, EventResult};
+use crate::types::{KeyAction, KeyContext, Keymap};
 use crate::views::settings::VcsCommand;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::KeyEvent;
 use std::time::{Duration, Instant};
 
 impl EventHandler for WorktreeTabState {
-    fn handle_key(&mut self, key: KeyEvent) -> EventResult {
+    fn handle_key(&mut self, key: KeyEvent, keymap: &Keymap) -> EventResult {
         match self.mode {
-            WorktreeMode::Normal => self.handle_normal_mode_event(key),
-            WorktreeMode::ConfirmDelete { worktree_index } => {
-                self.handle_confirm_delete_event(key, worktree_index)
+            WorktreeMode::Normal => {
+                self.handle_normal_mode_event(keymap.action(KeyContext::Worktree, key))
             }
+            WorktreeMode::ConfirmDelete { worktree_index } => self.handle_confirm_delete_event(
+                keymap.action(KeyContext::Dialog, key),
+                worktree_index,
+            ),
         }
     }
 }
 
 impl WorktreeTabState {
-    fn handle_normal_mode_event(&mut self, key: KeyEvent) -> EventResult {
-        match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
+    fn handle_normal_mode_event(&mut self, action: Option<KeyAction>) -> EventResult {
+        match action {
+            Some(KeyAction::MoveDown) => {
                 self.select_next();
                 EventResult::Consumed
             }
-            KeyCode::Char('k') | KeyCode::Up => {
+            Some(KeyAction::MoveUp) => {
                 self.select_previous();
                 EventResult::Consumed
             }
-            KeyCode::Char('d') => {
+            Some(KeyAction::Delete) => {
                 if let Some(index) = self.selected_index {
                     self.mode = WorktreeMode::ConfirmDelete {
                         worktree_index: index,
@@ -35,21 +39,15 @@ impl WorktreeTabState {
                 }
                 EventResult::Consumed
             }
-            KeyCode::Char('o') => {
-                if let Some(index) = self.selected_index {
-                    self.pending_ide_open = Some(index);
-                }
-                EventResult::Action(AppAction::Worktree(WorktreeAction::OpenInIde(
-                    self.selected_index.unwrap_or(0),
-                )))
+            Some(KeyAction::OpenInIde) => {
+                self.selected_index.map_or(EventResult::Consumed, |index| {
+                    EventResult::Action(AppAction::Worktree(WorktreeAction::OpenInIde(index)))
+                })
             }
-            KeyCode::Char('t') => {
-                if let Some(index) = self.selected_index {
-                    self.pending_terminal_open = Some(index);
-                }
-                EventResult::Action(AppAction::Worktree(WorktreeAction::OpenInTerminal(
-                    self.selected_index.unwrap_or(0),
-                )))
+            Some(KeyAction::OpenInTerminal) => {
+                self.selected_index.map_or(EventResult::Consumed, |index| {
+                    EventResult::Action(AppAction::Worktree(WorktreeAction::OpenInTerminal(index)))
+                })
             }
             _ => EventResult::Ignored,
         }
@@ -57,16 +55,16 @@ impl WorktreeTabState {
 
     const fn handle_confirm_delete_event(
         &mut self,
-        key: KeyEvent,
+        action: Option<KeyAction>,
         worktree_index: usize,
     ) -> EventResult {
-        match key.code {
-            KeyCode::Char('y' | 'Y') => {
+        match action {
+            Some(KeyAction::Confirm) => {
                 self.pending_worktree_delete = Some(worktree_index);
                 self.mode = WorktreeMode::Normal;
                 EventResult::Consumed
             }
-            KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+            Some(KeyAction::Cancel) => {
                 self.mode = WorktreeMode::Normal;
                 EventResult::Consumed
             }
@@ -74,57 +72,37 @@ impl WorktreeTabState {
         }
     }
 
-    pub fn handle_key_event(&mut self, key: KeyEvent, vcs_command: &VcsCommand) -> bool {
+    /// Answers the delete confirmation, which needs the VCS to remove the worktree. Returns
+    /// whether the key was used.
+    pub fn handle_key_event(
+        &mut self,
+        key: KeyEvent,
+        keymap: &Keymap,
+        vcs_command: &VcsCommand,
+    ) -> bool {
         match self.mode {
-            WorktreeMode::Normal => self.handle_normal_mode(key),
-            WorktreeMode::ConfirmDelete { worktree_index } => {
-                self.handle_confirm_delete_mode(key, worktree_index, vcs_command)
-            }
-        }
-    }
-
-    const fn handle_normal_mode(&mut self, key: KeyEvent) -> bool {
-        match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
-                self.select_next();
-                true
-            }
-            KeyCode::Char('k') | KeyCode::Up => {
-                self.select_previous();
-                true
-            }
-            KeyCode::Char('d') => {
-                if self.selected_index.is_some() {
-                    self.mode = WorktreeMode::ConfirmDelete {
-                        worktree_index: self.selected_index.unwrap(),
-                    };
-                }
-                true
-            }
-            KeyCode::Char('o') => {
-                if let Some(index) = self.selected_index {
-                    self.pending_ide_open = Some(index);
-                    self.pending_terminal_open = Some(index);
-                }
-                true
-            }
-            _ => false,
+            WorktreeMode::Normal => false,
+            WorktreeMode::ConfirmDelete { worktree_index } => self.handle_confirm_delete_mode(
+                keymap.action(KeyContext::Dialog, key),
+                worktree_index,
+                vcs_command,
+            ),
         }
     }
 
     fn handle_confirm_delete_mode(
         &mut self,
-        key: KeyEvent,
+        action: Option<KeyAction>,
         worktree_index: usize,
         vcs_command: &VcsCommand,
     ) -> bool {
-        match key.code {
-            KeyCode::Char('y' | 'Y') => {
+        match action {
+            Some(KeyAction::Confirm) => {
                 self.delete_worktree_at_index(worktree_index, vcs_command);
                 self.mode = WorktreeMode::Normal;
                 true
             }
-            KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+            Some(KeyAction::Cancel) => {
                 self.mode = WorktreeMode::Normal;
                 true
             }
@@ -132,7 +110,7 @@ impl WorktreeTabState {
         }
     }
 
-    const fn select_next(&mut self) {
+    pub const fn select_next(&mut self) {
         if self.worktrees.is_empty() {
             self.selected_index = None;
             return;
@@ -145,7 +123,7 @@ impl WorktreeTabState {
         });
     }
 
-    const fn select_previous(&mut self) {
+    pub const fn select_previous(&mut self) {
         if self.worktrees.is_empty() {
             self.selected_index = None;
             return;
@@ -203,11 +181,8 @@ impl WorktreeTabState {
             return;
         };
 
-        let worktree_info = super::WorktreeInfo {
-            branch_name: worktree.branch_name.clone(),
-            worktree_path: worktree.path.clone(),
-            auto_created: true,
-        };
+        let worktree_info =
+            super::WorktreeInfo::new(worktree.branch_name.clone(), worktree.path.clone());
 
         let current_dir = match std::env::current_dir() {
             Ok(dir) => dir,
@@ -16,10 +16,6 @@ pub struct WorktreeTabState {
     #[serde(skip)]
     pub(super) needs_initial_refresh: bool,
     #[serde(skip)]
-    pub pending_ide_open: Option<usize>,
-    #[serde(skip)]
-    pub pending_terminal_open: Option<usize>,
-    #[serde(skip)]
     pub pending_worktree_delete: Option<usize>,
 }
 
@@ -39,12 +35,16 @@ impl WorktreeTabState {
             error_message: None,
             last_refresh: None,
             needs_initial_refresh: true,
-            pending_ide_open: None,
-            pending_terminal_open: None,
             pending_worktree_delete: None,
         }
     }
 
+    /// Whether the delete confirmation has the keyboard.
+    #[must_use]
+    pub const fn captures_keys(&self) -> bool {
+        matches!(self.mode, WorktreeMode::ConfirmDelete { .. })
+    }
+
     pub const fn mark_needs_refresh(&mut self) {
         self.needs_initial_refresh = true;
     }
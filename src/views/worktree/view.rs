@@ -1,6 +1,7 @@
 use super::tab_state::{WorktreeMode, WorktreeTabState};
-use crate::views::StatusBarContent;
+use crate::types::{KeyAction, KeyContext, Keymap};
 use crate::views::settings::VcsCommand;
+use crate::views::{StatusBarContent, confirm_help, global_help, join_help};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -233,7 +234,11 @@ fn build_status_badge(worktree: &super::state::Worktree) -> Span<'static> {
 }
 
 #[must_use]
-pub fn get_status_bar_content(state: &WorktreeTabState, width: u16) -> StatusBarContent {
+pub fn get_status_bar_content(
+    state: &WorktreeTabState,
+    keymap: &Keymap,
+    width: u16,
+) -> StatusBarContent {
     let mode_color = match state.mode {
         WorktreeMode::Normal => Color::Cyan,
         WorktreeMode::ConfirmDelete { .. } => Color::Red,
@@ -244,25 +249,34 @@ pub fn get_status_bar_content(state: &WorktreeTabState, width: u16) -> StatusBar
         WorktreeMode::ConfirmDelete { .. } => "CONFIRM DELETE",
     };
 
-    let help_text = if width < STATUS_BAR_WIDTH_THRESHOLD {
-        match state.mode {
-            WorktreeMode::Normal => "jk:navigate  o:open  r:refresh  d:delete",
-            WorktreeMode::ConfirmDelete { .. } => "y:yes  n:no",
-        }
-    } else {
-        match state.mode {
-            WorktreeMode::Normal => {
-                "↑↓/jk:navigate  o:open  r:refresh  d:delete  Tab:switch-tabs  q:quit"
-            }
-            WorktreeMode::ConfirmDelete { .. } => "y:yes  n:no  Esc:cancel",
-        }
+    let is_wide = width >= STATUS_BAR_WIDTH_THRESHOLD;
+    let help_text = match state.mode {
+        WorktreeMode::Normal => normal_help(keymap, is_wide),
+        WorktreeMode::ConfirmDelete { .. } => confirm_help(keymap),
     };
 
     StatusBarContent {
         mode_text: mode_text.to_string(),
         mode_color,
         extra_info: None,
-        help_text: help_text.to_string(),
+        help_text,
+    }
+}
+
+fn normal_help(keymap: &Keymap, is_wide: bool) -> String {
+    let worktree = keymap.help(
+        KeyContext::Worktree,
+        &[
+            (&[KeyAction::MoveDown, KeyAction::MoveUp], "navigate"),
+            (&[KeyAction::OpenInIde], "open"),
+            (&[KeyAction::OpenInTerminal], "terminal"),
+            (&[KeyAction::Delete], "delete"),
+        ],
+    );
+    if is_wide {
+        join_help([worktree, global_help(keymap)])
+    } else {
+        worktree
     }
 }
 
@@ -1,9 +1,11 @@
 mod confirm;
 mod error;
 mod input;
+mod palette;
 mod style;
 
 pub use confirm::ConfirmDialog;
 pub use error::ErrorDialog;
 pub use input::InputDialog;
+pub use palette::{PaletteDialog, PaletteItem};
 pub use style::DialogStyle;
@@ -0,0 +1,139 @@
+use super::style::DialogStyle;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Widget},
+};
+
+const DIALOG_WIDTH_MAXIMUM: u16 = 90;
+const DIALOG_HEIGHT_MAXIMUM: u16 = 20;
+const DIALOG_TOP_MARGIN_DIVISOR: u16 = 6;
+const DIALOG_BORDER_AND_PADDING: u16 = 4;
+const QUERY_HEIGHT: u16 = 2;
+const KIND_WIDTH: usize = 10;
+
+/// One row of a [`PaletteDialog`]: what kind of thing it is, its name and a dimmed detail.
+pub struct PaletteItem<'a> {
+    pub kind: &'a str,
+    pub label: &'a str,
+    pub detail: &'a str,
+}
+
+/// A query line over a list of choices, the shape of a command palette. The list scrolls to
+/// keep the selected item in view.
+pub struct PaletteDialog<'a> {
+    title: &'a str,
+    query: &'a str,
+    items: Vec<PaletteItem<'a>>,
+    selected_index: usize,
+    style: DialogStyle,
+}
+
+impl<'a> PaletteDialog<'a> {
+    #[must_use]
+    pub const fn new(
+        title: &'a str,
+        query: &'a str,
+        items: Vec<PaletteItem<'a>>,
+        selected_index: usize,
+    ) -> Self {
+        Self {
+            title,
+            query,
+            items,
+            selected_index,
+            style: DialogStyle::Normal,
+        }
+    }
+}
+
+impl Widget for PaletteDialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dialog_width = DIALOG_WIDTH_MAXIMUM.min(area.width.saturating_sub(4));
+        let top_margin = area.height / DIALOG_TOP_MARGIN_DIVISOR;
+        let popup_area = Rect {
+            x: area.x + area.width.saturating_sub(dialog_width) / 2,
+            y: area.y + top_margin,
+            width: dialog_width,
+            height: DIALOG_HEIGHT_MAXIMUM.min(area.height.saturating_sub(top_margin)),
+        };
+        let border_color = self.style.color();
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(self.title)
+            .title_style(
+                Style::default()
+                    .fg(border_color)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color))
+            .padding(Padding::horizontal(1));
+
+        let inner_area = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(QUERY_HEIGHT), Constraint::Min(0)])
+            .split(inner_area);
+
+        let query_line = Line::from(vec![
+            Span::styled("> ", Style::default().fg(border_color)),
+            Span::raw(format!("{}▏", self.query)),
+        ]);
+        Paragraph::new(query_line).render(layout[0], buf);
+
+        if self.items.is_empty() {
+            Paragraph::new(Span::styled("No matches", Style::default().fg(Color::Gray)))
+                .render(layout[1], buf);
+            return;
+        }
+
+        let visible_rows = usize::from(layout[1].height);
+        let offset = (self.selected_index + 1).saturating_sub(visible_rows);
+        let content_width = usize::from(dialog_width.saturating_sub(DIALOG_BORDER_AND_PADDING));
+
+        let lines: Vec<Line> = self
+            .items
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible_rows)
+            .map(|(index, item)| item_line(item, index == self.selected_index, content_width))
+            .collect();
+        Paragraph::new(lines).render(layout[1], buf);
+    }
+}
+
+fn item_line<'a>(item: &PaletteItem<'a>, is_selected: bool, width: usize) -> Line<'a> {
+    let label_width = width.saturating_sub(KIND_WIDTH);
+    let label: String = item.label.chars().take(label_width).collect();
+    let detail_width = label_width.saturating_sub(label.chars().count() + 2);
+    let detail: String = item.detail.chars().take(detail_width).collect();
+    let padding = label_width.saturating_sub(label.chars().count() + detail.chars().count());
+
+    let line = Line::from(vec![
+        Span::styled(
+            format!("{:<KIND_WIDTH$}", item.kind),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::styled(label, Style::default().fg(Color::White)),
+        Span::raw(" ".repeat(padding)),
+        Span::styled(detail, Style::default().fg(Color::Gray)),
+    ]);
+
+    if is_selected {
+        line.style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+    } else {
+        line
+    }
+}
@@ -1,6 +1,7 @@
 use crate::helpers::text;
 use crate::views::instances::ClaudeState;
 use crate::views::tasks::TaskType;
+use crate::views::tasks::state::QueuePriority;
 use crate::widgets::{claude_indicator, spinner};
 use ratatui::{
     style::{Color, Modifier, Style},
@@ -15,6 +16,8 @@ pub struct TaskItem {
     task_type: TaskType,
     is_selected: bool,
     is_classifying: bool,
+    is_blocked: bool,
+    queue_priority: Option<QueuePriority>,
     claude_state: Option<ClaudeState>,
     title_max_length: usize,
     selection_color: Color,
@@ -30,6 +33,8 @@ impl TaskItem {
             task_type,
             is_selected: false,
             is_classifying: false,
+            is_blocked: false,
+            queue_priority: None,
             claude_state: None,
             title_max_length: DEFAULT_TITLE_MAX_LENGTH,
             selection_color: Color::Cyan,
@@ -50,6 +55,18 @@ impl TaskItem {
         self
     }
 
+    #[must_use]
+    pub const fn blocked(mut self, is_blocked: bool) -> Self {
+        self.is_blocked = is_blocked;
+        self
+    }
+
+    #[must_use]
+    pub const fn queued(mut self, priority: Option<QueuePriority>) -> Self {
+        self.queue_priority = priority;
+        self
+    }
+
     #[must_use]
     pub const fn claude_state(mut self, state: Option<ClaudeState>) -> Self {
         self.claude_state = state;
@@ -116,6 +133,22 @@ impl TaskItem {
         }
         spans.push(Span::raw(" "));
 
+        if self.is_blocked && !self.is_classifying {
+            spans.push(Span::styled(
+                "BLOCKED ",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        if let Some(priority) = self.queue_priority {
+            spans.push(Span::styled(
+                format!("{} ", priority.badge_text()),
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
         spans.push(Span::styled(
             truncated_title,
             Style::default()